@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use disposition::input_model::InputDiagram;
+use disposition_input_ir_rt::SvgSourceExtractor;
+
+use crate::CliError;
+
+/// Arguments to extract the input diagram from a generated SVG.
+#[derive(clap::Args)]
+pub(crate) struct ExtractArgs {
+    /// Path to the SVG generated by `disposition`.
+    svg: PathBuf,
+    /// Path to write the input diagram YAML to.
+    ///
+    /// When unspecified, the YAML is written to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Extracts the input diagram embedded in the SVG, and writes it as YAML.
+///
+/// The embedded YAML is written as-is, after checking that it deserializes to
+/// an input diagram.
+pub(crate) async fn run(extract_args: ExtractArgs) -> Result<(), CliError> {
+    let ExtractArgs { svg, output } = extract_args;
+
+    let svg = tokio::fs::read_to_string(&svg).await?;
+    let source_yaml = SvgSourceExtractor::source_yaml_extract(&svg)?;
+    serde_saphyr::from_str::<InputDiagram<'static>>(&source_yaml)?;
+
+    match output {
+        Some(output) => tokio::fs::write(output, source_yaml).await?,
+        None => print!("{source_yaml}"),
+    }
+
+    Ok(())
+}
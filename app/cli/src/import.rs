@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use disposition::{
+    input_model::InputDiagram,
+    input_rt::{
+        CargoMetadataImportOptions, CargoMetadataImporter, DockerComposeImporter, DotImportOutcome,
+        DotImporter, KubernetesImporter, KubernetesManifests, MermaidImportOutcome,
+        MermaidImporter, OtlpTraceImporter, TerraformImporter,
+    },
+};
+use miette::{Diagnostic, NamedSource, Report};
+use tokio::io::AsyncReadExt;
+
+use crate::CliError;
+
+/// Arguments to import a diagram from another format as an input diagram.
+#[derive(clap::Args)]
+pub(crate) struct ImportArgs {
+    /// Path to the file to import, or `-` to read from stdin.
+    ///
+    /// For `kubernetes`, this may be a directory, in which case every `.yaml`
+    /// and `.yml` file within it is imported.
+    input: PathBuf,
+    /// Format of the file to import.
+    ///
+    /// When unspecified, the format is inferred from the file extension.
+    #[arg(long, value_enum)]
+    format: Option<ImportFormat>,
+    /// Whether to import crates from outside the workspace that workspace
+    /// members depend on directly.
+    ///
+    /// Only supported with `--format cargo-metadata`.
+    #[arg(long, requires = "format")]
+    external_crates: bool,
+    /// Path to write the input diagram YAML to.
+    ///
+    /// When unspecified, the YAML is written to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Format of a file to import.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ImportFormat {
+    /// Graphviz DOT graph, with the `.dot` or `.gv` extension.
+    Dot,
+    /// Mermaid flowchart, with the `.mmd` or `.mermaid` extension.
+    Mermaid,
+    /// `cargo metadata --format-version 1` output.
+    ///
+    /// Not inferred from the file extension, as it is JSON.
+    CargoMetadata,
+    /// Docker compose file, named `compose.yaml`, `docker-compose.yml`, or
+    /// with one of those names and a `.<suffix>`, e.g.
+    /// `docker-compose.dev.yml`.
+    DockerCompose,
+    /// Kubernetes manifests, inferred when the path is a directory.
+    Kubernetes,
+    /// `terraform show -json` output, for the state or a plan.
+    ///
+    /// Not inferred from the file extension, as it is JSON.
+    TerraformJson,
+    /// `terraform graph` output.
+    ///
+    /// Not inferred from the file extension, as it is DOT.
+    TerraformGraph,
+    /// OTLP/JSON trace export, e.g. from the OpenTelemetry collector's `file`
+    /// exporter.
+    ///
+    /// Not inferred from the file extension, as it is JSON.
+    OtlpTraces,
+}
+
+impl ImportFormat {
+    /// Returns the format for the file name or extension of the path.
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "dot" | "gv" => Some(Self::Dot),
+            "mmd" | "mermaid" => Some(Self::Mermaid),
+            "yml" | "yaml" => {
+                let file_name = path.file_name()?.to_str()?.to_ascii_lowercase();
+                let is_compose_file = ["compose.", "docker-compose."]
+                    .iter()
+                    .any(|prefix| file_name.starts_with(prefix));
+                is_compose_file.then_some(Self::DockerCompose)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Imports the file as an input diagram, and writes it as YAML.
+///
+/// Parts of the file that were not imported are reported to stderr as
+/// warnings.
+pub(crate) async fn run(import_args: ImportArgs) -> Result<(), CliError> {
+    let ImportArgs {
+        input,
+        format,
+        external_crates,
+        output,
+    } = import_args;
+
+    let input_is_dir = input.as_os_str() != "-"
+        && tokio::fs::metadata(&input)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir());
+    let format = if input_is_dir {
+        format.or(Some(ImportFormat::Kubernetes))
+    } else {
+        format.or_else(|| ImportFormat::from_path(&input))
+    };
+    let Some(format) = format else {
+        return Err(CliError::ImportFormatUnknown {
+            path: input.display().to_string(),
+        });
+    };
+    if external_crates && format != ImportFormat::CargoMetadata {
+        return Err(CliError::ImportExternalCratesUnsupported);
+    }
+    if format == ImportFormat::Kubernetes && input_is_dir {
+        let input_diagram = kubernetes_dir_import(&input).await?;
+        return input_diagram_write(&input_diagram, output).await;
+    }
+
+    let contents = input_read(&input).await?;
+    let named_source = NamedSource::new(input.display().to_string(), contents.clone());
+
+    let input_diagram = match format {
+        ImportFormat::Dot => {
+            let DotImportOutcome {
+                input_diagram,
+                warnings,
+            } = DotImporter::import(&contents)
+                .map_err(|error| import_error_report(error, &named_source))?;
+            warnings_report(warnings, &named_source);
+            input_diagram
+        }
+        ImportFormat::Mermaid => {
+            let MermaidImportOutcome {
+                input_diagram,
+                warnings,
+            } = MermaidImporter::import(&contents)
+                .map_err(|error| import_error_report(error, &named_source))?;
+            warnings_report(warnings, &named_source);
+            input_diagram
+        }
+        ImportFormat::CargoMetadata => {
+            let cargo_metadata_import_options = CargoMetadataImportOptions::builder()
+                .with_external_crates_include(external_crates)
+                .build();
+            CargoMetadataImporter::import(&contents, &cargo_metadata_import_options)
+                .map_err(|error| import_error_report(error, &named_source))?
+        }
+        ImportFormat::DockerCompose => DockerComposeImporter::import(&contents)
+            .map_err(|error| import_error_report(error, &named_source))?,
+        ImportFormat::Kubernetes => {
+            let mut kubernetes_manifests = KubernetesManifests::new();
+            kubernetes_manifests
+                .extend_from_yaml(&contents)
+                .map_err(|error| import_error_report(error, &named_source))?;
+            KubernetesImporter::import(&kubernetes_manifests)
+        }
+        ImportFormat::TerraformJson => TerraformImporter::import_json(&contents)
+            .map_err(|error| import_error_report(error, &named_source))?,
+        ImportFormat::TerraformGraph => TerraformImporter::import_graph_dot(&contents)
+            .map_err(|error| import_error_report(error, &named_source))?,
+        ImportFormat::OtlpTraces => OtlpTraceImporter::import(&contents)
+            .map_err(|error| import_error_report(error, &named_source))?,
+    };
+
+    input_diagram_write(&input_diagram, output).await
+}
+
+/// Returns the contents of the file, or stdin for `-`.
+async fn input_read(input: &Path) -> Result<String, CliError> {
+    let contents = if input.as_os_str() == "-" {
+        let mut contents = String::new();
+        tokio::io::stdin().read_to_string(&mut contents).await?;
+        contents
+    } else {
+        tokio::fs::read_to_string(input).await?
+    };
+    Ok(contents)
+}
+
+/// Imports every Kubernetes manifest within the directory.
+async fn kubernetes_dir_import(dir: &Path) -> Result<InputDiagram<'static>, CliError> {
+    let mut kubernetes_manifests = KubernetesManifests::new();
+    for manifest_path in kubernetes_manifest_paths(dir).await? {
+        let contents = tokio::fs::read_to_string(&manifest_path).await?;
+        kubernetes_manifests
+            .extend_from_yaml(&contents)
+            .map_err(|error| {
+                let named_source =
+                    NamedSource::new(manifest_path.display().to_string(), contents.clone());
+                import_error_report(error, &named_source)
+            })?;
+    }
+
+    Ok(KubernetesImporter::import(&kubernetes_manifests))
+}
+
+/// Returns the paths of `.yaml` and `.yml` files within the directory and its
+/// subdirectories, sorted so that the import is deterministic.
+async fn kubernetes_manifest_paths(dir: &Path) -> Result<Vec<PathBuf>, CliError> {
+    let mut manifest_paths = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut read_dir = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                dirs.push(path);
+            } else if path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| matches!(extension, "yaml" | "yml"))
+            {
+                manifest_paths.push(path);
+            }
+        }
+    }
+    manifest_paths.sort();
+
+    Ok(manifest_paths)
+}
+
+/// Writes the input diagram as YAML to the output path, or stdout.
+async fn input_diagram_write(
+    input_diagram: &InputDiagram<'static>,
+    output: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let mut input_diagram_yaml = String::new();
+    serde_saphyr::to_fmt_writer(&mut input_diagram_yaml, input_diagram)?;
+    match output {
+        Some(output) => tokio::fs::write(output, input_diagram_yaml).await?,
+        None => print!("{input_diagram_yaml}"),
+    }
+
+    Ok(())
+}
+
+/// Reports the import error to stderr with the source it was found in.
+fn import_error_report<E>(error: E, named_source: &NamedSource<String>) -> CliError
+where
+    E: Diagnostic + Send + Sync + 'static,
+{
+    let report = Report::new(error).with_source_code(named_source.clone());
+    eprintln!("{report:?}");
+    CliError::ImportFailed
+}
+
+/// Reports parts of the file that were not imported to stderr.
+fn warnings_report<W>(warnings: Vec<W>, named_source: &NamedSource<String>)
+where
+    W: Diagnostic + Send + Sync + 'static,
+{
+    warnings.into_iter().for_each(|warning| {
+        let report = Report::new(warning).with_source_code(named_source.clone());
+        eprintln!("{report:?}");
+    });
+}
@@ -0,0 +1,89 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Interval between checks of the watched files' modification times.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Time the watched files must be unchanged for before a change is reported.
+///
+/// Editors may write a file in several steps, e.g. truncate then write, or
+/// write to a temporary file then rename it, so this avoids regenerating the
+/// diagram from a partially written file.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches input files for changes by polling their modification times.
+#[derive(Debug)]
+pub(crate) struct InputWatcher {
+    /// Files to watch, and their last seen state.
+    files: Vec<(PathBuf, Option<FileState>)>,
+}
+
+/// Modification time and length of a file, used to detect changes.
+///
+/// The length is included as some file systems have a coarse modification
+/// time resolution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileState {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl InputWatcher {
+    /// Returns an `InputWatcher` for the given files, recording their current
+    /// state.
+    pub(crate) async fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut files = Vec::new();
+        for path in paths {
+            let file_state = Self::file_state(&path).await;
+            files.push((path, file_state));
+        }
+        Self { files }
+    }
+
+    /// Waits until any watched file changes, and then is unchanged for the
+    /// debounce duration.
+    ///
+    /// A file that is removed, or created after being removed, is also a
+    /// change.
+    pub(crate) async fn changed_wait(&mut self) {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if self.files_state_update().await {
+                break;
+            }
+        }
+
+        loop {
+            tokio::time::sleep(DEBOUNCE).await;
+            if !self.files_state_update().await {
+                break;
+            }
+        }
+    }
+
+    /// Records the current state of each file, and returns whether any of them
+    /// changed.
+    async fn files_state_update(&mut self) -> bool {
+        let mut changed = false;
+        for (path, file_state_last) in self.files.iter_mut() {
+            let file_state = Self::file_state(path).await;
+            if file_state != *file_state_last {
+                *file_state_last = file_state;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Returns the state of the file, or `None` if it cannot be read.
+    async fn file_state(path: &Path) -> Option<FileState> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        let modified = metadata.modified().ok()?;
+        Some(FileState {
+            modified,
+            len: metadata.len(),
+        })
+    }
+}
@@ -1,18 +1,41 @@
-use std::path::{Path, PathBuf};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
-use clap::{Parser, ValueEnum};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use disposition::{
-    input_model::{DiagramFocus, InputDiagram},
+    input_ir_model::issue::{IssueLevel, IssueLevels, ModelToIrIssue, ModelToIrIssueKind},
+    input_model::{DiagramFocus, InputDiagram, InputDiagramSpans},
+    input_rt::InputDiagramLinter,
     ir_model::entity::EntityTailwindClasses,
     model_common::theme::Css,
-    output_model::DiagramGenerated,
+    output_model::{DiagramFocusGenerated, DiagramGenerated},
+    svg_model::SvgElements,
     taffy_model::TaffyTreeFmt,
 };
 use disposition_input_ir_rt::{
-    DiagramGenerateError, DiagramGenerator, EdgeAnimationActive, SvgElementsToSvgMapper,
+    ColorScheme, DiagramGenerateError, DiagramGenerator, DiagramToHtmlMapper,
+    DiagramToLayoutMapper, DiagramsToPdfError, DiagramsToPdfMapper, EdgeAnimationActive,
+    IrToDotMapper, IrToMermaidMapper, PdfOptions, PdfPageSize, PngOptions,
+    SvgElementsToDrawioMapper, SvgElementsToExcalidrawMapper, SvgElementsToPngError,
+    SvgElementsToPngMapper, SvgElementsToSvgMapper, SvgElementsToTikzMapper, SvgSourceExtractError,
 };
+use miette::{NamedSource, Report};
 use thiserror::Error;
 
+use crate::{
+    extract::ExtractArgs, import::ImportArgs, input_watcher::InputWatcher, serve::ServeArgs,
+    validate::ValidateArgs,
+};
+
+mod extract;
+mod import;
+mod input_watcher;
+mod serve;
+mod validate;
+
 /// Generates diagram artifacts from an input YAML diagram.
 ///
 /// By default, writes the following files to the output directory:
@@ -28,6 +51,31 @@ use thiserror::Error;
 /// `--stdout` to write that stage straight to stdout -- useful when debugging
 /// diagram generation without needing to write files.
 ///
+/// The following are only written when selected with `--data`:
+///
+/// * `diagram.dot` (`dot`): the intermediate representation diagram as a
+///   Graphviz DOT graph
+/// * `diagram.mmd` (`mermaid`): the intermediate representation diagram as a
+///   Mermaid flowchart
+/// * `diagram_layout.json` (`layout-json`): the computed layout of nodes,
+///   edges, and text, for custom renderers
+/// * `diagram.excalidraw` (`excalidraw`): the SVG elements as an Excalidraw
+///   scene
+/// * `diagram.drawio` (`drawio`): the SVG elements as a draw.io diagram
+/// * `diagram.tex` (`tikz`): the SVG elements as a TikZ picture for LaTeX
+///   documents
+/// * `diagram.html` (`html`): the final SVG in a page with a sidebar to step
+///   through processes, process steps, and tags
+/// * `diagram.png` (`png`): the final SVG rasterised to a PNG image
+/// * `diagram.pdf` (`pdf`): the final SVG as a PDF page
+///
+/// The PNG and PDF can only be written to files.
+///
+/// Use `--font` and `--color-scheme` to control how the PNG and PDF are
+/// rendered, `--png-scale` and `--png-dpi` for the PNG's resolution, and
+/// `--pdf-page-size` for the PDF's page size. `--color-scheme` also sets the
+/// TikZ picture's colors.
+///
 /// Every output file is prefixed with the input file's stem, e.g. for
 /// `something.yaml` the SVG is written to `something_diagram.svg`.
 ///
@@ -38,11 +86,87 @@ use thiserror::Error;
 /// the diagrams in generation order, and `focus` is the focused entity's ID
 /// (`idle` for the no-focus diagram), e.g. `something_00_idle_diagram.svg`. The
 /// `taffy_tree.txt` is shared by all diagrams, so it is prefixed with only the
-/// input stem (no ordinal). When writing to stdout, each diagram is preceded by
+/// input stem (no ordinal). The PDF has a page for each diagram, titled with
+/// the focused entity's name and description, so it is also prefixed with only
+/// the input stem. When writing to stdout, each diagram is preceded by
 /// a `<!-- focus: ID -->` comment header.
+///
+/// Issues found in the input diagram are reported to stderr, and by default do
+/// not stop the diagram from being generated. Use `--strict` to fail on any
+/// issue, and `--allow` / `--warn` / `--deny` to set the level of specific
+/// issue kinds.
+///
+/// Use `--lint` to also report configuration that has no effect on the
+/// diagram, such as unused style aliases, or things without edges.
+///
+/// Use `--watch` to regenerate the diagram whenever the input file changes.
+/// Issues and the time taken by each generation stage are reported to stderr
+/// after each run, and output files are only rewritten when their contents
+/// change.
+///
+/// Use the `validate` subcommand to check input diagrams without generating
+/// them, the `serve` subcommand to preview a diagram in the browser while
+/// editing it, and the `extract` subcommand to recover the input diagram from a
+/// generated SVG.
 #[derive(Parser)]
-#[command(version, about)]
+#[command(
+    version,
+    about,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+    arg_required_else_help = true
+)]
 struct Args {
+    /// Subcommand to run instead of generating a diagram.
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Arguments to generate a diagram, when no subcommand is specified.
+    ///
+    /// These are only read from the matches when no subcommand is specified,
+    /// as the `input` is required -- see [`run`].
+    #[command(flatten)]
+    generate_args: GenerateArgs,
+}
+
+/// Subcommands other than generating a diagram.
+#[derive(Subcommand)]
+enum Command {
+    /// Checks input diagrams for errors, without generating them.
+    ///
+    /// Each input diagram is deserialized, mapped to the intermediate
+    /// representation, and linted. Results are written to stdout as text, JSON,
+    /// or SARIF.
+    ///
+    /// Exits with a non-zero status if any input diagram fails to be read or
+    /// deserialized, or has an issue whose level is `deny`. References to
+    /// undeclared IDs are denied unless passed to `--allow` or `--warn`, and
+    /// other issues and lints are warnings.
+    Validate(ValidateArgs),
+    /// Serves a live preview of an input diagram on localhost.
+    ///
+    /// The preview page shows the interactive diagram, or the diagram with a
+    /// focus state selected from a dropdown baked in. The page reloads when
+    /// the input file changes.
+    Serve(ServeArgs),
+    /// Extracts the input diagram YAML embedded in a generated SVG.
+    ///
+    /// Generated SVGs embed the input diagram in a `<source>` element, so the
+    /// diagram can be regenerated or edited when only the SVG is available.
+    Extract(ExtractArgs),
+    /// Imports a diagram from another format, and writes it as input diagram
+    /// YAML.
+    ///
+    /// Graphviz DOT graphs, Mermaid flowcharts, `cargo metadata` output,
+    /// docker compose files, Kubernetes manifests, Terraform state, plans,
+    /// and graphs, and OpenTelemetry traces are supported. Parts of the
+    /// diagram that have no equivalent in the input diagram are reported as
+    /// warnings.
+    Import(ImportArgs),
+}
+
+/// Arguments to generate diagram artifacts from an input YAML diagram.
+#[derive(clap::Args)]
+struct GenerateArgs {
     /// Path to the input diagram YAML file.
     input: PathBuf,
     /// Directory to write output files to.
@@ -55,8 +179,9 @@ struct Args {
     structure_only: bool,
     /// Which intermediate diagram data to output.
     ///
-    /// When unspecified, all stages are written to the output directory. When
-    /// `--stdout` is specified without this, defaults to `svg`.
+    /// When unspecified, the `ir-diagram`, `taffy-tree`, `svg-elements`,
+    /// `edge-routing`, and `svg` stages are written to the output directory.
+    /// When `--stdout` is specified without this, defaults to `svg`.
     #[arg(long, value_enum)]
     data: Option<Data>,
     /// Output the selected `--data` to stdout instead of (or in addition to)
@@ -67,6 +192,213 @@ struct Args {
     /// no-focus diagram), instead of a single interactive diagram.
     #[arg(long)]
     diagram_per_interaction: bool,
+    /// Levels of issues found in the input diagram.
+    #[command(flatten)]
+    issue_level_args: IssueLevelArgs,
+    /// Report unused style aliases, entity types, and tags, things without
+    /// edges, and process steps without interactions.
+    ///
+    /// Lints are warnings, and do not stop the diagram from being generated.
+    #[arg(long)]
+    lint: bool,
+    /// Watch the input file, and regenerate the diagram when it changes.
+    ///
+    /// Errors are reported without exiting, and the diagram is regenerated on
+    /// the next change.
+    #[arg(long)]
+    watch: bool,
+    /// Options to render the PNG and PDF with.
+    #[command(flatten)]
+    render_args: RenderArgs,
+    /// Options to render the PNG with.
+    #[command(flatten)]
+    png_args: PngArgs,
+    /// Options to render the PDF with.
+    #[command(flatten)]
+    pdf_args: PdfArgs,
+}
+
+/// Arguments to render the diagram as an image or document.
+#[derive(clap::Args)]
+struct RenderArgs {
+    /// Path to a TrueType font to render characters that the bundled Noto Sans
+    /// Mono font does not have, such as emoji.
+    ///
+    /// May be specified multiple times. Fonts are used in the order given.
+    #[arg(long, value_name = "PATH")]
+    font: Vec<PathBuf>,
+    /// Whether to render the light or dark colors of the diagram's theme.
+    ///
+    /// Also applies to the TikZ picture.
+    #[arg(long, value_enum, default_value_t = ColorSchemeArg::Light)]
+    color_scheme: ColorSchemeArg,
+}
+
+impl RenderArgs {
+    /// Returns the bundled fonts followed by the `--font` files.
+    async fn fonts(&self) -> Result<Vec<Cow<'static, [u8]>>, CliError> {
+        let mut fonts = PngOptions::default().fonts;
+        for font_path in &self.font {
+            let font = tokio::fs::read(font_path).await?;
+            fonts.push(Cow::Owned(font));
+        }
+        Ok(fonts)
+    }
+}
+
+/// Arguments to render the diagram as a PNG.
+#[derive(clap::Args)]
+struct PngArgs {
+    /// Number of PNG pixels per SVG pixel.
+    #[arg(long, default_value_t = 1.0)]
+    png_scale: f32,
+    /// Resolution recorded in the PNG, in pixels per inch.
+    ///
+    /// Defaults to `96` times `--png-scale`, so the diagram has the same
+    /// physical size as in a browser.
+    #[arg(long)]
+    png_dpi: Option<f32>,
+}
+
+impl PngArgs {
+    /// Returns the `PngOptions` for these arguments, reading the font files.
+    async fn png_options(&self, render_args: &RenderArgs) -> Result<PngOptions, CliError> {
+        let PngArgs { png_scale, png_dpi } = self;
+
+        Ok(PngOptions::builder()
+            .with_scale(*png_scale)
+            .with_dpi(png_dpi.unwrap_or(96.0 * png_scale))
+            .with_fonts(render_args.fonts().await?)
+            .with_color_scheme(ColorScheme::from(render_args.color_scheme))
+            .build())
+    }
+}
+
+/// Arguments to render the diagram as a PDF.
+#[derive(clap::Args)]
+struct PdfArgs {
+    /// Size of each page of the PDF.
+    ///
+    /// `fit` sizes each page to its diagram. Paper sizes turn pages to
+    /// landscape for wide diagrams, and scale down diagrams that do not fit.
+    #[arg(long, value_enum, default_value_t = PdfPageSizeArg::Fit)]
+    pdf_page_size: PdfPageSizeArg,
+}
+
+impl PdfArgs {
+    /// Returns the `PdfOptions` for these arguments, reading the font files.
+    async fn pdf_options(&self, render_args: &RenderArgs) -> Result<PdfOptions, CliError> {
+        let PdfArgs { pdf_page_size } = self;
+
+        Ok(PdfOptions::builder()
+            .with_page_size(PdfPageSize::from(*pdf_page_size))
+            .with_fonts(render_args.fonts().await?)
+            .with_color_scheme(ColorScheme::from(render_args.color_scheme))
+            .build())
+    }
+}
+
+/// Page size to render the PDF with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum PdfPageSizeArg {
+    /// Each page is sized to fit its diagram.
+    Fit,
+    /// ISO A4 paper.
+    A4,
+    /// US Letter paper.
+    Letter,
+}
+
+impl From<PdfPageSizeArg> for PdfPageSize {
+    fn from(pdf_page_size_arg: PdfPageSizeArg) -> Self {
+        match pdf_page_size_arg {
+            PdfPageSizeArg::Fit => PdfPageSize::Fit,
+            PdfPageSizeArg::A4 => PdfPageSize::A4,
+            PdfPageSizeArg::Letter => PdfPageSize::Letter,
+        }
+    }
+}
+
+/// Color scheme to render the PNG, PDF, and TikZ picture with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ColorSchemeArg {
+    /// The light colors of the theme.
+    Light,
+    /// The dark colors of the theme.
+    Dark,
+}
+
+impl From<ColorSchemeArg> for ColorScheme {
+    fn from(color_scheme_arg: ColorSchemeArg) -> Self {
+        match color_scheme_arg {
+            ColorSchemeArg::Light => ColorScheme::Light,
+            ColorSchemeArg::Dark => ColorScheme::Dark,
+        }
+    }
+}
+
+/// Arguments to set the level of each kind of issue.
+#[derive(clap::Args)]
+struct IssueLevelArgs {
+    /// Fail if the input diagram has any issues.
+    ///
+    /// Issue kinds passed to `--allow` or `--warn` are still not denied.
+    #[arg(long, visible_alias = "deny-warnings")]
+    strict: bool,
+    /// Issue kind to discard, e.g. `thing_names_thing_unknown`.
+    ///
+    /// May be specified multiple times.
+    #[arg(long, value_name = "ISSUE_KIND")]
+    allow: Vec<ModelToIrIssueKind>,
+    /// Issue kind to report without failing, e.g. `thing_names_thing_unknown`.
+    ///
+    /// May be specified multiple times.
+    #[arg(long, value_name = "ISSUE_KIND")]
+    warn: Vec<ModelToIrIssueKind>,
+    /// Issue kind to fail on, e.g. `tag_things_thing_unknown`.
+    ///
+    /// May be specified multiple times. Takes precedence over `--allow` and
+    /// `--warn` for the same kind.
+    #[arg(long, value_name = "ISSUE_KIND")]
+    deny: Vec<ModelToIrIssueKind>,
+}
+
+impl IssueLevelArgs {
+    /// Returns the `IssueLevels` for these arguments.
+    fn issue_levels(&self) -> IssueLevels {
+        self.issue_levels_over(IssueLevels::new())
+    }
+
+    /// Returns the `IssueLevels` for these arguments, using
+    /// `issue_levels_base` for kinds that are not passed in.
+    ///
+    /// `--strict` replaces `issue_levels_base`.
+    fn issue_levels_over(&self, issue_levels_base: IssueLevels) -> IssueLevels {
+        let IssueLevelArgs {
+            strict,
+            allow,
+            warn,
+            deny,
+        } = self;
+
+        let mut issue_levels = if *strict {
+            IssueLevels::strict()
+        } else {
+            issue_levels_base
+        };
+        [
+            (allow, IssueLevel::Allow),
+            (warn, IssueLevel::Warn),
+            (deny, IssueLevel::Deny),
+        ]
+        .into_iter()
+        .for_each(|(kinds, level)| {
+            kinds.iter().for_each(|kind| {
+                issue_levels.kinds.insert(*kind, level);
+            })
+        });
+        issue_levels
+    }
 }
 
 /// An intermediate diagram transformation stage that can be output.
@@ -77,6 +409,10 @@ struct Args {
 enum Data {
     /// The intermediate representation diagram.
     IrDiagram,
+    /// The intermediate representation diagram as a Graphviz DOT graph.
+    Dot,
+    /// The intermediate representation diagram as a Mermaid flowchart.
+    Mermaid,
     /// The taffy layout tree.
     TaffyTree,
     /// The SVG elements.
@@ -84,8 +420,46 @@ enum Data {
     /// The edge-routing diagnostics (pass-1, offset, slot-index, rank-gap, and
     /// protrusion values), produced alongside the SVG elements.
     EdgeRouting,
+    /// The computed layout of nodes, edges, and text as JSON, for custom
+    /// renderers.
+    LayoutJson,
+    /// The SVG elements as an Excalidraw scene.
+    Excalidraw,
+    /// The SVG elements as a draw.io diagram.
+    Drawio,
+    /// The SVG elements as a TikZ picture, for LaTeX documents.
+    Tikz,
     /// The final SVG.
     Svg,
+    /// The final SVG in a self-contained HTML page, with a sidebar to step
+    /// through processes, process steps, and tags.
+    Html,
+    /// The final SVG rasterised to a PNG image.
+    Png,
+    /// The final SVG as a PDF, with a page per focus state when
+    /// `--diagram-per-interaction` is specified.
+    Pdf,
+}
+
+impl Data {
+    /// Returns whether this stage is output when `data_selected` is the
+    /// `--data` stage.
+    ///
+    /// When no stage is selected, only the intermediate stages and the final
+    /// SVG are output. The other formats are only output when selected.
+    fn is_selected(self, data_selected: Option<Data>) -> bool {
+        match data_selected {
+            Some(data_selected) => data_selected == self,
+            None => matches!(
+                self,
+                Data::IrDiagram
+                    | Data::TaffyTree
+                    | Data::SvgElements
+                    | Data::EdgeRouting
+                    | Data::Svg
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -98,8 +472,26 @@ enum CliError {
     YamlSerialize(#[from] serde_saphyr::ser::Error),
     #[error("generate: {0}")]
     Generate(#[from] DiagramGenerateError),
+    #[error("extract: {0}")]
+    SvgSourceExtract(#[from] SvgSourceExtractError),
+    #[error("png: {0}")]
+    SvgElementsToPng(#[from] SvgElementsToPngError),
+    #[error("pdf: {0}")]
+    DiagramsToPdf(#[from] DiagramsToPdfError),
+    #[error("json serialize: {0}")]
+    JsonSerialize(#[from] serde_json::Error),
     #[error("no output specified: provide an output directory or `--stdout`")]
     NoOutput,
+    #[error("`--data {data_name}` cannot be written to `--stdout`: provide an output directory")]
+    StdoutBinary { data_name: &'static str },
+    #[error("validation failed with {error_count} error(s)")]
+    ValidateFailed { error_count: usize },
+    #[error("cannot infer the format of `{path}` from its name: provide `--format`")]
+    ImportFormatUnknown { path: String },
+    #[error("`--external-crates` is only supported with `--format cargo-metadata`")]
+    ImportExternalCratesUnsupported,
+    #[error("import failed")]
+    ImportFailed,
 }
 
 #[tokio::main]
@@ -111,18 +503,78 @@ async fn main() {
 }
 
 async fn run() -> Result<(), CliError> {
-    let Args {
-        input,
-        output,
+    // `Args::parse` would require `input` even when a subcommand is specified,
+    // so the subcommand and generate arguments are read from the matches
+    // separately.
+    let arg_matches = Args::command().get_matches();
+    if arg_matches.subcommand().is_none() {
+        let generate_args =
+            GenerateArgs::from_arg_matches(&arg_matches).unwrap_or_else(|error| error.exit());
+        return generate(generate_args).await;
+    }
+
+    match Command::from_arg_matches(&arg_matches).unwrap_or_else(|error| error.exit()) {
+        Command::Validate(validate_args) => validate::run(validate_args).await,
+        Command::Serve(serve_args) => serve::run(serve_args).await,
+        Command::Extract(extract_args) => extract::run(extract_args).await,
+        Command::Import(import_args) => import::run(import_args).await,
+    }
+}
+
+async fn generate(generate_args: GenerateArgs) -> Result<(), CliError> {
+    let input = generate_args.input.as_path();
+    if generate_args.output.is_none() && !generate_args.stdout {
+        return Err(CliError::NoOutput);
+    }
+    if generate_args.stdout {
+        match generate_args.data {
+            Some(Data::Png) => return Err(CliError::StdoutBinary { data_name: "png" }),
+            Some(Data::Pdf) => return Err(CliError::StdoutBinary { data_name: "pdf" }),
+            _ => {}
+        }
+    }
+    let issue_levels = generate_args.issue_level_args.issue_levels();
+
+    if !generate_args.watch {
+        return generate_once(&generate_args, &issue_levels).await;
+    }
+
+    // Input diagrams do not reference other files, so only the input file is
+    // watched.
+    let mut input_watcher = InputWatcher::new([input.to_path_buf()]).await;
+    loop {
+        let generate_start = Instant::now();
+        match generate_once(&generate_args, &issue_levels).await {
+            Ok(()) => eprintln!("Generated in {:?}.", generate_start.elapsed()),
+            Err(error) => eprintln!("Error: {error}"),
+        }
+
+        eprintln!("Watching `{}` for changes.", input.display());
+        input_watcher.changed_wait().await;
+        eprintln!("`{}` changed, regenerating.", input.display());
+    }
+}
+
+/// Generates the diagram artifacts once, and writes them to the output
+/// directory and/or stdout.
+async fn generate_once(
+    generate_args: &GenerateArgs,
+    issue_levels: &IssueLevels,
+) -> Result<(), CliError> {
+    let &GenerateArgs {
+        ref input,
+        ref output,
         structure_only,
         data,
         stdout,
         diagram_per_interaction,
-    } = Args::parse();
-
-    if output.is_none() && !stdout {
-        return Err(CliError::NoOutput);
-    }
+        issue_level_args: _,
+        lint,
+        watch,
+        ref render_args,
+        ref png_args,
+        ref pdf_args,
+    } = generate_args;
 
     // The stage to output. When writing to stdout without an explicit `--data`,
     // default to the final SVG. When `data` is `None`, all stages are output.
@@ -131,8 +583,7 @@ async fn run() -> Result<(), CliError> {
     } else {
         data
     };
-    let data_is_selected =
-        |data: Data| data_selected.is_none_or(|data_selected| data_selected == data);
+    let data_is_selected = |data: Data| data.is_selected(data_selected);
 
     // Every output file is prefixed with the input file's stem, so it is clear
     // which input the outputs belong to. `file_stem` strips the directory and
@@ -142,24 +593,67 @@ async fn run() -> Result<(), CliError> {
         .and_then(std::ffi::OsStr::to_str)
         .unwrap_or("diagram");
 
-    let contents = tokio::fs::read_to_string(&input).await?;
+    let contents = tokio::fs::read_to_string(input).await?;
     let input_diagram: InputDiagram<'static> = serde_saphyr::from_str(&contents)?;
+    let input_source = InputSource {
+        named_source: NamedSource::new(input.display().to_string(), contents.clone()),
+        input_diagram_spans: InputDiagramSpans::from_yaml(&contents),
+    };
 
     if let Some(output) = output.as_deref() {
         tokio::fs::create_dir_all(output).await?;
     }
-    let output = output.as_deref();
+    let output = output.as_deref().map(|path| OutputDir {
+        path,
+        unchanged_skip: watch,
+    });
+    let png_options = if data_is_selected(Data::Png) && output.is_some() {
+        Some(png_args.png_options(render_args).await?)
+    } else {
+        None
+    };
+    let pdf_options = if data_is_selected(Data::Pdf) && output.is_some() {
+        Some(pdf_args.pdf_options(render_args).await?)
+    } else {
+        None
+    };
 
     if diagram_per_interaction {
-        let diagrams_focus_generated = DiagramGenerator::generate_per_process_step_or_tag(
-            &input_diagram,
-            EdgeAnimationActive::OnProcessStepFocus,
-        )?;
+        let diagrams_focus_generated =
+            DiagramGenerator::generate_per_process_step_or_tag_with_issue_levels(
+                &input_diagram,
+                EdgeAnimationActive::OnProcessStepFocus,
+                issue_levels,
+            )
+            .map_err(|error| generate_error_report(error, &input_source))?;
 
         // The IR mapping issues are identical across every focus, so report them
         // once.
         if let Some(diagram_focus_generated) = diagrams_focus_generated.first() {
-            issues_report(&diagram_focus_generated.diagram_generated);
+            issues_report(
+                &diagram_focus_generated.diagram_generated.ir_diagram_issues,
+                &input_source,
+            );
+            if lint {
+                lints_report(
+                    &diagram_focus_generated
+                        .diagram_generated
+                        .input_diagram_merged,
+                    &input_source,
+                );
+            }
+        }
+        if watch {
+            durations_report(
+                diagrams_focus_generated
+                    .iter()
+                    .map(|diagram_focus_generated| {
+                        (
+                            focus_id(&diagram_focus_generated.focus),
+                            &diagram_focus_generated.diagram_generated,
+                        )
+                    }),
+            );
         }
 
         // The taffy layout is focus-independent, so it is shared by every
@@ -199,16 +693,39 @@ async fn run() -> Result<(), CliError> {
                 output,
                 stdout,
                 data_selected,
+                png_options.as_ref(),
+                ColorScheme::from(render_args.color_scheme),
                 Some(&file_prefix),
                 Some(&stdout_header),
             )
             .await?;
         }
+
+        if let (Some(output), Some(pdf_options)) = (output, pdf_options.as_ref()) {
+            pdf_emit(
+                diagrams_focus_generated,
+                structure_only,
+                output,
+                pdf_options,
+                input_stem,
+            )
+            .await?;
+        }
     } else {
-        let diagram_generated =
-            DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)?;
+        let diagram_generated = DiagramGenerator::generate_with_issue_levels(
+            &input_diagram,
+            EdgeAnimationActive::OnProcessStepFocus,
+            issue_levels,
+        )
+        .map_err(|error| generate_error_report(error, &input_source))?;
 
-        issues_report(&diagram_generated);
+        issues_report(&diagram_generated.ir_diagram_issues, &input_source);
+        if lint {
+            lints_report(&diagram_generated.input_diagram_merged, &input_source);
+        }
+        if watch {
+            durations_report([(input_stem.to_string(), &diagram_generated)]);
+        }
 
         if data_is_selected(Data::TaffyTree) {
             taffy_tree_emit(&diagram_generated, output, stdout, Some(input_stem), None).await?;
@@ -221,25 +738,129 @@ async fn run() -> Result<(), CliError> {
             output,
             stdout,
             data_selected,
+            png_options.as_ref(),
+            ColorScheme::from(render_args.color_scheme),
             Some(input_stem),
             None,
         )
         .await?;
+
+        if let (Some(output), Some(pdf_options)) = (output, pdf_options.as_ref()) {
+            let diagram_focus_generated = DiagramFocusGenerated {
+                focus: DiagramFocus::None,
+                diagram_generated,
+            };
+            pdf_emit(
+                vec![diagram_focus_generated],
+                structure_only,
+                output,
+                pdf_options,
+                input_stem,
+            )
+            .await?;
+        }
     }
 
     Ok(())
 }
 
+/// The input diagram's source text, used to render issues with snippets of
+/// the input YAML.
+struct InputSource {
+    /// Input file name and contents.
+    named_source: NamedSource<String>,
+    /// Location of each field in the input YAML.
+    input_diagram_spans: InputDiagramSpans,
+}
+
 /// Reports any input-to-IR mapping issues to stderr.
-fn issues_report(diagram_generated: &DiagramGenerated) {
-    if !diagram_generated.ir_diagram_issues.is_empty() {
+///
+/// Each issue is rendered with a snippet of the input YAML pointing at the
+/// offending value, when its location is known.
+fn issues_report(issues: &[ModelToIrIssue], input_source: &InputSource) {
+    let InputSource {
+        named_source,
+        input_diagram_spans,
+    } = input_source;
+
+    if !issues.is_empty() {
         eprintln!("Issues mapping input to IR diagram:");
-        for issue in &diagram_generated.ir_diagram_issues {
-            eprintln!("  {issue}");
+        for issue in issues {
+            let mut issue = issue.clone();
+            issue.span_attach(input_diagram_spans);
+            let report = Report::new(issue).with_source_code(named_source.clone());
+            eprintln!("{report:?}");
+        }
+    }
+}
+
+/// Reports lints for the merged input diagram to stderr.
+///
+/// Each lint is rendered with a snippet of the input YAML pointing at the
+/// offending value, followed by its suggested fix, if any.
+fn lints_report(input_diagram_merged: &InputDiagram<'static>, input_source: &InputSource) {
+    let InputSource {
+        named_source,
+        input_diagram_spans,
+    } = input_source;
+
+    let lints = InputDiagramLinter::lint(input_diagram_merged);
+    if !lints.is_empty() {
+        eprintln!("Lints:");
+        for mut lint in lints {
+            lint.span_attach(input_diagram_spans);
+            let fix = lint.fix();
+            let report = Report::new(lint).with_source_code(named_source.clone());
+            eprintln!("{report:?}");
+            if let Some(fix) = fix {
+                eprintln!("  suggested fix: {fix}\n");
+            }
         }
     }
 }
 
+/// Reports the time taken by each generation stage to stderr, one line per
+/// diagram.
+///
+/// Each item is the diagram's name, and the generated diagram.
+fn durations_report<'d>(
+    diagrams_generated: impl IntoIterator<Item = (String, &'d DiagramGenerated)>,
+) {
+    eprintln!("Durations:");
+    diagrams_generated
+        .into_iter()
+        .for_each(|(name, diagram_generated)| {
+            let DiagramGenerated {
+                input_diagram_merged_merge_duration,
+                ir_diagram_map_duration,
+                taffy_node_mappings_build_duration,
+                svg_elements_map_duration,
+                svg_map_duration,
+                ..
+            } = diagram_generated;
+            eprintln!(
+                "  {name}: \
+                merge {input_diagram_merged_merge_duration:?}, \
+                ir_diagram_map {ir_diagram_map_duration:?}, \
+                taffy_node_mappings_build {taffy_node_mappings_build_duration:?}, \
+                svg_elements_map {svg_elements_map_duration:?}, \
+                svg_map {svg_map_duration:?}"
+            );
+        });
+}
+
+/// Reports the issues of a [`DiagramGenerateError::IssuesDenied`] to stderr,
+/// and returns the error.
+fn generate_error_report(
+    error: DiagramGenerateError,
+    input_source: &InputSource,
+) -> DiagramGenerateError {
+    if let DiagramGenerateError::IssuesDenied { issues } = &error {
+        issues_report(issues, input_source);
+    }
+    error
+}
+
 /// Returns the file-name / stdout-header prefix for a [`DiagramFocus`].
 ///
 /// Uses the focused entity's ID, or `idle` for the no-focus diagram.
@@ -268,7 +889,7 @@ fn file_name(file_prefix: Option<&str>, base: &str) -> String {
 /// Emits the taffy layout tree, optionally preceded by a stdout header.
 async fn taffy_tree_emit(
     diagram_generated: &DiagramGenerated,
-    output: Option<&Path>,
+    output: Option<OutputDir<'_>>,
     stdout: bool,
     file_prefix: Option<&str>,
     stdout_header: Option<&str>,
@@ -288,11 +909,16 @@ async fn taffy_tree_emit(
     Ok(())
 }
 
-/// Emits the `ir_diagram`, `svg_elements`, and `svg` stages for one diagram.
+/// Emits the `ir_diagram`, `svg_elements`, `svg`, `html`, and `png` stages for
+/// one diagram.
 ///
 /// The taffy tree is emitted separately via [`taffy_tree_emit`], because it is
 /// identical across all per-interaction diagrams.
 ///
+/// * `png_options`: options to render the PNG with, if it is written to the
+///   output directory.
+/// * `color_scheme`: whether to render the TikZ picture with the light or dark
+///   colors of the theme.
 /// * `file_prefix`: prefix applied to each output file name, e.g.
 ///   `Some("proc_one_step_build")`; `None` writes the bare stage names.
 /// * `stdout_header`: a header line printed to stdout once before this
@@ -303,23 +929,31 @@ async fn diagram_stages_emit(
     diagram_generated: &DiagramGenerated,
     input_diagram: &InputDiagram<'static>,
     structure_only: bool,
-    output: Option<&Path>,
+    output: Option<OutputDir<'_>>,
     stdout: bool,
     data_selected: Option<Data>,
+    png_options: Option<&PngOptions>,
+    color_scheme: ColorScheme,
     file_prefix: Option<&str>,
     stdout_header: Option<&str>,
 ) -> Result<(), CliError> {
-    let data_is_selected =
-        |data: Data| data_selected.is_none_or(|data_selected| data_selected == data);
+    let data_is_selected = |data: Data| data.is_selected(data_selected);
 
     // Print the stdout header once before this diagram's stage output, but only
     // when at least one of its stages will actually be written to stdout.
     if stdout
         && let Some(stdout_header) = stdout_header
         && (data_is_selected(Data::IrDiagram)
+            || data_is_selected(Data::Dot)
+            || data_is_selected(Data::Mermaid)
             || data_is_selected(Data::SvgElements)
             || data_is_selected(Data::EdgeRouting)
-            || data_is_selected(Data::Svg))
+            || data_is_selected(Data::LayoutJson)
+            || data_is_selected(Data::Excalidraw)
+            || data_is_selected(Data::Drawio)
+            || data_is_selected(Data::Tikz)
+            || data_is_selected(Data::Svg)
+            || data_is_selected(Data::Html))
     {
         println!("{stdout_header}");
     }
@@ -346,20 +980,29 @@ async fn diagram_stages_emit(
         .await?;
     }
 
+    // === DOT === //
+    if data_is_selected(Data::Dot) {
+        let dot = IrToDotMapper::map(&diagram_generated.ir_diagram);
+        data_emit(output, stdout, &file_name(file_prefix, "diagram.dot"), &dot).await?;
+    }
+
+    // === Mermaid === //
+    if data_is_selected(Data::Mermaid) {
+        let mermaid = IrToMermaidMapper::map(&diagram_generated.ir_diagram);
+        data_emit(
+            output,
+            stdout,
+            &file_name(file_prefix, "diagram.mmd"),
+            &mermaid,
+        )
+        .await?;
+    }
+
     // === SVG elements === //
     // Under `--structure-only` a stripped copy is also used to re-derive the
     // SVG, so the final SVG matches the structure-only SVG elements.
     let svg_elements_structure_only = if structure_only {
-        let mut svg_elements = diagram_generated.svg_elements.clone();
-        svg_elements.css = Css::default();
-        svg_elements.tailwind_classes = EntityTailwindClasses::default();
-        svg_elements
-            .svg_edge_infos
-            .iter_mut()
-            .for_each(|svg_edge_info| {
-                svg_edge_info.locus_path_d = String::new();
-            });
-        Some(svg_elements)
+        Some(svg_elements_structure_only(diagram_generated))
     } else {
         None
     };
@@ -397,20 +1040,168 @@ async fn diagram_stages_emit(
         .await?;
     }
 
+    // === Layout JSON === //
+    // Contains no styling values, so `--structure-only` does not affect it.
+    if data_is_selected(Data::LayoutJson) {
+        let diagram_layout = DiagramToLayoutMapper::map(diagram_generated);
+        let layout_json = serde_json::to_string_pretty(&diagram_layout)?;
+        data_emit(
+            output,
+            stdout,
+            &file_name(file_prefix, "diagram_layout.json"),
+            &layout_json,
+        )
+        .await?;
+    }
+
+    // === Excalidraw / draw.io === //
+    // Positioned by the same SVG elements as the SVG.
+    if data_is_selected(Data::Excalidraw) || data_is_selected(Data::Drawio) {
+        let svg_elements = svg_elements_structure_only
+            .as_ref()
+            .unwrap_or(&diagram_generated.svg_elements);
+        if data_is_selected(Data::Excalidraw) {
+            let excalidraw =
+                SvgElementsToExcalidrawMapper::map(&diagram_generated.ir_diagram, svg_elements);
+            data_emit(
+                output,
+                stdout,
+                &file_name(file_prefix, "diagram.excalidraw"),
+                &excalidraw,
+            )
+            .await?;
+        }
+        if data_is_selected(Data::Drawio) {
+            let drawio =
+                SvgElementsToDrawioMapper::map(&diagram_generated.ir_diagram, svg_elements);
+            data_emit(
+                output,
+                stdout,
+                &file_name(file_prefix, "diagram.drawio"),
+                &drawio,
+            )
+            .await?;
+        }
+    }
+
+    // === TikZ === //
+    if data_is_selected(Data::Tikz) {
+        let svg_elements = svg_elements_structure_only
+            .as_ref()
+            .unwrap_or(&diagram_generated.svg_elements);
+        let tikz = SvgElementsToTikzMapper::map(svg_elements, color_scheme);
+        data_emit(
+            output,
+            stdout,
+            &file_name(file_prefix, "diagram.tex"),
+            &tikz,
+        )
+        .await?;
+    }
+
     // === SVG === //
-    if data_is_selected(Data::Svg) {
+    let svg_is_needed = data_is_selected(Data::Svg) || data_is_selected(Data::Html);
+    let svg = if svg_is_needed {
         let svg = match svg_elements_structure_only.as_ref() {
-            Some(svg_elements) => {
-                SvgElementsToSvgMapper::map_with_input(input_diagram, svg_elements)
-            }
-            None => diagram_generated.svg.clone(),
+            Some(svg_elements) => Cow::Owned(SvgElementsToSvgMapper::map_with_input(
+                input_diagram,
+                svg_elements,
+            )),
+            None => Cow::Borrowed(diagram_generated.svg.as_str()),
         };
-        data_emit(output, stdout, &file_name(file_prefix, "diagram.svg"), &svg).await?;
+        Some(svg)
+    } else {
+        None
+    };
+    if data_is_selected(Data::Svg)
+        && let Some(svg) = svg.as_deref()
+    {
+        data_emit(output, stdout, &file_name(file_prefix, "diagram.svg"), svg).await?;
+    }
+
+    // === HTML === //
+    if data_is_selected(Data::Html)
+        && let Some(svg) = svg.as_deref()
+    {
+        let html = DiagramToHtmlMapper::map_with_svg(
+            diagram_generated,
+            svg,
+            file_prefix.unwrap_or("diagram"),
+        );
+        data_emit(
+            output,
+            stdout,
+            &file_name(file_prefix, "diagram.html"),
+            &html,
+        )
+        .await?;
+    }
+
+    // === PNG === //
+    // Binary, so it is only written to the output directory.
+    if let (Some(output), Some(png_options)) = (output, png_options) {
+        let svg_elements = svg_elements_structure_only
+            .as_ref()
+            .unwrap_or(&diagram_generated.svg_elements);
+        let png = SvgElementsToPngMapper::map(svg_elements, png_options)?;
+        file_emit(output, &file_name(file_prefix, "diagram.png"), &png).await?;
     }
 
     Ok(())
 }
 
+/// Writes the PDF with a page for each diagram to `{input_stem}_diagram.pdf`
+/// in the output directory.
+///
+/// Under `--structure-only`, each page is rendered from the structure-only SVG
+/// elements, matching the SVG and PNG outputs.
+async fn pdf_emit(
+    mut diagrams_focus_generated: Vec<DiagramFocusGenerated>,
+    structure_only: bool,
+    output: OutputDir<'_>,
+    pdf_options: &PdfOptions,
+    input_stem: &str,
+) -> Result<(), CliError> {
+    if structure_only {
+        diagrams_focus_generated
+            .iter_mut()
+            .for_each(|diagram_focus_generated| {
+                let diagram_generated = &mut diagram_focus_generated.diagram_generated;
+                diagram_generated.svg_elements = svg_elements_structure_only(diagram_generated);
+            });
+    }
+
+    let pdf = DiagramsToPdfMapper::map(&diagrams_focus_generated, pdf_options)?;
+    file_emit(output, &file_name(Some(input_stem), "diagram.pdf"), &pdf).await
+}
+
+/// Returns a copy of the diagram's SVG elements without styles, so that only
+/// values relevant to the structure of the diagram remain.
+fn svg_elements_structure_only(diagram_generated: &DiagramGenerated) -> SvgElements<'static> {
+    let mut svg_elements = diagram_generated.svg_elements.clone();
+    svg_elements.css = Css::default();
+    svg_elements.tailwind_classes = EntityTailwindClasses::default();
+    svg_elements
+        .svg_edge_infos
+        .iter_mut()
+        .for_each(|svg_edge_info| {
+            svg_edge_info.locus_path_d = String::new();
+        });
+    svg_elements
+}
+
+/// Directory to write output files to.
+#[derive(Clone, Copy, Debug)]
+struct OutputDir<'p> {
+    /// Path to the directory.
+    path: &'p Path,
+    /// Whether to skip rewriting files that already have the same contents.
+    ///
+    /// Set under `--watch`, so that tools watching the output directory only
+    /// see the stages that changed.
+    unchanged_skip: bool,
+}
+
 /// Writes the given `contents` to the output directory and/or stdout.
 ///
 /// * `output`: directory to write `file_name` to, if `Some`.
@@ -419,13 +1210,13 @@ async fn diagram_stages_emit(
 ///   `ir_diagram.yaml`.
 /// * `contents`: the data to write.
 async fn data_emit(
-    output: Option<&Path>,
+    output: Option<OutputDir<'_>>,
     stdout: bool,
     file_name: &str,
     contents: &str,
 ) -> Result<(), CliError> {
     if let Some(output) = output {
-        tokio::fs::write(output.join(file_name), contents).await?;
+        file_emit(output, file_name, contents.as_bytes()).await?;
     }
     if stdout {
         print!("{contents}");
@@ -435,3 +1226,24 @@ async fn data_emit(
     }
     Ok(())
 }
+
+/// Writes the given `contents` to `file_name` in the output directory.
+///
+/// When `output.unchanged_skip` is set, the file is not rewritten if it
+/// already has the same contents.
+async fn file_emit(
+    output: OutputDir<'_>,
+    file_name: &str,
+    contents: &[u8],
+) -> Result<(), CliError> {
+    let OutputDir {
+        path,
+        unchanged_skip,
+    } = output;
+    let path = path.join(file_name);
+    if unchanged_skip && tokio::fs::read(&path).await.ok().as_deref() == Some(contents) {
+        return Ok(());
+    }
+    tokio::fs::write(path, contents).await?;
+    Ok(())
+}
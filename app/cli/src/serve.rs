@@ -0,0 +1,425 @@
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    fmt::Write,
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use axum::{
+    extract::{Query, State},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html,
+    },
+    routing::get,
+    Router,
+};
+use disposition::{
+    input_ir_model::issue::IssueLevels,
+    input_model::{DiagramFocus, InputDiagram, InputDiagramSpans},
+};
+use disposition_input_ir_rt::{
+    DiagramGenerateError, DiagramGenerator, EdgeAnimationActive, StringPlaceholderReplacer,
+    StringXmlEscaper,
+};
+use futures::Stream;
+use miette::NamedSource;
+use serde::Deserialize;
+use tokio::sync::watch;
+
+use crate::{
+    generate_error_report, input_watcher::InputWatcher, issues_report, CliError, InputSource,
+    IssueLevelArgs,
+};
+
+/// HTML page that the diagram and focus options are rendered into.
+const INDEX_HTML: &str = include_str!("serve/index.html");
+
+/// Arguments to serve a live preview of an input diagram.
+#[derive(clap::Args)]
+pub(crate) struct ServeArgs {
+    /// Path to the input diagram YAML file.
+    input: PathBuf,
+    /// Port to listen on.
+    ///
+    /// The server only listens on `127.0.0.1`. Use `0` to pick any free port.
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+    /// Levels of issues found in the input diagram.
+    #[command(flatten)]
+    issue_level_args: IssueLevelArgs,
+}
+
+/// State shared between the HTTP handlers and the input file watcher.
+struct ServeState {
+    /// Path to the input diagram YAML file.
+    input: PathBuf,
+    /// Levels of issues found in the input diagram.
+    issue_levels: IssueLevels,
+    /// Diagrams generated from the current contents of the input file.
+    ///
+    /// Requests clone the `Arc` and release the lock before rendering the
+    /// page, so that a large preview is not copied while it is locked.
+    preview: Mutex<Arc<Preview>>,
+    /// Incremented each time the preview is regenerated, so that open pages
+    /// reload.
+    reload_tx: watch::Sender<u64>,
+}
+
+/// Diagrams generated from the current contents of the input file.
+enum Preview {
+    /// The diagram was generated.
+    Generated {
+        /// The interactive SVG.
+        svg: String,
+        /// Focus states that may be selected, from
+        /// `DiagramGenerator::focuses_collect`.
+        focuses: Vec<DiagramFocus<'static>>,
+        /// SVG with each focus baked in, in the same order as `focuses`.
+        svgs_focus: Result<Vec<String>, String>,
+    },
+    /// The input file could not be read, deserialized, or generated.
+    Failed {
+        /// Description of the failure.
+        message: String,
+    },
+}
+
+/// Query parameters of the preview page.
+#[derive(Deserialize)]
+struct IndexQuery {
+    /// Kind and ID of the focused entity, e.g. `process_step:proc_a_step_1`,
+    /// or `idle` for nothing focused.
+    ///
+    /// The interactive diagram is shown when this is absent.
+    focus: Option<String>,
+}
+
+/// Serves a live preview of the input diagram on localhost, until the process
+/// is stopped.
+///
+/// The preview is regenerated when the input file changes, and open pages are
+/// told to reload through server-sent events.
+pub(crate) async fn run(serve_args: ServeArgs) -> Result<(), CliError> {
+    let ServeArgs {
+        input,
+        port,
+        issue_level_args,
+    } = serve_args;
+    let issue_levels = issue_level_args.issue_levels();
+
+    let preview = Preview::generate(&input, &issue_levels).await;
+    let (reload_tx, _reload_rx) = watch::channel(0);
+    let serve_state = Arc::new(ServeState {
+        input,
+        issue_levels,
+        preview: Mutex::new(Arc::new(preview)),
+        reload_tx,
+    });
+    tokio::spawn(preview_regenerate_on_change(Arc::clone(&serve_state)));
+
+    let router = Router::new()
+        .route("/", get(index))
+        .route("/events", get(events))
+        .with_state(Arc::clone(&serve_state));
+    let listener =
+        tokio::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await?;
+    eprintln!(
+        "Serving `{}` at http://{}/",
+        serve_state.input.display(),
+        listener.local_addr()?
+    );
+    axum::serve(listener, router).await?;
+
+    Ok(())
+}
+
+/// Regenerates the preview each time the input file changes, and notifies
+/// open pages to reload.
+async fn preview_regenerate_on_change(serve_state: Arc<ServeState>) {
+    let mut input_watcher = InputWatcher::new([serve_state.input.clone()]).await;
+    loop {
+        input_watcher.changed_wait().await;
+        eprintln!("`{}` changed, regenerating.", serve_state.input.display());
+
+        let preview = Preview::generate(&serve_state.input, &serve_state.issue_levels).await;
+        *serve_state
+            .preview
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(preview);
+        serve_state
+            .reload_tx
+            .send_modify(|generation| *generation += 1);
+    }
+}
+
+/// Returns the preview page, with the diagram for the selected focus.
+async fn index(
+    State(serve_state): State<Arc<ServeState>>,
+    Query(index_query): Query<IndexQuery>,
+) -> Html<String> {
+    let IndexQuery { focus } = index_query;
+    let preview = Arc::clone(
+        &serve_state
+            .preview
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+
+    let (focuses, diagram) = match &*preview {
+        Preview::Generated {
+            svg,
+            focuses,
+            svgs_focus,
+        } => {
+            let focus_index = focus.as_deref().and_then(|focus| {
+                focuses
+                    .iter()
+                    .position(|diagram_focus| focus_query_value(diagram_focus) == focus)
+            });
+            let diagram = match focus_index {
+                Some(focus_index) => match svgs_focus {
+                    Ok(svgs_focus) => Cow::Borrowed(svgs_focus[focus_index].as_str()),
+                    Err(message) => Cow::Owned(message_html(message)),
+                },
+                None => Cow::Borrowed(svg.as_str()),
+            };
+            (focuses.as_slice(), diagram)
+        }
+        Preview::Failed { message } => (&[][..], Cow::Owned(message_html(message))),
+    };
+
+    Html(page_html(
+        &serve_state.input,
+        focuses,
+        focus.as_deref(),
+        &diagram,
+    ))
+}
+
+/// Returns a stream of `reload` events, sent each time the preview is
+/// regenerated.
+async fn events(
+    State(serve_state): State<Arc<ServeState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let reload_rx = serve_state.reload_tx.subscribe();
+    let reload_events = futures::stream::unfold(reload_rx, |mut reload_rx| async move {
+        reload_rx.changed().await.ok()?;
+        let generation = *reload_rx.borrow_and_update();
+        let event = Event::default()
+            .event("reload")
+            .data(generation.to_string());
+        Some((Ok(event), reload_rx))
+    });
+
+    Sse::new(reload_events).keep_alive(KeepAlive::default())
+}
+
+impl Preview {
+    /// Reads the input file, and generates the interactive diagram.
+    ///
+    /// Issues are reported to stderr, as when generating diagram files.
+    async fn generate(input: &Path, issue_levels: &IssueLevels) -> Self {
+        match Self::try_generate(input, issue_levels).await {
+            Ok(preview) => preview,
+            Err(error) => {
+                eprintln!("Error: {error}");
+                let mut message = error.to_string();
+                if let CliError::Generate(DiagramGenerateError::IssuesDenied { issues }) = &error {
+                    issues.iter().for_each(|issue| {
+                        let _ = write!(message, "\n* {issue}");
+                    });
+                }
+                Preview::Failed { message }
+            }
+        }
+    }
+
+    async fn try_generate(input: &Path, issue_levels: &IssueLevels) -> Result<Self, CliError> {
+        let contents = tokio::fs::read_to_string(input).await?;
+
+        // Generating the diagram for every focus takes long enough to stall
+        // other requests, so it runs on the blocking thread pool.
+        let input = input.to_path_buf();
+        let issue_levels = issue_levels.clone();
+        tokio::task::spawn_blocking(move || {
+            Self::contents_generate(&input, contents, &issue_levels)
+        })
+        .await
+        .map_err(std::io::Error::from)?
+    }
+
+    /// Generates the interactive diagram and the diagram for each focus from
+    /// the contents of the input file.
+    fn contents_generate(
+        input: &Path,
+        contents: String,
+        issue_levels: &IssueLevels,
+    ) -> Result<Self, CliError> {
+        let input_diagram: InputDiagram<'static> = serde_saphyr::from_str(&contents)?;
+        let input_source = InputSource {
+            named_source: NamedSource::new(input.display().to_string(), contents.clone()),
+            input_diagram_spans: InputDiagramSpans::from_yaml(&contents),
+        };
+
+        let diagram_generated = DiagramGenerator::generate_with_issue_levels(
+            &input_diagram,
+            EdgeAnimationActive::OnProcessStepFocus,
+            issue_levels,
+        )
+        .map_err(|error| generate_error_report(error, &input_source))?;
+        issues_report(&diagram_generated.ir_diagram_issues, &input_source);
+
+        let focuses = DiagramGenerator::focuses_collect(&diagram_generated.input_diagram_merged);
+        let svgs_focus = svgs_focus_generate(&input_diagram, issue_levels);
+        Ok(Preview::Generated {
+            svg: diagram_generated.svg,
+            focuses,
+            svgs_focus,
+        })
+    }
+}
+
+/// Generates the SVG for each focus state, with the focus baked in.
+fn svgs_focus_generate(
+    input_diagram: &InputDiagram<'static>,
+    issue_levels: &IssueLevels,
+) -> Result<Vec<String>, String> {
+    DiagramGenerator::generate_per_process_step_or_tag_with_issue_levels(
+        input_diagram,
+        EdgeAnimationActive::OnProcessStepFocus,
+        issue_levels,
+    )
+    .map(|diagrams_focus_generated| {
+        diagrams_focus_generated
+            .into_iter()
+            .map(|diagram_focus_generated| diagram_focus_generated.diagram_generated.svg)
+            .collect()
+    })
+    .map_err(|error| error.to_string())
+}
+
+/// Returns the preview page for the input file, with the focus dropdown and
+/// the diagram to show.
+fn page_html(
+    input: &Path,
+    focuses: &[DiagramFocus<'static>],
+    focus_selected: Option<&str>,
+    diagram: &str,
+) -> String {
+    let focus_options = focus_options_html(focuses, focus_selected);
+    let title = StringXmlEscaper::escape(&input.display().to_string());
+    StringPlaceholderReplacer::replace(
+        INDEX_HTML,
+        &[
+            ("__TITLE__", &title),
+            ("__FOCUS_OPTIONS__", &focus_options),
+            ("__DIAGRAM__", diagram),
+        ],
+    )
+}
+
+/// Returns the `<option>`s of the focus dropdown, with the interactive diagram
+/// first.
+fn focus_options_html(focuses: &[DiagramFocus<'static>], focus_selected: Option<&str>) -> String {
+    let mut focus_options = String::from("      <option value=\"\">interactive</option>\n");
+    focuses.iter().for_each(|diagram_focus| {
+        let focus_query_value = focus_query_value(diagram_focus);
+        let selected = if focus_selected == Some(focus_query_value.as_str()) {
+            " selected"
+        } else {
+            ""
+        };
+        let focus_query_value = StringXmlEscaper::escape(&focus_query_value);
+        let label = match diagram_focus {
+            DiagramFocus::None => String::from("nothing focused"),
+            DiagramFocus::Process(process_id) => {
+                format!("process: {}", StringXmlEscaper::escape(process_id.as_str()))
+            }
+            DiagramFocus::ProcessStep {
+                process_step_id, ..
+            } => format!(
+                "process step: {}",
+                StringXmlEscaper::escape(process_step_id.as_str())
+            ),
+            DiagramFocus::Tag(tag_id) => {
+                format!("tag: {}", StringXmlEscaper::escape(tag_id.as_str()))
+            }
+        };
+        let _ = writeln!(
+            focus_options,
+            "      <option value=\"{focus_query_value}\"{selected}>{label}</option>"
+        );
+    });
+    focus_options
+}
+
+/// Returns the `focus` query parameter that selects the focus.
+///
+/// The kind of the focused entity is included, as a process, process step, and
+/// tag may share the same ID.
+fn focus_query_value(diagram_focus: &DiagramFocus<'_>) -> String {
+    match diagram_focus {
+        DiagramFocus::None => String::from("idle"),
+        DiagramFocus::Process(process_id) => format!("process:{process_id}"),
+        DiagramFocus::ProcessStep {
+            process_step_id, ..
+        } => format!("process_step:{process_step_id}"),
+        DiagramFocus::Tag(tag_id) => format!("tag:{tag_id}"),
+    }
+}
+
+/// Returns a message to show in place of the diagram.
+fn message_html(message: &str) -> String {
+    format!("<pre>{}</pre>", StringXmlEscaper::escape(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use disposition::input_model::{process::ProcessId, tag::TagId};
+
+    use super::*;
+
+    #[test]
+    fn page_html_does_not_replace_placeholders_within_values() {
+        let focuses = [
+            DiagramFocus::Process(ProcessId::new("__DIAGRAM__").unwrap()),
+            DiagramFocus::Process(ProcessId::new("__FOCUS_OPTIONS__").unwrap()),
+        ];
+
+        let page = page_html(
+            Path::new("__DIAGRAM__.yaml"),
+            &focuses,
+            Some("process:__DIAGRAM__"),
+            "<svg></svg>",
+        );
+
+        assert!(page.contains("<title>__DIAGRAM__.yaml - disposition</title>"));
+        assert!(page.contains(
+            "<option value=\"process:__DIAGRAM__\" selected>process: __DIAGRAM__</option>"
+        ));
+        assert!(page.contains(
+            "<option value=\"process:__FOCUS_OPTIONS__\">process: __FOCUS_OPTIONS__</option>"
+        ));
+        assert_eq!(1, page.matches("<svg></svg>").count());
+    }
+
+    #[test]
+    fn focus_options_html_selects_only_focus_of_matching_kind() {
+        let focuses = [
+            DiagramFocus::None,
+            DiagramFocus::Process(ProcessId::new("shared_id").unwrap()),
+            DiagramFocus::Tag(TagId::new("shared_id").unwrap()),
+        ];
+
+        let focus_options = focus_options_html(&focuses, Some("tag:shared_id"));
+
+        assert!(focus_options
+            .contains("<option value=\"process:shared_id\">process: shared_id</option>"));
+        assert!(focus_options
+            .contains("<option value=\"tag:shared_id\" selected>tag: shared_id</option>"));
+        assert_eq!(1, focus_options.matches(" selected").count());
+    }
+}
@@ -0,0 +1,463 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use disposition::{
+    input_ir_model::issue::{IssueLevel, IssueLevels},
+    input_model::{InputDiagram, InputDiagramSpans},
+    input_rt::{ImportSourceSpan, InputDiagramLinter},
+};
+use disposition_input_ir_rt::DiagramGenerator;
+use miette::{Diagnostic, LabeledSpan, MietteDiagnostic, NamedSource, Report, Severity};
+use serde::Serialize;
+
+use crate::{CliError, IssueLevelArgs};
+
+use self::sarif_log::SarifLog;
+
+mod sarif_log;
+
+/// Arguments to check input diagrams for errors.
+#[derive(clap::Args)]
+pub(crate) struct ValidateArgs {
+    /// Paths to the input diagram YAML files.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+    /// Format to write the results to stdout in.
+    #[arg(long, value_enum, default_value_t = ValidateFormat::Text)]
+    format: ValidateFormat,
+    /// Levels of issues found in the input diagrams.
+    ///
+    /// References to undeclared IDs default to `deny`, and other issues to
+    /// `warn`.
+    #[command(flatten)]
+    issue_level_args: IssueLevelArgs,
+}
+
+/// Format to write validation results in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ValidateFormat {
+    /// Human readable diagnostics, with snippets of the input YAML.
+    Text,
+    /// JSON object with the findings of each file.
+    Json,
+    /// SARIF 2.1.0 log, for code scanning tools to annotate the input YAML.
+    Sarif,
+}
+
+/// Validates each input diagram file, and writes the results to stdout.
+///
+/// Returns [`CliError::ValidateFailed`] if any file has an error.
+pub(crate) async fn run(validate_args: ValidateArgs) -> Result<(), CliError> {
+    let ValidateArgs {
+        files,
+        format,
+        issue_level_args,
+    } = validate_args;
+    // A diagram with references to undeclared IDs is broken, so those fail
+    // validation even without `--strict`.
+    let issue_levels = issue_level_args.issue_levels_over(IssueLevels::references_strict());
+
+    let mut files_validated = Vec::with_capacity(files.len());
+    for file in files {
+        files_validated.push(file_validate(&file, &issue_levels).await);
+    }
+
+    let error_count = findings_count(&files_validated, FindingLevel::Error);
+    let warning_count = findings_count(&files_validated, FindingLevel::Warning);
+    match format {
+        ValidateFormat::Text => text_emit(&files_validated, error_count, warning_count),
+        ValidateFormat::Json => {
+            let validate_report = ValidateReport {
+                files: &files_validated,
+                error_count,
+                warning_count,
+            };
+            println!("{}", serde_json::to_string_pretty(&validate_report)?);
+        }
+        ValidateFormat::Sarif => {
+            let sarif_log = SarifLog::new(&files_validated);
+            println!("{}", serde_json::to_string_pretty(&sarif_log)?);
+        }
+    }
+
+    if error_count > 0 {
+        Err(CliError::ValidateFailed { error_count })
+    } else {
+        Ok(())
+    }
+}
+
+/// Deserializes, maps, and lints one input diagram file.
+///
+/// Read and deserialization failures are recorded as error findings, so that
+/// the remaining files are still validated.
+async fn file_validate(file: &Path, issue_levels: &IssueLevels) -> FileValidated {
+    let path = file.display().to_string();
+    let contents = match tokio::fs::read_to_string(file).await {
+        Ok(contents) => contents,
+        Err(error) => {
+            let finding = Finding {
+                level: FindingLevel::Error,
+                code: String::from("disposition::io"),
+                message: error.to_string(),
+                help: None,
+                field_path: None,
+                region: None,
+                fix: None,
+                labels: Vec::new(),
+            };
+            return FileValidated {
+                path,
+                contents: None,
+                findings: vec![finding],
+            };
+        }
+    };
+
+    let input_diagram = match serde_saphyr::from_str::<InputDiagram<'static>>(&contents) {
+        Ok(input_diagram) => input_diagram,
+        Err(error) => {
+            let offset = error.location().map(|location| {
+                ImportSourceSpan::from_line_column(
+                    &contents,
+                    usize::try_from(location.line()).unwrap_or(usize::MAX),
+                    usize::try_from(location.column()).unwrap_or(usize::MAX),
+                )
+                .offset()
+            });
+            let labels = offset
+                .map(|offset| vec![LabeledSpan::new(Some(String::from("here")), offset, 0)])
+                .unwrap_or_default();
+            let finding = Finding {
+                level: FindingLevel::Error,
+                code: String::from("disposition::yaml_deserialize"),
+                message: error.without_snippet().to_string(),
+                help: None,
+                field_path: None,
+                region: offset.map(|offset| Region::new(&contents, offset, 0)),
+                fix: None,
+                labels,
+            };
+            return FileValidated {
+                path,
+                contents: Some(contents),
+                findings: vec![finding],
+            };
+        }
+    };
+    let input_diagram_spans = InputDiagramSpans::from_yaml(&contents);
+
+    let (input_diagram_merged, ir_diagram_and_issues) =
+        DiagramGenerator::ir_diagram_map(&input_diagram);
+    let issue_findings = ir_diagram_and_issues
+        .issues
+        .into_iter()
+        .filter_map(|mut issue| {
+            let level = match issue_levels.level(issue.kind()) {
+                IssueLevel::Allow => return None,
+                IssueLevel::Warn => FindingLevel::Warning,
+                IssueLevel::Deny => FindingLevel::Error,
+            };
+            issue.span_attach(&input_diagram_spans);
+            let field_path = issue.field_path().to_owned();
+            Some(Finding::from_diagnostic(
+                &issue, level, field_path, None, &contents,
+            ))
+        });
+    let lint_findings = InputDiagramLinter::lint(&input_diagram_merged)
+        .into_iter()
+        .map(|mut lint| {
+            lint.span_attach(&input_diagram_spans);
+            let field_path = lint.field_path().to_owned();
+            let fix = lint.fix().map(|fix| fix.to_string());
+            Finding::from_diagnostic(&lint, FindingLevel::Warning, field_path, fix, &contents)
+        });
+    let findings = issue_findings.chain(lint_findings).collect::<Vec<_>>();
+
+    FileValidated {
+        path,
+        contents: Some(contents),
+        findings,
+    }
+}
+
+/// Returns the number of findings with the given level across all files.
+fn findings_count(files_validated: &[FileValidated], level: FindingLevel) -> usize {
+    files_validated
+        .iter()
+        .flat_map(|file_validated| file_validated.findings.iter())
+        .filter(|finding| finding.level == level)
+        .count()
+}
+
+/// Writes each finding as a diagnostic with a snippet of the input YAML,
+/// followed by a summary.
+fn text_emit(files_validated: &[FileValidated], error_count: usize, warning_count: usize) {
+    files_validated.iter().for_each(|file_validated| {
+        let FileValidated {
+            path,
+            contents,
+            findings,
+        } = file_validated;
+
+        if !findings.is_empty() {
+            println!("{path}:");
+        }
+        findings.iter().for_each(|finding| {
+            let severity = match finding.level {
+                FindingLevel::Error => Severity::Error,
+                FindingLevel::Warning => Severity::Warning,
+            };
+            let mut diagnostic = MietteDiagnostic::new(finding.message.clone())
+                .with_code(finding.code.clone())
+                .with_severity(severity)
+                .with_labels(finding.labels.iter().cloned());
+            if let Some(help) = finding.help.as_ref() {
+                diagnostic = diagnostic.with_help(help.clone());
+            }
+            let report = match contents {
+                Some(contents) => Report::new(diagnostic)
+                    .with_source_code(NamedSource::new(path.clone(), contents.clone())),
+                None => Report::new(diagnostic),
+            };
+            println!("{report:?}");
+            if let Some(fix) = finding.fix.as_ref() {
+                println!("  suggested fix: {fix}\n");
+            }
+        });
+    });
+
+    println!(
+        "Validated {} file(s): {error_count} error(s), {warning_count} warning(s).",
+        files_validated.len()
+    );
+}
+
+/// Results of validating every input diagram file, as written by
+/// `--format json`.
+#[derive(Serialize)]
+struct ValidateReport<'f> {
+    /// Results of each file.
+    files: &'f [FileValidated],
+    /// Number of findings whose level is `error`.
+    error_count: usize,
+    /// Number of findings whose level is `warning`.
+    warning_count: usize,
+}
+
+/// Results of validating one input diagram file.
+#[derive(Serialize)]
+struct FileValidated {
+    /// Path to the file, as passed on the command line.
+    path: String,
+    /// Contents of the file, if it could be read.
+    #[serde(skip)]
+    contents: Option<String>,
+    /// Errors and warnings found in the file.
+    findings: Vec<Finding>,
+}
+
+/// An error or warning found in an input diagram file.
+#[derive(Serialize)]
+struct Finding {
+    /// Whether this fails validation.
+    level: FindingLevel,
+    /// Diagnostic code, e.g. `disposition::tag_things_tag_unknown`.
+    code: String,
+    /// Description of the finding.
+    message: String,
+    /// How to address the finding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<String>,
+    /// Path to the offending value, e.g. `tag_things.tag_a[0]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field_path: Option<String>,
+    /// Location of the offending value in the file, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+    /// Suggested change to the input diagram that addresses the finding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+    /// Labelled spans within the file, used to render text snippets.
+    #[serde(skip)]
+    labels: Vec<LabeledSpan>,
+}
+
+impl Finding {
+    /// Returns a `Finding` from a diagnostic whose spans have been attached.
+    fn from_diagnostic(
+        diagnostic: &dyn Diagnostic,
+        level: FindingLevel,
+        field_path: String,
+        fix: Option<String>,
+        contents: &str,
+    ) -> Self {
+        let labels = diagnostic
+            .labels()
+            .map(Iterator::collect::<Vec<_>>)
+            .unwrap_or_default();
+        let region = labels
+            .first()
+            .map(|label| Region::new(contents, label.offset(), label.len()));
+
+        Finding {
+            level,
+            code: diagnostic
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            message: diagnostic.to_string(),
+            help: diagnostic.help().map(|help| help.to_string()),
+            field_path: Some(field_path),
+            region,
+            fix,
+            labels,
+        }
+    }
+}
+
+/// Whether a finding fails validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FindingLevel {
+    /// Fails validation.
+    Error,
+    /// Reported, but does not fail validation.
+    Warning,
+}
+
+/// 1-indexed line and column range of a finding within a file.
+///
+/// Columns count characters, and `end_column` is the column after the last
+/// character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Region {
+    /// Returns the `Region` of the byte range `offset..offset + len`.
+    fn new(contents: &str, offset: usize, len: usize) -> Self {
+        let (start_line, start_column) = Self::line_column(contents, offset);
+        let (end_line, end_column) = Self::line_column(contents, offset + len);
+        Region {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Returns the 1-indexed line and column of a byte offset.
+    fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+        let prefix = contents.get(..offset).unwrap_or(contents);
+        let line = prefix.matches('\n').count() + 1;
+        let line_start = prefix.rfind('\n').map_or(0, |index| index + 1);
+        let column = prefix[line_start..].chars().count() + 1;
+        (line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "things:\n  t_é: \"Ünïcode\"\n  t_b: B\n";
+
+    #[test]
+    fn region_new_counts_characters_within_line() {
+        let offset = CONTENTS.find("\"Ünïcode\"").unwrap();
+
+        assert_eq!(
+            Region {
+                start_line: 2,
+                start_column: 8,
+                end_line: 2,
+                end_column: 17,
+            },
+            Region::new(CONTENTS, offset, "\"Ünïcode\"".len())
+        );
+    }
+
+    #[test]
+    fn region_new_spans_multiple_lines() {
+        let offset = CONTENTS.find("t_é").unwrap();
+        let len = CONTENTS.find("t_b").unwrap() + "t_b".len() - offset;
+
+        assert_eq!(
+            Region {
+                start_line: 2,
+                start_column: 3,
+                end_line: 3,
+                end_column: 6,
+            },
+            Region::new(CONTENTS, offset, len)
+        );
+    }
+
+    #[test]
+    fn region_new_round_trips_import_source_span_from_line_column() {
+        let offset = ImportSourceSpan::from_line_column(CONTENTS, 2, 8).offset();
+
+        let region = Region::new(CONTENTS, offset, 0);
+
+        assert_eq!((2, 8), (region.start_line, region.start_column));
+    }
+
+    #[test]
+    fn validate_report_serializes_findings_and_counts() {
+        let files_validated = [file_validated()];
+        let validate_report = ValidateReport {
+            files: &files_validated,
+            error_count: 1,
+            warning_count: 0,
+        };
+
+        let json = serde_json::to_value(&validate_report).unwrap();
+
+        assert_eq!(
+            serde_json::json!({
+                "files": [{
+                    "path": "diagrams/my diagram.yaml",
+                    "findings": [{
+                        "level": "error",
+                        "code": "disposition::thing_unknown",
+                        "message": "`t_c` is not a thing.",
+                        "help": "Add `t_c` to `things`.",
+                        "field_path": "things.t_b",
+                        "region": {
+                            "start_line": 3,
+                            "start_column": 3,
+                            "end_line": 3,
+                            "end_column": 6,
+                        },
+                    }],
+                }],
+                "error_count": 1,
+                "warning_count": 0,
+            }),
+            json
+        );
+    }
+
+    pub(super) fn file_validated() -> FileValidated {
+        let offset = CONTENTS.find("t_b").unwrap();
+        FileValidated {
+            path: String::from("diagrams/my diagram.yaml"),
+            contents: Some(String::from(CONTENTS)),
+            findings: vec![Finding {
+                level: FindingLevel::Error,
+                code: String::from("disposition::thing_unknown"),
+                message: String::from("`t_c` is not a thing."),
+                help: Some(String::from("Add `t_c` to `things`.")),
+                field_path: Some(String::from("things.t_b")),
+                region: Some(Region::new(CONTENTS, offset, "t_b".len())),
+                fix: None,
+                labels: Vec::new(),
+            }],
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::path::{Component, Path};
+
+use serde::Serialize;
+
+use super::{FileValidated, Finding, FindingLevel, Region};
+
+/// [SARIF 2.1.0] log of validation findings.
+///
+/// Only the properties needed for code scanning tools to annotate the input
+/// YAML are written.
+///
+/// [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+#[derive(Serialize)]
+pub(super) struct SarifLog<'f> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [SarifRun<'f>; 1],
+}
+
+impl<'f> SarifLog<'f> {
+    /// Returns a `SarifLog` with one run containing every finding.
+    pub(super) fn new(files_validated: &'f [FileValidated]) -> Self {
+        let mut rules = files_validated
+            .iter()
+            .flat_map(|file_validated| file_validated.findings.iter())
+            .map(|finding| SarifRule {
+                id: finding.code.as_str(),
+            })
+            .collect::<Vec<_>>();
+        rules.sort_by_key(|rule| rule.id);
+        rules.dedup_by_key(|rule| rule.id);
+
+        let results = files_validated
+            .iter()
+            .flat_map(|file_validated| {
+                file_validated
+                    .findings
+                    .iter()
+                    .map(|finding| SarifResult::new(&file_validated.path, finding))
+            })
+            .collect::<Vec<_>>();
+
+        SarifLog {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json",
+            version: "2.1.0",
+            runs: [SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "disposition",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_HOMEPAGE"),
+                        rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results,
+            }],
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'f> {
+    tool: SarifTool<'f>,
+    column_kind: &'static str,
+    results: Vec<SarifResult<'f>>,
+}
+
+#[derive(Serialize)]
+struct SarifTool<'f> {
+    driver: SarifDriver<'f>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'f> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule<'f>>,
+}
+
+#[derive(Serialize)]
+struct SarifRule<'f> {
+    id: &'f str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'f> {
+    rule_id: &'f str,
+    level: &'static str,
+    message: SarifMessage,
+    locations: [SarifLocation; 1],
+}
+
+impl<'f> SarifResult<'f> {
+    fn new(path: &'f str, finding: &'f Finding) -> Self {
+        let level = match finding.level {
+            FindingLevel::Error => "error",
+            FindingLevel::Warning => "warning",
+        };
+        let mut text = finding.message.clone();
+        if let Some(help) = finding.help.as_deref() {
+            text.push_str("\n\n");
+            text.push_str(help);
+        }
+        if let Some(fix) = finding.fix.as_deref() {
+            text.push_str("\n\nSuggested fix: ");
+            text.push_str(fix);
+        }
+
+        SarifResult {
+            rule_id: &finding.code,
+            level,
+            message: SarifMessage { text },
+            locations: [SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: artifact_uri(Path::new(path)),
+                    },
+                    region: finding.region.map(SarifRegion::from),
+                },
+            }],
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl From<Region> for SarifRegion {
+    fn from(region: Region) -> Self {
+        let Region {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        } = region;
+        SarifRegion {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// Returns the `artifactLocation.uri` of an input diagram path.
+///
+/// Relative paths are written as relative references, e.g.
+/// `diagrams/my%20diagram.yaml`, which code scanning tools resolve against the
+/// repository root. Absolute paths are written as `file:` URIs, e.g.
+/// `file:///home/user/diagram.yaml`.
+///
+/// Path segments are joined with `/` and percent-encoded, so that Windows
+/// paths and file names with spaces or non-ASCII characters are valid URIs.
+fn artifact_uri(path: &Path) -> String {
+    let mut uri = String::new();
+    if path.has_root() {
+        uri.push_str("file://");
+    }
+    let mut separator_needed = false;
+    path.components().for_each(|component| {
+        let segment = match component {
+            Component::Prefix(prefix) => {
+                // e.g. `C:`, written as `file:///C:/..`.
+                uri.push('/');
+                uri.push_str(&prefix.as_os_str().to_string_lossy());
+                separator_needed = false;
+                return;
+            }
+            Component::RootDir => {
+                uri.push('/');
+                separator_needed = false;
+                return;
+            }
+            Component::CurDir => return,
+            Component::ParentDir => String::from(".."),
+            Component::Normal(segment) => {
+                urlencoding::encode(&segment.to_string_lossy()).into_owned()
+            }
+        };
+        if separator_needed {
+            uri.push('/');
+        }
+        uri.push_str(&segment);
+        separator_needed = true;
+    });
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::validate::tests::file_validated;
+
+    #[test]
+    fn sarif_log_serializes_results_with_relative_uri_and_region() {
+        let files_validated = [file_validated()];
+
+        let json = serde_json::to_value(SarifLog::new(&files_validated)).unwrap();
+
+        let run = &json["runs"][0];
+        assert_eq!("2.1.0", json["version"]);
+        assert_eq!("unicodeCodePoints", run["columnKind"]);
+        assert_eq!(
+            serde_json::json!([{ "id": "disposition::thing_unknown" }]),
+            run["tool"]["driver"]["rules"]
+        );
+        assert_eq!(
+            serde_json::json!([{
+                "ruleId": "disposition::thing_unknown",
+                "level": "error",
+                "message": {
+                    "text": "`t_c` is not a thing.\n\nAdd `t_c` to `things`.",
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "diagrams/my%20diagram.yaml" },
+                        "region": {
+                            "startLine": 3,
+                            "startColumn": 3,
+                            "endLine": 3,
+                            "endColumn": 6,
+                        },
+                    },
+                }],
+            }]),
+            run["results"]
+        );
+    }
+
+    #[test]
+    fn artifact_uri_percent_encodes_relative_path_segments() {
+        assert_eq!(
+            "diagrams/%C3%BCber%20diagram.yaml",
+            artifact_uri(Path::new("./diagrams/über diagram.yaml"))
+        );
+        assert_eq!(
+            "../a%23b/diagram.yaml",
+            artifact_uri(Path::new("../a#b/diagram.yaml"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn artifact_uri_writes_absolute_path_as_file_uri() {
+        assert_eq!(
+            "file:///home/user/my%20diagram.yaml",
+            artifact_uri(Path::new("/home/user/my diagram.yaml"))
+        );
+    }
+}
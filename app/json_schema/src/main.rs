@@ -1,10 +1,24 @@
 #[cfg(all(feature = "schemars", not(feature = "test")))]
-use disposition::{input_model::InputDiagram, schemars};
+use disposition::{input_model::InputDiagram, layout_model::DiagramLayout, schemars};
 
+/// Prints the JSON schema for the type named by the first argument:
+///
+/// * `input_diagram` (default): The input diagram YAML.
+/// * `diagram_layout`: The layout JSON written by `--data layout-json`.
 fn main() {
     #[cfg(all(feature = "schemars", not(feature = "test")))]
     {
-        let schema = schemars::schema_for!(InputDiagram);
+        let schema_name = std::env::args().nth(1);
+        let schema = match schema_name.as_deref() {
+            None | Some("input_diagram") => schemars::schema_for!(InputDiagram),
+            Some("diagram_layout") => schemars::schema_for!(DiagramLayout),
+            Some(schema_name) => {
+                eprintln!(
+                    "Unknown schema `{schema_name}`, expected `input_diagram` or `diagram_layout`."
+                );
+                std::process::exit(1);
+            }
+        };
         println!("{}", serde_json::to_string_pretty(&schema).unwrap());
     }
 
@@ -1,3 +1,13 @@
-pub use model_to_ir_issue::ModelToIrIssue;
+pub use self::{
+    dependency_cycle_edge::DependencyCycleEdge,
+    issue_level::IssueLevel,
+    issue_levels::IssueLevels,
+    model_to_ir_issue::ModelToIrIssue,
+    model_to_ir_issue_kind::{ModelToIrIssueKind, ModelToIrIssueKindParseError},
+};
 
+mod dependency_cycle_edge;
+mod issue_level;
+mod issue_levels;
 mod model_to_ir_issue;
+mod model_to_ir_issue_kind;
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use disposition_model_common::Id;
+use serde::{Deserialize, Serialize};
+
+/// An edge that is part of a dependency cycle, reported in
+/// [`ModelToIrIssue::ThingDependenciesCycle`].
+///
+/// `from` and `to` are the endpoints as written in the input diagram, which
+/// may be nested within the nodes that end up sharing a rank.
+///
+/// # Examples
+///
+/// ```rust
+/// use disposition_input_ir_model::issue::DependencyCycleEdge;
+/// use disposition_model_common::Id;
+///
+/// let dependency_cycle_edge = DependencyCycleEdge {
+///     field_path: "thing_dependencies.edge_b__a".to_owned(),
+///     from: Id::new("t_b").unwrap(),
+///     to: Id::new("t_a").unwrap(),
+/// };
+///
+/// assert_eq!(
+///     "`t_b` -> `t_a` (`thing_dependencies.edge_b__a`)",
+///     dependency_cycle_edge.to_string()
+/// );
+/// ```
+///
+/// [`ModelToIrIssue::ThingDependenciesCycle`]: crate::issue::ModelToIrIssue::ThingDependenciesCycle
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyCycleEdge {
+    /// Path to the edge group or layout edge that declares this edge, e.g.
+    /// `thing_dependencies.edge_a__b` or `thing_layout_edges.edge_layout_a__b`.
+    pub field_path: String,
+    /// ID of the node the edge starts from.
+    pub from: Id<'static>,
+    /// ID of the node the edge points to.
+    pub to: Id<'static>,
+}
+
+impl fmt::Display for DependencyCycleEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DependencyCycleEdge {
+            field_path,
+            from,
+            to,
+        } = self;
+        write!(f, "`{from}` -> `{to}` (`{field_path}`)")
+    }
+}
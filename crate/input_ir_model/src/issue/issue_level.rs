@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// How a kind of [`ModelToIrIssue`] is treated during diagram generation.
+///
+/// Mirrors `rustc`'s lint levels.
+///
+/// [`ModelToIrIssue`]: crate::issue::ModelToIrIssue
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueLevel {
+    /// The issue is discarded.
+    Allow,
+    /// The issue is reported, and the diagram is still generated.
+    #[default]
+    Warn,
+    /// The issue is reported, and the diagram is not generated.
+    Deny,
+}
@@ -0,0 +1,104 @@
+use disposition_model_common::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::issue::{IssueLevel, ModelToIrIssueKind};
+
+/// The [`IssueLevel`] of each kind of [`ModelToIrIssue`].
+///
+/// Kinds that are not in `kinds` use the `default` level.
+///
+/// # Examples
+///
+/// ```rust
+/// use disposition_input_ir_model::issue::{IssueLevel, IssueLevels, ModelToIrIssueKind};
+///
+/// // Fail on every issue except unknown `thing_names` keys.
+/// let mut issue_levels = IssueLevels::strict();
+/// issue_levels.kinds.insert(
+///     ModelToIrIssueKind::ThingNamesThingUnknown,
+///     IssueLevel::Allow,
+/// );
+///
+/// assert_eq!(
+///     IssueLevel::Deny,
+///     issue_levels.level(ModelToIrIssueKind::TagThingsThingUnknown)
+/// );
+/// assert_eq!(
+///     IssueLevel::Allow,
+///     issue_levels.level(ModelToIrIssueKind::ThingNamesThingUnknown)
+/// );
+/// ```
+///
+/// [`ModelToIrIssue`]: crate::issue::ModelToIrIssue
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IssueLevels {
+    /// Level for issue kinds that are not in `kinds`.
+    #[serde(default)]
+    pub default: IssueLevel,
+    /// Level for specific issue kinds, overriding `default`.
+    #[serde(default)]
+    pub kinds: Map<ModelToIrIssueKind, IssueLevel>,
+}
+
+impl IssueLevels {
+    /// Returns `IssueLevels` that warn on every issue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `IssueLevels` that deny every issue.
+    pub fn strict() -> Self {
+        Self {
+            default: IssueLevel::Deny,
+            kinds: Map::new(),
+        }
+    }
+
+    /// Returns `IssueLevels` that deny references to undeclared IDs, and warn
+    /// on every other issue.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_ir_model::issue::{IssueLevel, IssueLevels, ModelToIrIssueKind};
+    ///
+    /// let issue_levels = IssueLevels::references_strict();
+    ///
+    /// assert_eq!(
+    ///     IssueLevel::Deny,
+    ///     issue_levels.level(ModelToIrIssueKind::TagThingsThingUnknown)
+    /// );
+    /// assert_eq!(
+    ///     IssueLevel::Warn,
+    ///     issue_levels.level(ModelToIrIssueKind::ThingDependenciesCycle)
+    /// );
+    /// ```
+    pub fn references_strict() -> Self {
+        let kinds = [
+            ModelToIrIssueKind::EdgeGroupThingUnknown,
+            ModelToIrIssueKind::TagThingsTagUnknown,
+            ModelToIrIssueKind::TagThingsThingUnknown,
+            ModelToIrIssueKind::ThingNamesThingUnknown,
+            ModelToIrIssueKind::EntityTypesEntityUnknown,
+            ModelToIrIssueKind::StepThingInteractionsStepUnknown,
+            ModelToIrIssueKind::StepThingInteractionsEdgeGroupUnknown,
+        ]
+        .into_iter()
+        .map(|kind| (kind, IssueLevel::Deny))
+        .collect::<Map<ModelToIrIssueKind, IssueLevel>>();
+
+        Self {
+            default: IssueLevel::Warn,
+            kinds,
+        }
+    }
+
+    /// Returns the level for the given issue kind.
+    pub fn level(&self, kind: ModelToIrIssueKind) -> IssueLevel {
+        self.kinds.get(&kind).copied().unwrap_or(self.default)
+    }
+}
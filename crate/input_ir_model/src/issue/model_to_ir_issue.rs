@@ -1,7 +1,33 @@
+use disposition_input_model::{
+    process::{ProcessId, ProcessStepId},
+    tag::TagId,
+    theme::ThemeAttr,
+    thing::ThingId,
+    InputDiagramSpans,
+};
+use disposition_model_common::{edge::EdgeGroupId, Id};
+use miette::SourceSpan;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    issue::{DependencyCycleEdge, ModelToIrIssueKind},
+    ThemeValueSource,
+};
+
 /// Issue encountered while mapping the input model to the intermediate
 /// representation.
+///
+/// Most variants describe a reference in the input diagram to an ID that is
+/// not declared anywhere, e.g. a `ThingId` in an edge group that isn't in
+/// `things`. These don't prevent the diagram from being generated, but the
+/// referencing entry is ignored, so the diagram is likely not what the user
+/// intended.
+///
+/// Every variant carries the `field_path` of the offending value, using `.`
+/// to separate map keys and `[n]` for sequence indices, e.g.
+/// `thing_dependencies.edge_a__b.things[1]`. When the input diagram's
+/// [`InputDiagramSpans`] are available, [`ModelToIrIssue::span_attach`] sets
+/// each issue's `span` so the issue can be rendered with a source snippet.
 #[cfg_attr(
     all(feature = "schemars", not(feature = "test")),
     derive(schemars::JsonSchema)
@@ -9,4 +35,328 @@ use serde::{Deserialize, Serialize};
 #[derive(
     Clone, Debug, PartialEq, Eq, Serialize, Deserialize, thiserror::Error, miette::Diagnostic,
 )]
-pub enum ModelToIrIssue {}
+pub enum ModelToIrIssue {
+    /// A thing in a `thing_dependencies` / `thing_interactions` edge group is
+    /// not a declared thing, tag, process, or process step.
+    #[error("`{field_path}`: `{thing_id}` in edge group `{edge_group_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::edge_group_thing_unknown),
+        help("Add `{thing_id}` to `things`, or correct the ID.")
+    )]
+    EdgeGroupThingUnknown {
+        /// Path to the offending value, e.g.
+        /// `thing_dependencies.edge_a__b.things[1]`.
+        field_path: String,
+        /// ID of the edge group that references the unknown thing.
+        edge_group_id: EdgeGroupId<'static>,
+        /// The unknown thing ID.
+        thing_id: ThingId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared thing, tag, process, or process step")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A key in `tag_things` is not a declared tag.
+    #[error("`{field_path}`: tag `{tag_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::tag_things_tag_unknown),
+        help("Add `{tag_id}` to `tags`, or correct the ID.")
+    )]
+    TagThingsTagUnknown {
+        /// Path to the offending key, e.g. `tag_things.tag_a`.
+        field_path: String,
+        /// The unknown tag ID.
+        tag_id: TagId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared tag")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A thing listed in `tag_things` is not a declared thing.
+    #[error("`{field_path}`: thing `{thing_id}` in tag `{tag_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::tag_things_thing_unknown),
+        help("Add `{thing_id}` to `things`, or correct the ID.")
+    )]
+    TagThingsThingUnknown {
+        /// Path to the offending value, e.g. `tag_things.tag_a[0]`.
+        field_path: String,
+        /// ID of the tag that lists the unknown thing.
+        tag_id: TagId<'static>,
+        /// The unknown thing ID.
+        thing_id: ThingId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared thing")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A key in `thing_names` is not a declared thing.
+    #[error("`{field_path}`: thing `{thing_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::thing_names_thing_unknown),
+        help("Add `{thing_id}` to `things`, or correct the ID.")
+    )]
+    ThingNamesThingUnknown {
+        /// Path to the offending key, e.g. `thing_names.t_a`.
+        field_path: String,
+        /// The unknown thing ID.
+        thing_id: ThingId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared thing")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A key in `entity_types` is not a declared thing, tag, process, process
+    /// step, edge group, or edge.
+    #[error("`{field_path}`: entity `{entity_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::entity_types_entity_unknown),
+        help(
+            "`entity_types` keys must be a thing, tag, process, process step, edge group, or \
+             edge (`<edge_group_id>__<index>`) ID."
+        )
+    )]
+    EntityTypesEntityUnknown {
+        /// Path to the offending key, e.g. `entity_types.t_a`.
+        field_path: String,
+        /// The unknown entity ID.
+        entity_id: Id<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared entity")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A key in a process's `step_thing_interactions` is not a step of that
+    /// process.
+    #[error("`{field_path}`: step `{process_step_id}` is not a step of process `{process_id}`.")]
+    #[diagnostic(
+        code(disposition::step_thing_interactions_step_unknown),
+        help("Add `{process_step_id}` to `processes.{process_id}.steps`, or correct the ID.")
+    )]
+    StepThingInteractionsStepUnknown {
+        /// Path to the offending key, e.g.
+        /// `processes.proc_a.step_thing_interactions.proc_a_step_1`.
+        field_path: String,
+        /// ID of the process whose `step_thing_interactions` has the key.
+        process_id: ProcessId<'static>,
+        /// The unknown process step ID.
+        process_step_id: ProcessStepId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a step of `{process_id}`")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// An edge group listed in a process's `step_thing_interactions` is not a
+    /// declared `thing_interactions` / `thing_dependencies` edge group.
+    #[error("`{field_path}`: edge group `{edge_group_id}` does not exist.")]
+    #[diagnostic(
+        code(disposition::step_thing_interactions_edge_group_unknown),
+        help("Add `{edge_group_id}` to `thing_interactions`, or correct the ID.")
+    )]
+    StepThingInteractionsEdgeGroupUnknown {
+        /// Path to the offending value, e.g.
+        /// `processes.proc_a.step_thing_interactions.proc_a_step_1[0]`.
+        field_path: String,
+        /// ID of the process whose step references the unknown edge group.
+        process_id: ProcessId<'static>,
+        /// ID of the process step that references the unknown edge group.
+        process_step_id: ProcessStepId<'static>,
+        /// The unknown edge group ID.
+        edge_group_id: EdgeGroupId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a declared edge group")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// Dependency edges form a cycle through a `sequence` edge group or a
+    /// layout edge, so the nodes in the cycle are placed on the same rank.
+    ///
+    /// Cycles that `cyclic` and `symmetric` edge groups already form are
+    /// intentional, and are not reported.
+    #[error(
+        "`{field_path}`: {} form a dependency cycle, so they are placed on the same rank.",
+        ids_fmt(.node_ids)
+    )]
+    #[diagnostic(
+        code(disposition::thing_dependencies_cycle),
+        help(
+            "Remove or reverse one of the edges in the cycle, or use `kind: cyclic` if the \
+             cycle is intended:\n{}",
+            dependency_cycle_edges_fmt(.edges)
+        )
+    )]
+    ThingDependenciesCycle {
+        /// Path to the first non-cyclic edge group or layout edge in the
+        /// cycle, e.g. `thing_dependencies.edge_c__a`.
+        field_path: String,
+        /// IDs of the nodes that are placed on the same rank because of the
+        /// cycle.
+        ///
+        /// When the edges are between nested things, these are the ancestors
+        /// of the edges' endpoints that are siblings.
+        node_ids: Vec<Id<'static>>,
+        /// The dependency and layout edges that form the cycle.
+        edges: Vec<DependencyCycleEdge>,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("edge in a dependency cycle")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+
+    /// A theme attribute value cannot be parsed, or is not a known value for
+    /// that attribute, so it is ignored when styling the diagram.
+    ///
+    /// This is reported for values in `theme_default`, `theme_types_styles`,
+    /// `theme_thing_dependencies_styles`, and `theme_tag_things_focus`.
+    #[error("`{field_path}`: `{value}` is not a valid `{theme_attr}` value for `{entity}`.")]
+    #[diagnostic(
+        code(disposition::theme_attr_value_invalid),
+        help("`{theme_attr}` must be {expected}.")
+    )]
+    ThemeAttrValueInvalid {
+        /// Path to the offending value, e.g.
+        /// `theme_default.base_styles.node_defaults.fill_shade_normal`.
+        field_path: String,
+        /// Key of the styles entry that holds the value, e.g.
+        /// `node_defaults`, `t_a`, or the style alias `shade_light`.
+        entity: String,
+        /// The attribute whose value is invalid.
+        theme_attr: ThemeAttr,
+        /// The invalid value.
+        value: String,
+        /// Description of the values that are accepted for `theme_attr`, e.g.
+        /// `` a number, e.g. `4.0` ``.
+        expected: String,
+        /// Whether the value comes from the base diagram or the user's input
+        /// diagram.
+        value_source: ThemeValueSource,
+        /// Location of the offending value in the input YAML, if known.
+        ///
+        /// Set by [`ModelToIrIssue::span_attach`].
+        #[label("not a valid `{theme_attr}` value")]
+        #[serde(skip)]
+        span: Option<SourceSpan>,
+    },
+}
+
+impl ModelToIrIssue {
+    /// Returns the path to the offending value in the input diagram.
+    ///
+    /// Map keys are separated by `.`, and sequence items are suffixed with
+    /// `[n]`, e.g. `thing_dependencies.edge_a__b.things[1]`.
+    pub fn field_path(&self) -> &str {
+        match self {
+            ModelToIrIssue::EdgeGroupThingUnknown { field_path, .. }
+            | ModelToIrIssue::TagThingsTagUnknown { field_path, .. }
+            | ModelToIrIssue::TagThingsThingUnknown { field_path, .. }
+            | ModelToIrIssue::ThingNamesThingUnknown { field_path, .. }
+            | ModelToIrIssue::EntityTypesEntityUnknown { field_path, .. }
+            | ModelToIrIssue::StepThingInteractionsStepUnknown { field_path, .. }
+            | ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown { field_path, .. }
+            | ModelToIrIssue::ThingDependenciesCycle { field_path, .. }
+            | ModelToIrIssue::ThemeAttrValueInvalid { field_path, .. } => field_path,
+        }
+    }
+
+    /// Returns the kind of this issue.
+    pub fn kind(&self) -> ModelToIrIssueKind {
+        match self {
+            ModelToIrIssue::EdgeGroupThingUnknown { .. } => {
+                ModelToIrIssueKind::EdgeGroupThingUnknown
+            }
+            ModelToIrIssue::TagThingsTagUnknown { .. } => ModelToIrIssueKind::TagThingsTagUnknown,
+            ModelToIrIssue::TagThingsThingUnknown { .. } => {
+                ModelToIrIssueKind::TagThingsThingUnknown
+            }
+            ModelToIrIssue::ThingNamesThingUnknown { .. } => {
+                ModelToIrIssueKind::ThingNamesThingUnknown
+            }
+            ModelToIrIssue::EntityTypesEntityUnknown { .. } => {
+                ModelToIrIssueKind::EntityTypesEntityUnknown
+            }
+            ModelToIrIssue::StepThingInteractionsStepUnknown { .. } => {
+                ModelToIrIssueKind::StepThingInteractionsStepUnknown
+            }
+            ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown { .. } => {
+                ModelToIrIssueKind::StepThingInteractionsEdgeGroupUnknown
+            }
+            ModelToIrIssue::ThingDependenciesCycle { .. } => {
+                ModelToIrIssueKind::ThingDependenciesCycle
+            }
+            ModelToIrIssue::ThemeAttrValueInvalid { .. } => {
+                ModelToIrIssueKind::ThemeAttrValueInvalid
+            }
+        }
+    }
+
+    /// Sets the span of this issue to the location of its `field_path` in
+    /// the input YAML, so that it is rendered as a label on the source
+    /// snippet.
+    ///
+    /// The span is left unchanged if `input_diagram_spans` does not contain
+    /// the field path, e.g. when the value comes from the base diagram.
+    ///
+    /// Invalid theme attribute values are labelled at the value, and all other
+    /// issues at the map key or sequence item.
+    pub fn span_attach(&mut self, input_diagram_spans: &InputDiagramSpans) {
+        let Some(field_span) = input_diagram_spans.get(self.field_path()) else {
+            return;
+        };
+
+        match self {
+            ModelToIrIssue::EdgeGroupThingUnknown { span, .. }
+            | ModelToIrIssue::TagThingsTagUnknown { span, .. }
+            | ModelToIrIssue::TagThingsThingUnknown { span, .. }
+            | ModelToIrIssue::ThingNamesThingUnknown { span, .. }
+            | ModelToIrIssue::EntityTypesEntityUnknown { span, .. }
+            | ModelToIrIssue::StepThingInteractionsStepUnknown { span, .. }
+            | ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown { span, .. }
+            | ModelToIrIssue::ThingDependenciesCycle { span, .. } => {
+                *span = Some(SourceSpan::from(field_span.key_or_value()))
+            }
+            ModelToIrIssue::ThemeAttrValueInvalid { span, .. } => {
+                *span = Some(SourceSpan::from(field_span.value.clone()))
+            }
+        }
+    }
+}
+
+/// Formats IDs as a comma separated list, e.g. `` `t_a`, `t_b` ``.
+fn ids_fmt(ids: &[Id<'static>]) -> String {
+    ids.iter()
+        .map(|id| format!("`{id}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats dependency cycle edges as an indented list, one edge per line.
+fn dependency_cycle_edges_fmt(edges: &[DependencyCycleEdge]) -> String {
+    edges
+        .iter()
+        .map(|edge| format!("  * {edge}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,171 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of a [`ModelToIrIssue`], without its details.
+///
+/// Used to configure the [`IssueLevel`] of each kind of issue.
+///
+/// # Examples
+///
+/// Valid string representations (snake_case), which match the diagnostic
+/// code of each issue:
+///
+/// * `"edge_group_thing_unknown"`
+/// * `"tag_things_tag_unknown"`
+/// * `"tag_things_thing_unknown"`
+/// * `"thing_names_thing_unknown"`
+/// * `"entity_types_entity_unknown"`
+/// * `"step_thing_interactions_step_unknown"`
+/// * `"step_thing_interactions_edge_group_unknown"`
+/// * `"thing_dependencies_cycle"`
+/// * `"theme_attr_value_invalid"`
+///
+/// [`ModelToIrIssue`]: crate::issue::ModelToIrIssue
+/// [`IssueLevel`]: crate::issue::IssueLevel
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelToIrIssueKind {
+    /// See [`ModelToIrIssue::EdgeGroupThingUnknown`].
+    ///
+    /// [`ModelToIrIssue::EdgeGroupThingUnknown`]: crate::issue::ModelToIrIssue::EdgeGroupThingUnknown
+    EdgeGroupThingUnknown,
+    /// See [`ModelToIrIssue::TagThingsTagUnknown`].
+    ///
+    /// [`ModelToIrIssue::TagThingsTagUnknown`]: crate::issue::ModelToIrIssue::TagThingsTagUnknown
+    TagThingsTagUnknown,
+    /// See [`ModelToIrIssue::TagThingsThingUnknown`].
+    ///
+    /// [`ModelToIrIssue::TagThingsThingUnknown`]: crate::issue::ModelToIrIssue::TagThingsThingUnknown
+    TagThingsThingUnknown,
+    /// See [`ModelToIrIssue::ThingNamesThingUnknown`].
+    ///
+    /// [`ModelToIrIssue::ThingNamesThingUnknown`]: crate::issue::ModelToIrIssue::ThingNamesThingUnknown
+    ThingNamesThingUnknown,
+    /// See [`ModelToIrIssue::EntityTypesEntityUnknown`].
+    ///
+    /// [`ModelToIrIssue::EntityTypesEntityUnknown`]: crate::issue::ModelToIrIssue::EntityTypesEntityUnknown
+    EntityTypesEntityUnknown,
+    /// See [`ModelToIrIssue::StepThingInteractionsStepUnknown`].
+    ///
+    /// [`ModelToIrIssue::StepThingInteractionsStepUnknown`]: crate::issue::ModelToIrIssue::StepThingInteractionsStepUnknown
+    StepThingInteractionsStepUnknown,
+    /// See [`ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown`].
+    ///
+    /// [`ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown`]: crate::issue::ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown
+    StepThingInteractionsEdgeGroupUnknown,
+    /// See [`ModelToIrIssue::ThingDependenciesCycle`].
+    ///
+    /// [`ModelToIrIssue::ThingDependenciesCycle`]: crate::issue::ModelToIrIssue::ThingDependenciesCycle
+    ThingDependenciesCycle,
+    /// See [`ModelToIrIssue::ThemeAttrValueInvalid`].
+    ///
+    /// [`ModelToIrIssue::ThemeAttrValueInvalid`]: crate::issue::ModelToIrIssue::ThemeAttrValueInvalid
+    ThemeAttrValueInvalid,
+}
+
+impl ModelToIrIssueKind {
+    /// Every issue kind, in declaration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_ir_model::issue::ModelToIrIssueKind;
+    ///
+    /// ModelToIrIssueKind::ALL.iter().for_each(|kind| {
+    ///     assert_eq!(Ok(*kind), kind.as_str().parse::<ModelToIrIssueKind>());
+    /// });
+    /// ```
+    pub const ALL: &[ModelToIrIssueKind] = &[
+        ModelToIrIssueKind::EdgeGroupThingUnknown,
+        ModelToIrIssueKind::TagThingsTagUnknown,
+        ModelToIrIssueKind::TagThingsThingUnknown,
+        ModelToIrIssueKind::ThingNamesThingUnknown,
+        ModelToIrIssueKind::EntityTypesEntityUnknown,
+        ModelToIrIssueKind::StepThingInteractionsStepUnknown,
+        ModelToIrIssueKind::StepThingInteractionsEdgeGroupUnknown,
+        ModelToIrIssueKind::ThingDependenciesCycle,
+        ModelToIrIssueKind::ThemeAttrValueInvalid,
+    ];
+
+    /// Returns the string representation of this issue kind.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_ir_model::issue::ModelToIrIssueKind;
+    ///
+    /// assert_eq!(
+    ///     ModelToIrIssueKind::TagThingsThingUnknown.as_str(),
+    ///     "tag_things_thing_unknown"
+    /// );
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModelToIrIssueKind::EdgeGroupThingUnknown => "edge_group_thing_unknown",
+            ModelToIrIssueKind::TagThingsTagUnknown => "tag_things_tag_unknown",
+            ModelToIrIssueKind::TagThingsThingUnknown => "tag_things_thing_unknown",
+            ModelToIrIssueKind::ThingNamesThingUnknown => "thing_names_thing_unknown",
+            ModelToIrIssueKind::EntityTypesEntityUnknown => "entity_types_entity_unknown",
+            ModelToIrIssueKind::StepThingInteractionsStepUnknown => {
+                "step_thing_interactions_step_unknown"
+            }
+            ModelToIrIssueKind::StepThingInteractionsEdgeGroupUnknown => {
+                "step_thing_interactions_edge_group_unknown"
+            }
+            ModelToIrIssueKind::ThingDependenciesCycle => "thing_dependencies_cycle",
+            ModelToIrIssueKind::ThemeAttrValueInvalid => "theme_attr_value_invalid",
+        }
+    }
+}
+
+impl fmt::Display for ModelToIrIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ModelToIrIssueKind {
+    type Err = ModelToIrIssueKindParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModelToIrIssueKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| ModelToIrIssueKindParseError(s.to_owned()))
+    }
+}
+
+/// Error returned when parsing an invalid [`ModelToIrIssueKind`] string.
+///
+/// # Examples
+///
+/// ```rust,should_panic
+/// # use disposition_input_ir_model::issue::ModelToIrIssueKind;
+/// let _: ModelToIrIssueKind = "invalid".parse().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModelToIrIssueKindParseError(pub String);
+
+impl fmt::Display for ModelToIrIssueKindParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid issue kind `{}`, expected one of: ", self.0)?;
+        ModelToIrIssueKind::ALL
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, kind)| {
+                if index == 0 {
+                    write!(f, "`{kind}`")
+                } else {
+                    write!(f, ", `{kind}`")
+                }
+            })
+    }
+}
+
+impl std::error::Error for ModelToIrIssueKindParseError {}
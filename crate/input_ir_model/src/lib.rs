@@ -2,9 +2,11 @@
 
 pub use crate::{
     edge_animation_active::EdgeAnimationActive, ir_diagram_and_issues::IrDiagramAndIssues,
+    theme_value_source::ThemeValueSource,
 };
 
 pub mod issue;
 
 mod edge_animation_active;
 mod ir_diagram_and_issues;
+mod theme_value_source;
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Indicates where a theme value originated.
 ///
 /// Used to show users which values they have overridden vs. which come
@@ -7,7 +9,12 @@
 ///
 /// * `BaseDiagram`: the value comes from `InputDiagram::base()`.
 /// * `UserInput`: the value was provided by the user's overlay diagram.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ThemeValueSource {
     /// Value comes from the base diagram defaults.
     BaseDiagram,
@@ -0,0 +1,13 @@
+/// Which colors of the diagram's theme to use when rendering it to an image or
+/// document.
+///
+/// Generated SVGs switch between light and dark colors through CSS, depending
+/// on the viewer's preference. PNGs and PDFs have to pick one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorScheme {
+    /// Colors shown when the viewer prefers a light color scheme.
+    #[default]
+    Light,
+    /// Colors shown when the viewer prefers a dark color scheme.
+    Dark,
+}
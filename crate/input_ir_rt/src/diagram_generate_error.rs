@@ -1,3 +1,4 @@
+use disposition_input_ir_model::issue::ModelToIrIssue;
 use disposition_taffy_model::IrToTaffyError;
 use thiserror::Error;
 
@@ -10,4 +11,18 @@ pub enum DiagramGenerateError {
     /// The taffy builder produced no node mappings.
     #[error("no taffy node mappings generated")]
     NoTaffyMappings,
+    /// Mapping the input diagram to the IR produced at least one issue whose
+    /// `IssueLevel` is `Deny`.
+    ///
+    /// `issues` contains every issue that is not allowed -- including the
+    /// ones that are only warned about -- so that they can all be reported
+    /// together.
+    #[error(
+        "input diagram has {} issue(s), and at least one is denied",
+        .issues.len()
+    )]
+    IssuesDenied {
+        /// The denied and warned issues, in the order they were found.
+        issues: Vec<ModelToIrIssue>,
+    },
 }
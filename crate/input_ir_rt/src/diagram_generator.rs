@@ -1,23 +1,31 @@
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 
-use disposition_input_ir_model::{EdgeAnimationActive, IrDiagramAndIssues};
+use disposition_input_ir_model::{
+    issue::{IssueLevel, IssueLevels, ModelToIrIssue},
+    EdgeAnimationActive, IrDiagramAndIssues,
+};
 use disposition_input_model::{DiagramFocus, InputDiagram};
 use disposition_output_model::{DiagramFocusGenerated, DiagramGenerated};
 use disposition_taffy_model::DimensionAndLod;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
+use self::ir_diagram_mapped::IrDiagramMapped;
 use crate::{
     input_to_ir_diagram_mapper::tailwind_focus_mode::TailwindFocusMode, DiagramGenerateError,
-    InputDiagramMerger, InputToIrDiagramMapper, IrToTaffyBuilder, SvgElementsToSvgMapper,
-    TaffyToSvgElementsMapper, TaffyToSvgElementsOutcome,
+    InputDiagramMerger, InputDiagramThemeSources, InputToIrDiagramMapper, IrToTaffyBuilder,
+    SvgElementsToSvgMapper, TaffyToSvgElementsMapper, TaffyToSvgElementsOutcome,
+    ThemeAttrValidator,
 };
 
+mod ir_diagram_mapped;
+
 /// Runs the full diagram generation pipeline.
 ///
 /// This calls each processor in order -- `InputDiagramMerger`,
-/// `InputToIrDiagramMapper`, `IrToTaffyBuilder`, `TaffyToSvgElementsMapper`,
+/// `InputToIrDiagramMapper` (along with `ThemeAttrValidator`),
+/// `IrToTaffyBuilder`, `TaffyToSvgElementsMapper`,
 /// and `SvgElementsToSvgMapper` -- and collects every intermediate and final
 /// transformation, along with the time taken for each step, into a
 /// [`DiagramGenerated`].
@@ -53,18 +61,65 @@ impl DiagramGenerator {
         input_diagram: &InputDiagram<'static>,
         edge_animation_active: EdgeAnimationActive,
     ) -> Result<DiagramGenerated, DiagramGenerateError> {
-        // === Merge input diagram over base === //
-        let input_diagram_merged_merge_start = Instant::now();
-        let input_diagram_merged = InputDiagramMerger::merge(InputDiagram::base(), input_diagram);
-        let input_diagram_merged_merge_duration = input_diagram_merged_merge_start.elapsed();
+        Self::generate_with_issue_levels(
+            input_diagram,
+            edge_animation_active,
+            &IssueLevels::default(),
+        )
+    }
 
-        // === Map merged input diagram to IR diagram === //
-        let ir_diagram_map_start = Instant::now();
-        let IrDiagramAndIssues {
-            diagram: ir_diagram,
-            issues: ir_diagram_issues,
-        } = InputToIrDiagramMapper::map(&input_diagram_merged);
-        let ir_diagram_map_duration = ir_diagram_map_start.elapsed();
+    /// Generates a diagram from the given input diagram, failing if mapping
+    /// the input diagram produces any denied issues.
+    ///
+    /// This is the same as [`Self::generate`], except:
+    ///
+    /// * Issues whose level is [`IssueLevel::Allow`] are discarded from
+    ///   `ir_diagram_issues`.
+    /// * If any issue's level is [`IssueLevel::Deny`], this returns
+    ///   [`DiagramGenerateError::IssuesDenied`] with every issue that is not
+    ///   allowed.
+    ///
+    /// # Parameters
+    ///
+    /// * `input_diagram`: The user's input diagram to generate from.
+    /// * `edge_animation_active`: When edge animations should be active in the
+    ///   generated SVG elements.
+    /// * `issue_levels`: The level of each kind of issue.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_model::issue::IssueLevels;
+    /// # use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive};
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = InputDiagram::base();
+    /// let diagram_generated = DiagramGenerator::generate_with_issue_levels(
+    ///     &input_diagram,
+    ///     EdgeAnimationActive::OnProcessStepFocus,
+    ///     &IssueLevels::strict(),
+    /// )?;
+    /// # Ok::<(), disposition_input_ir_rt::DiagramGenerateError>(())
+    /// ```
+    pub fn generate_with_issue_levels(
+        input_diagram: &InputDiagram<'static>,
+        edge_animation_active: EdgeAnimationActive,
+        issue_levels: &IssueLevels,
+    ) -> Result<DiagramGenerated, DiagramGenerateError> {
+        // === Merge input diagram over base, and map it to the IR diagram === //
+        let IrDiagramMapped {
+            input_diagram_merged,
+            input_diagram_merged_merge_duration,
+            ir_diagram_and_issues:
+                IrDiagramAndIssues {
+                    diagram: ir_diagram,
+                    issues: ir_diagram_issues,
+                },
+            ir_diagram_map_duration,
+        } = Self::input_diagram_merge_and_map(input_diagram, |input_diagram_merged| {
+            InputToIrDiagramMapper::map(input_diagram_merged)
+        });
+        let ir_diagram_issues = Self::issue_levels_apply(ir_diagram_issues, issue_levels)?;
 
         // === Build taffy node mappings === //
         //
@@ -114,6 +169,42 @@ impl DiagramGenerator {
         })
     }
 
+    /// Maps the given input diagram to the IR diagram, without computing the
+    /// layout or SVG.
+    ///
+    /// This runs the same merge, IR mapping, and theme validation steps as
+    /// [`Self::generate`], so it is useful to check an input diagram for issues
+    /// without rendering it. Issue levels are not applied.
+    ///
+    /// Returns the input diagram merged over `InputDiagram::base()`, and the IR
+    /// diagram with every issue found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::DiagramGenerator;
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = InputDiagram::new();
+    /// let (_input_diagram_merged, ir_diagram_and_issues) =
+    ///     DiagramGenerator::ir_diagram_map(&input_diagram);
+    ///
+    /// assert!(ir_diagram_and_issues.issues.is_empty());
+    /// ```
+    pub fn ir_diagram_map(
+        input_diagram: &InputDiagram<'static>,
+    ) -> (InputDiagram<'static>, IrDiagramAndIssues<'static>) {
+        let IrDiagramMapped {
+            input_diagram_merged,
+            ir_diagram_and_issues,
+            ..
+        } = Self::input_diagram_merge_and_map(input_diagram, |input_diagram_merged| {
+            InputToIrDiagramMapper::map(input_diagram_merged)
+        });
+
+        (input_diagram_merged, ir_diagram_and_issues)
+    }
+
     /// Generates one diagram per focus state, with the focused entity's styles
     /// baked in statically.
     ///
@@ -140,18 +231,44 @@ impl DiagramGenerator {
         input_diagram: &InputDiagram<'static>,
         edge_animation_active: EdgeAnimationActive,
     ) -> Result<Vec<DiagramFocusGenerated>, DiagramGenerateError> {
-        // === Merge input diagram over base (once) === //
-        let input_diagram_merged_merge_start = Instant::now();
-        let input_diagram_merged = InputDiagramMerger::merge(InputDiagram::base(), input_diagram);
-        let input_diagram_merged_merge_duration = input_diagram_merged_merge_start.elapsed();
+        Self::generate_per_process_step_or_tag_with_issue_levels(
+            input_diagram,
+            edge_animation_active,
+            &IssueLevels::default(),
+        )
+    }
 
-        // === Map merged input diagram to the focus-independent IR (once) === //
-        let ir_structure_map_start = Instant::now();
-        let IrDiagramAndIssues {
-            diagram: ir_diagram_structure,
-            issues: ir_diagram_issues,
-        } = InputToIrDiagramMapper::map_structure(&input_diagram_merged);
-        let ir_structure_map_duration = ir_structure_map_start.elapsed();
+    /// Generates one diagram per focus state, failing if mapping the input
+    /// diagram produces any denied issues.
+    ///
+    /// This is the same as [`Self::generate_per_process_step_or_tag`], with
+    /// `issue_levels` applied as in [`Self::generate_with_issue_levels`].
+    ///
+    /// # Parameters
+    ///
+    /// * `input_diagram`: The user's input diagram to generate from.
+    /// * `edge_animation_active`: When edge animations should be active in the
+    ///   generated SVG elements.
+    /// * `issue_levels`: The level of each kind of issue.
+    pub fn generate_per_process_step_or_tag_with_issue_levels(
+        input_diagram: &InputDiagram<'static>,
+        edge_animation_active: EdgeAnimationActive,
+        issue_levels: &IssueLevels,
+    ) -> Result<Vec<DiagramFocusGenerated>, DiagramGenerateError> {
+        // === Merge input diagram, and map it to the focus-independent IR (once) === //
+        let IrDiagramMapped {
+            input_diagram_merged,
+            input_diagram_merged_merge_duration,
+            ir_diagram_and_issues:
+                IrDiagramAndIssues {
+                    diagram: ir_diagram_structure,
+                    issues: ir_diagram_issues,
+                },
+            ir_diagram_map_duration: ir_structure_map_duration,
+        } = Self::input_diagram_merge_and_map(input_diagram, |input_diagram_merged| {
+            InputToIrDiagramMapper::map_structure(input_diagram_merged)
+        });
+        let ir_diagram_issues = Self::issue_levels_apply(ir_diagram_issues, issue_levels)?;
 
         // === Build taffy node mappings (once) === //
         //
@@ -228,12 +345,94 @@ impl DiagramGenerator {
         Ok(diagrams_focus_generated)
     }
 
+    /// Merges the input diagram over `InputDiagram::base()`, and maps it to the
+    /// IR diagram with `ir_diagram_map`.
+    ///
+    /// The issues found by validating the merged diagram's theme are added to
+    /// the IR diagram's issues, so every caller reports the same issues.
+    fn input_diagram_merge_and_map(
+        input_diagram: &InputDiagram<'static>,
+        ir_diagram_map: impl FnOnce(&InputDiagram<'static>) -> IrDiagramAndIssues<'static>,
+    ) -> IrDiagramMapped {
+        let input_diagram_merged_merge_start = Instant::now();
+        let input_diagram_base = InputDiagram::base();
+        let input_diagram_merged =
+            InputDiagramMerger::merge(input_diagram_base.clone(), input_diagram);
+        let input_diagram_merged_merge_duration = input_diagram_merged_merge_start.elapsed();
+
+        let ir_diagram_map_start = Instant::now();
+        let mut ir_diagram_and_issues = ir_diagram_map(&input_diagram_merged);
+        ir_diagram_and_issues
+            .issues
+            .extend(ThemeAttrValidator::validate(
+                &input_diagram_merged,
+                &InputDiagramThemeSources::new(&input_diagram_base, input_diagram),
+            ));
+        let ir_diagram_map_duration = ir_diagram_map_start.elapsed();
+
+        IrDiagramMapped {
+            input_diagram_merged,
+            input_diagram_merged_merge_duration,
+            ir_diagram_and_issues,
+            ir_diagram_map_duration,
+        }
+    }
+
+    /// Discards allowed issues, and returns an error if any issue is denied.
+    ///
+    /// See [`Self::generate_with_issue_levels`].
+    fn issue_levels_apply(
+        ir_diagram_issues: Vec<ModelToIrIssue>,
+        issue_levels: &IssueLevels,
+    ) -> Result<Vec<ModelToIrIssue>, DiagramGenerateError> {
+        let mut has_denied = false;
+        let ir_diagram_issues = ir_diagram_issues
+            .into_iter()
+            .filter(|issue| match issue_levels.level(issue.kind()) {
+                IssueLevel::Allow => false,
+                IssueLevel::Warn => true,
+                IssueLevel::Deny => {
+                    has_denied = true;
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if has_denied {
+            Err(DiagramGenerateError::IssuesDenied {
+                issues: ir_diagram_issues,
+            })
+        } else {
+            Ok(ir_diagram_issues)
+        }
+    }
+
     /// Collects the ordered focus states to generate diagrams for.
     ///
     /// The order is: nothing focused, then each process followed by its steps,
     /// then each tag -- all in declaration (insertion) order. See
     /// [`Self::generate_per_process_step_or_tag`].
-    fn focuses_collect(input_diagram: &InputDiagram<'static>) -> Vec<DiagramFocus<'static>> {
+    ///
+    /// This is the same order as the diagrams returned by
+    /// [`Self::generate_per_process_step_or_tag`], so it can be used to list
+    /// the focus states without generating a diagram for each of them.
+    ///
+    /// # Parameters
+    ///
+    /// * `input_diagram`: The input diagram merged over `InputDiagram::base()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::DiagramGenerator;
+    /// # use disposition_input_model::{DiagramFocus, InputDiagram};
+    /// #
+    /// let input_diagram = InputDiagram::base();
+    /// let focuses = DiagramGenerator::focuses_collect(&input_diagram);
+    ///
+    /// assert_eq!(vec![DiagramFocus::None], focuses);
+    /// ```
+    pub fn focuses_collect(input_diagram: &InputDiagram<'static>) -> Vec<DiagramFocus<'static>> {
         let process_step_count: usize = input_diagram
             .processes
             .values()
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use disposition_input_ir_model::IrDiagramAndIssues;
+use disposition_input_model::InputDiagram;
+
+/// An input diagram merged over `InputDiagram::base()`, and mapped to the IR
+/// diagram with every issue found.
+#[derive(Clone, Debug)]
+pub(crate) struct IrDiagramMapped {
+    /// The input diagram merged over `InputDiagram::base()`.
+    pub(crate) input_diagram_merged: InputDiagram<'static>,
+    /// Time taken to merge the input diagram.
+    pub(crate) input_diagram_merged_merge_duration: Duration,
+    /// The IR diagram, with the mapping and theme validation issues.
+    pub(crate) ir_diagram_and_issues: IrDiagramAndIssues<'static>,
+    /// Time taken to map the IR diagram and validate the theme.
+    pub(crate) ir_diagram_map_duration: Duration,
+}
@@ -0,0 +1,206 @@
+use std::fmt::Write;
+
+use disposition_output_model::DiagramGenerated;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::{StringPlaceholderReplacer, StringXmlEscaper};
+
+/// HTML page that the diagram and sidebar are rendered into.
+const DIAGRAM_HTML: &str = include_str!("diagram_to_html_mapper/diagram.html");
+
+/// Title of the sidebar entry for the diagram with nothing focused.
+const TITLE_FOCUS_NONE: &str = "Overview";
+
+/// Wraps a generated diagram in a self-contained HTML page, with a sidebar to
+/// step through its processes, process steps, and tags.
+///
+/// Selecting an entry in the sidebar focuses that entity in the interactive
+/// SVG, and shows its description beneath the entry. Entries can be stepped
+/// through with the arrow keys, and each entry has a URL fragment, e.g.
+/// `#focus=proc_app_dev`, so that links can open the page with it selected.
+///
+/// The page does not load anything over the network. Images in descriptions
+/// are replaced by their alt text, and raw HTML in descriptions is shown as
+/// text.
+#[derive(Clone, Copy, Debug)]
+pub struct DiagramToHtmlMapper;
+
+impl DiagramToHtmlMapper {
+    /// Returns the HTML page for the diagram's interactive SVG.
+    ///
+    /// # Parameters
+    ///
+    /// * `diagram_generated`: The diagram to render, e.g. generated by
+    ///   `DiagramGenerator::generate`.
+    /// * `title`: Title of the page, e.g. the input file's name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{DiagramGenerator, DiagramToHtmlMapper, EdgeAnimationActive};
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "thing_names: { t_a: A }\nprocesses: { proc_a: { steps: { proc_a_step_a: Step } } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let html = DiagramToHtmlMapper::map(&diagram_generated, "example");
+    ///
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// assert!(html.contains(r##"href="#focus=proc_a_step_a""##));
+    /// ```
+    pub fn map(diagram_generated: &DiagramGenerated, title: &str) -> String {
+        Self::map_with_svg(diagram_generated, &diagram_generated.svg, title)
+    }
+
+    /// Returns the HTML page for the given SVG of the diagram.
+    ///
+    /// This is useful when the SVG was re-derived from modified SVG elements,
+    /// e.g. with styles stripped.
+    ///
+    /// # Parameters
+    ///
+    /// * `diagram_generated`: The diagram whose processes, process steps, and
+    ///   tags are listed in the sidebar.
+    /// * `svg`: The SVG to show, which must have the same entity IDs as the
+    ///   diagram's SVG.
+    /// * `title`: Title of the page, e.g. the input file's name.
+    pub fn map_with_svg(diagram_generated: &DiagramGenerated, svg: &str, title: &str) -> String {
+        // The XML declaration is not valid within an HTML document.
+        let svg = svg
+            .trim_start()
+            .strip_prefix("<?xml")
+            .and_then(|svg| svg.split_once("?>"))
+            .map(|(_declaration, svg)| svg.trim_start())
+            .unwrap_or(svg);
+
+        let title = StringXmlEscaper::escape(title);
+        let focuses = Self::focuses_html(diagram_generated);
+        StringPlaceholderReplacer::replace(
+            DIAGRAM_HTML,
+            &[
+                ("__TITLE__", &title),
+                ("__FOCUSES__", &focuses),
+                ("__DIAGRAM__", svg),
+            ],
+        )
+    }
+
+    /// Returns the sidebar entries, in the same order as
+    /// `DiagramGenerator::focuses_collect`.
+    fn focuses_html(diagram_generated: &DiagramGenerated) -> String {
+        let input_diagram = &diagram_generated.input_diagram_merged;
+        let mut html = String::new();
+
+        html.push_str("<ol>\n<li>");
+        Self::focus_html(&mut html, None, TITLE_FOCUS_NONE, None);
+        html.push_str("</li>\n</ol>\n");
+
+        if !input_diagram.processes.is_empty() {
+            html.push_str("<h2>Processes</h2>\n<ol>\n");
+            input_diagram
+                .processes
+                .iter()
+                .for_each(|(process_id, process_diagram)| {
+                    html.push_str("<li>");
+                    Self::focus_html(
+                        &mut html,
+                        Some(process_id.as_str()),
+                        process_diagram
+                            .name
+                            .as_deref()
+                            .unwrap_or(process_id.as_str()),
+                        process_diagram.desc.as_deref(),
+                    );
+
+                    if !process_diagram.steps.is_empty() {
+                        html.push_str("<ol>\n");
+                        process_diagram.steps.iter().for_each(
+                            |(process_step_id, process_step_name)| {
+                                html.push_str("<li>");
+                                Self::focus_html(
+                                    &mut html,
+                                    Some(process_step_id.as_str()),
+                                    process_step_name,
+                                    input_diagram
+                                        .entity_tooltips
+                                        .get(&**process_step_id)
+                                        .map(String::as_str),
+                                );
+                                html.push_str("</li>\n");
+                            },
+                        );
+                        html.push_str("</ol>\n");
+                    }
+                    html.push_str("</li>\n");
+                });
+            html.push_str("</ol>\n");
+        }
+
+        if !input_diagram.tags.is_empty() {
+            html.push_str("<h2>Tags</h2>\n<ol>\n");
+            input_diagram.tags.iter().for_each(|(tag_id, tag_name)| {
+                html.push_str("<li>");
+                Self::focus_html(
+                    &mut html,
+                    Some(tag_id.as_str()),
+                    tag_name,
+                    input_diagram
+                        .entity_tooltips
+                        .get(&**tag_id)
+                        .map(String::as_str),
+                );
+                html.push_str("</li>\n");
+            });
+            html.push_str("</ol>\n");
+        }
+
+        html
+    }
+
+    /// Appends the link that selects a focus, followed by its description.
+    ///
+    /// * `focus_id`: ID of the focused entity, which is also the ID of its SVG
+    ///   element, or `None` for the overview.
+    fn focus_html(html: &mut String, focus_id: Option<&str>, name: &str, desc: Option<&str>) {
+        let href = match focus_id {
+            Some(focus_id) => format!("#focus={focus_id}"),
+            None => String::from("#"),
+        };
+        let _ = write!(
+            html,
+            r#"<a class="focus" href="{}" data-focus-id="{}">{}</a>"#,
+            StringXmlEscaper::escape(&href),
+            StringXmlEscaper::escape(focus_id.unwrap_or_default()),
+            StringXmlEscaper::escape(name),
+        );
+        if let Some(desc) = desc {
+            let _ = write!(
+                html,
+                r#"<div class="desc">{}</div>"#,
+                Self::md_to_html(desc)
+            );
+        }
+        html.push('\n');
+    }
+
+    /// Returns the markdown rendered as HTML.
+    fn md_to_html(md: &str) -> String {
+        let events = Parser::new_ext(md, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES)
+            .filter_map(|event| match event {
+                // The alt text of images is between these events, so dropping
+                // them shows the alt text without loading the image.
+                Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => None,
+                Event::Html(html) | Event::InlineHtml(html) => Some(Event::Text(html)),
+                event => Some(event),
+            });
+
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(&mut html, events);
+        html
+    }
+}
@@ -0,0 +1,374 @@
+use disposition_ir_model::{
+    node::{NodeHierarchy, NodeId},
+    IrDiagram,
+};
+use disposition_layout_model::{
+    DiagramLayout, LayoutBox, LayoutCircle, LayoutEdge, LayoutEdgeDescription, LayoutEdgeKind,
+    LayoutEdgeLabel, LayoutNode, LayoutNodeKind, LayoutPathSegment, LayoutProcess, LayoutText,
+    LayoutTextSpan, LayoutTextStyle,
+};
+use disposition_model_common::{entity::EntityType, Map};
+use disposition_output_model::DiagramGenerated;
+use disposition_svg_model::{
+    SvgEdgeInfo, SvgEdgeLabelEndpointInfo, SvgMdStyle, SvgNodeInfo, SvgTextSpan,
+};
+use disposition_taffy_model::TaffyNodeMappings;
+use kurbo::{BezPath, PathEl};
+
+use crate::{AbsoluteCoordinates, StringXmlEscaper, TaffyNodeAbsoluteCoordinatesCalculator};
+
+/// Maps a generated diagram to its [`DiagramLayout`], for renderers that draw
+/// diagrams without reading SVG.
+///
+/// * Node, envelope, and text boxes, circles, and text spans are taken from the
+///   `SvgElements` and `TaffyNodeMappings`, with relative positions made
+///   absolute.
+/// * Edge paths are parsed from the SVG path `d` attributes into segments.
+/// * Node kinds and nesting, and edge kinds are taken from the `IrDiagram`.
+///
+/// Styling and animations are not included.
+#[derive(Clone, Copy, Debug)]
+pub struct DiagramToLayoutMapper;
+
+impl DiagramToLayoutMapper {
+    /// Returns the layout of the generated diagram.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     DiagramGenerator, DiagramToLayoutMapper, EdgeAnimationActive,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// # use disposition_layout_model::{DiagramLayout, LayoutPathSegment};
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {}, t_b: {} }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let diagram_layout = DiagramToLayoutMapper::map(&diagram_generated);
+    ///
+    /// assert_eq!(diagram_layout.version, DiagramLayout::VERSION);
+    /// assert_eq!(diagram_layout.nodes.len(), 2);
+    /// let layout_edge = &diagram_layout.edges[0];
+    /// assert_eq!(layout_edge.from_node_id.as_str(), "t_a");
+    /// assert!(matches!(
+    ///     layout_edge.path.first(),
+    ///     Some(LayoutPathSegment::MoveTo { .. })
+    /// ));
+    /// ```
+    pub fn map(diagram_generated: &DiagramGenerated) -> DiagramLayout<'static> {
+        let DiagramGenerated {
+            ir_diagram,
+            taffy_node_mappings,
+            svg_elements,
+            ..
+        } = diagram_generated;
+
+        let svg_node_infos = svg_elements
+            .svg_node_infos
+            .iter()
+            .map(|svg_node_info| (&svg_node_info.node_id, svg_node_info))
+            .collect::<Map<&NodeId<'static>, &SvgNodeInfo<'static>>>();
+
+        let mut diagram_layout =
+            DiagramLayout::new(svg_elements.svg_width, svg_elements.svg_height);
+        Self::nodes_collect(
+            &mut diagram_layout.nodes,
+            ir_diagram,
+            taffy_node_mappings,
+            &svg_node_infos,
+            &ir_diagram.node_hierarchy,
+            None,
+        );
+        diagram_layout.edges = svg_elements
+            .svg_edge_infos
+            .iter()
+            .map(|svg_edge_info| Self::edge(ir_diagram, svg_edge_info))
+            .collect();
+        diagram_layout.edge_labels = svg_elements
+            .edge_label_infos
+            .iter()
+            .map(|edge_label_info| LayoutEdgeLabel {
+                edge_id: edge_label_info.edge_id.clone(),
+                from_label: edge_label_info.from_label.as_ref().map(Self::label_text),
+                to_label: edge_label_info.to_label.as_ref().map(Self::label_text),
+            })
+            .collect();
+        diagram_layout.edge_descriptions = svg_elements
+            .edge_description_infos
+            .iter()
+            .map(|edge_description_info| LayoutEdgeDescription {
+                edge_id: edge_description_info.edge_id.clone(),
+                text: LayoutText {
+                    text_box: LayoutBox::new(
+                        edge_description_info.x,
+                        edge_description_info.y,
+                        edge_description_info.width,
+                        edge_description_info.height,
+                    ),
+                    text_spans: Self::text_spans(&edge_description_info.text_spans, 0.0, 0.0),
+                },
+            })
+            .collect();
+        diagram_layout.processes = ir_diagram
+            .node_hierarchy
+            .iter()
+            .filter(|(node_id, _)| {
+                ir_diagram
+                    .entity_types
+                    .get(node_id.as_ref())
+                    .is_some_and(|entity_types| entity_types.contains(&EntityType::ProcessDefault))
+            })
+            .map(|(process_id, process_steps)| {
+                // Processes only have process info when they are collapsed.
+                let svg_process_info = svg_elements.svg_process_infos.get(process_id);
+                let height = svg_node_infos
+                    .get(process_id)
+                    .map(|svg_node_info| svg_node_info.height_collapsed)
+                    .unwrap_or_default();
+                LayoutProcess {
+                    process_id: process_id.clone(),
+                    process_step_ids: process_steps.keys().cloned().collect(),
+                    is_collapsed: svg_process_info.is_some(),
+                    height_expanded: svg_process_info
+                        .map(|svg_process_info| svg_process_info.height_to_expand_to)
+                        .unwrap_or(height),
+                    steps_height: svg_process_info
+                        .map(|svg_process_info| svg_process_info.total_height)
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        diagram_layout
+    }
+
+    /// Appends a layout node for each node in the hierarchy, with each node
+    /// before the nodes nested within it.
+    fn nodes_collect(
+        nodes: &mut Vec<LayoutNode<'static>>,
+        ir_diagram: &IrDiagram<'static>,
+        taffy_node_mappings: &TaffyNodeMappings<'static>,
+        svg_node_infos: &Map<&NodeId<'static>, &SvgNodeInfo<'static>>,
+        node_hierarchy: &NodeHierarchy<'static>,
+        parent_id: Option<&NodeId<'static>>,
+    ) {
+        node_hierarchy.iter().for_each(|(node_id, node_children)| {
+            if let Some(svg_node_info) = svg_node_infos.get(node_id).copied() {
+                nodes.push(Self::node(
+                    ir_diagram,
+                    taffy_node_mappings,
+                    svg_node_info,
+                    parent_id,
+                ));
+            }
+            Self::nodes_collect(
+                nodes,
+                ir_diagram,
+                taffy_node_mappings,
+                svg_node_infos,
+                node_children,
+                Some(node_id),
+            );
+        });
+    }
+
+    fn node(
+        ir_diagram: &IrDiagram<'static>,
+        taffy_node_mappings: &TaffyNodeMappings<'static>,
+        svg_node_info: &SvgNodeInfo<'static>,
+        parent_id: Option<&NodeId<'static>>,
+    ) -> LayoutNode<'static> {
+        let SvgNodeInfo {
+            node_id,
+            x,
+            y,
+            width,
+            height_collapsed,
+            envelope_x,
+            envelope_y,
+            envelope_width,
+            envelope_height_collapsed,
+            text_spans,
+            circle,
+            ..
+        } = svg_node_info;
+        let (x, y) = (*x, *y);
+
+        let kind = ir_diagram
+            .entity_types
+            .get(node_id.as_ref())
+            .and_then(|entity_types| {
+                entity_types
+                    .iter()
+                    .find_map(|entity_type| match entity_type {
+                        EntityType::TagDefault => Some(LayoutNodeKind::Tag),
+                        EntityType::ProcessDefault => Some(LayoutNodeKind::Process),
+                        EntityType::ProcessStepDefault => Some(LayoutNodeKind::ProcessStep),
+                        _ => None,
+                    })
+            })
+            .unwrap_or(LayoutNodeKind::Thing);
+
+        // The text box is the text's taffy node, which is the node itself for
+        // nodes without nested nodes or a circle.
+        let text = (!text_spans.is_empty()).then(|| {
+            let text_box = taffy_node_mappings
+                .node_id_to_taffy
+                .get(node_id)
+                .and_then(|node_to_taffy_node_ids| {
+                    let text_taffy_node_id = node_to_taffy_node_ids.text_taffy_node_id();
+                    let taffy_tree = &taffy_node_mappings.taffy_tree;
+                    let layout = taffy_tree.layout(text_taffy_node_id).ok()?;
+                    let AbsoluteCoordinates { x, y } =
+                        TaffyNodeAbsoluteCoordinatesCalculator::calculate(
+                            taffy_tree,
+                            text_taffy_node_id,
+                            layout,
+                        );
+                    Some(LayoutBox::new(x, y, layout.size.width, layout.size.height))
+                })
+                .unwrap_or_else(|| LayoutBox::new(x, y, *width, *height_collapsed));
+            LayoutText {
+                text_box,
+                text_spans: Self::text_spans(text_spans, x, y),
+            }
+        });
+
+        LayoutNode {
+            node_id: node_id.clone(),
+            parent_id: parent_id.cloned(),
+            kind,
+            node_box: LayoutBox::new(x, y, *width, *height_collapsed),
+            envelope_box: LayoutBox::new(
+                *envelope_x,
+                *envelope_y,
+                *envelope_width,
+                *envelope_height_collapsed,
+            ),
+            circle: circle.as_ref().map(|circle| LayoutCircle {
+                cx: x + circle.cx,
+                cy: y + circle.cy,
+                radius: circle.radius,
+            }),
+            text,
+        }
+    }
+
+    fn edge(
+        ir_diagram: &IrDiagram<'static>,
+        svg_edge_info: &SvgEdgeInfo<'static>,
+    ) -> LayoutEdge<'static> {
+        let is_interaction = ir_diagram
+            .entity_types
+            .get(svg_edge_info.edge_group_id.as_ref())
+            .is_some_and(|entity_types| entity_types.iter().any(EntityType::is_interaction_edge));
+
+        LayoutEdge {
+            edge_id: svg_edge_info.edge_id.clone(),
+            edge_group_id: svg_edge_info.edge_group_id.clone(),
+            from_node_id: svg_edge_info.from_node_id.clone(),
+            to_node_id: svg_edge_info.to_node_id.clone(),
+            kind: if is_interaction {
+                LayoutEdgeKind::Interaction
+            } else {
+                LayoutEdgeKind::Dependency
+            },
+            path: Self::path_segments(&svg_edge_info.path_d),
+            arrow_head_path: Self::path_segments(&svg_edge_info.arrow_head_path_d),
+        }
+    }
+
+    fn label_text(endpoint_info: &SvgEdgeLabelEndpointInfo) -> LayoutText {
+        LayoutText {
+            text_box: LayoutBox::new(
+                endpoint_info.x,
+                endpoint_info.y,
+                endpoint_info.width,
+                endpoint_info.height,
+            ),
+            text_spans: Self::text_spans(&endpoint_info.text_spans, 0.0, 0.0),
+        }
+    }
+
+    /// Returns the path's segments, or no segments if the path cannot be
+    /// parsed.
+    ///
+    /// Arcs are converted to cubic Bézier curves.
+    fn path_segments(path_d: &str) -> Vec<LayoutPathSegment> {
+        let Ok(path) = BezPath::from_svg(path_d) else {
+            return Vec::new();
+        };
+        path.elements()
+            .iter()
+            .map(|path_el| match *path_el {
+                PathEl::MoveTo(point) => LayoutPathSegment::MoveTo {
+                    x: point.x,
+                    y: point.y,
+                },
+                PathEl::LineTo(point) => LayoutPathSegment::LineTo {
+                    x: point.x,
+                    y: point.y,
+                },
+                PathEl::QuadTo(point_1, point) => LayoutPathSegment::QuadTo {
+                    x1: point_1.x,
+                    y1: point_1.y,
+                    x: point.x,
+                    y: point.y,
+                },
+                PathEl::CurveTo(point_1, point_2, point) => LayoutPathSegment::CubicTo {
+                    x1: point_1.x,
+                    y1: point_1.y,
+                    x2: point_2.x,
+                    y2: point_2.y,
+                    x: point.x,
+                    y: point.y,
+                },
+                PathEl::ClosePath => LayoutPathSegment::Close,
+            })
+            .collect()
+    }
+
+    /// Returns the text spans offset by `x` and `y`, with their text
+    /// unescaped.
+    fn text_spans(text_spans: &[SvgTextSpan], x: f32, y: f32) -> Vec<LayoutTextSpan> {
+        text_spans
+            .iter()
+            .map(|text_span| LayoutTextSpan {
+                x: x + text_span.x,
+                y: y + text_span.y,
+                width: text_span.width,
+                height: text_span.height,
+                text: StringXmlEscaper::unescape(&text_span.text),
+                style: text_span.md_style.as_ref().map(Self::text_style),
+            })
+            .collect()
+    }
+
+    fn text_style(md_style: &SvgMdStyle) -> LayoutTextStyle {
+        let SvgMdStyle {
+            bold,
+            italic,
+            strikethrough,
+            code,
+            blockquote,
+            heading_level,
+            link_dest,
+        } = md_style;
+        LayoutTextStyle {
+            bold: *bold,
+            italic: *italic,
+            strikethrough: *strikethrough,
+            code: *code,
+            blockquote: *blockquote,
+            heading_level: *heading_level,
+            link_dest: link_dest.clone(),
+        }
+    }
+}
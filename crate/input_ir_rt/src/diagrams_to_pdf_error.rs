@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+/// Errors that can occur while rendering diagrams to a PDF via
+/// `DiagramsToPdfMapper`.
+#[derive(Debug, Error)]
+pub enum DiagramsToPdfError {
+    /// No diagrams were passed in, so there are no pages to render.
+    #[error("no diagrams to render to PDF pages")]
+    DiagramsEmpty,
+    /// A font in `PdfOptions::fonts` is not a TrueType or OpenType font.
+    #[error("font {font_index} is not a TrueType or OpenType font")]
+    FontInvalid {
+        /// Index of the font in `PdfOptions::fonts`.
+        font_index: usize,
+    },
+}
@@ -0,0 +1,371 @@
+use std::fmt::Write;
+
+use disposition_output_model::DiagramFocusGenerated;
+use disposition_taffy_model::{TEXT_FONT_SIZE, TEXT_LINE_HEIGHT};
+use kurbo::{Affine, Point, Rect, Shape};
+use pdf_writer::{types::PageMode, Content, Finish, Name, Pdf, Ref, TextStr};
+use usvg::fontdb::Database;
+
+use crate::{
+    ir_to_taffy_builder::text_measure::MONOSPACE_CHAR_WIDTH_RATIO,
+    svg_elements_to_paint_items_mapper::{
+        FillRule, PaintColor, PaintItems, PaintPath, PaintText, SvgElementsToPaintItemsMapper,
+    },
+    usvg_tree_builder::UsvgTreeBuilder,
+    ColorScheme, DiagramsToPdfError, PdfOptions,
+};
+
+use self::{pdf_page::PdfPage, pdf_page_header::PdfPageHeader};
+
+mod pdf_page;
+mod pdf_page_header;
+
+/// Points per SVG pixel, as there are 72 points and 96 pixels per inch.
+const POINTS_PER_PIXEL: f64 = 0.75;
+
+/// Space around the page's content, in pixels.
+const PAGE_MARGIN: f64 = 48.0;
+
+/// Space between the page's header and its diagram, in pixels.
+const HEADER_GAP: f64 = 24.0;
+
+/// Size of the title text relative to the description text.
+const TITLE_SCALE: f64 = 1.5;
+
+/// Minimum number of columns that descriptions are wrapped to, so that pages
+/// sized to narrow diagrams are still wide enough to read.
+const DESC_COLUMNS_MIN: f64 = 80.0;
+
+/// Color of page titles, as `--tw-{color}-{light_shade}-{dark_shade}`.
+const TITLE_FILL: &str = "var(--tw-slate-900-100)";
+
+/// Color of page descriptions, as `--tw-{color}-{light_shade}-{dark_shade}`.
+const DESC_FILL: &str = "var(--tw-slate-700-300)";
+
+/// Color of pages for the dark color scheme. Pages are left white for the
+/// light color scheme.
+const PAGE_FILL_DARK: &str = "var(--tw-neutral-900-900)";
+
+/// Name of each page's diagram in its resources.
+const PAGE_X_OBJECT_NAME: Name<'static> = Name(b"Page");
+
+/// Renders diagrams to a PDF, with one page per diagram.
+///
+/// Each page is titled with the name of the focused process, process step, or
+/// tag, and shows its description above the diagram. Pages are also
+/// bookmarked with their titles.
+///
+/// Diagrams are drawn from the same shapes as `SvgElementsToPngMapper`, and
+/// converted to PDF with [`svg2pdf`], so they stay sharp when printed. Text is
+/// drawn as glyph outlines, so it cannot be selected or searched.
+#[derive(Clone, Copy, Debug)]
+pub struct DiagramsToPdfMapper;
+
+impl DiagramsToPdfMapper {
+    /// Returns the PDF file with a page for each diagram.
+    ///
+    /// # Parameters
+    ///
+    /// * `diagrams_focus_generated`: The diagrams to render, e.g. generated by
+    ///   `DiagramGenerator::generate_per_process_step_or_tag`.
+    /// * `pdf_options`: Page size, fonts, and color scheme to render with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     DiagramGenerator, DiagramsToPdfMapper, EdgeAnimationActive, PdfOptions,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {} }\nthing_names: { t_a: A }\nprocesses: { proc_a: { steps: { proc_a_step_a: Step } } }",
+    /// )
+    /// .unwrap();
+    /// let diagrams_focus_generated = DiagramGenerator::generate_per_process_step_or_tag(
+    ///     &input_diagram,
+    ///     EdgeAnimationActive::OnProcessStepFocus,
+    /// )
+    /// .unwrap();
+    ///
+    /// let pdf = DiagramsToPdfMapper::map(&diagrams_focus_generated, &PdfOptions::default())?;
+    ///
+    /// assert!(pdf.starts_with(b"%PDF-"));
+    /// # Ok::<(), disposition_input_ir_rt::DiagramsToPdfError>(())
+    /// ```
+    pub fn map(
+        diagrams_focus_generated: &[DiagramFocusGenerated],
+        pdf_options: &PdfOptions,
+    ) -> Result<Vec<u8>, DiagramsToPdfError> {
+        if diagrams_focus_generated.is_empty() {
+            return Err(DiagramsToPdfError::DiagramsEmpty);
+        }
+
+        let fontdb = UsvgTreeBuilder::fontdb_build(&pdf_options.fonts)
+            .map_err(|font_index| DiagramsToPdfError::FontInvalid { font_index })?;
+        let pdf_pages = diagrams_focus_generated
+            .iter()
+            .map(|diagram_focus_generated| {
+                Self::page_render(diagram_focus_generated, pdf_options, &fontdb)
+            })
+            .collect::<Vec<PdfPage>>();
+
+        Ok(Self::pdf_write(&pdf_pages))
+    }
+
+    /// Returns the PDF file with the pages, with a bookmark for each page.
+    fn pdf_write(pdf_pages: &[PdfPage]) -> Vec<u8> {
+        let mut ref_next = Ref::new(1);
+        let catalog_ref = ref_next.bump();
+        let pages_ref = ref_next.bump();
+        let outline_ref = ref_next.bump();
+        let page_refs = pdf_pages
+            .iter()
+            .map(|_| ref_next.bump())
+            .collect::<Vec<Ref>>();
+        let content_refs = pdf_pages
+            .iter()
+            .map(|_| ref_next.bump())
+            .collect::<Vec<Ref>>();
+        let outline_item_refs = pdf_pages
+            .iter()
+            .map(|_| ref_next.bump())
+            .collect::<Vec<Ref>>();
+        let page_count = pdf_pages.len() as i32;
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_ref)
+            .pages(pages_ref)
+            .outlines(outline_ref)
+            .page_mode(PageMode::UseOutlines);
+        pdf.pages(pages_ref)
+            .kids(page_refs.iter().copied())
+            .count(page_count);
+        let mut outline = pdf.outline(outline_ref);
+        if let (Some(outline_item_first), Some(outline_item_last)) =
+            (outline_item_refs.first(), outline_item_refs.last())
+        {
+            outline
+                .first(*outline_item_first)
+                .last(*outline_item_last)
+                .count(page_count);
+        }
+        outline.finish();
+
+        pdf_pages
+            .iter()
+            .enumerate()
+            .for_each(|(page_index, pdf_page)| {
+                let PdfPage {
+                    width,
+                    height,
+                    title,
+                    tree,
+                } = pdf_page;
+                let page_ref = page_refs[page_index];
+                let content_ref = content_refs[page_index];
+
+                // `svg2pdf` draws the page as a form XObject that is one
+                // point wide and tall, so it is scaled up to fill the page.
+                let x_object_ref = ref_next;
+                ref_next = svg2pdf::convert_tree_into(
+                    tree,
+                    svg2pdf::Options::default(),
+                    &mut pdf,
+                    x_object_ref,
+                );
+
+                let mut page = pdf.page(page_ref);
+                page.media_box(pdf_writer::Rect::new(0.0, 0.0, *width, *height))
+                    .parent(pages_ref)
+                    .contents(content_ref);
+                page.resources()
+                    .x_objects()
+                    .pair(PAGE_X_OBJECT_NAME, x_object_ref);
+                page.finish();
+
+                let mut content = Content::new();
+                content
+                    .transform([*width, 0.0, 0.0, *height, 0.0, 0.0])
+                    .x_object(PAGE_X_OBJECT_NAME);
+                pdf.stream(content_ref, &content.finish());
+
+                let mut outline_item = pdf.outline_item(outline_item_refs[page_index]);
+                outline_item.title(TextStr(title)).parent(outline_ref);
+                if let Some(outline_item_prev) = page_index
+                    .checked_sub(1)
+                    .map(|page_index_prev| outline_item_refs[page_index_prev])
+                {
+                    outline_item.prev(outline_item_prev);
+                }
+                if let Some(outline_item_next) = outline_item_refs.get(page_index + 1) {
+                    outline_item.next(*outline_item_next);
+                }
+                outline_item.dest().page(page_ref).fit();
+            });
+
+        pdf.finish()
+    }
+
+    /// Returns the page with the diagram and its header.
+    fn page_render(
+        diagram_focus_generated: &DiagramFocusGenerated,
+        pdf_options: &PdfOptions,
+        fontdb: &Database,
+    ) -> PdfPage {
+        let DiagramFocusGenerated {
+            focus,
+            diagram_generated,
+        } = diagram_focus_generated;
+        let color_scheme = pdf_options.color_scheme;
+        let paint_items =
+            SvgElementsToPaintItemsMapper::map(&diagram_generated.svg_elements, color_scheme);
+        let PaintItems {
+            width: diagram_width,
+            height: diagram_height,
+            ..
+        } = paint_items;
+        let pdf_page_header = PdfPageHeader::new(focus, &diagram_generated.input_diagram_merged);
+
+        // Layout is done in pixels, and converted to points when drawing.
+        let char_width = f64::from(MONOSPACE_CHAR_WIDTH_RATIO * TEXT_FONT_SIZE);
+        let line_height = f64::from(TEXT_LINE_HEIGHT);
+        let page_portrait = pdf_options
+            .page_size
+            .portrait_points()
+            .map(|(width, height)| (width / POINTS_PER_PIXEL, height / POINTS_PER_PIXEL));
+        let content_width = match page_portrait {
+            Some((_width, height)) if diagram_width > diagram_height => height - 2.0 * PAGE_MARGIN,
+            Some((width, _height)) => width - 2.0 * PAGE_MARGIN,
+            None => diagram_width.max(DESC_COLUMNS_MIN * char_width),
+        };
+
+        let columns = (content_width / char_width) as f32;
+        let title_lines = pdf_page_header.title_lines(columns / TITLE_SCALE as f32);
+        let desc_lines = pdf_page_header.desc_lines(columns);
+        let title_height = title_lines.len() as f64 * line_height * TITLE_SCALE;
+        let desc_height = if desc_lines.is_empty() {
+            0.0
+        } else {
+            line_height + desc_lines.len() as f64 * line_height
+        };
+        let header_height = title_height + desc_height + HEADER_GAP;
+
+        let (page_width, page_height, diagram_scale) = match page_portrait {
+            Some((width, height)) => {
+                let (page_width, page_height) = if diagram_width > diagram_height {
+                    (height, width)
+                } else {
+                    (width, height)
+                };
+                let diagram_height_available = page_height - 2.0 * PAGE_MARGIN - header_height;
+                let diagram_scale = [
+                    1.0,
+                    content_width / diagram_width,
+                    diagram_height_available / diagram_height,
+                ]
+                .into_iter()
+                .filter(|scale| scale.is_finite() && *scale > 0.0)
+                .fold(1.0, f64::min);
+                (page_width, page_height, diagram_scale)
+            }
+            None => (
+                content_width + 2.0 * PAGE_MARGIN,
+                header_height + diagram_height + 2.0 * PAGE_MARGIN,
+                1.0,
+            ),
+        };
+
+        let mut page_svg = String::with_capacity(4096);
+        let _ = write!(
+            page_svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{page_width}\" \
+            height=\"{page_height}\">"
+        );
+
+        if color_scheme == ColorScheme::Dark {
+            let page_background = PaintPath {
+                path: Rect::new(0.0, 0.0, page_width, page_height).to_path(0.1),
+                fill: Some(Self::theme_color(PAGE_FILL_DARK, color_scheme)),
+                fill_rule: FillRule::NonZero,
+                stroke: None,
+            };
+            PaintItems::svg_path_write(&mut page_svg, &page_background);
+        }
+
+        let mut line_top = PAGE_MARGIN;
+        let title_fill = Self::theme_color(TITLE_FILL, color_scheme);
+        title_lines.into_iter().for_each(|title_line| {
+            Self::header_line_write(
+                &mut page_svg,
+                Self::header_text(title_line, title_fill, true),
+                Affine::translate((PAGE_MARGIN, line_top)) * Affine::scale(TITLE_SCALE),
+            );
+            line_top += line_height * TITLE_SCALE;
+        });
+        line_top += desc_height.min(line_height);
+        let desc_fill = Self::theme_color(DESC_FILL, color_scheme);
+        desc_lines.into_iter().for_each(|desc_line| {
+            Self::header_line_write(
+                &mut page_svg,
+                Self::header_text(desc_line, desc_fill, false),
+                Affine::translate((PAGE_MARGIN, line_top)),
+            );
+            line_top += line_height;
+        });
+
+        let diagram_left =
+            PAGE_MARGIN + (content_width - diagram_width * diagram_scale).max(0.0) / 2.0;
+        let diagram_top = PAGE_MARGIN + header_height;
+        Self::group_open(
+            &mut page_svg,
+            Affine::translate((diagram_left, diagram_top)) * Affine::scale(diagram_scale),
+        );
+        paint_items.svg_content_write(&mut page_svg);
+        page_svg.push_str("</g></svg>");
+
+        PdfPage {
+            width: (page_width * POINTS_PER_PIXEL) as f32,
+            height: (page_height * POINTS_PER_PIXEL) as f32,
+            title: pdf_page_header.title,
+            tree: UsvgTreeBuilder::build(&page_svg, fontdb),
+        }
+    }
+
+    /// Returns the color of a theme variable, e.g. `var(--tw-slate-900-100)`.
+    fn theme_color(value: &str, color_scheme: ColorScheme) -> PaintColor {
+        PaintColor::css_value_parse(value, color_scheme)
+            .flatten()
+            .expect("Expected page colors to be theme variables.")
+    }
+
+    /// Returns a line of header text, with its top at `y = 0`.
+    fn header_text(text: String, fill: PaintColor, bold: bool) -> PaintText {
+        PaintText {
+            point: Point::new(0.0, f64::from(TEXT_FONT_SIZE)),
+            text,
+            fill,
+            bold,
+            italic: false,
+            underline: false,
+            line_through: false,
+            is_preserved: true,
+        }
+    }
+
+    /// Writes a line of header text, placed by `affine`.
+    fn header_line_write(page_svg: &mut String, paint_text: PaintText, affine: Affine) {
+        Self::group_open(page_svg, affine);
+        PaintItems::svg_text_write(page_svg, &paint_text);
+        page_svg.push_str("</g>");
+    }
+
+    /// Writes the start tag of a `<g>` element transformed by `affine`.
+    fn group_open(page_svg: &mut String, affine: Affine) {
+        let [a, b, c, d, e, f] = affine.as_coeffs();
+        let _ = write!(
+            page_svg,
+            "<g transform=\"matrix({a} {b} {c} {d} {e} {f})\">"
+        );
+    }
+}
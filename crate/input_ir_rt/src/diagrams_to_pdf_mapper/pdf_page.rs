@@ -0,0 +1,12 @@
+/// A page to write to a PDF document.
+#[derive(Clone, Debug)]
+pub(crate) struct PdfPage {
+    /// Width of the page in points.
+    pub(crate) width: f32,
+    /// Height of the page in points.
+    pub(crate) height: f32,
+    /// Title of the page's bookmark.
+    pub(crate) title: String,
+    /// What is drawn on the page, with text converted to paths.
+    pub(crate) tree: usvg::Tree,
+}
@@ -0,0 +1,188 @@
+use disposition_input_model::{process::ProcessId, DiagramFocus, InputDiagram};
+use disposition_model_common::Id;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    ir_to_taffy_builder::text_measure::grapheme_char_column_count,
+    md_text::md_blocks_parser::{MdBlocksParser, MdTokenItem},
+};
+
+/// Title of the page for the diagram with nothing focused.
+const TITLE_FOCUS_NONE: &str = "Overview";
+
+/// Number of columns that each level of list nesting is indented by.
+const LIST_INDENT_COLUMNS: usize = 2;
+
+/// Title and description of the focused entity, shown above its diagram.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PdfPageHeader {
+    /// Name of the focused process, process step, or tag.
+    pub(crate) title: String,
+    /// Markdown description of the focused entity, if any.
+    pub(crate) desc: Option<String>,
+}
+
+impl PdfPageHeader {
+    /// Returns the header for the focused entity in the input diagram.
+    ///
+    /// Process step titles are prefixed with their process' name, and entities
+    /// without a name are titled with their ID.
+    ///
+    /// Descriptions are the process' `desc`, or the `entity_tooltips` of the
+    /// process step or tag.
+    pub(crate) fn new(focus: &DiagramFocus<'_>, input_diagram: &InputDiagram<'_>) -> Self {
+        let process_name = |process_id: &ProcessId<'_>| {
+            input_diagram
+                .processes
+                .get(process_id)
+                .and_then(|process_diagram| process_diagram.name.clone())
+                .unwrap_or_else(|| process_id.as_str().to_string())
+        };
+        let tooltip = |id: &Id<'_>| input_diagram.entity_tooltips.get(id).cloned();
+
+        match focus {
+            DiagramFocus::None => Self {
+                title: TITLE_FOCUS_NONE.to_string(),
+                desc: None,
+            },
+            DiagramFocus::Process(process_id) => Self {
+                title: process_name(process_id),
+                desc: input_diagram
+                    .processes
+                    .get(process_id)
+                    .and_then(|process_diagram| process_diagram.desc.clone()),
+            },
+            DiagramFocus::ProcessStep {
+                process_id,
+                process_step_id,
+            } => {
+                let process_step_name = input_diagram
+                    .processes
+                    .get(process_id)
+                    .and_then(|process_diagram| process_diagram.steps.get(process_step_id))
+                    .cloned()
+                    .unwrap_or_else(|| process_step_id.as_str().to_string());
+                Self {
+                    title: format!("{}: {process_step_name}", process_name(process_id)),
+                    desc: tooltip(process_step_id),
+                }
+            }
+            DiagramFocus::Tag(tag_id) => Self {
+                title: input_diagram
+                    .tags
+                    .get(tag_id)
+                    .cloned()
+                    .unwrap_or_else(|| tag_id.as_str().to_string()),
+                desc: tooltip(tag_id),
+            },
+        }
+    }
+
+    /// Returns the lines of the title, wrapped to the given number of columns.
+    pub(crate) fn title_lines(&self, columns: f32) -> Vec<String> {
+        let words = self
+            .title
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        let mut lines = Vec::new();
+        Self::words_wrap(&mut lines, &words, "", columns);
+        lines
+    }
+
+    /// Returns the lines of the description as plain text, wrapped to the
+    /// given number of columns.
+    ///
+    /// Inline markdown styles are dropped, list items keep their markers, and
+    /// code blocks keep their lines as is.
+    pub(crate) fn desc_lines(&self, columns: f32) -> Vec<String> {
+        let Some(desc) = self.desc.as_deref() else {
+            return Vec::new();
+        };
+
+        let md_blocks = MdBlocksParser::parse(desc);
+        let mut lines = Vec::new();
+        md_blocks
+            .iter()
+            .enumerate()
+            .for_each(|(block_index, md_block)| {
+                // Separate blocks with a blank line, except between list items.
+                let is_list_item_after_list_item = md_block.list_item.is_some()
+                    && block_index > 0
+                    && md_blocks[block_index - 1].list_item.is_some();
+                if block_index > 0 && !is_list_item_after_list_item {
+                    lines.push(String::new());
+                }
+
+                let indent_columns = md_block
+                    .list_item
+                    .as_ref()
+                    .map(|md_list_item| usize::from(md_list_item.depth) * LIST_INDENT_COLUMNS)
+                    .unwrap_or_default();
+                let indent = " ".repeat(indent_columns);
+
+                if let Some(md_code_block) = md_block.code_block.as_ref() {
+                    lines.extend(
+                        md_code_block
+                            .lines
+                            .iter()
+                            .map(|line| format!("{indent}{line}")),
+                    );
+                    return;
+                }
+
+                let mut words_lines = vec![Vec::<String>::new()];
+                md_block.tokens.iter().for_each(|md_token_item| {
+                    let word = match md_token_item {
+                        MdTokenItem::Word { text, .. } => text.as_str(),
+                        MdTokenItem::Image { alt, .. } => alt.as_str(),
+                        MdTokenItem::LineBreak => {
+                            words_lines.push(Vec::new());
+                            return;
+                        }
+                    };
+                    let words = words_lines.last_mut().expect("Always has a line.");
+                    match words.last_mut() {
+                        Some(word_last) if md_token_item.glue_prev() => word_last.push_str(word),
+                        _ => words.push(word.to_string()),
+                    }
+                });
+
+                words_lines.iter().for_each(|words| {
+                    Self::words_wrap(&mut lines, words, &indent, columns);
+                });
+            });
+
+        lines
+    }
+
+    /// Appends the words to `lines`, starting a new line before a word that
+    /// would exceed `columns`.
+    fn words_wrap(lines: &mut Vec<String>, words: &[String], indent: &str, columns: f32) {
+        let column_count = |text: &str| -> f32 {
+            text.graphemes(true)
+                .map(grapheme_char_column_count)
+                .sum::<f32>()
+        };
+
+        let mut line = indent.to_string();
+        let mut line_columns = column_count(indent);
+        let mut line_has_word = false;
+        words.iter().for_each(|word| {
+            let word_columns = column_count(word);
+            if line_has_word && line_columns + 1.0 + word_columns > columns {
+                lines.push(std::mem::replace(&mut line, indent.to_string()));
+                line_columns = column_count(indent);
+                line_has_word = false;
+            }
+            if line_has_word {
+                line.push(' ');
+                line_columns += 1.0;
+            }
+            line.push_str(word);
+            line_columns += word_columns;
+            line_has_word = true;
+        });
+        lines.push(line);
+    }
+}
@@ -33,14 +33,19 @@ use crate::{
 };
 
 use self::{
-    css_theme_vars::CssThemeVars, node_nesting_infos_builder::NodeNestingInfosBuilder,
+    css_theme_vars::CssThemeVars,
+    dependency_cycle_issues_collector::DependencyCycleIssuesCollector,
+    node_nesting_infos_builder::NodeNestingInfosBuilder,
+    reference_issues_collector::ReferenceIssuesCollector,
     tailwind_classes_builder::TailwindClassesBuilder, tailwind_focus_mode::TailwindFocusMode,
     theme_attr_resolver::ThemeAttrResolver,
 };
 
 mod css_theme_vars;
+mod dependency_cycle_issues_collector;
 mod node_nesting_infos_builder;
-mod tailwind_class_state;
+mod reference_issues_collector;
+pub(crate) mod tailwind_class_state;
 mod tailwind_classes_builder;
 pub(crate) mod tailwind_color_shade;
 pub(crate) mod tailwind_colors;
@@ -69,6 +74,12 @@ impl InputToIrDiagramMapper {
     /// This builds the focus-independent structure via `Self::map_structure`,
     /// then applies the interactive (`peer` / `group-has`) tailwind classes via
     /// `Self::tailwind_classes_apply`.
+    ///
+    /// References to undeclared IDs, e.g. an unknown `ThingId` in an edge group
+    /// or `tag_things`, and unintended dependency cycles are returned as
+    /// [`ModelToIrIssue`]s alongside the diagram.
+    ///
+    /// [`ModelToIrIssue`]: disposition_input_ir_model::issue::ModelToIrIssue
     pub fn map<'f, 'id>(input_diagram: &'f InputDiagram<'id>) -> IrDiagramAndIssues<'id>
     where
         'id: 'f,
@@ -95,7 +106,9 @@ impl InputToIrDiagramMapper {
     where
         'id: 'f,
     {
-        let issues = Vec::new();
+        // 0. Collect issues for references to undeclared IDs. These don't stop the
+        //    mapping -- dangling references are ignored by later stages.
+        let mut issues = ReferenceIssuesCollector::collect(input_diagram);
 
         let InputDiagram {
             things,
@@ -260,7 +273,16 @@ impl InputToIrDiagramMapper {
             &layout_edges,
         );
 
-        // 16a. Reverse the stored direction of descending-rank `Curved` edges
+        // 16a. Report dependency cycles that were contracted onto one rank,
+        //      unless they only come from `cyclic` / `symmetric` edge groups.
+        issues.extend(DependencyCycleIssuesCollector::collect(
+            &edge_groups,
+            &ir_entity_types,
+            &node_nesting_infos,
+            thing_layout_edges,
+        ));
+
+        // 16b. Reverse the stored direction of descending-rank `Curved` edges
         //      so every later stage (spacer construction, face assignment,
         //      offsets, protrusions, path building) computes the cleaner
         //      mirror geometry. The SVG path is reversed back at emission.
@@ -0,0 +1,267 @@
+use disposition_input_ir_model::issue::{DependencyCycleEdge, ModelToIrIssue};
+use disposition_ir_model::{
+    edge::EdgeGroups,
+    entity::{EntityType, EntityTypes},
+    node::{NodeId, NodeNestingInfos},
+};
+use disposition_model_common::{thing::ThingLayoutEdges, Id, Map};
+
+use crate::NodeRanksCalculator;
+
+/// Collects [`ModelToIrIssue::ThingDependenciesCycle`]s for dependency cycles
+/// that [`NodeRanksCalculator`] contracts onto a single rank.
+///
+/// Cycles are found per hierarchy level, the same way ranks are computed:
+/// edges between nested things are lifted to their sibling ancestors at the
+/// lowest common ancestor's level.
+///
+/// Cycles formed by `cyclic` and `symmetric` edge groups are intentional, so
+/// they are contracted first. A strongly connected component (SCC) is then
+/// reported when `sequence` edge groups or `thing_layout_edges` join more
+/// than one contracted node into a cycle.
+pub(crate) struct DependencyCycleIssuesCollector;
+
+/// A dependency or layout edge, with where it is declared.
+struct SourcedEdge<'id> {
+    /// Path to the declaring edge group or layout edge, e.g.
+    /// `thing_dependencies.edge_a__b`.
+    field_path: String,
+    /// Node the edge starts from, as declared.
+    from: NodeId<'id>,
+    /// Node the edge points to, as declared.
+    to: NodeId<'id>,
+    /// Whether the edge belongs to a `cyclic` or `symmetric` edge group, so
+    /// it is expected to be part of a cycle.
+    is_cycle_intended: bool,
+}
+
+/// A [`SourcedEdge`] lifted to the hierarchy level it is ranked at.
+struct LevelEdge<'e, 'id> {
+    /// Sibling ancestor of `from` at the edge's level.
+    from: NodeId<'id>,
+    /// Sibling ancestor of `to` at the edge's level.
+    to: NodeId<'id>,
+    /// The edge as declared.
+    sourced_edge: &'e SourcedEdge<'id>,
+}
+
+impl DependencyCycleIssuesCollector {
+    /// Returns an issue for each unintended dependency cycle, ordered by
+    /// hierarchy level, then by the cycle's first node.
+    pub(crate) fn collect<'id>(
+        edge_groups: &EdgeGroups<'id>,
+        entity_types: &EntityTypes<'id>,
+        node_nesting_infos: &NodeNestingInfos<'id>,
+        thing_layout_edges: &ThingLayoutEdges<'id>,
+    ) -> Vec<ModelToIrIssue> {
+        let sourced_edges =
+            Self::sourced_edges_collect(edge_groups, entity_types, thing_layout_edges);
+
+        // Group edges by the container whose children they are ranked
+        // between (`None` for root).
+        let lca_level_edges = sourced_edges
+            .iter()
+            .filter_map(|sourced_edge| {
+                NodeRanksCalculator::lca_level_edge_compute(
+                    &sourced_edge.from,
+                    &sourced_edge.to,
+                    node_nesting_infos,
+                )
+                .map(|(lca_container, from, to)| {
+                    (
+                        lca_container,
+                        LevelEdge {
+                            from,
+                            to,
+                            sourced_edge,
+                        },
+                    )
+                })
+            })
+            .fold(
+                Map::<Option<NodeId<'id>>, Vec<LevelEdge<'_, 'id>>>::new(),
+                |mut lca_level_edges, (lca_container, level_edge)| {
+                    lca_level_edges
+                        .entry(lca_container)
+                        .or_default()
+                        .push(level_edge);
+                    lca_level_edges
+                },
+            );
+
+        lca_level_edges
+            .values()
+            .flat_map(|level_edges| Self::level_issues_collect(level_edges))
+            .collect()
+    }
+
+    /// Returns the dependency edges from `edge_groups` and the layout edges
+    /// from `thing_layout_edges`, excluding self-loops.
+    fn sourced_edges_collect<'id>(
+        edge_groups: &EdgeGroups<'id>,
+        entity_types: &EntityTypes<'id>,
+        thing_layout_edges: &ThingLayoutEdges<'id>,
+    ) -> Vec<SourcedEdge<'id>> {
+        let dependency_edges = edge_groups
+            .iter()
+            .filter(|(edge_group_id, _edge_group)| {
+                NodeRanksCalculator::edge_group_is_dependency(edge_group_id.as_ref(), entity_types)
+            })
+            .flat_map(|(edge_group_id, edge_group)| {
+                let field_path = format!("thing_dependencies.{edge_group_id}");
+                let is_cycle_intended = entity_types
+                    .get(edge_group_id.as_ref())
+                    .map(|types| {
+                        types.iter().any(|entity_type| {
+                            matches!(
+                                entity_type,
+                                EntityType::DependencyEdgeCyclicDefault
+                                    | EntityType::DependencyEdgeSymmetricDefault
+                            )
+                        })
+                    })
+                    .unwrap_or(false);
+
+                edge_group.iter().map(move |edge| SourcedEdge {
+                    field_path: field_path.clone(),
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                    is_cycle_intended,
+                })
+            });
+
+        let layout_edges = thing_layout_edges
+            .iter()
+            .map(|(edge_id, layout_edge)| SourcedEdge {
+                field_path: format!("thing_layout_edges.{edge_id}"),
+                from: NodeId::from(layout_edge.from.clone()),
+                to: NodeId::from(layout_edge.to.clone()),
+                is_cycle_intended: false,
+            });
+
+        dependency_edges
+            .chain(layout_edges)
+            .filter(|sourced_edge| sourced_edge.from != sourced_edge.to)
+            .collect()
+    }
+
+    /// Returns an issue for each unintended cycle among the edges at a single
+    /// hierarchy level.
+    ///
+    /// Cycles formed by `cyclic` / `symmetric` edge groups are contracted
+    /// first, so a `sequence` edge between nodes that are already in an
+    /// intended cycle is not reported.
+    fn level_issues_collect(level_edges: &[LevelEdge<'_, '_>]) -> Vec<ModelToIrIssue> {
+        // Index nodes in the order they first appear, so issues are reported
+        // in a stable order.
+        let node_to_index = level_edges
+            .iter()
+            .flat_map(|level_edge| [&level_edge.from, &level_edge.to])
+            .fold(
+                Map::<&NodeId<'_>, usize>::new(),
+                |mut node_to_index, node_id| {
+                    let node_index = node_to_index.len();
+                    node_to_index.entry(node_id).or_insert(node_index);
+                    node_to_index
+                },
+            );
+        let edge_indices = |level_edge: &LevelEdge<'_, '_>| {
+            (
+                node_to_index[&level_edge.from],
+                node_to_index[&level_edge.to],
+            )
+        };
+
+        // === Contract Intended Cycles === //
+        let intended_adjacency = level_edges
+            .iter()
+            .filter(|level_edge| level_edge.sourced_edge.is_cycle_intended)
+            .map(edge_indices)
+            .fold(
+                vec![Vec::new(); node_to_index.len()],
+                |mut adjacency, (from_index, to_index)| {
+                    adjacency[from_index].push(to_index);
+                    adjacency
+                },
+            );
+        let intended_scc_ids =
+            NodeRanksCalculator::tarjan_scc(&intended_adjacency, node_to_index.len());
+        let intended_scc_count = intended_scc_ids
+            .iter()
+            .copied()
+            .max()
+            .map_or(0, |scc_id| scc_id + 1);
+
+        // === Find Cycles Between Contracted Nodes === //
+        let contracted_adjacency = level_edges.iter().map(edge_indices).fold(
+            vec![Vec::new(); intended_scc_count],
+            |mut adjacency, (from_index, to_index)| {
+                let (from_scc, to_scc) = (intended_scc_ids[from_index], intended_scc_ids[to_index]);
+                if from_scc != to_scc {
+                    adjacency[from_scc].push(to_scc);
+                }
+                adjacency
+            },
+        );
+        let contracted_scc_ids =
+            NodeRanksCalculator::tarjan_scc(&contracted_adjacency, intended_scc_count);
+        let scc_ids = intended_scc_ids
+            .iter()
+            .map(|&intended_scc_id| contracted_scc_ids[intended_scc_id])
+            .collect::<Vec<usize>>();
+
+        let scc_members = node_to_index.iter().fold(
+            Map::<usize, Vec<Id<'static>>>::new(),
+            |mut scc_members, (node_id, &node_index)| {
+                scc_members
+                    .entry(scc_ids[node_index])
+                    .or_default()
+                    .push(node_id.as_ref().clone().into_static());
+                scc_members
+            },
+        );
+
+        scc_members
+            .into_iter()
+            .filter(|(_scc_id, node_ids)| node_ids.len() > 1)
+            .filter_map(|(scc_id, node_ids)| {
+                let scc_edges = level_edges
+                    .iter()
+                    .map(|level_edge| (level_edge, edge_indices(level_edge)))
+                    .filter(|(_level_edge, (from_index, to_index))| {
+                        scc_ids[*from_index] == scc_id && scc_ids[*to_index] == scc_id
+                    })
+                    .collect::<Vec<_>>();
+
+                // The SCC is only larger than an intended cycle if an
+                // unintended edge connects different intended cycles.
+                let (level_edge_first, _) =
+                    scc_edges
+                        .iter()
+                        .find(|(level_edge, (from_index, to_index))| {
+                            !level_edge.sourced_edge.is_cycle_intended
+                                && intended_scc_ids[*from_index] != intended_scc_ids[*to_index]
+                        })?;
+                let field_path = level_edge_first.sourced_edge.field_path.clone();
+                let edges = scc_edges
+                    .iter()
+                    .map(|(level_edge, _)| {
+                        let sourced_edge = level_edge.sourced_edge;
+                        DependencyCycleEdge {
+                            field_path: sourced_edge.field_path.clone(),
+                            from: sourced_edge.from.as_ref().clone().into_static(),
+                            to: sourced_edge.to.as_ref().clone().into_static(),
+                        }
+                    })
+                    .collect();
+
+                Some(ModelToIrIssue::ThingDependenciesCycle {
+                    field_path,
+                    node_ids,
+                    edges,
+                    span: None,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,284 @@
+use disposition_input_ir_model::issue::ModelToIrIssue;
+use disposition_input_model::{
+    edge::EdgeGroup,
+    process::Processes,
+    tag::{TagNames, TagThings},
+    thing::{ThingDependencies, ThingHierarchy, ThingInteractions, ThingNames},
+    InputDiagram,
+};
+use disposition_model_common::{edge::EdgeGroupId, entity::EntityTypes, Id, Map, Set};
+
+use crate::{EdgeIdGenerator, InputToIrDiagramMapper};
+
+/// Collects [`ModelToIrIssue`]s for references in an [`InputDiagram`] to IDs
+/// that are not declared.
+///
+/// The declared IDs are:
+///
+/// * Nodes: things in `things`, tags in `tags`, and processes / process steps
+///   in `processes`.
+/// * Edge groups: keys of `thing_dependencies` and `thing_interactions`.
+/// * Edges: `<edge_group_id>__<index>` for each edge in each edge group.
+pub(crate) struct ReferenceIssuesCollector;
+
+impl ReferenceIssuesCollector {
+    /// Returns the issues for every reference to an undeclared ID, in input
+    /// field order.
+    pub(crate) fn collect(input_diagram: &InputDiagram<'_>) -> Vec<ModelToIrIssue> {
+        let InputDiagram {
+            things,
+            thing_names,
+            thing_dependencies,
+            thing_interactions,
+            processes,
+            tags,
+            tag_things,
+            entity_types,
+            ..
+        } = input_diagram;
+
+        let thing_ids = Self::thing_ids_collect(things);
+        let node_ids = Self::node_ids_collect(&thing_ids, tags, processes);
+        let edge_group_ids = Self::edge_group_ids_collect(thing_dependencies, thing_interactions);
+
+        let mut issues = Vec::new();
+
+        Self::thing_names_check(&mut issues, thing_names, &thing_ids);
+        Self::edge_groups_check(
+            &mut issues,
+            "thing_dependencies",
+            thing_dependencies,
+            &node_ids,
+        );
+        Self::edge_groups_check(
+            &mut issues,
+            "thing_interactions",
+            thing_interactions,
+            &node_ids,
+        );
+        Self::step_thing_interactions_check(&mut issues, processes, &edge_group_ids);
+        Self::tag_things_check(&mut issues, tag_things, tags, &thing_ids);
+        Self::entity_types_check(
+            &mut issues,
+            entity_types,
+            &node_ids,
+            thing_dependencies,
+            thing_interactions,
+        );
+
+        issues
+    }
+
+    /// Returns the IDs of every thing in the hierarchy.
+    fn thing_ids_collect<'f, 'id>(things: &'f ThingHierarchy<'id>) -> Set<&'f str> {
+        things
+            .thing_ids_recursive()
+            .into_iter()
+            .map(|id| id.as_str())
+            .collect()
+    }
+
+    /// Returns the IDs of every node an edge may connect: things, tags,
+    /// processes, and process steps.
+    fn node_ids_collect<'f, 'id>(
+        thing_ids: &Set<&'f str>,
+        tags: &'f TagNames<'id>,
+        processes: &'f Processes<'id>,
+    ) -> Set<&'f str> {
+        let tag_ids = tags.keys().map(|tag_id| tag_id.as_str());
+        let process_and_step_ids = processes.iter().flat_map(|(process_id, process_diagram)| {
+            std::iter::once(process_id.as_str()).chain(
+                process_diagram
+                    .steps
+                    .keys()
+                    .map(|process_step_id| process_step_id.as_str()),
+            )
+        });
+
+        thing_ids
+            .iter()
+            .copied()
+            .chain(tag_ids)
+            .chain(process_and_step_ids)
+            .collect()
+    }
+
+    /// Returns the IDs of every dependency and interaction edge group.
+    fn edge_group_ids_collect<'f, 'id>(
+        thing_dependencies: &'f ThingDependencies<'id>,
+        thing_interactions: &'f ThingInteractions<'id>,
+    ) -> Set<&'f str> {
+        thing_dependencies
+            .keys()
+            .chain(thing_interactions.keys())
+            .map(|id| id.as_str())
+            .collect()
+    }
+
+    // === Checks === //
+
+    fn thing_names_check(
+        issues: &mut Vec<ModelToIrIssue>,
+        thing_names: &ThingNames<'_>,
+        thing_ids: &Set<&str>,
+    ) {
+        let thing_names_issues = thing_names
+            .keys()
+            .filter(|thing_id| !thing_ids.contains(thing_id.as_str()))
+            .map(|thing_id| ModelToIrIssue::ThingNamesThingUnknown {
+                field_path: format!("thing_names.{thing_id}"),
+                thing_id: thing_id.clone().into_static(),
+                span: None,
+            });
+
+        issues.extend(thing_names_issues);
+    }
+
+    /// Checks that each thing in each edge group is a declared node.
+    ///
+    /// `field_name` is the top level field the edge groups are declared in,
+    /// i.e. `thing_dependencies` or `thing_interactions`.
+    fn edge_groups_check(
+        issues: &mut Vec<ModelToIrIssue>,
+        field_name: &str,
+        edge_groups: &Map<EdgeGroupId<'_>, EdgeGroup<'_>>,
+        node_ids: &Set<&str>,
+    ) {
+        let edge_group_issues = edge_groups.iter().flat_map(|(edge_group_id, edge_group)| {
+            edge_group
+                .things
+                .iter()
+                .enumerate()
+                .filter(|(_index, thing_id)| !node_ids.contains(thing_id.as_str()))
+                .map(
+                    move |(index, thing_id)| ModelToIrIssue::EdgeGroupThingUnknown {
+                        field_path: format!("{field_name}.{edge_group_id}.things[{index}]"),
+                        edge_group_id: edge_group_id.clone().into_static(),
+                        thing_id: thing_id.clone().into_static(),
+                        span: None,
+                    },
+                )
+        });
+
+        issues.extend(edge_group_issues);
+    }
+
+    /// Checks that each process's `step_thing_interactions` is keyed by that
+    /// process's steps, and only references declared edge groups.
+    fn step_thing_interactions_check(
+        issues: &mut Vec<ModelToIrIssue>,
+        processes: &Processes<'_>,
+        edge_group_ids: &Set<&str>,
+    ) {
+        processes.iter().for_each(|(process_id, process_diagram)| {
+            process_diagram.step_thing_interactions.iter().for_each(
+                |(process_step_id, step_edge_group_ids)| {
+                    let field_path_step =
+                        format!("processes.{process_id}.step_thing_interactions.{process_step_id}");
+
+                    if !process_diagram.steps.contains_key(process_step_id) {
+                        issues.push(ModelToIrIssue::StepThingInteractionsStepUnknown {
+                            field_path: field_path_step.clone(),
+                            process_id: process_id.clone().into_static(),
+                            process_step_id: process_step_id.clone().into_static(),
+                            span: None,
+                        });
+                    }
+
+                    let edge_group_issues = step_edge_group_ids
+                        .iter()
+                        .enumerate()
+                        .filter(|(_index, edge_group_id)| {
+                            !edge_group_ids.contains(edge_group_id.as_str())
+                        })
+                        .map(|(index, edge_group_id)| {
+                            ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown {
+                                field_path: format!("{field_path_step}[{index}]"),
+                                process_id: process_id.clone().into_static(),
+                                process_step_id: process_step_id.clone().into_static(),
+                                edge_group_id: edge_group_id.clone().into_static(),
+                                span: None,
+                            }
+                        });
+                    issues.extend(edge_group_issues);
+                },
+            );
+        });
+    }
+
+    /// Checks that `tag_things` is keyed by declared tags, and only lists
+    /// declared things.
+    fn tag_things_check(
+        issues: &mut Vec<ModelToIrIssue>,
+        tag_things: &TagThings<'_>,
+        tags: &TagNames<'_>,
+        thing_ids: &Set<&str>,
+    ) {
+        tag_things.iter().for_each(|(tag_id, tag_thing_ids)| {
+            let field_path_tag = format!("tag_things.{tag_id}");
+
+            if !tags.contains_key(tag_id) {
+                issues.push(ModelToIrIssue::TagThingsTagUnknown {
+                    field_path: field_path_tag.clone(),
+                    tag_id: tag_id.clone().into_static(),
+                    span: None,
+                });
+            }
+
+            let thing_issues = tag_thing_ids
+                .iter()
+                .enumerate()
+                .filter(|(_index, thing_id)| !thing_ids.contains(thing_id.as_str()))
+                .map(|(index, thing_id)| ModelToIrIssue::TagThingsThingUnknown {
+                    field_path: format!("{field_path_tag}[{index}]"),
+                    tag_id: tag_id.clone().into_static(),
+                    thing_id: thing_id.clone().into_static(),
+                    span: None,
+                });
+            issues.extend(thing_issues);
+        });
+    }
+
+    /// Checks that `entity_types` is keyed by declared nodes, edge groups, or
+    /// edges.
+    fn entity_types_check<'id>(
+        issues: &mut Vec<ModelToIrIssue>,
+        entity_types: &EntityTypes<'id>,
+        node_ids: &Set<&str>,
+        thing_dependencies: &ThingDependencies<'id>,
+        thing_interactions: &ThingInteractions<'id>,
+    ) {
+        if entity_types.is_empty() {
+            return;
+        }
+
+        // Edge groups and the edges within them may both be typed.
+        let edge_entity_ids: Set<Id<'_>> = thing_dependencies
+            .iter()
+            .chain(thing_interactions.iter())
+            .flat_map(|(edge_group_id, edge_group)| {
+                let edge_count =
+                    InputToIrDiagramMapper::edge_kind_to_edges(edge_group.kind, &edge_group.things)
+                        .len();
+                let edge_ids = (0..edge_count).map(move |edge_index| {
+                    EdgeIdGenerator::generate(edge_group_id, edge_index).into_inner()
+                });
+
+                std::iter::once(edge_group_id.as_ref().clone()).chain(edge_ids)
+            })
+            .collect();
+
+        let entity_types_issues = entity_types
+            .keys()
+            .filter(|entity_id| {
+                !node_ids.contains(entity_id.as_str()) && !edge_entity_ids.contains(*entity_id)
+            })
+            .map(|entity_id| ModelToIrIssue::EntityTypesEntityUnknown {
+                field_path: format!("entity_types.{entity_id}"),
+                entity_id: entity_id.clone().into_static(),
+                span: None,
+            });
+
+        issues.extend(entity_types_issues);
+    }
+}
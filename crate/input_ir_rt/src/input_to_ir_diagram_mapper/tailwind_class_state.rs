@@ -12,7 +12,7 @@ use crate::svg_element_classes::{
 
 use super::{css_theme_vars::CssThemeVars, tailwind_color_shade::TailwindColorShade};
 
-use self::shade_computer::ShadeComputer;
+pub(crate) use self::shade_computer::ShadeComputer;
 
 mod shade_computer;
 
@@ -0,0 +1,55 @@
+use disposition_ir_model::{
+    node::{NodeId, NodeShapeRect},
+    IrDiagram,
+};
+use disposition_model_common::{entity::EntityType, RankDir, Set};
+
+/// Attributes of an IR diagram's nodes and edges that are written the same
+/// way by the text graph mappers, i.e. `IrToDotMapper` and
+/// `IrToMermaidMapper`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct IrGraphAttrs;
+
+impl IrGraphAttrs {
+    /// Returns the node's name, followed by its description on the next line.
+    pub(crate) fn node_label(ir_diagram: &IrDiagram<'_>, node_id: &NodeId<'_>) -> String {
+        let name = ir_diagram
+            .nodes
+            .get(node_id)
+            .map(String::as_str)
+            .unwrap_or(node_id.as_str());
+        match ir_diagram.thing_descs.get(node_id.as_ref()) {
+            Some(thing_desc) => format!("{name}\n{thing_desc}"),
+            None => String::from(name),
+        }
+    }
+
+    /// Returns whether the edge group's entity types mark it as an
+    /// interaction.
+    pub(crate) fn is_interaction(edge_group_types: &Set<EntityType>) -> bool {
+        edge_group_types.iter().any(EntityType::is_interaction_edge)
+    }
+
+    /// Returns whether any corner of the rectangle is rounded.
+    pub(crate) fn is_rounded(node_shape_rect: &NodeShapeRect) -> bool {
+        [
+            node_shape_rect.radius_top_left,
+            node_shape_rect.radius_top_right,
+            node_shape_rect.radius_bottom_left,
+            node_shape_rect.radius_bottom_right,
+        ]
+        .iter()
+        .any(|radius| *radius > 0.0)
+    }
+
+    /// Returns the rank direction as written by both Graphviz and Mermaid,
+    /// e.g. `"LR"`.
+    pub(crate) fn rank_dir_str(rank_dir: RankDir) -> &'static str {
+        match rank_dir {
+            RankDir::LeftToRight => "LR",
+            RankDir::RightToLeft => "RL",
+            RankDir::TopToBottom => "TB",
+            RankDir::BottomToTop => "BT",
+        }
+    }
+}
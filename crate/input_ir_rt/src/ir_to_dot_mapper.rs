@@ -0,0 +1,336 @@
+use std::fmt::Write;
+
+use disposition_input_model::edge::EdgeKind;
+use disposition_ir_model::{
+    edge::Edge,
+    node::{NodeHierarchy, NodeId, NodeShape},
+    IrDiagram,
+};
+use disposition_model_common::{entity::EntityType, Map, Set};
+
+use crate::{ir_graph_attrs::IrGraphAttrs, EdgeIdGenerator};
+
+/// Indentation for each level of nesting in the DOT output.
+const INDENT: &str = "    ";
+
+/// Prefix of subgraph names that Graphviz draws as a box around its nodes.
+const CLUSTER_PREFIX: &str = "cluster_";
+
+/// Maps an `IrDiagram` to a Graphviz DOT graph.
+///
+/// Graphviz positions the nodes and routes the edges itself, so the graph
+/// holds the node hierarchy, edges, and text, without sizes, positions, or
+/// theme colours:
+///
+/// * `node_hierarchy`: Nodes with children become nested `subgraph
+///   cluster_{node_id}`s, and leaf nodes become nodes.
+/// * `edge_groups`: Each edge becomes an edge, with the entity types of the
+///   edge as its `class`. Interaction edges are dashed. The reverse edges of
+///   `symmetric` groups and the edge that closes a `cyclic` group do not
+///   constrain the ranks of their nodes, so that the nodes are ranked in the
+///   direction of the sequence.
+/// * `render_options.rank_dir`: `rankdir`.
+/// * `nodes` and `thing_descs`: Node and cluster `label`s.
+/// * `edge_descs` and `edge_labels`: Edge `label`, `taillabel`, and
+///   `headlabel`s.
+/// * `entity_tooltips`: `tooltip`s.
+///
+/// Graphviz cannot draw edges to a cluster, so when a node with children is
+/// the end of an edge, an invisible node is added to its cluster, and the edge
+/// is clipped to the cluster with `ltail` / `lhead`.
+#[derive(Clone, Copy, Debug)]
+pub struct IrToDotMapper;
+
+impl IrToDotMapper {
+    /// Returns the DOT graph for the IR diagram.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive, IrToDotMapper};
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "thing_names: { t_a: A, t_b: B }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let dot = IrToDotMapper::map(&diagram_generated.ir_diagram);
+    ///
+    /// assert!(dot.starts_with("digraph {\n"));
+    /// assert!(dot.contains(r#""t_a" -> "t_b" [id="edge_ab__0""#));
+    /// ```
+    pub fn map(ir_diagram: &IrDiagram<'_>) -> String {
+        let node_endpoints = ir_diagram
+            .edge_groups
+            .values()
+            .flat_map(|edge_group| edge_group.iter())
+            .flat_map(|edge| [&edge.from, &edge.to])
+            .collect::<Set<&NodeId<'_>>>();
+        let mut node_children = Map::new();
+        Self::node_children_collect(&ir_diagram.node_hierarchy, &mut node_children);
+
+        let mut dot = String::new();
+        dot.push_str("digraph {\n");
+        let _ = writeln!(
+            dot,
+            r#"{INDENT}graph [rankdir="{}", compound="true"];"#,
+            IrGraphAttrs::rank_dir_str(ir_diagram.render_options.rank_dir)
+        );
+        let _ = writeln!(dot, r#"{INDENT}node [shape="box"];"#);
+
+        if !ir_diagram.node_hierarchy.is_empty() {
+            dot.push('\n');
+            Self::node_hierarchy_write(
+                &mut dot,
+                ir_diagram,
+                &ir_diagram.node_hierarchy,
+                &node_endpoints,
+                1,
+            );
+        }
+
+        ir_diagram
+            .edge_groups
+            .iter()
+            .for_each(|(edge_group_id, edge_group)| {
+                let edge_group_types = ir_diagram.entity_types.get(edge_group_id.as_ref());
+                let edge_kind = edge_group_types.and_then(Self::edge_kind);
+                let is_interaction = edge_group_types.is_some_and(IrGraphAttrs::is_interaction);
+
+                let _ = writeln!(dot, "\n{INDENT}// {edge_group_id}");
+                edge_group
+                    .iter()
+                    .enumerate()
+                    .for_each(|(edge_index, edge)| {
+                        let edge_id = EdgeIdGenerator::generate(edge_group_id, edge_index);
+                        let edge_types = ir_diagram.entity_types.get(edge_id.as_ref());
+                        let is_rank_constraint = match edge_kind {
+                            Some(EdgeKind::Cyclic) => {
+                                edge_group.len() == 1 || edge_index + 1 < edge_group.len()
+                            }
+                            Some(EdgeKind::Symmetric) => {
+                                !edge_types.is_some_and(Self::is_symmetric_reverse)
+                            }
+                            Some(EdgeKind::Sequence) | None => true,
+                        };
+
+                        let mut attrs = vec![("id", edge_id.to_string())];
+                        if let Some(edge_types) = edge_types {
+                            attrs.push(("class", Self::class_str(edge_types)));
+                        }
+                        if let Some(edge_desc) =
+                            ir_diagram.edge_descs.get_for_edge(&edge_id, edge_group_id)
+                        {
+                            attrs.push(("label", edge_desc.clone()));
+                        }
+                        if let Some(edge_label) =
+                            ir_diagram.edge_labels.get_for_edge(&edge_id, edge_group_id)
+                        {
+                            if !edge_label.from.is_empty() {
+                                attrs.push(("taillabel", edge_label.from.clone()));
+                            }
+                            if !edge_label.to.is_empty() {
+                                attrs.push(("headlabel", edge_label.to.clone()));
+                            }
+                        }
+                        if is_interaction {
+                            attrs.push(("style", String::from("dashed")));
+                        }
+                        if !is_rank_constraint {
+                            attrs.push(("constraint", String::from("false")));
+                        }
+                        Self::edge_cluster_attrs_push(&mut attrs, &node_children, edge);
+
+                        let _ = write!(
+                            dot,
+                            "{INDENT}{} -> {} ",
+                            Self::quote(&edge.from),
+                            Self::quote(&edge.to)
+                        );
+                        Self::attrs_write(&mut dot, &attrs);
+                        dot.push_str(";\n");
+                    });
+            });
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Appends each node in the hierarchy, with nodes that have children
+    /// written as clusters.
+    fn node_hierarchy_write(
+        dot: &mut String,
+        ir_diagram: &IrDiagram<'_>,
+        node_hierarchy: &NodeHierarchy<'_>,
+        node_endpoints: &Set<&NodeId<'_>>,
+        depth: usize,
+    ) {
+        let indent = INDENT.repeat(depth);
+        node_hierarchy.iter().for_each(|(node_id, node_children)| {
+            let mut attrs = Vec::with_capacity(4);
+            attrs.push(("id", node_id.to_string()));
+            if let Some(node_types) = ir_diagram.entity_types.get(node_id.as_ref()) {
+                attrs.push(("class", Self::class_str(node_types)));
+            }
+            attrs.push(("label", IrGraphAttrs::node_label(ir_diagram, node_id)));
+            if let Some(tooltip) = ir_diagram.entity_tooltips.get(node_id.as_ref()) {
+                attrs.push(("tooltip", tooltip.clone()));
+            }
+            match ir_diagram.node_shapes.get(node_id) {
+                Some(NodeShape::Circle(_)) => attrs.push(("shape", String::from("circle"))),
+                Some(NodeShape::Rect(node_shape_rect))
+                    if IrGraphAttrs::is_rounded(node_shape_rect) =>
+                {
+                    attrs.push(("style", String::from("rounded")))
+                }
+                Some(NodeShape::Rect(_)) | None => {}
+            }
+
+            if node_children.is_leaf() {
+                let _ = write!(dot, "{indent}{} ", Self::quote(node_id));
+                Self::attrs_write(dot, &attrs);
+                dot.push_str(";\n");
+            } else {
+                let _ = writeln!(
+                    dot,
+                    "{indent}subgraph {} {{",
+                    Self::quote(&format!("{CLUSTER_PREFIX}{node_id}"))
+                );
+                attrs.iter().for_each(|(name, value)| {
+                    let _ = writeln!(dot, "{indent}{INDENT}{name}={};", Self::quote(value));
+                });
+                if node_endpoints.contains(node_id) {
+                    let _ = writeln!(
+                        dot,
+                        r#"{indent}{INDENT}{} [shape="point", style="invis", label=""];"#,
+                        Self::quote(node_id)
+                    );
+                }
+                dot.push('\n');
+                Self::node_hierarchy_write(
+                    dot,
+                    ir_diagram,
+                    node_children,
+                    node_endpoints,
+                    depth + 1,
+                );
+                let _ = writeln!(dot, "{indent}}}");
+            }
+        });
+    }
+
+    /// Collects the children of each node that has children.
+    fn node_children_collect<'h, 'id>(
+        node_hierarchy: &'h NodeHierarchy<'id>,
+        node_children: &mut Map<&'h NodeId<'id>, &'h NodeHierarchy<'id>>,
+    ) {
+        node_hierarchy.iter().for_each(|(node_id, children)| {
+            if !children.is_leaf() {
+                node_children.insert(node_id, children);
+                Self::node_children_collect(children, node_children);
+            }
+        });
+    }
+
+    /// Pushes `ltail` / `lhead` for edge ends that are clusters, unless the
+    /// other end is inside that cluster.
+    fn edge_cluster_attrs_push(
+        attrs: &mut Vec<(&'static str, String)>,
+        node_children: &Map<&NodeId<'_>, &NodeHierarchy<'_>>,
+        edge: &Edge<'_>,
+    ) {
+        let cluster_name = |node_id: &NodeId<'_>, node_id_other: &NodeId<'_>| {
+            node_children
+                .get(node_id)
+                .filter(|children| {
+                    node_id != node_id_other && !Self::is_descendant(children, node_id_other)
+                })
+                .map(|_| format!("{CLUSTER_PREFIX}{node_id}"))
+        };
+        if let Some(cluster_name) = cluster_name(&edge.from, &edge.to) {
+            attrs.push(("ltail", cluster_name));
+        }
+        if let Some(cluster_name) = cluster_name(&edge.to, &edge.from) {
+            attrs.push(("lhead", cluster_name));
+        }
+    }
+
+    /// Returns whether the node is anywhere within the hierarchy.
+    fn is_descendant(node_hierarchy: &NodeHierarchy<'_>, node_id: &NodeId<'_>) -> bool {
+        node_hierarchy.iter().any(|(child_id, children)| {
+            child_id == node_id || Self::is_descendant(children, node_id)
+        })
+    }
+
+    /// Returns the edge group's kind from its entity types.
+    fn edge_kind(edge_group_types: &Set<EntityType>) -> Option<EdgeKind> {
+        edge_group_types
+            .iter()
+            .find_map(|entity_type| match entity_type {
+                EntityType::DependencyEdgeSequenceDefault
+                | EntityType::InteractionEdgeSequenceDefault => Some(EdgeKind::Sequence),
+                EntityType::DependencyEdgeCyclicDefault
+                | EntityType::InteractionEdgeCyclicDefault => Some(EdgeKind::Cyclic),
+                EntityType::DependencyEdgeSymmetricDefault
+                | EntityType::InteractionEdgeSymmetricDefault => Some(EdgeKind::Symmetric),
+                _ => None,
+            })
+    }
+
+    /// Returns whether the edge's entity types mark it as the reverse edge of
+    /// a symmetric edge group.
+    fn is_symmetric_reverse(edge_types: &Set<EntityType>) -> bool {
+        edge_types.iter().any(|entity_type| {
+            matches!(
+                entity_type,
+                EntityType::DependencyEdgeSymmetricReverseDefault
+                    | EntityType::InteractionEdgeSymmetricReverseDefault
+            )
+        })
+    }
+
+    /// Returns the entity types separated by spaces.
+    fn class_str(entity_types: &Set<EntityType>) -> String {
+        entity_types
+            .iter()
+            .map(EntityType::as_str)
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Appends the attributes as a DOT attribute list, e.g. `[id="a",
+    /// label="A"]`.
+    fn attrs_write(dot: &mut String, attrs: &[(&'static str, String)]) {
+        dot.push('[');
+        attrs.iter().enumerate().for_each(|(index, (name, value))| {
+            if index > 0 {
+                dot.push_str(", ");
+            }
+            let _ = write!(dot, "{name}={}", Self::quote(value));
+        });
+        dot.push(']');
+    }
+
+    /// Returns the value as a quoted DOT string.
+    ///
+    /// Every ID is quoted, as DOT keywords such as `node` and `edge` are
+    /// otherwise not valid node IDs.
+    fn quote(value: &str) -> String {
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        value.chars().for_each(|c| match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            c => quoted.push(c),
+        });
+        quoted.push('"');
+        quoted
+    }
+}
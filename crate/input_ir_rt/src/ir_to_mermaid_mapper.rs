@@ -0,0 +1,298 @@
+use std::fmt::Write;
+
+use disposition_ir_model::{
+    node::{NodeHierarchy, NodeId, NodeShape},
+    IrDiagram,
+};
+use disposition_model_common::entity::EntityType;
+
+use crate::{ir_graph_attrs::IrGraphAttrs, EdgeIdGenerator};
+
+/// Indentation for each level of nesting in the Mermaid output.
+const INDENT: &str = "    ";
+
+/// Words that start a statement in a Mermaid flowchart, so cannot be used as
+/// node IDs.
+const KEYWORDS: &[&str] = &[
+    "class",
+    "classDef",
+    "click",
+    "direction",
+    "end",
+    "flowchart",
+    "graph",
+    "linkStyle",
+    "style",
+    "subgraph",
+];
+
+/// Maps an `IrDiagram` to a Mermaid flowchart.
+///
+/// Mermaid chooses where to draw each node and link, and styles the
+/// flowchart with its own theme, so each part of the diagram is written as:
+///
+/// * `node_hierarchy`: Things with children become nested `subgraph`s, and
+///   other things become nodes.
+/// * `edge_groups`: Each edge becomes a link. Dependency edges are solid
+///   (`-->`), and interaction edges are dotted (`-.->`).
+/// * `render_options.rank_dir`: The flowchart direction.
+/// * `nodes` and `thing_descs`: Node text and subgraph titles.
+/// * `edge_descs` and `edge_labels`: Link text, as Mermaid draws one label per
+///   link.
+///
+/// Mermaid flowcharts have no equivalent of processes and tags, so they are
+/// listed in `%%` comments at the start of the flowchart, with each process
+/// step's interactions.
+#[derive(Clone, Copy, Debug)]
+pub struct IrToMermaidMapper;
+
+impl IrToMermaidMapper {
+    /// Returns the Mermaid flowchart for the IR diagram.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive, IrToMermaidMapper};
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {}, t_b: {} }\n\
+    ///      thing_names: { t_a: A, t_b: B }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let mermaid = IrToMermaidMapper::map(&diagram_generated.ir_diagram);
+    ///
+    /// assert!(mermaid.starts_with("flowchart TB\n"));
+    /// assert!(mermaid.contains(r#"t_a("A")"#));
+    /// assert!(mermaid.contains("t_a --> t_b"));
+    /// ```
+    pub fn map(ir_diagram: &IrDiagram<'_>) -> String {
+        let mut mermaid = String::new();
+        let _ = writeln!(
+            mermaid,
+            "flowchart {}",
+            IrGraphAttrs::rank_dir_str(ir_diagram.render_options.rank_dir)
+        );
+
+        Self::processes_and_tags_write(&mut mermaid, ir_diagram);
+
+        let things_hierarchy = ir_diagram
+            .node_hierarchy
+            .iter()
+            .filter(|(node_id, _)| Self::is_thing(ir_diagram, node_id))
+            .collect::<Vec<_>>();
+        if !things_hierarchy.is_empty() {
+            mermaid.push('\n');
+            things_hierarchy
+                .into_iter()
+                .for_each(|(node_id, node_children)| {
+                    Self::node_write(&mut mermaid, ir_diagram, node_id, node_children, 1);
+                });
+        }
+
+        ir_diagram
+            .edge_groups
+            .iter()
+            .for_each(|(edge_group_id, edge_group)| {
+                let is_interaction = ir_diagram
+                    .entity_types
+                    .get(edge_group_id.as_ref())
+                    .is_some_and(IrGraphAttrs::is_interaction);
+                let link = if is_interaction { "-.->" } else { "-->" };
+
+                let _ = writeln!(mermaid, "\n{INDENT}%% {edge_group_id}");
+                edge_group
+                    .iter()
+                    .enumerate()
+                    .for_each(|(edge_index, edge)| {
+                        let edge_id = EdgeIdGenerator::generate(edge_group_id, edge_index);
+                        let edge_desc = ir_diagram.edge_descs.get_for_edge(&edge_id, edge_group_id);
+                        let edge_label =
+                            ir_diagram.edge_labels.get_for_edge(&edge_id, edge_group_id);
+                        let label_lines = [
+                            edge_label.map(|edge_label| edge_label.from.as_str()),
+                            edge_desc.map(String::as_str),
+                            edge_label.map(|edge_label| edge_label.to.as_str()),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<&str>>();
+
+                        let _ = write!(mermaid, "{INDENT}{} {link}", Self::id(&edge.from));
+                        if !label_lines.is_empty() {
+                            let _ = write!(mermaid, "|{}|", Self::text(&label_lines.join("\n")));
+                        }
+                        let _ = writeln!(mermaid, " {}", Self::id(&edge.to));
+                    });
+            });
+
+        mermaid
+    }
+
+    /// Appends a comment listing each process with its steps, and each tag.
+    fn processes_and_tags_write(mermaid: &mut String, ir_diagram: &IrDiagram<'_>) {
+        let processes = ir_diagram
+            .node_hierarchy
+            .iter()
+            .filter(|(node_id, _)| Self::has_type(ir_diagram, node_id, EntityType::ProcessDefault))
+            .collect::<Vec<_>>();
+        let tags = ir_diagram
+            .node_hierarchy
+            .iter()
+            .filter(|(node_id, _)| Self::has_type(ir_diagram, node_id, EntityType::TagDefault))
+            .map(|(node_id, _)| node_id)
+            .collect::<Vec<_>>();
+
+        if !processes.is_empty() {
+            let _ = writeln!(mermaid, "\n{INDENT}%% Processes:");
+            processes
+                .into_iter()
+                .for_each(|(process_id, process_steps)| {
+                    let _ = writeln!(
+                        mermaid,
+                        "{INDENT}%% * {process_id}: {}",
+                        Self::comment_text(&IrGraphAttrs::node_label(ir_diagram, process_id))
+                    );
+                    process_steps.iter().for_each(|(process_step_id, _)| {
+                        let _ = write!(
+                            mermaid,
+                            "{INDENT}%%     * {process_step_id}: {}",
+                            Self::comment_text(&IrGraphAttrs::node_label(
+                                ir_diagram,
+                                process_step_id
+                            ))
+                        );
+                        if let Some(entity_ids) =
+                            ir_diagram.process_step_entities.get(process_step_id)
+                            && !entity_ids.is_empty()
+                        {
+                            let entity_ids = entity_ids
+                                .iter()
+                                .map(|entity_id| entity_id.as_str())
+                                .collect::<Vec<&str>>()
+                                .join(", ");
+                            let _ = write!(mermaid, " ({entity_ids})");
+                        }
+                        mermaid.push('\n');
+                    });
+                });
+        }
+
+        if !tags.is_empty() {
+            let _ = writeln!(mermaid, "\n{INDENT}%% Tags:");
+            tags.into_iter().for_each(|tag_id| {
+                let _ = writeln!(
+                    mermaid,
+                    "{INDENT}%% * {tag_id}: {}",
+                    Self::comment_text(&IrGraphAttrs::node_label(ir_diagram, tag_id))
+                );
+            });
+        }
+    }
+
+    /// Appends the node, with nodes that have children written as subgraphs.
+    fn node_write(
+        mermaid: &mut String,
+        ir_diagram: &IrDiagram<'_>,
+        node_id: &NodeId<'_>,
+        node_children: &NodeHierarchy<'_>,
+        depth: usize,
+    ) {
+        let indent = INDENT.repeat(depth);
+        let node_label = Self::text(&IrGraphAttrs::node_label(ir_diagram, node_id));
+        if node_children.is_leaf() {
+            let (open, close) = match ir_diagram.node_shapes.get(node_id) {
+                Some(NodeShape::Circle(_)) => ("((", "))"),
+                Some(NodeShape::Rect(node_shape_rect))
+                    if IrGraphAttrs::is_rounded(node_shape_rect) =>
+                {
+                    ("(", ")")
+                }
+                Some(NodeShape::Rect(_)) | None => ("[", "]"),
+            };
+            let _ = writeln!(
+                mermaid,
+                "{indent}{}{open}{node_label}{close}",
+                Self::id(node_id)
+            );
+        } else {
+            let _ = writeln!(
+                mermaid,
+                "{indent}subgraph {} [{node_label}]",
+                Self::id(node_id)
+            );
+            node_children
+                .iter()
+                .for_each(|(node_id_child, node_children_child)| {
+                    Self::node_write(
+                        mermaid,
+                        ir_diagram,
+                        node_id_child,
+                        node_children_child,
+                        depth + 1,
+                    );
+                });
+            let _ = writeln!(mermaid, "{indent}end");
+        }
+    }
+
+    /// Returns whether the node is a thing, rather than a process, process
+    /// step, or tag.
+    fn is_thing(ir_diagram: &IrDiagram<'_>, node_id: &NodeId<'_>) -> bool {
+        !Self::has_type(ir_diagram, node_id, EntityType::ProcessDefault)
+            && !Self::has_type(ir_diagram, node_id, EntityType::TagDefault)
+    }
+
+    fn has_type(ir_diagram: &IrDiagram<'_>, node_id: &NodeId<'_>, entity_type: EntityType) -> bool {
+        ir_diagram
+            .entity_types
+            .get(node_id.as_ref())
+            .is_some_and(|entity_types| entity_types.contains(&entity_type))
+    }
+
+    /// Returns the node ID, with a `_` suffix if it is a Mermaid keyword.
+    fn id(node_id: &NodeId<'_>) -> String {
+        if KEYWORDS.contains(&node_id.as_str()) {
+            format!("{node_id}_")
+        } else {
+            node_id.to_string()
+        }
+    }
+
+    /// Returns the text as a quoted Mermaid string.
+    ///
+    /// Characters that Mermaid would otherwise read as markup are written as
+    /// entity codes, and new lines as `<br/>`.
+    fn text(value: &str) -> String {
+        let mut text = String::with_capacity(value.len() + 2);
+        text.push('"');
+        value.chars().for_each(|c| match c {
+            '"' => text.push_str("#quot;"),
+            '#' => text.push_str("#35;"),
+            '&' => text.push_str("#amp;"),
+            '<' => text.push_str("#lt;"),
+            '>' => text.push_str("#gt;"),
+            '\n' => text.push_str("<br/>"),
+            '\r' => {}
+            c => text.push(c),
+        });
+        text.push('"');
+        text
+    }
+
+    /// Returns the text on one line, for use in a `%%` comment.
+    fn comment_text(value: &str) -> String {
+        value
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
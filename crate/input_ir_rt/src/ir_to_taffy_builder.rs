@@ -12,8 +12,6 @@ use disposition_taffy_model::{
 };
 use typed_builder::TypedBuilder;
 
-use crate::EdgeIdGenerator;
-
 pub(crate) use self::edge_spacer_builder::LcaDepthCalculator;
 
 use self::{
@@ -44,7 +42,7 @@ mod taffy_container_builder;
 mod taffy_diagram_node_builder;
 mod taffy_envelope_builder;
 mod taffy_node_build_context;
-mod text_measure;
+pub(crate) mod text_measure;
 
 /// Maps an intermediate representation diagram to a `TaffyNodeMappings`.
 ///
@@ -172,7 +170,7 @@ impl IrToTaffyBuilder<'_> {
         // Precompute the edge ID -> edge group ID lookup once. It is used to
         // resolve the group-ID fallback when looking up `edge_descs` /
         // `edge_labels` for a specific edge instance.
-        let edge_id_to_group_id = EdgeIdGenerator::edge_id_to_group_id_build(edge_groups);
+        let edge_id_to_group_id = edge_groups.edge_id_to_group_id_build();
 
         let ctx = TaffyBuildCtx {
             node_layouts,
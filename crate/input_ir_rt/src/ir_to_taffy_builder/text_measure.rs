@@ -66,13 +66,19 @@ pub(crate) fn compute_text_dimensions(
 /// counting emoji graphemes as wider than regular characters.
 pub(crate) fn line_char_column_count(line: &str) -> f32 {
     line.graphemes(true)
-        .map(|grapheme| match emojis::get(grapheme).is_some() {
-            true => EMOJI_CHAR_WIDTH,
-            false => 1.0f32,
-        })
+        .map(grapheme_char_column_count)
         .sum::<f32>()
 }
 
+/// Returns the number of character columns occupied by the given grapheme,
+/// counting emoji as wider than regular characters.
+pub(crate) fn grapheme_char_column_count(grapheme: &str) -> f32 {
+    match emojis::get(grapheme).is_some() {
+        true => EMOJI_CHAR_WIDTH,
+        false => 1.0f32,
+    }
+}
+
 /// Returns the width in pixels to display the given line of text.
 pub(crate) fn line_width_measure(line: &str, char_width: f32) -> f32 {
     if line.is_empty() {
@@ -1,20 +1,23 @@
 //! Logic to map `disposition` input model to intermediate representation.
 
-pub use disposition_input_ir_model::EdgeAnimationActive;
+pub use disposition_input_ir_model::{EdgeAnimationActive, ThemeValueSource};
 pub use disposition_input_rt::id_parse;
+pub use disposition_model_common::edge::EdgeIdGenerator;
 
 pub(crate) use crate::{
     absolute_coordinates::AbsoluteCoordinates,
     taffy_node_absolute_coordinates_calculator::TaffyNodeAbsoluteCoordinatesCalculator,
 };
 pub use crate::{
+    color_scheme::ColorScheme,
     diagram_generate_error::DiagramGenerateError,
     diagram_generator::DiagramGenerator,
+    diagram_to_html_mapper::DiagramToHtmlMapper,
+    diagram_to_layout_mapper::DiagramToLayoutMapper,
     edge_desc_bg_id_generator::EdgeDescBgIdGenerator,
     edge_face_assigner::EdgeFaceAssigner,
     edge_halo_id_generator::EdgeHaloIdGenerator,
     edge_halo_outline_id_generator::EdgeHaloOutlineIdGenerator,
-    edge_id_generator::EdgeIdGenerator,
     edge_label_bg_id_generator::EdgeLabelBgIdGenerator,
     input_diagram_merger::InputDiagramMerger,
     input_diagram_theme_sources::InputDiagramThemeSources,
@@ -23,13 +26,32 @@ pub use crate::{
         tailwind_colors::{TailwindColor, TAILWIND_COLORS},
         InputToIrDiagramMapper,
     },
+    ir_to_dot_mapper::IrToDotMapper,
+    ir_to_mermaid_mapper::IrToMermaidMapper,
     ir_to_taffy_builder::IrToTaffyBuilder,
     node_ranks_calculator::NodeRanksCalculator,
     process_step_graph_calculator::ProcessStepGraphCalculator,
+    string_placeholder_replacer::StringPlaceholderReplacer,
     string_xml_escaper::StringXmlEscaper,
+    svg_elements_to_drawio_mapper::SvgElementsToDrawioMapper,
+    svg_elements_to_excalidraw_mapper::SvgElementsToExcalidrawMapper,
     svg_elements_to_svg_mapper::SvgElementsToSvgMapper,
+    svg_elements_to_tikz_mapper::SvgElementsToTikzMapper,
+    svg_source_extract_error::SvgSourceExtractError,
+    svg_source_extractor::SvgSourceExtractor,
     taffy_to_svg_elements_mapper::{TaffyToSvgElementsMapper, TaffyToSvgElementsOutcome},
-    theme_value_source::ThemeValueSource,
+    theme_attr_validator::ThemeAttrValidator,
+};
+
+#[cfg(feature = "pdf")]
+pub use crate::{
+    diagrams_to_pdf_error::DiagramsToPdfError, diagrams_to_pdf_mapper::DiagramsToPdfMapper,
+    pdf_options::PdfOptions, pdf_page_size::PdfPageSize,
+};
+#[cfg(feature = "png")]
+pub use crate::{
+    png_options::PngOptions, svg_elements_to_png_error::SvgElementsToPngError,
+    svg_elements_to_png_mapper::SvgElementsToPngMapper,
 };
 
 // Used by `cosmic-text` for calculating text layout, and `base64` for encoding
@@ -38,26 +60,55 @@ const NOTO_SANS_MONO_TTF: &[u8] =
     include_bytes!("../fonts/noto_sans_mono/NotoSansMono-Regular.ttf");
 
 mod absolute_coordinates;
+mod color_scheme;
 mod diagram_generate_error;
 mod diagram_generator;
+mod diagram_to_html_mapper;
+mod diagram_to_layout_mapper;
+#[cfg(feature = "pdf")]
+mod diagrams_to_pdf_error;
+#[cfg(feature = "pdf")]
+mod diagrams_to_pdf_mapper;
 mod divergent_ancestor_ranks_calculator;
 mod edge_desc_bg_id_generator;
 mod edge_face_assigner;
 mod edge_halo_id_generator;
 mod edge_halo_outline_id_generator;
-mod edge_id_generator;
 mod edge_label_bg_id_generator;
 mod edge_route_normalizer;
 mod input_diagram_merger;
 mod input_diagram_theme_sources;
 mod input_to_ir_diagram_mapper;
+mod ir_graph_attrs;
+mod ir_to_dot_mapper;
+mod ir_to_mermaid_mapper;
 mod ir_to_taffy_builder;
 mod md_text;
 mod node_ranks_calculator;
+#[cfg(feature = "pdf")]
+mod pdf_options;
+#[cfg(feature = "pdf")]
+mod pdf_page_size;
+#[cfg(feature = "png")]
+mod png_options;
 mod process_step_graph_calculator;
+mod string_placeholder_replacer;
 mod string_xml_escaper;
 mod svg_element_classes;
+mod svg_elements_to_drawio_mapper;
+mod svg_elements_to_excalidraw_mapper;
+mod svg_elements_to_paint_items_mapper;
+#[cfg(feature = "png")]
+mod svg_elements_to_png_error;
+#[cfg(feature = "png")]
+mod svg_elements_to_png_mapper;
+mod svg_elements_to_shapes_mapper;
 mod svg_elements_to_svg_mapper;
+mod svg_elements_to_tikz_mapper;
+mod svg_source_extract_error;
+mod svg_source_extractor;
 mod taffy_node_absolute_coordinates_calculator;
 mod taffy_to_svg_elements_mapper;
-mod theme_value_source;
+mod theme_attr_validator;
+#[cfg(any(feature = "pdf", feature = "png"))]
+mod usvg_tree_builder;
@@ -196,7 +196,7 @@ impl NodeRanksCalculator {
     /// * `divergent_from`, `divergent_to` -- the first ancestors of `from` and
     ///   `to` that differ under the LCA, at depth `lca_depth` in their
     ///   respective `ancestor_chain`s.
-    fn lca_level_edge_compute<'id>(
+    pub(crate) fn lca_level_edge_compute<'id>(
         from_id: &NodeId<'id>,
         to_id: &NodeId<'id>,
         node_nesting_infos: &NodeNestingInfos<'id>,
@@ -265,7 +265,10 @@ impl NodeRanksCalculator {
 
     /// Returns whether the edge group with the given ID is a dependency edge
     /// group (as opposed to an interaction edge group).
-    fn edge_group_is_dependency(edge_group_id: &Id, entity_types: &EntityTypes<'_>) -> bool {
+    pub(crate) fn edge_group_is_dependency(
+        edge_group_id: &Id,
+        entity_types: &EntityTypes<'_>,
+    ) -> bool {
         entity_types
             .get(edge_group_id)
             .map(|types| types.iter().any(Self::entity_type_is_dependency_edge_group))
@@ -414,7 +417,7 @@ impl NodeRanksCalculator {
     ///
     /// Returns a vector where `result[node_index]` is the SCC id that node
     /// belongs to. Nodes in the same SCC share the same id.
-    fn tarjan_scc(adjacency: &[Vec<usize>], node_count: usize) -> Vec<usize> {
+    pub(crate) fn tarjan_scc(adjacency: &[Vec<usize>], node_count: usize) -> Vec<usize> {
         let mut state = TarjanState {
             index_counter: 0,
             stack: Vec::new(),
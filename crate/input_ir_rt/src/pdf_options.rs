@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+
+use typed_builder::TypedBuilder;
+
+use crate::{ColorScheme, PdfPageSize, NOTO_SANS_MONO_TTF};
+
+/// Options to render diagrams to a PDF via `DiagramsToPdfMapper`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use disposition_input_ir_rt::{ColorScheme, PdfOptions, PdfPageSize};
+/// #
+/// let pdf_options = PdfOptions::builder()
+///     .with_page_size(PdfPageSize::A4)
+///     .with_color_scheme(ColorScheme::Light)
+///     .build();
+///
+/// assert_eq!(1, pdf_options.fonts.len());
+/// ```
+#[derive(Clone, Debug, TypedBuilder)]
+pub struct PdfOptions {
+    /// Size of each page.
+    #[builder(setter(prefix = "with_"), default)]
+    pub page_size: PdfPageSize,
+    /// TrueType or OpenType fonts to render text with.
+    ///
+    /// Text is rendered with the first font's family, and each character that
+    /// it does not have a glyph for is rendered with another font that does.
+    ///
+    /// Defaults to the Noto Sans Mono font that diagrams are laid out with.
+    /// Append other fonts to render characters that Noto Sans Mono does not
+    /// have, such as emoji.
+    #[builder(
+        setter(prefix = "with_"),
+        default = vec![Cow::Borrowed(NOTO_SANS_MONO_TTF)]
+    )]
+    pub fonts: Vec<Cow<'static, [u8]>>,
+    /// Whether to use the light or dark colors of the diagram's theme.
+    ///
+    /// Pages are white for the light color scheme, and dark gray for the dark
+    /// color scheme.
+    #[builder(setter(prefix = "with_"), default)]
+    pub color_scheme: ColorScheme,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
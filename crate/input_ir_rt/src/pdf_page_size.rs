@@ -0,0 +1,27 @@
+/// Size of each page of a PDF generated by `DiagramsToPdfMapper`.
+///
+/// For paper sizes, each page is in landscape orientation when its diagram is
+/// wider than it is tall, and diagrams that do not fit are scaled down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PdfPageSize {
+    /// Each page is sized to fit its title, description, and diagram, without
+    /// scaling the diagram.
+    #[default]
+    Fit,
+    /// ISO A4 paper, `210mm x 297mm`.
+    A4,
+    /// US Letter paper, `8.5in x 11in`.
+    Letter,
+}
+
+impl PdfPageSize {
+    /// Returns the width and height in points in portrait orientation, or
+    /// `None` for [`PdfPageSize::Fit`].
+    pub fn portrait_points(self) -> Option<(f64, f64)> {
+        match self {
+            PdfPageSize::Fit => None,
+            PdfPageSize::A4 => Some((595.276, 841.89)),
+            PdfPageSize::Letter => Some((612.0, 792.0)),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use typed_builder::TypedBuilder;
+
+use crate::{ColorScheme, NOTO_SANS_MONO_TTF};
+
+/// Options to rasterise SVG elements to PNG via `SvgElementsToPngMapper`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use disposition_input_ir_rt::{ColorScheme, PngOptions};
+/// #
+/// let png_options = PngOptions::builder()
+///     .with_scale(2.0)
+///     .with_dpi(192.0)
+///     .with_color_scheme(ColorScheme::Dark)
+///     .build();
+///
+/// assert_eq!(1, png_options.fonts.len());
+/// ```
+#[derive(Clone, Debug, TypedBuilder)]
+pub struct PngOptions {
+    /// Number of PNG pixels per SVG pixel.
+    ///
+    /// e.g. `2.0` renders a `400x300` diagram as an `800x600` PNG.
+    #[builder(setter(prefix = "with_"), default = 1.0)]
+    pub scale: f32,
+    /// Resolution recorded in the PNG, in pixels per inch.
+    ///
+    /// This does not change the number of pixels, but tells image viewers and
+    /// printers how large the image is physically. Use `96.0 * scale` to keep
+    /// the diagram's size the same as in a browser.
+    #[builder(setter(prefix = "with_"), default = 96.0)]
+    pub dpi: f32,
+    /// TrueType or OpenType fonts to render text with.
+    ///
+    /// Text is rendered with the first font's family, and each character that
+    /// it does not have a glyph for is rendered with another font that does.
+    /// Defaults to the Noto Sans Mono font that is also embedded in the
+    /// SVG, so text is laid out the same as in a browser. Append other fonts
+    /// to render characters that Noto Sans Mono does not have, such as emoji.
+    #[builder(
+        setter(prefix = "with_"),
+        default = vec![Cow::Borrowed(NOTO_SANS_MONO_TTF)]
+    )]
+    pub fonts: Vec<Cow<'static, [u8]>>,
+    /// Whether to use the light or dark colors of the diagram's theme.
+    #[builder(setter(prefix = "with_"), default)]
+    pub color_scheme: ColorScheme,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
@@ -0,0 +1,47 @@
+/// Replaces placeholders in a template string.
+pub struct StringPlaceholderReplacer;
+
+impl StringPlaceholderReplacer {
+    /// Returns the `template` with each placeholder replaced by its value.
+    ///
+    /// The template is replaced in a single pass, so placeholders that appear
+    /// within a value, e.g. a title of `__DIAGRAM__`, are left as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_ir_rt::StringPlaceholderReplacer;
+    ///
+    /// assert_eq!(
+    ///     StringPlaceholderReplacer::replace(
+    ///         "<title>__TITLE__</title>__BODY__",
+    ///         &[("__TITLE__", "__BODY__"), ("__BODY__", "<p>hi</p>")],
+    ///     ),
+    ///     "<title>__BODY__</title><p>hi</p>"
+    /// );
+    /// ```
+    pub fn replace(template: &str, placeholder_values: &[(&str, &str)]) -> String {
+        let capacity = template.len()
+            + placeholder_values
+                .iter()
+                .map(|(_placeholder, value)| value.len())
+                .sum::<usize>();
+        let mut replaced = String::with_capacity(capacity);
+        let mut template_rest = template;
+        while let Some((offset, placeholder, value)) = placeholder_values
+            .iter()
+            .filter_map(|(placeholder, value)| {
+                template_rest
+                    .find(placeholder)
+                    .map(|offset| (offset, *placeholder, *value))
+            })
+            .min_by_key(|(offset, _placeholder, _value)| *offset)
+        {
+            replaced.push_str(&template_rest[..offset]);
+            replaced.push_str(value);
+            template_rest = &template_rest[offset + placeholder.len()..];
+        }
+        replaced.push_str(template_rest);
+        replaced
+    }
+}
@@ -44,4 +44,60 @@ impl StringXmlEscaper {
         });
         result
     }
+
+    /// Unescapes XML entity and character references in a string, returning
+    /// the unescaped result.
+    ///
+    /// This is the reverse of [`Self::escape`], and also replaces decimal
+    /// (`&#39;`) and hexadecimal (`&#x27;`) character references. References
+    /// that are not recognized are kept as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_ir_rt::StringXmlEscaper;
+    ///
+    /// assert_eq!(StringXmlEscaper::unescape("hello"), "hello");
+    /// assert_eq!(StringXmlEscaper::unescape("a &amp; b"), "a & b");
+    /// assert_eq!(StringXmlEscaper::unescape("&lt;tag&gt;"), "<tag>");
+    /// assert_eq!(StringXmlEscaper::unescape("it&#39;s &#x263A;"), "it's ☺");
+    /// assert_eq!(StringXmlEscaper::unescape("&unknown; &"), "&unknown; &");
+    /// ```
+    pub fn unescape(s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(ampersand_index) = rest.find('&') {
+            result.push_str(&rest[..ampersand_index]);
+            rest = &rest[ampersand_index..];
+
+            let c = rest.find(';').and_then(|semicolon_index| {
+                let c = match &rest[1..semicolon_index] {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    reference => reference
+                        .strip_prefix("#x")
+                        .map(|hex| u32::from_str_radix(hex, 16))
+                        .or_else(|| reference.strip_prefix('#').map(str::parse::<u32>))
+                        .and_then(Result::ok)
+                        .and_then(char::from_u32),
+                };
+                c.map(|c| (c, semicolon_index))
+            });
+            match c {
+                Some((c, semicolon_index)) => {
+                    result.push(c);
+                    rest = &rest[semicolon_index + 1..];
+                }
+                None => {
+                    result.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
 }
@@ -0,0 +1,287 @@
+use std::fmt::Write;
+
+use disposition_ir_model::IrDiagram;
+use disposition_model_common::Map;
+use disposition_svg_model::SvgElements;
+use disposition_taffy_model::TEXT_FONT_SIZE;
+
+use crate::{
+    svg_elements_to_shapes_mapper::{
+        Connector, Shape, ShapeKind, SvgElementsToShapesMapper, TextBox,
+    },
+    StringXmlEscaper,
+};
+
+/// ID of the layer that top level cells are placed in.
+const LAYER_ID: &str = "1";
+
+/// Maps `SvgElements` to a draw.io diagram, so that diagrams can be edited by
+/// hand in draw.io (diagrams.net).
+///
+/// * Nodes become rectangles, or ellipses for circle nodes, at the positions
+///   computed by the diagram's layout. Process nodes are drawn tall enough to
+///   contain all of their steps.
+/// * Nodes nested within another node are children of that node's container
+///   cell, so that moving the outer node moves everything within it.
+/// * Edges become connectors through the points of the edge's path, with the
+///   nodes they connect as their source and target. Interaction edges are
+///   dashed.
+/// * Edge labels and descriptions become text cells.
+///
+/// Colours and animations are not exported.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgElementsToDrawioMapper;
+
+impl SvgElementsToDrawioMapper {
+    /// Returns the draw.io `mxGraph` XML for the SVG elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `ir_diagram`: The IR diagram the SVG elements were generated from,
+    ///   used for node nesting and edge kinds.
+    /// * `svg_elements`: The SVG elements with the computed layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     DiagramGenerator, EdgeAnimationActive, SvgElementsToDrawioMapper,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {}, t_b: {} }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let drawio = SvgElementsToDrawioMapper::map(
+    ///     &diagram_generated.ir_diagram,
+    ///     &diagram_generated.svg_elements,
+    /// );
+    ///
+    /// assert!(drawio.starts_with("<mxfile"));
+    /// assert!(drawio.contains(r#"source="t_a" target="t_b""#));
+    /// ```
+    pub fn map(ir_diagram: &IrDiagram<'_>, svg_elements: &SvgElements<'_>) -> String {
+        let shapes = SvgElementsToShapesMapper::map(ir_diagram, svg_elements);
+
+        let mut drawio = String::new();
+        drawio.push_str("<mxfile host=\"disposition\">\n");
+        drawio.push_str("  <diagram id=\"disposition\" name=\"Diagram\">\n");
+        let _ = writeln!(
+            drawio,
+            "    <mxGraphModel grid=\"0\" page=\"0\" pageWidth=\"{}\" pageHeight=\"{}\" \
+            connect=\"1\" arrows=\"1\" fold=\"1\" math=\"0\" shadow=\"0\">",
+            shapes.width, shapes.height
+        );
+        drawio.push_str("      <root>\n");
+        drawio.push_str("        <mxCell id=\"0\" />\n");
+        let _ = writeln!(drawio, "        <mxCell id=\"{LAYER_ID}\" parent=\"0\" />");
+
+        // Child cells are positioned relative to their parent cell.
+        let mut shape_positions = Map::<&str, (f64, f64)>::new();
+        shapes.shapes.iter().for_each(|shape| {
+            let (parent_id, parent_x, parent_y) = match shape.ancestor_ids.last() {
+                Some(parent_id) => {
+                    let (parent_x, parent_y) =
+                        shape_positions.get(parent_id).copied().unwrap_or_default();
+                    (*parent_id, parent_x, parent_y)
+                }
+                None => (LAYER_ID, 0.0, 0.0),
+            };
+            shape_positions.insert(shape.node_id, (shape.x, shape.y));
+            Self::shape_write(&mut drawio, shape, parent_id, parent_x, parent_y);
+        });
+        shapes.connectors.iter().for_each(|connector| {
+            Self::connector_write(&mut drawio, connector, &shape_positions);
+        });
+        shapes.text_boxes.iter().for_each(|text_box| {
+            Self::text_write(&mut drawio, text_box, LAYER_ID, 0.0, 0.0);
+        });
+
+        drawio.push_str("      </root>\n");
+        drawio.push_str("    </mxGraphModel>\n");
+        drawio.push_str("  </diagram>\n");
+        drawio.push_str("</mxfile>\n");
+        drawio
+    }
+
+    /// Appends the cell for a node's shape, and a separate cell for its text
+    /// when the text is not centred within the shape.
+    fn shape_write(
+        drawio: &mut String,
+        shape: &Shape<'_>,
+        parent_id: &str,
+        parent_x: f64,
+        parent_y: f64,
+    ) {
+        let mut style = match shape.kind {
+            ShapeKind::Rect { is_rounded } => {
+                format!("rounded={};", u8::from(is_rounded))
+            }
+            ShapeKind::Circle => String::from("ellipse;aspect=fixed;"),
+        };
+        style.push_str("whiteSpace=wrap;html=0;");
+        let _ = write!(style, "fontSize={TEXT_FONT_SIZE};fontFamily=monospace;");
+        if shape.is_container {
+            style.push_str("container=1;collapsible=0;");
+        }
+
+        // Text for nodes with nested nodes is written as the container's
+        // label at the top, so that it moves with the container.
+        let value = match shape.text_box.as_ref() {
+            Some(text_box) if shape.text_is_contained => text_box.text.as_str(),
+            Some(text_box) if shape.is_container => {
+                let _ = write!(
+                    style,
+                    "align=left;verticalAlign=top;spacingLeft={};spacingTop={};",
+                    SvgElementsToShapesMapper::round(text_box.x - shape.x),
+                    SvgElementsToShapesMapper::round(text_box.y - shape.y)
+                );
+                text_box.text.as_str()
+            }
+            Some(_) | None => "",
+        };
+
+        Self::vertex_write(
+            drawio,
+            shape.node_id,
+            value,
+            &style,
+            parent_id,
+            (
+                shape.x - parent_x,
+                shape.y - parent_y,
+                shape.width,
+                shape.height,
+            ),
+        );
+
+        // Circle nodes have their text beside the circle.
+        if let ShapeKind::Circle = shape.kind
+            && !shape.is_container
+            && let Some(text_box) = shape.text_box.as_ref()
+        {
+            Self::text_write(drawio, text_box, parent_id, parent_x, parent_y);
+        }
+    }
+
+    /// Appends a connector through the connector's points, with the shapes of
+    /// the nodes it connects as its source and target.
+    fn connector_write(
+        drawio: &mut String,
+        connector: &Connector<'_>,
+        shape_positions: &Map<&str, (f64, f64)>,
+    ) {
+        let mut style = String::from("endArrow=classic;html=0;rounded=0;edgeStyle=none;");
+        if connector.is_interaction {
+            style.push_str("dashed=1;");
+        }
+
+        let _ = write!(
+            drawio,
+            "        <mxCell id=\"{}\" value=\"\" style=\"{style}\" edge=\"1\" parent=\"{LAYER_ID}\"",
+            Self::attr_escape(connector.edge_id)
+        );
+        if shape_positions.contains_key(connector.from_node_id) {
+            let _ = write!(
+                drawio,
+                " source=\"{}\"",
+                Self::attr_escape(connector.from_node_id)
+            );
+        }
+        if shape_positions.contains_key(connector.to_node_id) {
+            let _ = write!(
+                drawio,
+                " target=\"{}\"",
+                Self::attr_escape(connector.to_node_id)
+            );
+        }
+        drawio.push_str(">\n");
+        drawio.push_str("          <mxGeometry relative=\"1\" as=\"geometry\">\n");
+
+        let points = &connector.points;
+        let (x_source, y_source) = points[0];
+        let (x_target, y_target) = points[points.len() - 1];
+        let _ = writeln!(
+            drawio,
+            "            <mxPoint x=\"{x_source}\" y=\"{y_source}\" as=\"sourcePoint\" />"
+        );
+        let _ = writeln!(
+            drawio,
+            "            <mxPoint x=\"{x_target}\" y=\"{y_target}\" as=\"targetPoint\" />"
+        );
+        let waypoints = &points[1..points.len() - 1];
+        if !waypoints.is_empty() {
+            drawio.push_str("            <Array as=\"points\">\n");
+            waypoints.iter().for_each(|(x, y)| {
+                let _ = writeln!(drawio, "              <mxPoint x=\"{x}\" y=\"{y}\" />");
+            });
+            drawio.push_str("            </Array>\n");
+        }
+
+        drawio.push_str("          </mxGeometry>\n");
+        drawio.push_str("        </mxCell>\n");
+    }
+
+    /// Appends a cell with only text.
+    fn text_write(
+        drawio: &mut String,
+        text_box: &TextBox,
+        parent_id: &str,
+        parent_x: f64,
+        parent_y: f64,
+    ) {
+        let style = format!(
+            "text;whiteSpace=wrap;html=0;align=left;verticalAlign=top;\
+            fontSize={TEXT_FONT_SIZE};fontFamily=monospace;"
+        );
+        Self::vertex_write(
+            drawio,
+            &text_box.id,
+            &text_box.text,
+            &style,
+            parent_id,
+            (
+                text_box.x - parent_x,
+                text_box.y - parent_y,
+                text_box.width,
+                text_box.height,
+            ),
+        );
+    }
+
+    fn vertex_write(
+        drawio: &mut String,
+        id: &str,
+        value: &str,
+        style: &str,
+        parent_id: &str,
+        (x, y, width, height): (f64, f64, f64, f64),
+    ) {
+        let [x, y, width, height] = [x, y, width, height].map(SvgElementsToShapesMapper::round);
+        let _ = writeln!(
+            drawio,
+            "        <mxCell id=\"{}\" value=\"{}\" style=\"{style}\" vertex=\"1\" parent=\"{}\">",
+            Self::attr_escape(id),
+            Self::attr_escape(value),
+            Self::attr_escape(parent_id)
+        );
+        let _ = writeln!(
+            drawio,
+            "          <mxGeometry x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" \
+            as=\"geometry\" />"
+        );
+        drawio.push_str("        </mxCell>\n");
+    }
+
+    /// Returns the value escaped for an XML attribute, with new lines kept as
+    /// character references.
+    fn attr_escape(value: &str) -> String {
+        StringXmlEscaper::escape(value).replace('\n', "&#10;")
+    }
+}
@@ -0,0 +1,307 @@
+use disposition_ir_model::IrDiagram;
+use disposition_model_common::{Map, Set};
+use disposition_svg_model::SvgElements;
+use disposition_taffy_model::{TEXT_FONT_SIZE, TEXT_LINE_HEIGHT};
+use serde_json::{json, Value};
+
+use crate::svg_elements_to_shapes_mapper::{
+    Connector, Shape, ShapeKind, SvgElementsToShapesMapper, TextBox,
+};
+
+/// Colour of shape outlines, connectors, and text.
+const STROKE_COLOR: &str = "#1e1e1e";
+
+/// Excalidraw's monospace font family.
+const FONT_FAMILY_MONOSPACE: u32 = 3;
+
+/// Padding between a shape's outline and text centred within it.
+const TEXT_PADDING: f64 = 5.0;
+
+/// Maps `SvgElements` to an Excalidraw scene, so that diagrams can be edited
+/// by hand in Excalidraw.
+///
+/// * Nodes become rectangles, or ellipses for circle nodes, at the positions
+///   computed by the diagram's layout. Process nodes are drawn tall enough to
+///   contain all of their steps.
+/// * Nodes nested within another node are grouped with it, so that moving the
+///   outer node moves everything within it.
+/// * Edges become arrows through the points of the edge's path, bound to the
+///   shapes of the nodes they connect. Interaction edges are dashed.
+/// * Edge labels and descriptions become text elements.
+///
+/// Colours and animations are not exported.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgElementsToExcalidrawMapper;
+
+impl SvgElementsToExcalidrawMapper {
+    /// Returns the Excalidraw scene JSON for the SVG elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `ir_diagram`: The IR diagram the SVG elements were generated from,
+    ///   used for node nesting and edge kinds.
+    /// * `svg_elements`: The SVG elements with the computed layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     DiagramGenerator, EdgeAnimationActive, SvgElementsToExcalidrawMapper,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {}, t_b: {} }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let excalidraw = SvgElementsToExcalidrawMapper::map(
+    ///     &diagram_generated.ir_diagram,
+    ///     &diagram_generated.svg_elements,
+    /// );
+    ///
+    /// assert!(excalidraw.contains(r#""type": "excalidraw""#));
+    /// assert!(excalidraw.contains(r#""id": "edge_ab__0""#));
+    /// ```
+    pub fn map(ir_diagram: &IrDiagram<'_>, svg_elements: &SvgElements<'_>) -> String {
+        let shapes = SvgElementsToShapesMapper::map(ir_diagram, svg_elements);
+
+        // Each shape lists the arrows bound to it.
+        let mut arrow_ids_by_node_id = Map::<&str, Vec<&str>>::new();
+        shapes.connectors.iter().for_each(|connector| {
+            [connector.from_node_id, connector.to_node_id]
+                .into_iter()
+                .for_each(|node_id| {
+                    let arrow_ids = arrow_ids_by_node_id.entry(node_id).or_default();
+                    if !arrow_ids.contains(&connector.edge_id) {
+                        arrow_ids.push(connector.edge_id);
+                    }
+                });
+        });
+
+        let mut elements = Vec::new();
+        shapes.shapes.iter().for_each(|shape| {
+            let arrow_ids = arrow_ids_by_node_id
+                .get(shape.node_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            Self::shape_elements_push(&mut elements, shape, arrow_ids);
+        });
+        let shape_node_ids = shapes
+            .shapes
+            .iter()
+            .map(|shape| shape.node_id)
+            .collect::<Set<&str>>();
+        shapes.connectors.iter().for_each(|connector| {
+            let element = Self::arrow_element(elements.len(), connector, &shape_node_ids);
+            elements.push(element);
+        });
+        shapes.text_boxes.iter().for_each(|text_box| {
+            let element = Self::text_element(elements.len(), text_box, &[], None);
+            elements.push(element);
+        });
+
+        let scene = json!({
+            "type": "excalidraw",
+            "version": 2,
+            "source": "https://github.com/azriel91/disposition",
+            "elements": elements,
+            "appState": {
+                "gridSize": null,
+                "viewBackgroundColor": "#ffffff",
+            },
+            "files": {},
+        });
+        serde_json::to_string_pretty(&scene).expect("Excalidraw scene should serialize")
+    }
+
+    /// Appends the elements for a node's shape and its text.
+    fn shape_elements_push(elements: &mut Vec<Value>, shape: &Shape<'_>, arrow_ids: &[&str]) {
+        // Excalidraw lists groups innermost first, and a node with nodes nested
+        // within it has its own group.
+        let group_ids = shape
+            .is_container
+            .then_some(shape.node_id)
+            .into_iter()
+            .chain(shape.ancestor_ids.iter().rev().copied())
+            .map(Self::group_id)
+            .collect::<Vec<String>>();
+
+        let text_box_contained = shape.text_box.as_ref().filter(|_| shape.text_is_contained);
+        let mut bound_elements = arrow_ids
+            .iter()
+            .map(|arrow_id| json!({ "id": arrow_id, "type": "arrow" }))
+            .collect::<Vec<Value>>();
+        if let Some(text_box) = text_box_contained {
+            bound_elements.push(json!({ "id": text_box.id, "type": "text" }));
+        }
+
+        let (element_type, roundness) = match shape.kind {
+            ShapeKind::Rect { is_rounded: true } => ("rectangle", json!({ "type": 3 })),
+            ShapeKind::Rect { is_rounded: false } => ("rectangle", Value::Null),
+            ShapeKind::Circle => ("ellipse", json!({ "type": 2 })),
+        };
+        let mut element = Self::element(
+            elements.len(),
+            shape.node_id,
+            element_type,
+            (shape.x, shape.y, shape.width, shape.height),
+            &group_ids,
+        );
+        element["roundness"] = roundness;
+        element["boundElements"] = Value::Array(bound_elements);
+        elements.push(element);
+
+        if let Some(text_box) = shape.text_box.as_ref() {
+            let element = match text_box_contained {
+                Some(text_box) => {
+                    // Bound text is centred within the shape by Excalidraw.
+                    let line_count = text_box.text.lines().count().max(1) as f64;
+                    let height = line_count * f64::from(TEXT_LINE_HEIGHT);
+                    let text_box = TextBox {
+                        id: text_box.id.clone(),
+                        x: text_box.x + TEXT_PADDING,
+                        y: text_box.y + (text_box.height - height) / 2.0,
+                        width: (text_box.width - 2.0 * TEXT_PADDING).max(0.0),
+                        height,
+                        text: text_box.text.clone(),
+                    };
+                    Self::text_element(elements.len(), &text_box, &group_ids, Some(shape.node_id))
+                }
+                None => Self::text_element(elements.len(), text_box, &group_ids, None),
+            };
+            elements.push(element);
+        }
+    }
+
+    /// Returns an arrow through the connector's points, bound to the shapes of
+    /// the nodes it connects.
+    fn arrow_element(index: usize, connector: &Connector<'_>, shape_node_ids: &Set<&str>) -> Value {
+        let (x_start, y_start) = connector.points[0];
+        let (x_min, x_max, y_min, y_max) = connector.points.iter().fold(
+            (x_start, x_start, y_start, y_start),
+            |(x_min, x_max, y_min, y_max), (x, y)| {
+                (x_min.min(*x), x_max.max(*x), y_min.min(*y), y_max.max(*y))
+            },
+        );
+        let points = connector
+            .points
+            .iter()
+            .map(|(x, y)| {
+                json!([
+                    SvgElementsToShapesMapper::round(x - x_start),
+                    SvgElementsToShapesMapper::round(y - y_start),
+                ])
+            })
+            .collect::<Vec<Value>>();
+        let binding = |node_id: &str| {
+            if shape_node_ids.contains(node_id) {
+                json!({ "elementId": node_id, "focus": 0, "gap": 1 })
+            } else {
+                Value::Null
+            }
+        };
+
+        let mut element = Self::element(
+            index,
+            connector.edge_id,
+            "arrow",
+            (x_start, y_start, x_max - x_min, y_max - y_min),
+            &[],
+        );
+        if connector.is_interaction {
+            element["strokeStyle"] = json!("dashed");
+        }
+        element["points"] = Value::Array(points);
+        element["lastCommittedPoint"] = Value::Null;
+        element["startBinding"] = binding(connector.from_node_id);
+        element["endBinding"] = binding(connector.to_node_id);
+        element["startArrowhead"] = Value::Null;
+        element["endArrowhead"] = json!("arrow");
+        element["elbowed"] = json!(false);
+        element
+    }
+
+    /// Returns a text element, which is centred within its container if it has
+    /// one.
+    fn text_element(
+        index: usize,
+        text_box: &TextBox,
+        group_ids: &[String],
+        container_id: Option<&str>,
+    ) -> Value {
+        let (text_align, vertical_align) = if container_id.is_some() {
+            ("center", "middle")
+        } else {
+            ("left", "top")
+        };
+
+        let mut element = Self::element(
+            index,
+            &text_box.id,
+            "text",
+            (text_box.x, text_box.y, text_box.width, text_box.height),
+            group_ids,
+        );
+        element["text"] = json!(text_box.text);
+        element["originalText"] = json!(text_box.text);
+        element["fontSize"] = json!(TEXT_FONT_SIZE);
+        element["fontFamily"] = json!(FONT_FAMILY_MONOSPACE);
+        element["lineHeight"] = json!(TEXT_LINE_HEIGHT / TEXT_FONT_SIZE);
+        element["textAlign"] = json!(text_align);
+        element["verticalAlign"] = json!(vertical_align);
+        element["containerId"] = json!(container_id);
+        element["autoResize"] = json!(container_id.is_none());
+        element
+    }
+
+    /// Returns the properties common to all elements.
+    ///
+    /// The element's index in the scene is used as its seed, so that the same
+    /// diagram is always exported to the same scene.
+    fn element(
+        index: usize,
+        id: &str,
+        element_type: &str,
+        (x, y, width, height): (f64, f64, f64, f64),
+        group_ids: &[String],
+    ) -> Value {
+        let seed = index + 1;
+        let [x, y, width, height] = [x, y, width, height].map(SvgElementsToShapesMapper::round);
+        json!({
+            "id": id,
+            "type": element_type,
+            "x": x,
+            "y": y,
+            "width": width,
+            "height": height,
+            "angle": 0,
+            "strokeColor": STROKE_COLOR,
+            "backgroundColor": "transparent",
+            "fillStyle": "solid",
+            "strokeWidth": 1,
+            "strokeStyle": "solid",
+            "roughness": 0,
+            "opacity": 100,
+            "groupIds": group_ids,
+            "frameId": null,
+            "roundness": null,
+            "seed": seed,
+            "version": 1,
+            "versionNonce": seed,
+            "isDeleted": false,
+            "boundElements": null,
+            "updated": 1,
+            "link": null,
+            "locked": false,
+        })
+    }
+
+    fn group_id(node_id: &str) -> String {
+        format!("group_{node_id}")
+    }
+}
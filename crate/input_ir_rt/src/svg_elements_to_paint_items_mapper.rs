@@ -0,0 +1,467 @@
+use disposition_ir_model::entity::EntityTailwindClasses;
+use disposition_model_common::Id;
+use disposition_svg_model::{SvgElements, SvgTextSpan};
+use disposition_taffy_model::{MD_BLOCKQUOTE_BORDER_COLOR, MD_CODE_BG_COLOR};
+use kurbo::{Affine, BezPath, Point};
+
+use crate::{
+    svg_element_classes::{
+        EDGE_ARROW_HEAD_CLASS, EDGE_BODY_CLASS, NODE_CIRCLE_CLASS, NODE_WRAPPER_CLASS,
+    },
+    svg_elements_to_svg_mapper::{
+        CODE_BG_CORNER_RADIUS, CODE_BG_DESCENT_OFFSET, EDGE_BG_CORNER_RADIUS,
+    },
+    ColorScheme, EdgeDescBgIdGenerator, EdgeHaloIdGenerator, EdgeHaloOutlineIdGenerator,
+    EdgeLabelBgIdGenerator, StringXmlEscaper, SvgElementsToSvgMapper,
+};
+
+pub(crate) use self::{
+    fill_rule::FillRule, paint_color::PaintColor, paint_item::PaintItem, paint_items::PaintItems,
+    paint_path::PaintPath, paint_stroke::PaintStroke, paint_style::PaintStyle,
+    paint_style_decl::PaintStyleDecl, paint_style_rule::PaintStyleRule,
+    paint_style_target::PaintStyleTarget, paint_text::PaintText,
+};
+
+mod fill_rule;
+mod paint_color;
+mod paint_item;
+mod paint_items;
+mod paint_path;
+mod paint_stroke;
+mod paint_style;
+mod paint_style_decl;
+mod paint_style_rule;
+mod paint_style_target;
+mod paint_text;
+
+/// Maps `SvgElements` to the shapes and text that draw the diagram, for
+/// exporting to formats that do not read CSS.
+///
+/// The elements are walked in the order that `SvgElementsToSvgMapper` writes
+/// them, and each element's Tailwind classes are resolved the way a browser
+/// would resolve them for the diagram at rest:
+///
+/// * Colors are resolved from the theme's `--tw-*` variables for the color
+///   scheme.
+/// * Classes for interactive states, such as `hover:..` and
+///   `group-has-[..]:..`, are ignored, so interaction edges stay hidden.
+/// * Positions and node shapes come from the `translate-*` and `[d:path(..)]`
+///   classes, so processes are drawn collapsed.
+///
+/// Inline images are not drawn.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SvgElementsToPaintItemsMapper;
+
+impl SvgElementsToPaintItemsMapper {
+    /// Returns the items that draw the SVG elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `svg_elements`: The SVG elements with the computed layout.
+    /// * `color_scheme`: Whether to use the light or dark colors of the theme.
+    pub(crate) fn map(svg_elements: &SvgElements<'_>, color_scheme: ColorScheme) -> PaintItems {
+        let SvgElements {
+            svg_width,
+            svg_height,
+            svg_node_infos,
+            svg_edge_infos,
+            edge_label_infos,
+            edge_description_infos,
+            svg_process_infos: _,
+            tailwind_classes,
+            css: _,
+        } = svg_elements;
+
+        let mut paint_items_builder = PaintItemsBuilder {
+            color_scheme,
+            items: Vec::new(),
+        };
+        let frame_svg =
+            paint_items_builder.frame_enter(&PaintFrame::default(), "svg", "group", &[]);
+
+        svg_node_infos.iter().for_each(|svg_node_info| {
+            let node_id = &svg_node_info.node_id;
+            paint_items_builder
+                .items
+                .push(PaintItem::Entity(node_id.to_string()));
+            let frame_node = paint_items_builder.frame_enter(
+                &frame_svg,
+                "g",
+                Self::entity_classes(tailwind_classes, node_id),
+                &[],
+            );
+
+            let wrapper_classes = match svg_node_info.wrapper_tailwind_classes.as_deref() {
+                Some(wrapper_tailwind_classes) => {
+                    format!("{NODE_WRAPPER_CLASS} {wrapper_tailwind_classes}")
+                }
+                None => String::from(NODE_WRAPPER_CLASS),
+            };
+            paint_items_builder.path_push(
+                &frame_node,
+                &wrapper_classes,
+                &[],
+                &svg_node_info.path_d_collapsed,
+            );
+            if let Some(circle) = svg_node_info.circle.as_ref() {
+                paint_items_builder.path_push(&frame_node, NODE_CIRCLE_CLASS, &[], &circle.path_d);
+            }
+            paint_items_builder.text_spans_push(&frame_node, &svg_node_info.text_spans);
+        });
+
+        svg_edge_infos.iter().for_each(|svg_edge_info| {
+            let edge_id = &svg_edge_info.edge_id;
+            paint_items_builder
+                .items
+                .push(PaintItem::Entity(edge_id.to_string()));
+            let frame_edge = paint_items_builder.frame_enter(
+                &frame_svg,
+                "g",
+                Self::entity_classes(tailwind_classes, edge_id),
+                &[],
+            );
+
+            if let Some(halo_classes) =
+                tailwind_classes.get(&EdgeHaloIdGenerator::generate(edge_id))
+            {
+                paint_items_builder.path_push(
+                    &frame_edge,
+                    &format!("edge_halo {halo_classes}"),
+                    &[("fill", "none")],
+                    &svg_edge_info.path_d,
+                );
+            }
+            if let Some(outline_classes) =
+                tailwind_classes.get(&EdgeHaloOutlineIdGenerator::generate(edge_id))
+            {
+                let outline_classes = format!("edge_halo_outline {outline_classes}");
+                [
+                    &svg_edge_info.halo_outline_rail_a_path_d,
+                    &svg_edge_info.halo_outline_rail_b_path_d,
+                ]
+                .into_iter()
+                .for_each(|rail_path_d| {
+                    paint_items_builder.path_push(
+                        &frame_edge,
+                        &outline_classes,
+                        &[("fill", "none")],
+                        rail_path_d,
+                    );
+                });
+            }
+            paint_items_builder.path_push(
+                &frame_edge,
+                EDGE_BODY_CLASS,
+                &[("fill", "none")],
+                &svg_edge_info.path_d,
+            );
+            paint_items_builder.path_push(
+                &frame_edge,
+                "locus",
+                &[("fill", "none")],
+                &svg_edge_info.locus_path_d,
+            );
+
+            let arrow_head_classes = Id::try_from(format!("{edge_id}__arrow_head"))
+                .ok()
+                .and_then(|arrow_head_id| tailwind_classes.get(&arrow_head_id))
+                .map(|arrow_head_classes| format!("{EDGE_ARROW_HEAD_CLASS} {arrow_head_classes}"))
+                .unwrap_or_else(|| String::from(EDGE_ARROW_HEAD_CLASS));
+            paint_items_builder.path_push(
+                &frame_edge,
+                &arrow_head_classes,
+                &[],
+                &svg_edge_info.arrow_head_path_d,
+            );
+        });
+
+        edge_label_infos.iter().for_each(|svg_edge_label_info| {
+            let edge_id = &svg_edge_label_info.edge_id;
+            let label_bg_classes = tailwind_classes.get(&EdgeLabelBgIdGenerator::generate(edge_id));
+            [
+                ("from_label", svg_edge_label_info.from_label.as_ref()),
+                ("to_label", svg_edge_label_info.to_label.as_ref()),
+            ]
+            .into_iter()
+            .filter_map(|(suffix, endpoint_info)| Some((suffix, endpoint_info?)))
+            .filter(|(_suffix, endpoint_info)| {
+                !endpoint_info.text_spans.is_empty() || !endpoint_info.image_spans.is_empty()
+            })
+            .for_each(|(suffix, endpoint_info)| {
+                paint_items_builder
+                    .items
+                    .push(PaintItem::Entity(format!("{edge_id}__{suffix}")));
+                let frame_label = paint_items_builder.frame_enter(
+                    &frame_svg,
+                    "g",
+                    Self::entity_classes(tailwind_classes, edge_id),
+                    &[],
+                );
+                if let Some(label_bg_classes) = label_bg_classes {
+                    paint_items_builder.path_push(
+                        &frame_label,
+                        label_bg_classes,
+                        &[("stroke-width", "0")],
+                        &SvgElementsToSvgMapper::code_bg_path_d(
+                            endpoint_info.x,
+                            endpoint_info.y,
+                            endpoint_info.width,
+                            endpoint_info.height,
+                            EDGE_BG_CORNER_RADIUS,
+                        ),
+                    );
+                }
+                paint_items_builder.text_spans_push(&frame_label, &endpoint_info.text_spans);
+            });
+        });
+
+        edge_description_infos
+            .iter()
+            .filter(|svg_edge_description_info| {
+                !svg_edge_description_info.text_spans.is_empty()
+                    || !svg_edge_description_info.image_spans.is_empty()
+            })
+            .for_each(|svg_edge_description_info| {
+                let edge_id = &svg_edge_description_info.edge_id;
+                paint_items_builder
+                    .items
+                    .push(PaintItem::Entity(format!("{edge_id}__desc")));
+                let frame_desc = paint_items_builder.frame_enter(
+                    &frame_svg,
+                    "g",
+                    Self::entity_classes(tailwind_classes, edge_id),
+                    &[],
+                );
+                if let Some(desc_bg_classes) =
+                    tailwind_classes.get(&EdgeDescBgIdGenerator::generate(edge_id))
+                {
+                    paint_items_builder.path_push(
+                        &frame_desc,
+                        desc_bg_classes,
+                        &[("stroke-width", "0")],
+                        &SvgElementsToSvgMapper::code_bg_path_d(
+                            svg_edge_description_info.x,
+                            svg_edge_description_info.y,
+                            svg_edge_description_info.width,
+                            svg_edge_description_info.height,
+                            EDGE_BG_CORNER_RADIUS,
+                        ),
+                    );
+                }
+                paint_items_builder
+                    .text_spans_push(&frame_desc, &svg_edge_description_info.text_spans);
+            });
+
+        PaintItems {
+            width: f64::from(*svg_width),
+            height: f64::from(*svg_height),
+            items: paint_items_builder.items,
+        }
+    }
+
+    /// Returns the Tailwind classes of the node or edge, or `""` if it has
+    /// none.
+    fn entity_classes<'t, 'id>(
+        tailwind_classes: &'t EntityTailwindClasses<'id>,
+        id: &Id<'id>,
+    ) -> &'t str {
+        tailwind_classes
+            .get(id)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+/// Style and position of an element whose children are being drawn.
+#[derive(Clone, Debug)]
+struct PaintFrame {
+    /// Style of the element.
+    style: PaintStyle,
+    /// Rules from the element's classes that target its children, such as
+    /// `[&>.wrapper]:fill-..`.
+    rules_children: Vec<PaintStyleRule>,
+    /// Transform from the element's coordinates to the diagram's.
+    affine: Affine,
+    /// Opacity of the element and its ancestors.
+    opacity: f32,
+}
+
+impl Default for PaintFrame {
+    fn default() -> Self {
+        Self {
+            style: PaintStyle::default(),
+            rules_children: Vec::new(),
+            affine: Affine::IDENTITY,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// Collects the items that draw the diagram.
+struct PaintItemsBuilder {
+    /// Whether to use the light or dark colors of theme variables.
+    color_scheme: ColorScheme,
+    /// Items to draw, from back to front.
+    items: Vec<PaintItem>,
+}
+
+impl PaintItemsBuilder {
+    /// Returns the frame of a container element, such as a `<g>`.
+    fn frame_enter(
+        &self,
+        frame_parent: &PaintFrame,
+        element_name: &str,
+        classes: &str,
+        attrs: &[(&str, &str)],
+    ) -> PaintFrame {
+        let (style, rules_children) = PaintStyle::element_resolve(
+            element_name,
+            classes,
+            attrs,
+            &frame_parent.style,
+            &frame_parent.rules_children,
+            self.color_scheme,
+        );
+        let affine =
+            frame_parent.affine * Affine::translate((style.translate_x, style.translate_y));
+        let opacity = frame_parent.opacity * style.opacity;
+
+        PaintFrame {
+            style,
+            rules_children,
+            affine,
+            opacity,
+        }
+    }
+
+    /// Pushes the item for a `<path>` element, if it is visible and painted.
+    fn path_push(
+        &mut self,
+        frame_parent: &PaintFrame,
+        classes: &str,
+        attrs: &[(&str, &str)],
+        path_d: &str,
+    ) {
+        let PaintFrame {
+            style,
+            affine,
+            opacity,
+            ..
+        } = self.frame_enter(frame_parent, "path", classes, attrs);
+        if !style.visible {
+            return;
+        }
+        let path_d = style.path_d.as_deref().unwrap_or(path_d);
+        let Ok(mut path) = BezPath::from_svg(path_d) else {
+            return;
+        };
+        path.apply_affine(affine * style.offset_path_end_affine());
+
+        let fill = style
+            .fill
+            .map(|fill| fill.opacity_apply(style.fill_opacity * opacity))
+            .filter(|fill| fill.alpha > 0.0);
+        let stroke = style
+            .stroke
+            .map(|stroke| stroke.opacity_apply(style.stroke_opacity * opacity))
+            .filter(|stroke| stroke.alpha > 0.0 && style.stroke_width > 0.0)
+            .map(|color| PaintStroke {
+                color,
+                width: style.stroke_width,
+                dasharray: style.stroke_dasharray.clone(),
+            });
+        if fill.is_none() && stroke.is_none() {
+            return;
+        }
+
+        self.items.push(PaintItem::Path(PaintPath {
+            path,
+            fill,
+            fill_rule: style.fill_rule,
+            stroke,
+        }));
+    }
+
+    /// Pushes the items for text spans, with the backgrounds of inline code
+    /// and the borders of blockquotes drawn behind them.
+    fn text_spans_push(&mut self, frame_parent: &PaintFrame, text_spans: &[SvgTextSpan]) {
+        text_spans.iter().for_each(|text_span| {
+            let SvgTextSpan {
+                x,
+                y,
+                width,
+                height,
+                ..
+            } = *text_span;
+            let md_style = text_span.md_style.as_ref();
+
+            if md_style.is_some_and(|svg_md_style| svg_md_style.blockquote) {
+                self.path_push(
+                    frame_parent,
+                    &MD_BLOCKQUOTE_BORDER_COLOR.fill_class(),
+                    &[("fill-rule", "evenodd"), ("stroke-width", "0")],
+                    &SvgElementsToSvgMapper::blockquote_border_path_d(x, y - height, width, height),
+                );
+            }
+            if md_style.is_some_and(|svg_md_style| svg_md_style.code) {
+                self.path_push(
+                    frame_parent,
+                    &MD_CODE_BG_COLOR.fill_class(),
+                    &[],
+                    &SvgElementsToSvgMapper::code_bg_path_d(
+                        x,
+                        y - height + CODE_BG_DESCENT_OFFSET,
+                        width,
+                        height,
+                        CODE_BG_CORNER_RADIUS,
+                    ),
+                );
+            }
+
+            // Links are wrapped in an `<a>`, so rules that target the `<g>`'s
+            // `<text>` children do not apply to them.
+            if md_style.is_some_and(|svg_md_style| svg_md_style.link_dest.is_some()) {
+                let frame_link = self.frame_enter(frame_parent, "a", "", &[]);
+                self.text_push(&frame_link, text_span);
+            } else {
+                self.text_push(frame_parent, text_span);
+            }
+        });
+    }
+
+    /// Pushes the item for a text span, if it is visible and painted.
+    fn text_push(&mut self, frame_parent: &PaintFrame, text_span: &SvgTextSpan) {
+        let PaintFrame {
+            style,
+            affine,
+            opacity,
+            ..
+        } = self.frame_enter(
+            frame_parent,
+            "text",
+            &text_span.tailwind_classes.join(" "),
+            &[("stroke-width", "0")],
+        );
+        if !style.visible {
+            return;
+        }
+        let Some(fill) = style.fill else {
+            return;
+        };
+        let text = StringXmlEscaper::unescape(&text_span.text);
+        if text.is_empty() {
+            return;
+        }
+
+        self.items.push(PaintItem::Text(PaintText {
+            point: affine * Point::new(f64::from(text_span.x), f64::from(text_span.y)),
+            text,
+            fill: fill.opacity_apply(style.fill_opacity * opacity),
+            bold: style.font_bold,
+            italic: style.font_italic,
+            underline: style.text_underline,
+            line_through: style.text_line_through,
+            is_preserved: text_span.md_style.is_some(),
+        }));
+    }
+}
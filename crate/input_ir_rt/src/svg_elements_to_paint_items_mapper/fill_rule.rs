@@ -0,0 +1,8 @@
+/// How to determine which areas of a path are inside it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FillRule {
+    /// Areas that subpaths wind around a non-zero number of times.
+    NonZero,
+    /// Areas that subpaths wind around an odd number of times.
+    EvenOdd,
+}
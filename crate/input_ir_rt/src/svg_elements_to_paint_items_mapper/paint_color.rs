@@ -0,0 +1,238 @@
+use crate::{input_to_ir_diagram_mapper::tailwind_colors::tailwind_color_lookup, ColorScheme};
+
+/// An sRGB color with straight (not premultiplied) alpha, each component
+/// between `0.0` and `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PaintColor {
+    pub(crate) red: f32,
+    pub(crate) green: f32,
+    pub(crate) blue: f32,
+    pub(crate) alpha: f32,
+}
+
+impl PaintColor {
+    pub(crate) const BLACK: PaintColor = PaintColor::opaque(0.0, 0.0, 0.0);
+    pub(crate) const TRANSPARENT: PaintColor = PaintColor {
+        red: 0.0,
+        green: 0.0,
+        blue: 0.0,
+        alpha: 0.0,
+    };
+    pub(crate) const WHITE: PaintColor = PaintColor::opaque(1.0, 1.0, 1.0);
+
+    const fn opaque(red: f32, green: f32, blue: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha: 1.0,
+        }
+    }
+
+    /// Returns this color with its alpha multiplied by `opacity`.
+    pub(crate) fn opacity_apply(self, opacity: f32) -> Self {
+        Self {
+            alpha: self.alpha * opacity.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Returns the red, green, and blue components as uppercase hexadecimal
+    /// digits, e.g. `"0369A1"`.
+    pub(crate) fn hex(self) -> String {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        format!(
+            "{:02X}{:02X}{:02X}",
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue)
+        )
+    }
+
+    /// Parses a CSS color value, such as `var(--tw-blue-100-900)`, `#0369a1`,
+    /// or `oklch(93.2% 0.032 255.585)`.
+    ///
+    /// Returns `Some(None)` for `none`, and `None` if the value is not
+    /// supported.
+    pub(crate) fn css_value_parse(
+        value: &str,
+        color_scheme: ColorScheme,
+    ) -> Option<Option<PaintColor>> {
+        let value = value.trim();
+        if value == "none" {
+            return Some(None);
+        }
+
+        let css_color = if let Some(var_name) = value
+            .strip_prefix("var(")
+            .and_then(|var| var.strip_suffix(')'))
+        {
+            Self::theme_var_parse(var_name.trim(), color_scheme)?
+        } else if let Some(hex) = value.strip_prefix('#') {
+            Self::hex_parse(hex)?
+        } else if let Some(oklch) = value
+            .strip_prefix("oklch(")
+            .and_then(|oklch| oklch.strip_suffix(')'))
+        {
+            Self::oklch_parse(oklch)?
+        } else {
+            Self::keyword_parse(value)?
+        };
+
+        Some(Some(css_color))
+    }
+
+    /// Parses the color of a Tailwind `fill-*` / `stroke-*` utility, after
+    /// the `fill-` / `stroke-` prefix.
+    ///
+    /// e.g. `[var(--tw-blue-100-900)]`, `blue-500`, `blue-500/50`, `white`.
+    ///
+    /// Returns `Some(None)` for `none`, and `None` if the value is not a
+    /// supported color.
+    pub(crate) fn tailwind_value_parse(
+        value: &str,
+        color_scheme: ColorScheme,
+    ) -> Option<Option<PaintColor>> {
+        if let Some(arbitrary) = value
+            .strip_prefix('[')
+            .and_then(|arbitrary| arbitrary.strip_suffix(']'))
+        {
+            return Self::css_value_parse(&arbitrary.replace('_', " "), color_scheme);
+        }
+
+        let (color, opacity) = match value.split_once('/') {
+            Some((color, opacity)) => (color, Some(opacity.parse::<f32>().ok()? / 100.0)),
+            None => (value, None),
+        };
+        let css_color = match color {
+            "none" => return Some(None),
+            _ => match color.rsplit_once('-') {
+                Some((color_name, shade)) => Self::oklch_parse(Self::oklch_inner(
+                    tailwind_color_lookup(color_name, shade)?,
+                )?)?,
+                None => Self::keyword_parse(color)?,
+            },
+        };
+
+        Some(Some(match opacity {
+            Some(opacity) => css_color.opacity_apply(opacity),
+            None => css_color,
+        }))
+    }
+
+    /// Parses a `--tw-{color}-{light_shade}-{dark_shade}` theme variable
+    /// registered by `CssThemeVars`.
+    fn theme_var_parse(var_name: &str, color_scheme: ColorScheme) -> Option<PaintColor> {
+        let color_shades = var_name.strip_prefix("--tw-")?;
+        let (color_and_light_shade, dark_shade) = color_shades.rsplit_once('-')?;
+        let (color, light_shade) = color_and_light_shade.rsplit_once('-')?;
+        let shade = match color_scheme {
+            ColorScheme::Light => light_shade,
+            ColorScheme::Dark => dark_shade,
+        };
+
+        Self::oklch_parse(Self::oklch_inner(tailwind_color_lookup(color, shade)?)?)
+    }
+
+    /// Returns the arguments of an `oklch(..)` value.
+    fn oklch_inner(oklch: &str) -> Option<&str> {
+        oklch.strip_prefix("oklch(")?.strip_suffix(')')
+    }
+
+    fn keyword_parse(keyword: &str) -> Option<PaintColor> {
+        match keyword {
+            "black" => Some(Self::BLACK),
+            "white" => Some(Self::WHITE),
+            "transparent" => Some(Self::TRANSPARENT),
+            _ => None,
+        }
+    }
+
+    /// Parses `rgb`, `rgba`, `rrggbb`, or `rrggbbaa` hexadecimal digits.
+    fn hex_parse(hex: &str) -> Option<PaintColor> {
+        let digits = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|digit| digit as f32))
+            .collect::<Option<Vec<f32>>>()?;
+        let components = match digits.len() {
+            3 | 4 => digits
+                .iter()
+                .map(|digit| digit * 17.0 / 255.0)
+                .collect::<Vec<f32>>(),
+            6 | 8 => digits
+                .chunks(2)
+                .map(|pair| (pair[0] * 16.0 + pair[1]) / 255.0)
+                .collect::<Vec<f32>>(),
+            _ => return None,
+        };
+
+        Some(PaintColor {
+            red: components[0],
+            green: components[1],
+            blue: components[2],
+            alpha: components.get(3).copied().unwrap_or(1.0),
+        })
+    }
+
+    /// Parses the arguments of an `oklch(..)` value, e.g. `93.2% 0.032
+    /// 255.585`, and converts it to sRGB.
+    ///
+    /// Colors outside the sRGB gamut are clipped.
+    fn oklch_parse(oklch: &str) -> Option<PaintColor> {
+        let (lch, alpha) = match oklch.split_once('/') {
+            Some((lch, alpha)) => (lch, Self::number_or_percentage_parse(alpha)?),
+            None => (oklch, 1.0),
+        };
+        let mut lch_parts = lch.split_whitespace();
+        let lightness = Self::number_or_percentage_parse(lch_parts.next()?)?;
+        let chroma = lch_parts.next()?.parse::<f32>().ok()?;
+        let hue = lch_parts
+            .next()?
+            .trim_end_matches("deg")
+            .parse::<f32>()
+            .ok()?;
+
+        // oklch -> oklab
+        let hue = hue.to_radians();
+        let a = chroma * hue.cos();
+        let b = chroma * hue.sin();
+
+        // oklab -> linear sRGB, from <https://bottosson.github.io/posts/oklab/>.
+        let l_ = lightness + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = lightness - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = lightness - 0.089_484_18 * a - 1.291_485_5 * b;
+        let l = l_ * l_ * l_;
+        let m = m_ * m_ * m_;
+        let s = s_ * s_ * s_;
+        let red = 4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s;
+        let green = -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s;
+        let blue = -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s;
+
+        Some(PaintColor {
+            red: Self::srgb_gamma_encode(red),
+            green: Self::srgb_gamma_encode(green),
+            blue: Self::srgb_gamma_encode(blue),
+            alpha: alpha.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Parses `0.5` or `50%` as `0.5`.
+    fn number_or_percentage_parse(value: &str) -> Option<f32> {
+        let value = value.trim();
+        match value.strip_suffix('%') {
+            Some(percentage) => percentage.parse::<f32>().ok().map(|value| value / 100.0),
+            None => value.parse::<f32>().ok(),
+        }
+    }
+
+    /// Converts a linear sRGB component to gamma-encoded sRGB, clipped to
+    /// `0.0..=1.0`.
+    fn srgb_gamma_encode(linear: f32) -> f32 {
+        let linear = linear.clamp(0.0, 1.0);
+        if linear <= 0.003_130_8 {
+            linear * 12.92
+        } else {
+            1.055 * linear.powf(1.0 / 2.4) - 0.055
+        }
+    }
+}
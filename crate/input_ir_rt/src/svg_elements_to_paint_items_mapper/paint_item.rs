@@ -0,0 +1,13 @@
+use super::{PaintPath, PaintText};
+
+/// An item to draw, in the order that it is painted.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PaintItem {
+    /// Start of the items that draw a node, edge, or edge label, with its
+    /// ID, e.g. `edge_ab__0__from_label`.
+    Entity(String),
+    /// A filled and/or outlined shape.
+    Path(PaintPath),
+    /// A line of text.
+    Text(PaintText),
+}
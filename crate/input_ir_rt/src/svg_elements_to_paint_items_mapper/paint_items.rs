@@ -0,0 +1,138 @@
+#[cfg(any(feature = "pdf", feature = "png"))]
+use std::fmt::Write;
+
+#[cfg(any(feature = "pdf", feature = "png"))]
+use crate::StringXmlEscaper;
+
+use super::PaintItem;
+#[cfg(any(feature = "pdf", feature = "png"))]
+use super::{FillRule, PaintPath, PaintText};
+
+/// Items that draw a diagram in its resting state.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PaintItems {
+    /// Width of the diagram.
+    pub(crate) width: f64,
+    /// Height of the diagram.
+    pub(crate) height: f64,
+    /// Items to draw, from back to front.
+    pub(crate) items: Vec<PaintItem>,
+}
+
+#[cfg(any(feature = "pdf", feature = "png"))]
+impl PaintItems {
+    /// Returns an SVG that draws the items with presentation attributes only,
+    /// for renderers that do not support CSS.
+    ///
+    /// Text does not specify its font family or size, so that the renderer's
+    /// defaults are used.
+    #[cfg(feature = "png")]
+    pub(crate) fn svg(&self) -> String {
+        let mut svg = String::with_capacity(4096);
+        let _ = write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\">",
+            width = self.width.max(1.0),
+            height = self.height.max(1.0),
+        );
+        self.svg_content_write(&mut svg);
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Writes the `<path>` and `<text>` elements that draw the items.
+    pub(crate) fn svg_content_write(&self, buffer: &mut String) {
+        self.items.iter().for_each(|paint_item| match paint_item {
+            PaintItem::Entity(_) => {}
+            PaintItem::Path(paint_path) => Self::svg_path_write(buffer, paint_path),
+            PaintItem::Text(paint_text) => Self::svg_text_write(buffer, paint_text),
+        });
+    }
+
+    /// Writes a `<path>` element that draws the path.
+    pub(crate) fn svg_path_write(buffer: &mut String, paint_path: &PaintPath) {
+        let PaintPath {
+            path,
+            fill,
+            fill_rule,
+            stroke,
+        } = paint_path;
+
+        let _ = write!(buffer, "<path d=\"{}\"", path.to_svg());
+        match fill {
+            Some(fill) => {
+                let _ = write!(buffer, " fill=\"#{}\"", fill.hex());
+                if fill.alpha < 1.0 {
+                    let _ = write!(buffer, " fill-opacity=\"{}\"", fill.alpha);
+                }
+                if *fill_rule == FillRule::EvenOdd {
+                    buffer.push_str(" fill-rule=\"evenodd\"");
+                }
+            }
+            None => buffer.push_str(" fill=\"none\""),
+        }
+        if let Some(stroke) = stroke {
+            let _ = write!(
+                buffer,
+                " stroke=\"#{}\" stroke-width=\"{}\"",
+                stroke.color.hex(),
+                stroke.width
+            );
+            if stroke.color.alpha < 1.0 {
+                let _ = write!(buffer, " stroke-opacity=\"{}\"", stroke.color.alpha);
+            }
+            if !stroke.dasharray.is_empty() {
+                let dasharray = stroke
+                    .dasharray
+                    .iter()
+                    .map(f64::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                let _ = write!(buffer, " stroke-dasharray=\"{dasharray}\"");
+            }
+        }
+        buffer.push_str(" />");
+    }
+
+    /// Writes a `<text>` element that draws the text.
+    pub(crate) fn svg_text_write(buffer: &mut String, paint_text: &PaintText) {
+        let PaintText {
+            point,
+            text,
+            fill,
+            bold,
+            italic,
+            underline,
+            line_through,
+            is_preserved,
+        } = paint_text;
+
+        let _ = write!(
+            buffer,
+            "<text x=\"{}\" y=\"{}\" fill=\"#{}\"",
+            point.x,
+            point.y,
+            fill.hex()
+        );
+        if fill.alpha < 1.0 {
+            let _ = write!(buffer, " fill-opacity=\"{}\"", fill.alpha);
+        }
+        if *bold {
+            buffer.push_str(" font-weight=\"bold\"");
+        }
+        if *italic {
+            buffer.push_str(" font-style=\"italic\"");
+        }
+        let text_decoration = [(*underline, "underline"), (*line_through, "line-through")]
+            .into_iter()
+            .filter_map(|(is_set, decoration)| is_set.then_some(decoration))
+            .collect::<Vec<&str>>();
+        if !text_decoration.is_empty() {
+            let _ = write!(buffer, " text-decoration=\"{}\"", text_decoration.join(" "));
+        }
+        if *is_preserved {
+            buffer.push_str(" xml:space=\"preserve\"");
+        }
+        let _ = write!(buffer, ">{}</text>", StringXmlEscaper::escape(text));
+    }
+}
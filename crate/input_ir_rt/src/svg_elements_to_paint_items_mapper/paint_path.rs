@@ -0,0 +1,16 @@
+use kurbo::BezPath;
+
+use super::{FillRule, PaintColor, PaintStroke};
+
+/// A shape that is filled and/or outlined.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PaintPath {
+    /// The shape, in the diagram's coordinates.
+    pub(crate) path: BezPath,
+    /// Color to fill the shape with, with opacity applied.
+    pub(crate) fill: Option<PaintColor>,
+    /// How to determine the inside of the shape.
+    pub(crate) fill_rule: FillRule,
+    /// How to outline the shape.
+    pub(crate) stroke: Option<PaintStroke>,
+}
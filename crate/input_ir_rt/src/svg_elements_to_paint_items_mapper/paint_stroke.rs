@@ -0,0 +1,14 @@
+use super::PaintColor;
+
+/// How to outline a shape.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PaintStroke {
+    /// Color of the outline, with opacity applied.
+    pub(crate) color: PaintColor,
+    /// Width of the outline.
+    pub(crate) width: f64,
+    /// Lengths of alternating dashes and gaps, empty for a solid outline.
+    ///
+    /// There is always an even number of lengths.
+    pub(crate) dasharray: Vec<f64>,
+}
@@ -0,0 +1,198 @@
+use kurbo::{Affine, BezPath, PathSeg, Vec2};
+
+use crate::ColorScheme;
+
+use super::{FillRule, PaintColor, PaintStyleDecl, PaintStyleRule, PaintStyleTarget};
+
+/// Paint properties of an element.
+///
+/// These are computed from the parent element's style, the element's
+/// presentation attributes, and the Tailwind classes of the element and its
+/// parent.
+#[derive(Clone, Debug)]
+pub(crate) struct PaintStyle {
+    /// Color to fill shapes and text with, `None` for `none`.
+    pub(crate) fill: Option<PaintColor>,
+    /// Opacity of the fill.
+    pub(crate) fill_opacity: f32,
+    /// How to determine the inside of paths.
+    pub(crate) fill_rule: FillRule,
+    /// Color to outline shapes with, `None` for `none`.
+    pub(crate) stroke: Option<PaintColor>,
+    /// Width of the outline.
+    pub(crate) stroke_width: f64,
+    /// Opacity of the outline.
+    pub(crate) stroke_opacity: f32,
+    /// Lengths of alternating dashes and gaps, empty for a solid outline.
+    pub(crate) stroke_dasharray: Vec<f64>,
+    /// Whether the element is drawn.
+    pub(crate) visible: bool,
+    /// Whether text is bold.
+    pub(crate) font_bold: bool,
+    /// Whether text is italic.
+    pub(crate) font_italic: bool,
+    /// Whether text is underlined.
+    pub(crate) text_underline: bool,
+    /// Whether text is struck through.
+    pub(crate) text_line_through: bool,
+    /// Opacity of the element and its descendants.
+    ///
+    /// Not inherited.
+    pub(crate) opacity: f32,
+    /// Horizontal translation of the element and its descendants.
+    ///
+    /// Not inherited.
+    pub(crate) translate_x: f64,
+    /// Vertical translation of the element and its descendants.
+    ///
+    /// Not inherited.
+    pub(crate) translate_y: f64,
+    /// Path data that replaces the `d` attribute.
+    ///
+    /// Not inherited.
+    pub(crate) path_d: Option<String>,
+    /// Path that the element is moved along, with the element's origin
+    /// placed on the path.
+    ///
+    /// Not inherited.
+    pub(crate) offset_path: Option<String>,
+}
+
+impl Default for PaintStyle {
+    /// Returns the initial values of the properties in SVG.
+    fn default() -> Self {
+        Self {
+            fill: Some(PaintColor::BLACK),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            stroke_dasharray: Vec::new(),
+            visible: true,
+            font_bold: false,
+            font_italic: false,
+            text_underline: false,
+            text_line_through: false,
+            opacity: 1.0,
+            translate_x: 0.0,
+            translate_y: 0.0,
+            path_d: None,
+            offset_path: None,
+        }
+    }
+}
+
+impl PaintStyle {
+    /// Returns the style that a child element starts with, before its own
+    /// attributes and classes are applied.
+    pub(crate) fn child_style(&self) -> Self {
+        Self {
+            opacity: 1.0,
+            translate_x: 0.0,
+            translate_y: 0.0,
+            path_d: None,
+            offset_path: None,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the style of an element that `SvgElementsToSvgMapper` writes,
+    /// and the rules from its classes that target its children.
+    ///
+    /// # Parameters
+    ///
+    /// * `element_name`: Tag name of the element, e.g. `path`.
+    /// * `classes`: The element's `class` attribute.
+    /// * `attrs`: The element's presentation attributes, e.g. `fill="none"`.
+    /// * `style_parent`: Style of the parent element.
+    /// * `rules_parent`: Rules from the parent's classes that target children,
+    ///   such as `[&>.wrapper]:fill-..`.
+    /// * `color_scheme`: Whether to use the light or dark colors of theme
+    ///   variables.
+    pub(crate) fn element_resolve(
+        element_name: &str,
+        classes: &str,
+        attrs: &[(&str, &str)],
+        style_parent: &PaintStyle,
+        rules_parent: &[PaintStyleRule],
+        color_scheme: ColorScheme,
+    ) -> (Self, Vec<PaintStyleRule>) {
+        let classes = classes.split_whitespace().collect::<Vec<&str>>();
+        let (rules_element, rules_children): (Vec<PaintStyleRule>, Vec<PaintStyleRule>) = classes
+            .iter()
+            .filter_map(|class| PaintStyleRule::class_parse(class, color_scheme))
+            .partition(|rule| rule.target == PaintStyleTarget::Element);
+
+        // Presentation attributes have the lowest precedence, then the
+        // element's classes, then the parent's classes that target this element
+        // through a more specific selector.
+        let mut style = style_parent.child_style();
+        attrs
+            .iter()
+            .filter_map(|(name, value)| PaintStyleDecl::attr_parse(name, value, color_scheme))
+            .chain(rules_element.into_iter().map(|rule| rule.decl))
+            .chain(
+                rules_parent
+                    .iter()
+                    .filter(|rule| rule.target.child_matches(element_name, &classes))
+                    .map(|rule| rule.decl.clone()),
+            )
+            .for_each(|decl| style.decl_apply(&decl));
+
+        (style, rules_children)
+    }
+
+    /// Returns the transform that places the element's origin at the end of
+    /// its `offset-path`, rotated to the path's direction.
+    ///
+    /// Returns the identity transform if the element has no `offset-path`.
+    pub(crate) fn offset_path_end_affine(&self) -> Affine {
+        let Some(path_seg_last) = self
+            .offset_path
+            .as_deref()
+            .and_then(|offset_path| BezPath::from_svg(offset_path).ok())
+            .and_then(|path| path.segments().last())
+        else {
+            return Affine::IDENTITY;
+        };
+
+        let (end, points_before_end) = match path_seg_last {
+            PathSeg::Line(line) => (line.p1, vec![line.p0]),
+            PathSeg::Quad(quad) => (quad.p2, vec![quad.p1, quad.p0]),
+            PathSeg::Cubic(cubic) => (cubic.p3, vec![cubic.p2, cubic.p1, cubic.p0]),
+        };
+        let direction = points_before_end
+            .iter()
+            .map(|point| end - *point)
+            .find(|direction| direction.hypot2() > 0.0)
+            .unwrap_or(Vec2::new(1.0, 0.0));
+
+        Affine::translate(end.to_vec2()) * Affine::rotate(direction.atan2())
+    }
+
+    /// Sets the property of the declaration.
+    fn decl_apply(&mut self, decl: &PaintStyleDecl) {
+        match decl {
+            PaintStyleDecl::Fill(fill) => self.fill = *fill,
+            PaintStyleDecl::FillOpacity(fill_opacity) => self.fill_opacity = *fill_opacity,
+            PaintStyleDecl::FillRule(fill_rule) => self.fill_rule = *fill_rule,
+            PaintStyleDecl::Stroke(stroke) => self.stroke = *stroke,
+            PaintStyleDecl::StrokeWidth(stroke_width) => self.stroke_width = *stroke_width,
+            PaintStyleDecl::StrokeOpacity(stroke_opacity) => self.stroke_opacity = *stroke_opacity,
+            PaintStyleDecl::StrokeDasharray(stroke_dasharray) => {
+                self.stroke_dasharray = stroke_dasharray.clone()
+            }
+            PaintStyleDecl::Visible(visible) => self.visible = *visible,
+            PaintStyleDecl::FontBold(font_bold) => self.font_bold = *font_bold,
+            PaintStyleDecl::FontItalic(font_italic) => self.font_italic = *font_italic,
+            PaintStyleDecl::TextUnderline => self.text_underline = true,
+            PaintStyleDecl::TextLineThrough => self.text_line_through = true,
+            PaintStyleDecl::Opacity(opacity) => self.opacity = *opacity,
+            PaintStyleDecl::TranslateX(translate_x) => self.translate_x = *translate_x,
+            PaintStyleDecl::TranslateY(translate_y) => self.translate_y = *translate_y,
+            PaintStyleDecl::PathD(path_d) => self.path_d = Some(path_d.clone()),
+            PaintStyleDecl::OffsetPath(offset_path) => self.offset_path = Some(offset_path.clone()),
+        }
+    }
+}
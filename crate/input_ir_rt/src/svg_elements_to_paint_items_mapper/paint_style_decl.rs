@@ -0,0 +1,132 @@
+use crate::ColorScheme;
+
+use super::{FillRule, PaintColor};
+
+/// A property value set by a presentation attribute or a Tailwind class.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PaintStyleDecl {
+    Fill(Option<PaintColor>),
+    FillOpacity(f32),
+    FillRule(FillRule),
+    Stroke(Option<PaintColor>),
+    StrokeWidth(f64),
+    StrokeOpacity(f32),
+    StrokeDasharray(Vec<f64>),
+    Visible(bool),
+    FontBold(bool),
+    FontItalic(bool),
+    TextUnderline,
+    TextLineThrough,
+    Opacity(f32),
+    TranslateX(f64),
+    TranslateY(f64),
+    PathD(String),
+    OffsetPath(String),
+}
+
+impl PaintStyleDecl {
+    /// Parses a presentation attribute, e.g. `fill="none"`.
+    ///
+    /// Returns `None` if the attribute is not a supported property.
+    pub(crate) fn attr_parse(name: &str, value: &str, color_scheme: ColorScheme) -> Option<Self> {
+        Self::css_property_parse(name, value, color_scheme)
+    }
+
+    /// Parses a CSS property, e.g. `stroke-dasharray: 2,4`.
+    ///
+    /// Returns `None` if the property or value is not supported.
+    pub(crate) fn css_property_parse(
+        property: &str,
+        value: &str,
+        color_scheme: ColorScheme,
+    ) -> Option<Self> {
+        let value = value.trim();
+        let decl = match property.trim() {
+            "fill" => PaintStyleDecl::Fill(PaintColor::css_value_parse(value, color_scheme)?),
+            "stroke" => PaintStyleDecl::Stroke(PaintColor::css_value_parse(value, color_scheme)?),
+            "fill-opacity" => PaintStyleDecl::FillOpacity(Self::opacity_parse(value)?),
+            "stroke-opacity" => PaintStyleDecl::StrokeOpacity(Self::opacity_parse(value)?),
+            "opacity" => PaintStyleDecl::Opacity(Self::opacity_parse(value)?),
+            "fill-rule" => match value {
+                "nonzero" => PaintStyleDecl::FillRule(FillRule::NonZero),
+                "evenodd" => PaintStyleDecl::FillRule(FillRule::EvenOdd),
+                _ => return None,
+            },
+            "stroke-width" => PaintStyleDecl::StrokeWidth(Self::length_parse(value)?),
+            "stroke-dasharray" => PaintStyleDecl::StrokeDasharray(Self::dasharray_parse(value)?),
+            "visibility" => match value {
+                "visible" => PaintStyleDecl::Visible(true),
+                "hidden" | "collapse" => PaintStyleDecl::Visible(false),
+                _ => return None,
+            },
+            "font-weight" => match value {
+                "bold" | "bolder" => PaintStyleDecl::FontBold(true),
+                "normal" | "lighter" => PaintStyleDecl::FontBold(false),
+                _ => PaintStyleDecl::FontBold(value.parse::<u16>().ok()? >= 600),
+            },
+            "font-style" => PaintStyleDecl::FontItalic(matches!(value, "italic" | "oblique")),
+            "d" => PaintStyleDecl::PathD(Self::path_function_parse(value)?),
+            "offset-path" => PaintStyleDecl::OffsetPath(Self::path_function_parse(value)?),
+            _ => return None,
+        };
+
+        Some(decl)
+    }
+
+    pub(crate) fn length_parse(value: &str) -> Option<f64> {
+        let value = value.trim();
+        value
+            .strip_suffix("px")
+            .unwrap_or(value)
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|length| length.is_finite())
+    }
+
+    fn opacity_parse(value: &str) -> Option<f32> {
+        let opacity = match value.strip_suffix('%') {
+            Some(percentage) => percentage.trim().parse::<f32>().ok()? / 100.0,
+            None => value.parse::<f32>().ok()?,
+        };
+        Some(opacity.clamp(0.0, 1.0))
+    }
+
+    /// Parses `none`, or lengths separated by commas and/or whitespace.
+    fn dasharray_parse(value: &str) -> Option<Vec<f64>> {
+        if value == "none" {
+            return Some(Vec::new());
+        }
+        let dasharray = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|length| !length.is_empty())
+            .map(Self::length_parse)
+            .collect::<Option<Vec<f64>>>()?;
+
+        // Dashes are not drawn if every length is zero, or any is negative.
+        if dasharray.iter().any(|length| *length < 0.0)
+            || dasharray.iter().all(|length| *length == 0.0)
+        {
+            Some(Vec::new())
+        } else if dasharray.len() % 2 == 1 {
+            // An odd number of lengths is repeated to make it even.
+            Some([dasharray.as_slice(), dasharray.as_slice()].concat())
+        } else {
+            Some(dasharray)
+        }
+    }
+
+    /// Parses `path('..')`, returning the path data.
+    fn path_function_parse(value: &str) -> Option<String> {
+        let path_data = value.strip_prefix("path(")?.strip_suffix(')')?.trim();
+        let path_data = path_data
+            .strip_prefix('\'')
+            .and_then(|path_data| path_data.strip_suffix('\''))
+            .or_else(|| {
+                path_data
+                    .strip_prefix('"')
+                    .and_then(|path_data| path_data.strip_suffix('"'))
+            })?;
+        Some(path_data.to_string())
+    }
+}
@@ -0,0 +1,143 @@
+use crate::ColorScheme;
+
+use super::{PaintColor, PaintStyleDecl, PaintStyleTarget};
+
+/// A property value from a Tailwind class, and the elements it applies to.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PaintStyleRule {
+    /// Which elements the declaration applies to.
+    pub(crate) target: PaintStyleTarget,
+    /// The property value.
+    pub(crate) decl: PaintStyleDecl,
+}
+
+impl PaintStyleRule {
+    /// Parses a Tailwind class that applies to the element at rest.
+    ///
+    /// Returns `None` for classes that only apply in interactive states, such
+    /// as `hover:..`, `focus:..`, and `peer-[..]:..`, and for classes that do
+    /// not affect what is painted.
+    pub(crate) fn class_parse(class: &str, color_scheme: ColorScheme) -> Option<Self> {
+        let mut segments = Self::variants_split(class);
+        let utility = segments.pop()?;
+        let target = match segments.as_slice() {
+            [] => PaintStyleTarget::Element,
+            [variant] => Self::child_variant_parse(variant)?,
+            _ => return None,
+        };
+        let decl = Self::utility_parse(utility, color_scheme)?;
+
+        Some(PaintStyleRule { target, decl })
+    }
+
+    /// Splits a class on the `:`s that separate variants from the utility,
+    /// ignoring `:`s within `[..]`.
+    fn variants_split(class: &str) -> Vec<&str> {
+        let mut segments = Vec::new();
+        let mut bracket_depth = 0u32;
+        let mut segment_start = 0;
+        class.char_indices().for_each(|(index, c)| match c {
+            '[' => bracket_depth += 1,
+            ']' => bracket_depth = bracket_depth.saturating_sub(1),
+            ':' if bracket_depth == 0 => {
+                segments.push(&class[segment_start..index]);
+                segment_start = index + 1;
+            }
+            _ => {}
+        });
+        segments.push(&class[segment_start..]);
+        segments
+    }
+
+    /// Parses an arbitrary variant that targets children, e.g. `[&>.wrapper]`,
+    /// `[&>text]`, or `[&>path.wrapper]`.
+    fn child_variant_parse(variant: &str) -> Option<PaintStyleTarget> {
+        let selector = variant.strip_prefix("[&>")?.strip_suffix(']')?;
+        let (name, class) = match selector.split_once('.') {
+            Some((name, class)) => (name, Some(class)),
+            None => (selector, None),
+        };
+        let name_is_valid = name.chars().all(|c| c.is_ascii_alphanumeric());
+        let class_is_valid = class.is_none_or(|class| {
+            !class.is_empty()
+                && class
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        });
+        if !name_is_valid || !class_is_valid || (name.is_empty() && class.is_none()) {
+            return None;
+        }
+
+        Some(PaintStyleTarget::Children {
+            name: Some(name).filter(|name| !name.is_empty()).map(String::from),
+            class: class.map(String::from),
+        })
+    }
+
+    /// Parses a Tailwind utility, without variants.
+    fn utility_parse(utility: &str, color_scheme: ColorScheme) -> Option<PaintStyleDecl> {
+        let decl = match utility {
+            "visible" => PaintStyleDecl::Visible(true),
+            "invisible" => PaintStyleDecl::Visible(false),
+            "font-bold" | "font-semibold" | "font-extrabold" | "font-black" => {
+                PaintStyleDecl::FontBold(true)
+            }
+            "font-normal" => PaintStyleDecl::FontBold(false),
+            "italic" => PaintStyleDecl::FontItalic(true),
+            "not-italic" => PaintStyleDecl::FontItalic(false),
+            "underline" => PaintStyleDecl::TextUnderline,
+            "line-through" => PaintStyleDecl::TextLineThrough,
+            _ => {
+                if let Some(arbitrary) = utility
+                    .strip_prefix('[')
+                    .and_then(|arbitrary| arbitrary.strip_suffix(']'))
+                {
+                    let (property, value) = arbitrary.split_once(':')?;
+                    return PaintStyleDecl::css_property_parse(
+                        property,
+                        &value.replace('_', " "),
+                        color_scheme,
+                    );
+                } else if let Some(opacity) = utility.strip_prefix("opacity-") {
+                    PaintStyleDecl::Opacity((opacity.parse::<f32>().ok()? / 100.0).clamp(0.0, 1.0))
+                } else if let Some((translate, is_negative)) = utility
+                    .strip_prefix("translate-")
+                    .map(|translate| (translate, false))
+                    .or_else(|| {
+                        utility
+                            .strip_prefix("-translate-")
+                            .map(|translate| (translate, true))
+                    })
+                {
+                    let (axis, length) = translate.split_once('-')?;
+                    let length = length.strip_prefix('[')?.strip_suffix(']')?;
+                    let length = PaintStyleDecl::length_parse(length)?;
+                    let length = if is_negative { -length } else { length };
+                    match axis {
+                        "x" => PaintStyleDecl::TranslateX(length),
+                        "y" => PaintStyleDecl::TranslateY(length),
+                        _ => return None,
+                    }
+                } else if let Some(stroke) = utility.strip_prefix("stroke-") {
+                    let stroke_width = stroke
+                        .strip_prefix('[')
+                        .and_then(|stroke| stroke.strip_suffix(']'))
+                        .unwrap_or(stroke);
+                    match PaintStyleDecl::length_parse(stroke_width) {
+                        Some(stroke_width) => PaintStyleDecl::StrokeWidth(stroke_width),
+                        None => PaintStyleDecl::Stroke(PaintColor::tailwind_value_parse(
+                            stroke,
+                            color_scheme,
+                        )?),
+                    }
+                } else if let Some(fill) = utility.strip_prefix("fill-") {
+                    PaintStyleDecl::Fill(PaintColor::tailwind_value_parse(fill, color_scheme)?)
+                } else {
+                    return None;
+                }
+            }
+        };
+
+        Some(decl)
+    }
+}
@@ -0,0 +1,30 @@
+/// Which elements a Tailwind class applies to.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PaintStyleTarget {
+    /// The element that has the class.
+    Element,
+    /// Children of the element that has the class, for classes with an
+    /// arbitrary variant such as `[&>.wrapper]:` or `[&>text]:`.
+    Children {
+        /// Tag name that children must have, e.g. `text`.
+        name: Option<String>,
+        /// Class that children must have, e.g. `wrapper`.
+        class: Option<String>,
+    },
+}
+
+impl PaintStyleTarget {
+    /// Returns whether a child element with the given name and classes is
+    /// targeted.
+    pub(crate) fn child_matches(&self, child_name: &str, child_classes: &[&str]) -> bool {
+        match self {
+            PaintStyleTarget::Element => false,
+            PaintStyleTarget::Children { name, class } => {
+                name.as_deref().is_none_or(|name| name == child_name)
+                    && class
+                        .as_deref()
+                        .is_none_or(|class| child_classes.contains(&class))
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use kurbo::Point;
+
+use super::PaintColor;
+
+/// A line of text, anchored at the start of its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PaintText {
+    /// Start of the text's baseline, in the diagram's coordinates.
+    pub(crate) point: Point,
+    /// The text, unescaped.
+    pub(crate) text: String,
+    /// Color of the text, with opacity applied.
+    pub(crate) fill: PaintColor,
+    /// Whether the text is bold.
+    pub(crate) bold: bool,
+    /// Whether the text is italic.
+    pub(crate) italic: bool,
+    /// Whether the text is underlined.
+    pub(crate) underline: bool,
+    /// Whether the text is struck through.
+    pub(crate) line_through: bool,
+    /// Whether leading, trailing, and consecutive spaces are kept.
+    pub(crate) is_preserved: bool,
+}
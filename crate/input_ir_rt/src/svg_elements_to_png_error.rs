@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+/// Errors that can occur while rasterising SVG elements to PNG via
+/// `SvgElementsToPngMapper`.
+#[derive(Debug, Error)]
+pub enum SvgElementsToPngError {
+    /// The scale is zero, negative, or not finite.
+    #[error("scale must be a positive number, but was `{scale}`")]
+    ScaleInvalid {
+        /// The scale passed in `PngOptions`.
+        scale: f32,
+    },
+    /// The image would have more pixels than `SvgElementsToPngMapper`
+    /// renders.
+    #[error("image of {width}x{height} pixels is too large to render")]
+    ImageTooLarge {
+        /// Width of the image in pixels.
+        width: u64,
+        /// Height of the image in pixels.
+        height: u64,
+    },
+    /// A font in `PngOptions::fonts` is not a TrueType or OpenType font.
+    #[error("font {font_index} is not a TrueType or OpenType font")]
+    FontInvalid {
+        /// Index of the font in `PngOptions::fonts`.
+        font_index: usize,
+    },
+    /// The rendered image could not be encoded as a PNG.
+    #[error("failed to encode PNG: {0}")]
+    PngEncode(#[source] png::EncodingError),
+}
@@ -0,0 +1,125 @@
+use disposition_svg_model::SvgElements;
+use resvg::tiny_skia::{Pixmap, Transform};
+
+use crate::{
+    svg_elements_to_paint_items_mapper::SvgElementsToPaintItemsMapper,
+    usvg_tree_builder::UsvgTreeBuilder, PngOptions, SvgElementsToPngError,
+};
+
+/// Maximum number of pixels in a rendered image.
+///
+/// Each pixel takes 4 bytes while rendering, and 4 more while encoding, so
+/// this limits memory use to 2 GiB.
+const PIXELS_MAX: u64 = 1 << 28;
+
+/// Meters per inch, to record the DPI in the PNG's `pHYs` chunk.
+const METERS_PER_INCH: f32 = 0.0254;
+
+/// Rasterises `SvgElements` to a PNG image.
+///
+/// The diagram is drawn in its resting state, i.e. as it appears when nothing
+/// is hovered or focused, so to render the diagram with a process step or
+/// thing focused, pass in the SVG elements generated for that focus.
+///
+/// Shapes and text are resolved from the SVG elements' Tailwind classes, and
+/// rendered with [`resvg`] on the CPU. Inline images are not drawn.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgElementsToPngMapper;
+
+impl SvgElementsToPngMapper {
+    /// Returns the PNG file for the SVG elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `svg_elements`: The SVG elements with the computed layout, e.g.
+    ///   generated by `DiagramGenerator::generate`.
+    /// * `png_options`: Scale, DPI, fonts, and color scheme to render with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     DiagramGenerator, EdgeAnimationActive, PngOptions, SvgElementsToPngMapper,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram =
+    ///     serde_saphyr::from_str::<InputDiagram>("{ things: { t_a: {} }, thing_names: { t_a: A } }")
+    ///         .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let png = SvgElementsToPngMapper::map(&diagram_generated.svg_elements, &PngOptions::default())?;
+    ///
+    /// assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    /// # Ok::<(), disposition_input_ir_rt::SvgElementsToPngError>(())
+    /// ```
+    pub fn map(
+        svg_elements: &SvgElements<'_>,
+        png_options: &PngOptions,
+    ) -> Result<Vec<u8>, SvgElementsToPngError> {
+        let PngOptions {
+            scale,
+            dpi,
+            fonts,
+            color_scheme,
+        } = png_options;
+        let scale = *scale;
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(SvgElementsToPngError::ScaleInvalid { scale });
+        }
+
+        let paint_items = SvgElementsToPaintItemsMapper::map(svg_elements, *color_scheme);
+        let dimension = |length: f64| (length.max(1.0) * f64::from(scale)).ceil() as u64;
+        let width = dimension(paint_items.width);
+        let height = dimension(paint_items.height);
+        if width.saturating_mul(height) > PIXELS_MAX {
+            return Err(SvgElementsToPngError::ImageTooLarge { width, height });
+        }
+
+        let fontdb = UsvgTreeBuilder::fontdb_build(fonts)
+            .map_err(|font_index| SvgElementsToPngError::FontInvalid { font_index })?;
+        let tree = UsvgTreeBuilder::build(&paint_items.svg(), &fontdb);
+        let mut pixmap = Pixmap::new(width as u32, height as u32)
+            .expect("Expected image size to be non-zero and within `PIXELS_MAX`.");
+        resvg::render(
+            &tree,
+            Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        Self::png_encode(&pixmap, *dpi).map_err(SvgElementsToPngError::PngEncode)
+    }
+
+    /// Returns the PNG file for the pixmap, with the DPI recorded in its
+    /// `pHYs` chunk.
+    fn png_encode(pixmap: &Pixmap, dpi: f32) -> Result<Vec<u8>, png::EncodingError> {
+        // `tiny-skia` stores premultiplied alpha, and PNG stores straight
+        // alpha.
+        let rgba8 = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect::<Vec<u8>>();
+
+        let mut png = Vec::with_capacity(rgba8.len() / 4 + 1024);
+        let mut encoder = png::Encoder::new(&mut png, pixmap.width(), pixmap.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let pixels_per_meter = (dpi / METERS_PER_INCH).round() as u32;
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: pixels_per_meter,
+            yppu: pixels_per_meter,
+            unit: png::Unit::Meter,
+        }));
+        let mut png_writer = encoder.write_header()?;
+        png_writer.write_image_data(&rgba8)?;
+        png_writer.finish()?;
+
+        Ok(png)
+    }
+}
@@ -0,0 +1,300 @@
+use disposition_ir_model::{
+    node::{NodeHierarchy, NodeId},
+    IrDiagram,
+};
+use disposition_model_common::{entity::EntityType, Map};
+use disposition_svg_model::{SvgEdgeInfo, SvgElements, SvgNodeInfo, SvgProcessInfo, SvgTextSpan};
+use disposition_taffy_model::{TEXT_FONT_SIZE, TEXT_LINE_HEIGHT};
+use kurbo::{BezPath, PathEl};
+
+use crate::StringXmlEscaper;
+
+pub(crate) use self::{
+    connector::Connector,
+    shape::{Shape, ShapeKind},
+    shapes::Shapes,
+    text_box::TextBox,
+};
+
+mod connector;
+mod shape;
+mod shapes;
+mod text_box;
+
+/// Maximum distance between an edge's curve and the line segments it is
+/// flattened to.
+const FLATTEN_TOLERANCE: f64 = 0.5;
+
+/// Distance from a text span's baseline to the top of its line.
+const TEXT_ASCENT: f32 = 0.8 * TEXT_FONT_SIZE;
+
+/// Maps `SvgElements` to positioned shapes, text, and connectors, for
+/// exporting to drawing tools that do not read SVG.
+///
+/// Positions are taken from the computed layout. Process nodes are sized to
+/// contain all of their steps, as steps are laid out below their process.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SvgElementsToShapesMapper;
+
+impl SvgElementsToShapesMapper {
+    /// Returns the shapes for the SVG elements, with nesting taken from the
+    /// IR diagram's `node_hierarchy`.
+    pub(crate) fn map<'d>(
+        ir_diagram: &'d IrDiagram<'_>,
+        svg_elements: &'d SvgElements<'_>,
+    ) -> Shapes<'d> {
+        let svg_node_infos = svg_elements
+            .svg_node_infos
+            .iter()
+            .map(|svg_node_info| (svg_node_info.node_id.as_str(), svg_node_info))
+            .collect::<Map<&str, &SvgNodeInfo<'_>>>();
+
+        let mut shapes = Vec::with_capacity(svg_node_infos.len());
+        Self::shapes_collect(
+            &mut shapes,
+            &svg_node_infos,
+            &svg_elements.svg_process_infos,
+            &ir_diagram.node_hierarchy,
+            &mut Vec::new(),
+        );
+
+        let connectors = svg_elements
+            .svg_edge_infos
+            .iter()
+            .filter_map(|svg_edge_info| Self::connector(ir_diagram, svg_edge_info))
+            .collect::<Vec<Connector<'d>>>();
+
+        let edge_label_text_boxes =
+            svg_elements
+                .edge_label_infos
+                .iter()
+                .flat_map(|edge_label_info| {
+                    [
+                        ("label_from", edge_label_info.from_label.as_ref()),
+                        ("label_to", edge_label_info.to_label.as_ref()),
+                    ]
+                    .into_iter()
+                    .filter_map(move |(suffix, endpoint_info)| {
+                        let endpoint_info = endpoint_info?;
+                        let text = Self::text(&endpoint_info.text_spans);
+                        (!text.is_empty()).then(|| TextBox {
+                            id: format!("{}_{suffix}", edge_label_info.edge_id),
+                            x: Self::coord(endpoint_info.x),
+                            y: Self::coord(endpoint_info.y),
+                            width: Self::coord(endpoint_info.width),
+                            height: Self::coord(endpoint_info.height),
+                            text,
+                        })
+                    })
+                });
+        let edge_description_text_boxes =
+            svg_elements
+                .edge_description_infos
+                .iter()
+                .filter_map(|edge_description_info| {
+                    let text = Self::text(&edge_description_info.text_spans);
+                    (!text.is_empty()).then(|| TextBox {
+                        id: format!("{}_desc", edge_description_info.edge_id),
+                        x: Self::coord(edge_description_info.x),
+                        y: Self::coord(edge_description_info.y),
+                        width: Self::coord(edge_description_info.width),
+                        height: Self::coord(edge_description_info.height),
+                        text,
+                    })
+                });
+        let text_boxes = edge_label_text_boxes
+            .chain(edge_description_text_boxes)
+            .collect::<Vec<TextBox>>();
+
+        Shapes {
+            width: Self::coord(svg_elements.svg_width),
+            height: Self::coord(svg_elements.svg_height),
+            shapes,
+            connectors,
+            text_boxes,
+        }
+    }
+
+    /// Appends a shape for each node in the hierarchy, with each node before
+    /// the nodes nested within it.
+    fn shapes_collect<'d>(
+        shapes: &mut Vec<Shape<'d>>,
+        svg_node_infos: &Map<&str, &'d SvgNodeInfo<'_>>,
+        svg_process_infos: &Map<NodeId<'_>, SvgProcessInfo<'_>>,
+        node_hierarchy: &'d NodeHierarchy<'_>,
+        ancestor_ids: &mut Vec<&'d str>,
+    ) {
+        node_hierarchy.iter().for_each(|(node_id, node_children)| {
+            let node_id = node_id.as_str();
+            let Some(svg_node_info) = svg_node_infos.get(node_id).copied() else {
+                return;
+            };
+            let is_container = !node_children.is_leaf();
+            let height = svg_process_infos
+                .get(&svg_node_info.node_id)
+                .map(|svg_process_info| svg_process_info.height_to_expand_to)
+                .unwrap_or(svg_node_info.height_collapsed);
+            shapes.push(Self::shape(
+                svg_node_info,
+                height,
+                is_container,
+                ancestor_ids.clone(),
+            ));
+
+            if is_container {
+                ancestor_ids.push(node_id);
+                Self::shapes_collect(
+                    shapes,
+                    svg_node_infos,
+                    svg_process_infos,
+                    node_children,
+                    ancestor_ids,
+                );
+                ancestor_ids.pop();
+            }
+        });
+    }
+
+    fn shape<'d>(
+        svg_node_info: &'d SvgNodeInfo<'_>,
+        height: f32,
+        is_container: bool,
+        ancestor_ids: Vec<&'d str>,
+    ) -> Shape<'d> {
+        let SvgNodeInfo {
+            node_id,
+            x,
+            y,
+            width,
+            path_d_collapsed,
+            text_spans,
+            circle,
+            ..
+        } = svg_node_info;
+        let (x, y, width) = (*x, *y, *width);
+
+        let text = Self::text(text_spans);
+        // Text is drawn within a rectangle that has nothing nested within it,
+        // and otherwise at its position in the layout.
+        let text_is_contained = !is_container && circle.is_none();
+        let text_box = if text.is_empty() {
+            None
+        } else if text_is_contained {
+            Some(TextBox {
+                id: format!("{node_id}_text"),
+                x: Self::coord(x),
+                y: Self::coord(y),
+                width: Self::coord(width),
+                height: Self::coord(height),
+                text,
+            })
+        } else {
+            let span_first = &text_spans[0];
+            let span_last = &text_spans[text_spans.len() - 1];
+            let text_x = span_first.x;
+            let text_y = span_first.y - TEXT_ASCENT;
+            Some(TextBox {
+                id: format!("{node_id}_text"),
+                x: Self::coord(x + text_x),
+                y: Self::coord(y + text_y),
+                width: Self::coord((width - text_x).max(0.0)),
+                height: Self::coord(span_last.y - span_first.y + TEXT_LINE_HEIGHT),
+                text,
+            })
+        };
+
+        let (kind, shape_x, shape_y, shape_width, shape_height) = match circle {
+            Some(circle) => (
+                ShapeKind::Circle,
+                x + circle.cx - circle.radius,
+                y + circle.cy - circle.radius,
+                2.0 * circle.radius,
+                2.0 * circle.radius,
+            ),
+            None => (
+                ShapeKind::Rect {
+                    is_rounded: path_d_collapsed.contains('A'),
+                },
+                x,
+                y,
+                width,
+                height,
+            ),
+        };
+
+        Shape {
+            node_id: node_id.as_str(),
+            kind,
+            x: Self::coord(shape_x),
+            y: Self::coord(shape_y),
+            width: Self::coord(shape_width),
+            height: Self::coord(shape_height),
+            text_box,
+            text_is_contained,
+            is_container,
+            ancestor_ids,
+        }
+    }
+
+    /// Returns the connector for the edge, or `None` if its path is empty.
+    fn connector<'d>(
+        ir_diagram: &IrDiagram<'_>,
+        svg_edge_info: &'d SvgEdgeInfo<'_>,
+    ) -> Option<Connector<'d>> {
+        let path = BezPath::from_svg(&svg_edge_info.path_d).ok()?;
+        let mut points = Vec::<(f64, f64)>::new();
+        kurbo::flatten(path, FLATTEN_TOLERANCE, |path_el| match path_el {
+            PathEl::MoveTo(point) | PathEl::LineTo(point) => {
+                let point = (Self::round(point.x), Self::round(point.y));
+                if points.last() != Some(&point) {
+                    points.push(point);
+                }
+            }
+            // `flatten` only emits lines, and edges are not closed.
+            PathEl::QuadTo(..) | PathEl::CurveTo(..) | PathEl::ClosePath => {}
+        });
+        if points.len() < 2 {
+            return None;
+        }
+
+        let is_interaction = ir_diagram
+            .entity_types
+            .get(svg_edge_info.edge_group_id.as_ref())
+            .is_some_and(|entity_types| entity_types.iter().any(EntityType::is_interaction_edge));
+
+        Some(Connector {
+            edge_id: svg_edge_info.edge_id.as_str(),
+            from_node_id: svg_edge_info.from_node_id.as_str(),
+            to_node_id: svg_edge_info.to_node_id.as_str(),
+            points,
+            is_interaction,
+        })
+    }
+
+    /// Returns the plain text of the spans, with spans on the same line joined
+    /// together.
+    fn text(text_spans: &[SvgTextSpan]) -> String {
+        let mut text = String::new();
+        let mut line_y = None;
+        text_spans.iter().for_each(|text_span| {
+            if let Some(line_y) = line_y
+                && line_y != text_span.y
+            {
+                text.push('\n');
+            }
+            line_y = Some(text_span.y);
+            text.push_str(&StringXmlEscaper::unescape(&text_span.text));
+        });
+        text
+    }
+
+    /// Rounds the coordinate to two decimal places, so that exported files do
+    /// not carry floating point noise.
+    pub(crate) fn round(value: f64) -> f64 {
+        (value * 100.0).round() / 100.0
+    }
+
+    fn coord(value: f32) -> f64 {
+        Self::round(f64::from(value))
+    }
+}
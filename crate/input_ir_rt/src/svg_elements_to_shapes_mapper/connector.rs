@@ -0,0 +1,16 @@
+/// Line drawn for an edge, from one node's shape to another's.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Connector<'d> {
+    /// ID of the edge.
+    pub(crate) edge_id: &'d str,
+    /// ID of the node the edge starts from.
+    pub(crate) from_node_id: &'d str,
+    /// ID of the node the edge ends at.
+    pub(crate) to_node_id: &'d str,
+    /// Points along the edge's path, with curves flattened to line segments.
+    ///
+    /// There are always at least two points.
+    pub(crate) points: Vec<(f64, f64)>,
+    /// Whether the edge is an interaction, rather than a dependency.
+    pub(crate) is_interaction: bool,
+}
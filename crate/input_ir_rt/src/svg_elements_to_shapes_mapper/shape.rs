@@ -0,0 +1,39 @@
+use crate::svg_elements_to_shapes_mapper::TextBox;
+
+/// Shape drawn for a node.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Shape<'d> {
+    /// ID of the node.
+    pub(crate) node_id: &'d str,
+    /// Kind of shape.
+    pub(crate) kind: ShapeKind,
+    /// X coordinate of the shape's top left corner.
+    pub(crate) x: f64,
+    /// Y coordinate of the shape's top left corner.
+    pub(crate) y: f64,
+    /// Width of the shape.
+    pub(crate) width: f64,
+    /// Height of the shape.
+    pub(crate) height: f64,
+    /// The node's text, if any.
+    pub(crate) text_box: Option<TextBox>,
+    /// Whether the text is centred within the shape, rather than positioned
+    /// separately.
+    pub(crate) text_is_contained: bool,
+    /// Whether other nodes are nested within this node.
+    pub(crate) is_container: bool,
+    /// IDs of the nodes that this node is nested within, outermost first.
+    pub(crate) ancestor_ids: Vec<&'d str>,
+}
+
+/// Kind of shape drawn for a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ShapeKind {
+    /// Rectangle, which may have rounded corners.
+    Rect {
+        /// Whether the rectangle's corners are rounded.
+        is_rounded: bool,
+    },
+    /// Circle, drawn beside the node's text.
+    Circle,
+}
@@ -0,0 +1,17 @@
+use crate::svg_elements_to_shapes_mapper::{Connector, Shape, TextBox};
+
+/// Shapes, connectors, and free-standing text of a diagram, positioned by its
+/// computed layout.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Shapes<'d> {
+    /// Width of the diagram.
+    pub(crate) width: f64,
+    /// Height of the diagram.
+    pub(crate) height: f64,
+    /// Shape for each node, with each node before the nodes nested within it.
+    pub(crate) shapes: Vec<Shape<'d>>,
+    /// Connector for each edge.
+    pub(crate) connectors: Vec<Connector<'d>>,
+    /// Edge labels and descriptions.
+    pub(crate) text_boxes: Vec<TextBox>,
+}
@@ -0,0 +1,16 @@
+/// Text positioned within a box.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TextBox {
+    /// ID of the element drawn for the text.
+    pub(crate) id: String,
+    /// X coordinate of the box's top left corner.
+    pub(crate) x: f64,
+    /// Y coordinate of the box's top left corner.
+    pub(crate) y: f64,
+    /// Width of the box.
+    pub(crate) width: f64,
+    /// Height of the box.
+    pub(crate) height: f64,
+    /// Plain text, with lines separated by `\n`.
+    pub(crate) text: String,
+}
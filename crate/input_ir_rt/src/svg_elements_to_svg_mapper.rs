@@ -24,11 +24,11 @@ use crate::NOTO_SANS_MONO_TTF;
 pub(crate) const CODE_BG_DESCENT_OFFSET: f32 = 3.0;
 
 /// Corner radius (pixels) of the inline-code background box.
-const CODE_BG_CORNER_RADIUS: f32 = 3.0;
+pub(crate) const CODE_BG_CORNER_RADIUS: f32 = 3.0;
 
 /// Corner radius (pixels) of the background box drawn behind edge label and
 /// description text.
-const EDGE_BG_CORNER_RADIUS: f32 = 4.0;
+pub(crate) const EDGE_BG_CORNER_RADIUS: f32 = 4.0;
 
 /// Width (pixels) of the blockquote's left bar (its thick left border). Kept in
 /// sync with `MdNodeBuilder::BLOCKQUOTE_BAR_WIDTH` so the bar sits in the
@@ -648,7 +648,7 @@ impl SvgElementsToSvgMapper {
     /// exceeds half the width or height. Example: a `75x17` box at `(96, 109)`
     /// with radius `3` yields a `d` starting `M 99 109 H 168 A 3 3 0 0 1 171
     /// 112`.
-    fn code_bg_path_d(x: f32, y: f32, width: f32, height: f32, radius: f32) -> String {
+    pub(crate) fn code_bg_path_d(x: f32, y: f32, width: f32, height: f32, radius: f32) -> String {
         let mut d = String::with_capacity(160);
         Self::rounded_rect_subpath(&mut d, x, y, width, height, radius);
         d
@@ -723,7 +723,7 @@ impl SvgElementsToSvgMapper {
     /// rounded with a small [`BLOCKQUOTE_BORDER_RADIUS`] arc; the inner radius
     /// is reduced by the thin border so the rounded borders keep a uniform
     /// width.
-    fn blockquote_border_path_d(x: f32, y: f32, width: f32, height: f32) -> String {
+    pub(crate) fn blockquote_border_path_d(x: f32, y: f32, width: f32, height: f32) -> String {
         let inner_x = x + BLOCKQUOTE_BORDER_LEFT;
         let inner_y = y + BLOCKQUOTE_BORDER_THIN;
         let inner_w = (width - BLOCKQUOTE_BORDER_LEFT - BLOCKQUOTE_BORDER_THIN).max(0.0);
@@ -0,0 +1,332 @@
+use std::fmt::Write;
+
+use disposition_model_common::Set;
+use disposition_svg_model::SvgElements;
+use disposition_taffy_model::{TEXT_FONT_SIZE, TEXT_LINE_HEIGHT};
+use kurbo::{CubicBez, PathEl, Point, QuadBez};
+
+use crate::{
+    svg_elements_to_paint_items_mapper::{
+        FillRule, PaintColor, PaintItem, PaintPath, PaintText, SvgElementsToPaintItemsMapper,
+    },
+    ColorScheme,
+};
+
+/// Points per SVG pixel, as there are 72 points and 96 pixels per inch.
+const POINTS_PER_PIXEL: f64 = 0.75;
+
+/// Prefix of the names of colors defined in the picture.
+const COLOR_NAME_PREFIX: &str = "dsp";
+
+/// Maps `SvgElements` to a TikZ picture, so that diagrams can be included in
+/// LaTeX documents.
+///
+/// The picture is a `tikzpicture` environment to `\input` into a document
+/// that loads the `tikz` package, so text is typeset with the document's
+/// typewriter font, at the size the diagram was laid out with.
+///
+/// * Node shapes, edges, and arrow heads become paths, with arcs converted to
+///   curves.
+/// * Text spans become nodes anchored at their baseline.
+/// * Colors are resolved from the theme for the color scheme.
+///
+/// The diagram is drawn in its resting state, so interaction edges are hidden
+/// and processes are collapsed. Struck through text is drawn without the line,
+/// as that needs another package, and inline images are not drawn.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgElementsToTikzMapper;
+
+impl SvgElementsToTikzMapper {
+    /// Returns the TikZ picture for the SVG elements.
+    ///
+    /// # Parameters
+    ///
+    /// * `svg_elements`: The SVG elements with the computed layout.
+    /// * `color_scheme`: Whether to use the light or dark colors of the theme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{
+    /// #     ColorScheme, DiagramGenerator, EdgeAnimationActive, SvgElementsToTikzMapper,
+    /// # };
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+    ///     "things: { t_a: {}, t_b: {} }\n\
+    ///      thing_names: { t_a: A, t_b: B }\n\
+    ///      thing_dependencies: { edge_ab: { kind: sequence, things: [t_a, t_b] } }",
+    /// )
+    /// .unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let tikz = SvgElementsToTikzMapper::map(&diagram_generated.svg_elements, ColorScheme::Light);
+    ///
+    /// assert!(tikz.contains("\\begin{tikzpicture}"));
+    /// assert!(tikz.contains("% edge_ab__0"));
+    /// assert!(tikz.contains("{A};"));
+    /// ```
+    pub fn map(svg_elements: &SvgElements<'_>, color_scheme: ColorScheme) -> String {
+        let paint_items = SvgElementsToPaintItemsMapper::map(svg_elements, color_scheme);
+
+        let mut tikz_body = TikzBody {
+            colors: Set::new(),
+            commands: String::new(),
+        };
+        paint_items
+            .items
+            .iter()
+            .for_each(|paint_item| match paint_item {
+                PaintItem::Entity(id) => {
+                    let _ = writeln!(tikz_body.commands, "  % {id}");
+                }
+                PaintItem::Path(paint_path) => tikz_body.path_write(paint_path),
+                PaintItem::Text(paint_text) => tikz_body.text_write(paint_text),
+            });
+        let TikzBody { colors, commands } = tikz_body;
+
+        let font_size = Self::points(f64::from(TEXT_FONT_SIZE));
+        let line_height = Self::points(f64::from(TEXT_LINE_HEIGHT));
+        let mut tikz = String::new();
+        tikz.push_str("% Generated by disposition. Requires `\\usepackage{tikz}`.\n");
+        let _ = writeln!(
+            tikz,
+            "\\begin{{tikzpicture}}[x={POINTS_PER_PIXEL}pt, y=-{POINTS_PER_PIXEL}pt, \
+            font=\\ttfamily\\fontsize{{{font_size}pt}}{{{line_height}pt}}\\selectfont]"
+        );
+        colors.iter().for_each(|color_hex| {
+            let _ = writeln!(
+                tikz,
+                "  \\definecolor{{{COLOR_NAME_PREFIX}{color_hex}}}{{HTML}}{{{color_hex}}}"
+            );
+        });
+        let _ = writeln!(
+            tikz,
+            "  \\useasboundingbox (0,0) rectangle ({},{});",
+            Self::number(paint_items.width),
+            Self::number(paint_items.height)
+        );
+        tikz.push_str(&commands);
+        tikz.push_str("\\end{tikzpicture}\n");
+        tikz
+    }
+
+    /// Returns the length in pixels as points.
+    fn points(pixels: f64) -> String {
+        Self::number(pixels * POINTS_PER_PIXEL)
+    }
+
+    /// Returns the number rounded to two decimal places, without trailing
+    /// zeros.
+    fn number(value: f64) -> String {
+        let value = (value * 100.0).round() / 100.0;
+        // Avoid writing `-0`.
+        let value = if value == 0.0 { 0.0 } else { value };
+        format!("{value}")
+    }
+
+    /// Returns the text with LaTeX's special characters escaped.
+    ///
+    /// When whitespace is preserved, leading, trailing, and consecutive spaces
+    /// are made non-breaking, so that LaTeX does not collapse them.
+    fn text_escape(text: &str, is_preserved: bool) -> String {
+        let chars = text.chars().collect::<Vec<char>>();
+        let is_space = |index: Option<usize>| {
+            index
+                .and_then(|index| chars.get(index))
+                .is_none_or(|c| *c == ' ')
+        };
+        chars
+            .iter()
+            .enumerate()
+            .fold(String::new(), |mut escaped, (index, c)| {
+                match c {
+                    '\\' => escaped.push_str("\\textbackslash{}"),
+                    '~' => escaped.push_str("\\textasciitilde{}"),
+                    '^' => escaped.push_str("\\textasciicircum{}"),
+                    '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                        escaped.push('\\');
+                        escaped.push(*c);
+                    }
+                    ' ' if is_preserved
+                        && (is_space(index.checked_sub(1)) || is_space(Some(index + 1))) =>
+                    {
+                        escaped.push('~')
+                    }
+                    _ => escaped.push(*c),
+                }
+                escaped
+            })
+    }
+}
+
+/// Commands of the picture, and the colors that they use.
+struct TikzBody {
+    /// Hex codes of the colors used, e.g. `"0369A1"`.
+    colors: Set<String>,
+    /// Commands that draw the diagram.
+    commands: String,
+}
+
+impl TikzBody {
+    /// Writes a `\path` that fills and/or draws the shape.
+    fn path_write(&mut self, paint_path: &PaintPath) {
+        let PaintPath {
+            path,
+            fill,
+            fill_rule,
+            stroke,
+        } = paint_path;
+
+        let mut options = Vec::new();
+        if let Some(fill) = fill {
+            options.push(format!("fill={}", self.color_name(*fill)));
+            if fill.alpha < 1.0 {
+                options.push(format!(
+                    "fill opacity={}",
+                    SvgElementsToTikzMapper::number(f64::from(fill.alpha))
+                ));
+            }
+            if *fill_rule == FillRule::EvenOdd {
+                options.push(String::from("even odd rule"));
+            }
+        }
+        if let Some(stroke) = stroke {
+            options.push(format!("draw={}", self.color_name(stroke.color)));
+            if stroke.color.alpha < 1.0 {
+                options.push(format!(
+                    "draw opacity={}",
+                    SvgElementsToTikzMapper::number(f64::from(stroke.color.alpha))
+                ));
+            }
+            options.push(format!(
+                "line width={}pt",
+                SvgElementsToTikzMapper::points(stroke.width)
+            ));
+            if !stroke.dasharray.is_empty() {
+                let dash_pattern = stroke
+                    .dasharray
+                    .iter()
+                    .enumerate()
+                    .map(|(index, length)| {
+                        let on_off = if index % 2 == 0 { "on" } else { "off" };
+                        format!("{on_off} {}pt", SvgElementsToTikzMapper::points(*length))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                options.push(format!("dash pattern={dash_pattern}"));
+            }
+        }
+
+        let _ = write!(self.commands, "  \\path[{}]", options.join(", "));
+        // TikZ curves are cubic, so quadratic segments are raised to cubics,
+        // which needs the point that each segment starts from.
+        let mut point_current = Point::ZERO;
+        let mut point_subpath_start = Point::ZERO;
+        path.elements().iter().for_each(|path_el| {
+            let _ = match *path_el {
+                PathEl::MoveTo(point) => {
+                    point_current = point;
+                    point_subpath_start = point;
+                    write!(self.commands, " {}", Self::point(point))
+                }
+                PathEl::LineTo(point) => {
+                    point_current = point;
+                    write!(self.commands, " -- {}", Self::point(point))
+                }
+                PathEl::QuadTo(control, point) => {
+                    let CubicBez { p1, p2, p3, .. } =
+                        QuadBez::new(point_current, control, point).raise();
+                    point_current = point;
+                    self.curve_write(p1, p2, p3)
+                }
+                PathEl::CurveTo(control_1, control_2, point) => {
+                    point_current = point;
+                    self.curve_write(control_1, control_2, point)
+                }
+                PathEl::ClosePath => {
+                    point_current = point_subpath_start;
+                    write!(self.commands, " -- cycle")
+                }
+            };
+        });
+        self.commands.push_str(";\n");
+    }
+
+    /// Writes a cubic Bézier curve to `point`.
+    fn curve_write(
+        &mut self,
+        control_1: Point,
+        control_2: Point,
+        point: Point,
+    ) -> std::fmt::Result {
+        write!(
+            self.commands,
+            " .. controls {} and {} .. {}",
+            Self::point(control_1),
+            Self::point(control_2),
+            Self::point(point)
+        )
+    }
+
+    /// Writes a `\node` with the text, anchored at its baseline.
+    fn text_write(&mut self, paint_text: &PaintText) {
+        let PaintText {
+            point,
+            text,
+            fill,
+            bold,
+            italic,
+            underline,
+            line_through: _,
+            is_preserved,
+        } = paint_text;
+
+        let mut text = SvgElementsToTikzMapper::text_escape(text, *is_preserved);
+        if *underline {
+            text = format!("\\underline{{{text}}}");
+        }
+        if *italic {
+            text = format!("\\textit{{{text}}}");
+        }
+        if *bold {
+            text = format!("\\textbf{{{text}}}");
+        }
+
+        let mut options = vec![
+            String::from("anchor=base west"),
+            String::from("inner sep=0pt"),
+            format!("text={}", self.color_name(*fill)),
+        ];
+        if fill.alpha < 1.0 {
+            options.push(format!(
+                "text opacity={}",
+                SvgElementsToTikzMapper::number(f64::from(fill.alpha))
+            ));
+        }
+        let _ = writeln!(
+            self.commands,
+            "  \\node[{}] at {} {{{text}}};",
+            options.join(", "),
+            Self::point(*point)
+        );
+    }
+
+    /// Returns the name of the color, defining it if it is not yet used.
+    fn color_name(&mut self, color: PaintColor) -> String {
+        let color_hex = color.hex();
+        let color_name = format!("{COLOR_NAME_PREFIX}{color_hex}");
+        self.colors.insert(color_hex);
+        color_name
+    }
+
+    fn point(point: Point) -> String {
+        format!(
+            "({},{})",
+            SvgElementsToTikzMapper::number(point.x),
+            SvgElementsToTikzMapper::number(point.y)
+        )
+    }
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+/// Errors that can occur while extracting the input diagram from an SVG via
+/// `SvgSourceExtractor`.
+#[derive(Debug, Error)]
+pub enum SvgSourceExtractError {
+    /// The SVG has no `<source>` element.
+    ///
+    /// The SVG may not have been generated by `disposition`, or the element
+    /// may have been removed by an SVG optimizer.
+    #[error("no `<source>` element found in the SVG")]
+    SourceNotFound,
+    /// The `<source>` element is not closed, or contains an element other than
+    /// CDATA sections and text.
+    #[error("`<source>` element is malformed: {detail}")]
+    SourceMalformed {
+        /// Description of what is malformed.
+        detail: &'static str,
+    },
+    /// The YAML in the `<source>` element is not a valid input diagram.
+    #[error("yaml deserialize: {0}")]
+    YamlDeserialize(#[from] serde_saphyr::Error),
+}
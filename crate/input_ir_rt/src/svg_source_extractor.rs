@@ -0,0 +1,114 @@
+use disposition_input_model::InputDiagram;
+
+use crate::{StringXmlEscaper, SvgSourceExtractError};
+
+/// Extracts the input diagram embedded in an SVG's `<source>` element.
+///
+/// This is the reverse of the embedding done by
+/// `SvgElementsToSvgMapper::map_with_input`, so a diagram can be regenerated or
+/// edited when only the generated SVG is available.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgSourceExtractor;
+
+impl SvgSourceExtractor {
+    /// Returns the input diagram embedded in the SVG's `<source>` element.
+    ///
+    /// # Parameters
+    ///
+    /// * `svg`: The SVG markup, e.g. generated by `DiagramGenerator::generate`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive, SvgSourceExtractor};
+    /// # use disposition_input_model::InputDiagram;
+    /// #
+    /// let input_diagram =
+    ///     serde_saphyr::from_str::<InputDiagram>("thing_names: { t_a: A }").unwrap();
+    /// let diagram_generated =
+    ///     DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+    ///         .unwrap();
+    ///
+    /// let input_diagram_extracted = SvgSourceExtractor::extract(&diagram_generated.svg)?;
+    ///
+    /// assert_eq!(input_diagram, input_diagram_extracted);
+    /// # Ok::<(), disposition_input_ir_rt::SvgSourceExtractError>(())
+    /// ```
+    pub fn extract(svg: &str) -> Result<InputDiagram<'static>, SvgSourceExtractError> {
+        let source_yaml = Self::source_yaml_extract(svg)?;
+        let input_diagram = serde_saphyr::from_str::<InputDiagram<'static>>(&source_yaml)?;
+        Ok(input_diagram)
+    }
+
+    /// Returns the YAML embedded in the SVG's `<source>` element.
+    ///
+    /// CDATA sections that were split to escape `]]>` are re-joined, and any
+    /// text outside CDATA sections is unescaped, e.g. if an SVG optimizer
+    /// converted the CDATA sections to escaped text.
+    ///
+    /// # Parameters
+    ///
+    /// * `svg`: The SVG markup, e.g. generated by `DiagramGenerator::generate`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_ir_rt::SvgSourceExtractor;
+    /// #
+    /// let svg = "<svg><source><![CDATA[things:\n  t_a: ]]]]><![CDATA[>\n]]></source></svg>";
+    ///
+    /// let source_yaml = SvgSourceExtractor::source_yaml_extract(svg)?;
+    ///
+    /// assert_eq!("things:\n  t_a: ]]>\n", source_yaml);
+    /// # Ok::<(), disposition_input_ir_rt::SvgSourceExtractError>(())
+    /// ```
+    pub fn source_yaml_extract(svg: &str) -> Result<String, SvgSourceExtractError> {
+        // Skip the XML declaration and comment before the `<svg>` element.
+        let svg_element = svg
+            .find("<svg")
+            .map(|svg_start| &svg[svg_start..])
+            .ok_or(SvgSourceExtractError::SourceNotFound)?;
+        let source_start_tag = svg_element
+            .match_indices("<source")
+            .map(|(source_start, _)| &svg_element[source_start + "<source".len()..])
+            .find(|after_name| after_name.starts_with(['>', ' ', '\t', '\r', '\n']))
+            .ok_or(SvgSourceExtractError::SourceNotFound)?;
+        let mut rest = source_start_tag
+            .find('>')
+            .map(|tag_end| &source_start_tag[tag_end + 1..])
+            .ok_or(SvgSourceExtractError::SourceMalformed {
+                detail: "`<source>` start tag is not closed",
+            })?;
+
+        let mut source_yaml = String::new();
+        loop {
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let cdata_end =
+                    cdata
+                        .find("]]>")
+                        .ok_or(SvgSourceExtractError::SourceMalformed {
+                            detail: "CDATA section is not closed",
+                        })?;
+                source_yaml.push_str(&cdata[..cdata_end]);
+                rest = &cdata[cdata_end + "]]>".len()..];
+            } else if rest.starts_with("</source>") {
+                break;
+            } else {
+                let text_end = rest
+                    .find('<')
+                    .ok_or(SvgSourceExtractError::SourceMalformed {
+                        detail: "`<source>` element is not closed",
+                    })?;
+                if text_end == 0 {
+                    return Err(SvgSourceExtractError::SourceMalformed {
+                        detail: "`<source>` element contains an element",
+                    });
+                }
+                source_yaml.push_str(&StringXmlEscaper::unescape(&rest[..text_end]));
+                rest = &rest[text_end..];
+            }
+        }
+
+        Ok(source_yaml)
+    }
+}
@@ -0,0 +1,333 @@
+use disposition_input_ir_model::{issue::ModelToIrIssue, ThemeValueSource};
+use disposition_input_model::{
+    theme::{CssClassPartials, ThemeAttr, ThemeStyles},
+    InputDiagram,
+};
+
+use crate::{
+    input_to_ir_diagram_mapper::{
+        tailwind_class_state::ShadeComputer, tailwind_colors::tailwind_color_lookup,
+    },
+    InputDiagramThemeSources, TailwindColorShade,
+};
+
+/// Validates the `ThemeAttr` values in an input diagram's themes.
+///
+/// Invalid values are otherwise silently ignored when the diagram is styled,
+/// e.g. a `fill_shade_normal: "1000"` is dropped because it is not a tailwind
+/// shade, and a `padding: "4px"` is dropped because it is not a number.
+#[derive(Clone, Copy, Debug)]
+pub struct ThemeAttrValidator;
+
+impl ThemeAttrValidator {
+    /// Returns a [`ModelToIrIssue::ThemeAttrValueInvalid`] for each theme
+    /// attribute value that cannot be used.
+    ///
+    /// The following theme fields are validated:
+    ///
+    /// * `theme_default.style_aliases`
+    /// * `theme_default.base_styles`
+    /// * `theme_default.process_step_selected_styles`
+    /// * `theme_types_styles`
+    /// * `theme_thing_dependencies_styles`
+    /// * `theme_tag_things_focus`
+    ///
+    /// # Parameters
+    ///
+    /// * `input_diagram_merged`: The user's input diagram merged over the base
+    ///   diagram.
+    /// * `theme_sources`: The base and user's input diagram, used to determine
+    ///   the `ThemeValueSource` of each invalid value.
+    pub fn validate(
+        input_diagram_merged: &InputDiagram<'static>,
+        theme_sources: &InputDiagramThemeSources<'_>,
+    ) -> Vec<ModelToIrIssue> {
+        let mut issues = Vec::new();
+        let InputDiagram {
+            theme_default,
+            theme_types_styles,
+            theme_thing_dependencies_styles,
+            theme_tag_things_focus,
+            ..
+        } = input_diagram_merged;
+
+        theme_default
+            .style_aliases
+            .iter()
+            .for_each(|(style_alias, css_class_partials)| {
+                let entity = style_alias.as_str();
+                Self::css_class_partials_validate(
+                    &mut issues,
+                    &format!("theme_default.style_aliases.{entity}"),
+                    entity,
+                    css_class_partials,
+                    theme_sources.style_alias_source(entity),
+                );
+            });
+        Self::theme_styles_validate(
+            &mut issues,
+            "theme_default.base_styles",
+            &theme_default.base_styles,
+            |entry_key| theme_sources.base_styles_entry_source(entry_key),
+        );
+        Self::theme_styles_validate(
+            &mut issues,
+            "theme_default.process_step_selected_styles",
+            &theme_default.process_step_selected_styles,
+            |entry_key| theme_sources.process_step_selected_styles_entry_source(entry_key),
+        );
+        theme_types_styles
+            .iter()
+            .for_each(|(entity_type_id, theme_styles)| {
+                let type_key = entity_type_id.as_str();
+                Self::theme_styles_validate(
+                    &mut issues,
+                    &format!("theme_types_styles.{type_key}"),
+                    theme_styles,
+                    |entry_key| theme_sources.types_styles_entry_source(type_key, entry_key),
+                );
+            });
+        Self::theme_styles_validate(
+            &mut issues,
+            "theme_thing_dependencies_styles.things_included_styles",
+            &theme_thing_dependencies_styles.things_included_styles,
+            |entry_key| theme_sources.dependencies_included_entry_source(entry_key),
+        );
+        Self::theme_styles_validate(
+            &mut issues,
+            "theme_thing_dependencies_styles.things_excluded_styles",
+            &theme_thing_dependencies_styles.things_excluded_styles,
+            |entry_key| theme_sources.dependencies_excluded_entry_source(entry_key),
+        );
+        theme_tag_things_focus
+            .iter()
+            .for_each(|(tag_id_or_defaults, theme_styles)| {
+                let tag_key = tag_id_or_defaults.as_str();
+                Self::theme_styles_validate(
+                    &mut issues,
+                    &format!("theme_tag_things_focus.{tag_key}"),
+                    theme_styles,
+                    |entry_key| theme_sources.tag_focus_entry_source(tag_key, entry_key),
+                );
+            });
+
+        issues
+    }
+
+    /// Validates each entry of a `ThemeStyles` map.
+    ///
+    /// `value_source_fn` returns the `ThemeValueSource` for an entry key.
+    fn theme_styles_validate<F>(
+        issues: &mut Vec<ModelToIrIssue>,
+        field_path_prefix: &str,
+        theme_styles: &ThemeStyles<'static>,
+        value_source_fn: F,
+    ) where
+        F: Fn(&str) -> ThemeValueSource,
+    {
+        theme_styles
+            .iter()
+            .for_each(|(id_or_defaults, css_class_partials)| {
+                let entity = id_or_defaults.as_str();
+                Self::css_class_partials_validate(
+                    issues,
+                    &format!("{field_path_prefix}.{entity}"),
+                    entity,
+                    css_class_partials,
+                    value_source_fn(entity),
+                );
+            });
+    }
+
+    fn css_class_partials_validate(
+        issues: &mut Vec<ModelToIrIssue>,
+        field_path_prefix: &str,
+        entity: &str,
+        css_class_partials: &CssClassPartials<'static>,
+        value_source: ThemeValueSource,
+    ) {
+        let issues_invalid = css_class_partials.iter().filter_map(|(theme_attr, value)| {
+            let theme_attr_values = ThemeAttrValues::from(*theme_attr);
+            if theme_attr_values.is_valid(*theme_attr, value) {
+                None
+            } else {
+                Some(ModelToIrIssue::ThemeAttrValueInvalid {
+                    field_path: format!("{field_path_prefix}.{theme_attr}"),
+                    entity: entity.to_owned(),
+                    theme_attr: *theme_attr,
+                    value: value.clone(),
+                    expected: theme_attr_values.expected().to_owned(),
+                    value_source,
+                    span: None,
+                })
+            }
+        });
+        issues.extend(issues_invalid);
+    }
+}
+
+/// The values accepted by a `ThemeAttr`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ThemeAttrValues {
+    /// Tailwind colour names, e.g. `"slate"`.
+    Color,
+    /// Tailwind colour shades, e.g. `"300"`.
+    Shade,
+    /// Line style keywords, or a `dasharray:` value, e.g. `"dashed"`.
+    LineStyle,
+    /// Visibility keywords, or `"hidden"`, e.g. `"invisible"`.
+    Visibility,
+    /// Animation keywords, or an arbitrary value, e.g. `"pulse"`.
+    Animate,
+    /// Numbers that are used to compute the layout, e.g. `"4.0"`.
+    Number,
+    /// Numbers that are used as a tailwind class value, or an arbitrary value,
+    /// e.g. `"2"`.
+    NumberOrArbitrary,
+    /// Values that are copied as-is, so any value is accepted.
+    Freeform,
+}
+
+impl ThemeAttrValues {
+    /// Returns whether `value` is accepted for `theme_attr`.
+    ///
+    /// Colours, shades, and line styles are checked with the same functions
+    /// that the `ThemeAttrResolver` and `TailwindClassState` use to parse
+    /// them.
+    fn is_valid(self, theme_attr: ThemeAttr, value: &str) -> bool {
+        let is_keyword = || theme_attr.value_suggestions().contains(&value);
+        match self {
+            // Every tailwind colour has every shade, so any shade can be used
+            // to look up whether the colour exists.
+            ThemeAttrValues::Color => {
+                tailwind_color_lookup(value, TailwindColorShade::_500.as_str()).is_some()
+            }
+            // The visibility value is written as-is as a class, so tailwind's
+            // `hidden` (`display: none`) class also works.
+            ThemeAttrValues::Visibility => is_keyword() || value == "hidden",
+            ThemeAttrValues::Shade => value.parse::<TailwindColorShade>().is_ok(),
+            // `none` has no dasharray, but is written as-is as the node's
+            // `outline-none` class.
+            ThemeAttrValues::LineStyle => {
+                value == "none" || ShadeComputer::stroke_style_to_dasharray(value).is_some()
+            }
+            ThemeAttrValues::Animate => is_keyword() || Self::is_arbitrary(value),
+            ThemeAttrValues::Number => value.parse::<f32>().is_ok(),
+            ThemeAttrValues::NumberOrArbitrary => {
+                value.parse::<f32>().is_ok() || Self::is_arbitrary(value)
+            }
+            ThemeAttrValues::Freeform => true,
+        }
+    }
+
+    /// Returns a description of the accepted values, used in the issue's help
+    /// text.
+    fn expected(self) -> &'static str {
+        match self {
+            ThemeAttrValues::Color => "a tailwind colour name, e.g. `slate`",
+            ThemeAttrValues::Shade => {
+                "a tailwind colour shade: `50`, `100`, `200`, .., `900`, or `950`"
+            }
+            ThemeAttrValues::LineStyle => {
+                "one of `none`, `solid`, `dashed`, `dotted`, or `dasharray:<dasharray>`"
+            }
+            ThemeAttrValues::Visibility => "one of `visible`, `invisible`, `collapse`, or `hidden`",
+            ThemeAttrValues::Animate => {
+                "one of `none`, `spin`, `ping`, `pulse`, `bounce`, or an arbitrary value such as \
+                 `[stroke-dashoffset-move_2s_linear_infinite]`"
+            }
+            ThemeAttrValues::Number => "a number, e.g. `4.0`",
+            ThemeAttrValues::NumberOrArbitrary => {
+                "a number, e.g. `2`, or an arbitrary value such as `[3px]`"
+            }
+            ThemeAttrValues::Freeform => "any value",
+        }
+    }
+
+    /// Returns whether the value is a tailwind arbitrary value, e.g. `[3px]`.
+    fn is_arbitrary(value: &str) -> bool {
+        value.len() > 2 && value.starts_with('[') && value.ends_with(']')
+    }
+}
+
+impl From<ThemeAttr> for ThemeAttrValues {
+    fn from(theme_attr: ThemeAttr) -> Self {
+        match theme_attr {
+            ThemeAttr::FillColor
+            | ThemeAttr::FillColorNormal
+            | ThemeAttr::FillColorFocus
+            | ThemeAttr::FillColorHover
+            | ThemeAttr::FillColorActive
+            | ThemeAttr::OutlineColor
+            | ThemeAttr::OutlineColorNormal
+            | ThemeAttr::OutlineColorFocus
+            | ThemeAttr::OutlineColorHover
+            | ThemeAttr::OutlineColorActive
+            | ThemeAttr::ShapeColor
+            | ThemeAttr::StrokeColor
+            | ThemeAttr::StrokeColorNormal
+            | ThemeAttr::StrokeColorFocus
+            | ThemeAttr::StrokeColorHover
+            | ThemeAttr::StrokeColorActive
+            | ThemeAttr::TextColor => ThemeAttrValues::Color,
+
+            ThemeAttr::FillShade
+            | ThemeAttr::FillShadeNormal
+            | ThemeAttr::FillShadeFocus
+            | ThemeAttr::FillShadeHover
+            | ThemeAttr::FillShadeActive
+            | ThemeAttr::OutlineShade
+            | ThemeAttr::OutlineShadeNormal
+            | ThemeAttr::OutlineShadeFocus
+            | ThemeAttr::OutlineShadeHover
+            | ThemeAttr::OutlineShadeActive
+            | ThemeAttr::StrokeShade
+            | ThemeAttr::StrokeShadeNormal
+            | ThemeAttr::StrokeShadeFocus
+            | ThemeAttr::StrokeShadeHover
+            | ThemeAttr::StrokeShadeActive
+            | ThemeAttr::TextShade => ThemeAttrValues::Shade,
+
+            ThemeAttr::OutlineStyle
+            | ThemeAttr::OutlineStyleNormal
+            | ThemeAttr::OutlineStyleFocus
+            | ThemeAttr::OutlineStyleHover
+            | ThemeAttr::OutlineStyleActive
+            | ThemeAttr::StrokeStyle
+            | ThemeAttr::StrokeStyleNormal
+            | ThemeAttr::StrokeStyleFocus
+            | ThemeAttr::StrokeStyleHover
+            | ThemeAttr::StrokeStyleActive => ThemeAttrValues::LineStyle,
+
+            ThemeAttr::Visibility => ThemeAttrValues::Visibility,
+            ThemeAttr::Animate => ThemeAttrValues::Animate,
+
+            ThemeAttr::CircleRadius
+            | ThemeAttr::Gap
+            | ThemeAttr::Padding
+            | ThemeAttr::PaddingX
+            | ThemeAttr::PaddingY
+            | ThemeAttr::PaddingLeft
+            | ThemeAttr::PaddingRight
+            | ThemeAttr::PaddingTop
+            | ThemeAttr::PaddingBottom
+            | ThemeAttr::Margin
+            | ThemeAttr::MarginX
+            | ThemeAttr::MarginY
+            | ThemeAttr::MarginLeft
+            | ThemeAttr::MarginRight
+            | ThemeAttr::MarginTop
+            | ThemeAttr::MarginBottom
+            | ThemeAttr::RadiusTopLeft
+            | ThemeAttr::RadiusTopRight
+            | ThemeAttr::RadiusBottomLeft
+            | ThemeAttr::RadiusBottomRight => ThemeAttrValues::Number,
+
+            ThemeAttr::Opacity | ThemeAttr::OutlineWidth | ThemeAttr::StrokeWidth => {
+                ThemeAttrValues::NumberOrArbitrary
+            }
+
+            ThemeAttr::Cursor | ThemeAttr::Extra => ThemeAttrValues::Freeform,
+        }
+    }
+}
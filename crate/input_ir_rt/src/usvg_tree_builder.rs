@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use disposition_taffy_model::TEXT_FONT_SIZE;
+use usvg::{fontdb::Database, PostProcessingSteps, TreeParsing, TreePostProc};
+
+/// Builds `usvg` trees with text converted to paths, for rendering diagrams
+/// without a browser.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct UsvgTreeBuilder;
+
+impl UsvgTreeBuilder {
+    /// Returns a font database with the fonts loaded.
+    ///
+    /// Text is drawn with the family of the first font, and characters that it
+    /// does not have are drawn with the other fonts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the index of the first font that has no faces, e.g. when it is
+    /// not a TrueType or OpenType font.
+    pub(crate) fn fontdb_build(fonts: &[Cow<'static, [u8]>]) -> Result<Database, usize> {
+        let mut fontdb = Database::new();
+        fonts
+            .iter()
+            .enumerate()
+            .try_for_each(|(font_index, font_data)| {
+                let face_count = fontdb.len();
+                fontdb.load_font_data(font_data.to_vec());
+                if fontdb.len() == face_count {
+                    Err(font_index)
+                } else {
+                    Ok(())
+                }
+            })?;
+        Ok(fontdb)
+    }
+
+    /// Returns the tree for the SVG, with text converted to paths.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the SVG cannot be parsed, as it is expected to be written by
+    /// `PaintItems::svg`.
+    pub(crate) fn build(svg: &str, fontdb: &Database) -> usvg::Tree {
+        let font_family = fontdb
+            .faces()
+            .next()
+            .and_then(|face_info| face_info.families.first())
+            .map(|(family, _language)| family.clone())
+            .unwrap_or_else(|| String::from("monospace"));
+        let options = usvg::Options {
+            font_family,
+            font_size: TEXT_FONT_SIZE,
+            ..Default::default()
+        };
+
+        let mut tree = usvg::Tree::from_str(svg, &options)
+            .expect("Expected SVG written from paint items to be valid.");
+        tree.postprocess(
+            PostProcessingSteps {
+                convert_text_into_paths: true,
+            },
+            fontdb,
+        );
+        tree
+    }
+}
@@ -0,0 +1,35 @@
+use std::ops::Range;
+
+/// Byte ranges of a map entry or sequence item in the input YAML.
+///
+/// # Examples
+///
+/// For the following YAML:
+///
+/// ```yaml
+/// thing_names:
+///   t_a: "A"
+/// ```
+///
+/// the `FieldSpan` for `thing_names.t_a` has a `key` covering `t_a`, and a
+/// `value` covering `"A"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldSpan {
+    /// Byte range of the map key, or `None` for sequence items.
+    pub key: Option<Range<usize>>,
+    /// Byte range of the value.
+    ///
+    /// For maps and sequences, this covers the whole collection.
+    pub value: Range<usize>,
+}
+
+impl FieldSpan {
+    /// Returns the byte range of the key if this is a map entry, otherwise
+    /// the byte range of the value.
+    ///
+    /// This is the range to point at when the field itself is wrong, e.g. an
+    /// unknown ID used as a map key or listed in a sequence.
+    pub fn key_or_value(&self) -> Range<usize> {
+        self.key.clone().unwrap_or_else(|| self.value.clone())
+    }
+}
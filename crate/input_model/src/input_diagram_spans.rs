@@ -0,0 +1,293 @@
+use std::ops::{Deref, DerefMut, Range};
+
+use disposition_model_common::Map;
+use granit_parser::{Event, Marker, Parser, ScalarStyle};
+
+use crate::FieldSpan;
+
+/// Source locations of the fields of an `InputDiagram`, keyed by field path.
+///
+/// This is a side table captured from the input YAML, so that issues found
+/// in the deserialized `InputDiagram` can point back to the text the user
+/// wrote.
+///
+/// Field paths use `.` to separate map keys and `[n]` for sequence indices,
+/// e.g. `thing_dependencies.edge_a__b.things[1]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use disposition_input_model::InputDiagramSpans;
+///
+/// let yaml = "\
+/// thing_names:
+///   t_a: A
+/// tag_things:
+///   tag_a: [t_a, t_b]
+/// ";
+/// let input_diagram_spans = InputDiagramSpans::from_yaml(yaml);
+///
+/// let field_span = input_diagram_spans.get("thing_names.t_a").unwrap();
+/// assert_eq!(Some("t_a"), field_span.key.clone().map(|key| &yaml[key]));
+/// assert_eq!("A", &yaml[field_span.value.clone()]);
+///
+/// let field_span = input_diagram_spans.get("tag_things.tag_a[1]").unwrap();
+/// assert_eq!("t_b", &yaml[field_span.key_or_value()]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputDiagramSpans(Map<String, FieldSpan>);
+
+impl InputDiagramSpans {
+    /// Returns a new `InputDiagramSpans` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `InputDiagramSpans` map with the given preallocated
+    /// capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Map::with_capacity(capacity))
+    }
+
+    /// Returns the underlying map.
+    pub fn into_inner(self) -> Map<String, FieldSpan> {
+        self.0
+    }
+
+    /// Returns true if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the spans of every map entry and sequence item in the first
+    /// document of the given YAML.
+    ///
+    /// This is best effort: if the YAML is malformed, the spans of fields up
+    /// to the error are returned. Entries with non-scalar keys are skipped.
+    ///
+    /// Entries merged in with a `<<` merge key are skipped as well, as they
+    /// are written under the anchor rather than the mapping they are merged
+    /// into. Their spans are recorded at the anchor's own field path.
+    pub fn from_yaml(yaml: &str) -> Self {
+        let mut input_diagram_spans = Self::new();
+        let mut frames = Vec::<Frame>::new();
+
+        let events = Parser::new_from_str(yaml).map_while(Result::ok);
+        for (event, span) in events {
+            match event {
+                Event::Scalar(name, scalar_style, ..) => {
+                    let range = Self::range(yaml, span.start, span.end);
+                    match Self::node_position(&mut frames) {
+                        NodePosition::Key => {
+                            let is_merge_key = scalar_style == ScalarStyle::Plain && name == "<<";
+                            let name = (!is_merge_key).then(|| name.into_owned());
+                            Self::key_set(&mut frames, name, range)
+                        }
+                        NodePosition::Value { path, key } => {
+                            input_diagram_spans.field_span_insert(path, key, range)
+                        }
+                    }
+                }
+                Event::Alias(_) => {
+                    let range = Self::range(yaml, span.start, span.end);
+                    match Self::node_position(&mut frames) {
+                        NodePosition::Key => Self::key_set(&mut frames, None, range),
+                        NodePosition::Value { path, key } => {
+                            input_diagram_spans.field_span_insert(path, key, range)
+                        }
+                    }
+                }
+                Event::MappingStart(..) | Event::SequenceStart(..) => {
+                    let kind = if matches!(event, Event::MappingStart(..)) {
+                        FrameKind::Mapping { key: None }
+                    } else {
+                        FrameKind::Sequence { index: 0 }
+                    };
+                    let position = Self::node_position(&mut frames);
+                    frames.push(Frame {
+                        position,
+                        start: span.start,
+                        kind,
+                    });
+                }
+                Event::MappingEnd | Event::SequenceEnd => {
+                    let Some(Frame {
+                        position, start, ..
+                    }) = frames.pop()
+                    else {
+                        continue;
+                    };
+                    let range = Self::range(yaml, start, span.end);
+                    match position {
+                        NodePosition::Key => Self::key_set(&mut frames, None, range),
+                        NodePosition::Value { path, key } => {
+                            input_diagram_spans.field_span_insert(path, key, range)
+                        }
+                    }
+                }
+                Event::DocumentEnd => break,
+                Event::Nothing
+                | Event::StreamStart
+                | Event::StreamEnd
+                | Event::DocumentStart(_)
+                | Event::Comment(..) => {}
+            }
+        }
+
+        input_diagram_spans
+    }
+
+    /// Returns whether the next node is a map key or a value, advancing the
+    /// enclosing collection's state.
+    fn node_position(frames: &mut [Frame]) -> NodePosition {
+        let Some(frame) = frames.last_mut() else {
+            // Root node of the document.
+            return NodePosition::Value {
+                path: Some(String::new()),
+                key: None,
+            };
+        };
+        let path_parent = match &frame.position {
+            NodePosition::Value { path, .. } => path.as_deref(),
+            NodePosition::Key => None,
+        };
+
+        match &mut frame.kind {
+            FrameKind::Mapping { key } => match key.take() {
+                None => NodePosition::Key,
+                Some(MapKey { name, range }) => {
+                    let path = path_parent.zip(name).map(|(path_parent, name)| {
+                        if path_parent.is_empty() {
+                            name
+                        } else {
+                            format!("{path_parent}.{name}")
+                        }
+                    });
+                    NodePosition::Value {
+                        path,
+                        key: Some(range),
+                    }
+                }
+            },
+            FrameKind::Sequence { index } => {
+                let path = path_parent.map(|path_parent| format!("{path_parent}[{index}]"));
+                *index += 1;
+                NodePosition::Value { path, key: None }
+            }
+        }
+    }
+
+    /// Records the key for the next value in the enclosing mapping.
+    ///
+    /// `name` is `None` for keys that cannot be represented in a field path,
+    /// i.e. aliases, collections, and merge keys.
+    fn key_set(frames: &mut [Frame], name: Option<String>, range: Range<usize>) {
+        if let Some(Frame {
+            kind: FrameKind::Mapping { key },
+            ..
+        }) = frames.last_mut()
+        {
+            *key = Some(MapKey { name, range });
+        }
+    }
+
+    fn field_span_insert(
+        &mut self,
+        path: Option<String>,
+        key: Option<Range<usize>>,
+        value: Range<usize>,
+    ) {
+        if let Some(path) = path.filter(|path| !path.is_empty()) {
+            self.0.insert(path, FieldSpan { key, value });
+        }
+    }
+
+    /// Returns the byte range between two markers, excluding trailing
+    /// whitespace.
+    ///
+    /// The end marker of a block collection is at the start of the next
+    /// token, so it would otherwise include trailing newlines and comments'
+    /// indentation.
+    fn range(yaml: &str, start: Marker, end: Marker) -> Range<usize> {
+        let start = start.byte_offset().unwrap_or(start.index()).min(yaml.len());
+        let end = end
+            .byte_offset()
+            .unwrap_or(end.index())
+            .clamp(start, yaml.len());
+        let len = yaml
+            .get(start..end)
+            .map_or(end - start, |s| s.trim_end().len());
+
+        start..start + len
+    }
+}
+
+/// A mapping or sequence that is being walked.
+#[derive(Debug)]
+struct Frame {
+    /// Where the collection sits in its parent.
+    position: NodePosition,
+    /// Marker at the start of the collection.
+    start: Marker,
+    /// Mapping or sequence specific state.
+    kind: FrameKind,
+}
+
+#[derive(Debug)]
+enum FrameKind {
+    Mapping {
+        /// The key for the next value, if the key has been read.
+        key: Option<MapKey>,
+    },
+    Sequence {
+        /// Index of the next item.
+        index: usize,
+    },
+}
+
+#[derive(Debug)]
+struct MapKey {
+    /// Key name, or `None` if the key is not a scalar.
+    name: Option<String>,
+    /// Byte range of the key.
+    range: Range<usize>,
+}
+
+/// Whether a node is a map key or a value.
+#[derive(Debug)]
+enum NodePosition {
+    Key,
+    Value {
+        /// Field path of the value, or `None` if it is nested within a
+        /// non-scalar key.
+        path: Option<String>,
+        /// Byte range of the map key, or `None` for sequence items.
+        key: Option<Range<usize>>,
+    },
+}
+
+impl Deref for InputDiagramSpans {
+    type Target = Map<String, FieldSpan>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for InputDiagramSpans {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Map<String, FieldSpan>> for InputDiagramSpans {
+    fn from(inner: Map<String, FieldSpan>) -> Self {
+        Self(inner)
+    }
+}
+
+impl FromIterator<(String, FieldSpan)> for InputDiagramSpans {
+    fn from_iter<I: IntoIterator<Item = (String, FieldSpan)>>(iter: I) -> Self {
+        Self(Map::from_iter(iter))
+    }
+}
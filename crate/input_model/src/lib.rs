@@ -3,7 +3,10 @@
 //! The diagram input model is hand written, as an OpenAPI spec doesn't support
 //! modelling certain data structures such as a Map with a particular key type.
 
-pub use crate::{diagram_focus::DiagramFocus, input_diagram::InputDiagram};
+pub use crate::{
+    diagram_focus::DiagramFocus, field_span::FieldSpan, input_diagram::InputDiagram,
+    input_diagram_spans::InputDiagramSpans,
+};
 
 pub mod edge;
 pub mod entity;
@@ -13,4 +16,6 @@ pub mod theme;
 pub mod thing;
 
 mod diagram_focus;
+mod field_span;
 mod input_diagram;
+mod input_diagram_spans;
@@ -61,6 +61,25 @@ impl<'s> ProcessId<'s> {
     pub fn into_inner(self) -> Id<'s> {
         self.0
     }
+
+    /// Converts this `ProcessId` into one with a `'static` lifetime.
+    ///
+    /// If the inner `Cow` is borrowed, this will clone the string to create
+    /// an owned version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_model::process::ProcessId;
+    ///
+    /// let process_id = ProcessId::new("example_id").unwrap();
+    /// let process_id_static: ProcessId<'static> = process_id.into_static();
+    ///
+    /// assert_eq!(process_id_static.as_str(), "example_id");
+    /// ```
+    pub fn into_static(self) -> ProcessId<'static> {
+        ProcessId(self.0.into_static())
+    }
 }
 
 impl<'s> From<Id<'s>> for ProcessId<'s> {
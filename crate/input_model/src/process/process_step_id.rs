@@ -61,6 +61,25 @@ impl<'s> ProcessStepId<'s> {
     pub fn into_inner(self) -> Id<'s> {
         self.0
     }
+
+    /// Converts this `ProcessStepId` into one with a `'static` lifetime.
+    ///
+    /// If the inner `Cow` is borrowed, this will clone the string to create
+    /// an owned version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_model::process::ProcessStepId;
+    ///
+    /// let step_id = ProcessStepId::new("step_clone_repo").unwrap();
+    /// let step_id_static: ProcessStepId<'static> = step_id.into_static();
+    ///
+    /// assert_eq!(step_id_static.as_str(), "step_clone_repo");
+    /// ```
+    pub fn into_static(self) -> ProcessStepId<'static> {
+        ProcessStepId(self.0.into_static())
+    }
 }
 
 impl<'s> From<Id<'s>> for ProcessStepId<'s> {
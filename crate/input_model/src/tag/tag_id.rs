@@ -61,6 +61,25 @@ impl<'s> TagId<'s> {
     pub fn into_inner(self) -> Id<'s> {
         self.0
     }
+
+    /// Converts this `TagId` into one with a `'static` lifetime.
+    ///
+    /// If the inner `Cow` is borrowed, this will clone the string to create
+    /// an owned version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_model::tag::TagId;
+    ///
+    /// let tag_id = TagId::new("example_id").unwrap();
+    /// let tag_id_static: TagId<'static> = tag_id.into_static();
+    ///
+    /// assert_eq!(tag_id_static.as_str(), "example_id");
+    /// ```
+    pub fn into_static(self) -> TagId<'static> {
+        TagId(self.0.into_static())
+    }
 }
 
 impl<'s> From<Id<'s>> for TagId<'s> {
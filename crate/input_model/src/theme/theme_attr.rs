@@ -1,3 +1,5 @@
+use std::fmt::{self, Display};
+
 use disposition_model_common::theme::{TAILWIND_COLOR_NAMES, TAILWIND_COLOR_SHADES};
 use serde::{Deserialize, Serialize};
 
@@ -583,6 +585,87 @@ pub enum ThemeAttr {
 }
 
 impl ThemeAttr {
+    /// Returns the string representation of this `ThemeAttr`, as used in the
+    /// input YAML.
+    ///
+    /// # Examples
+    ///
+    /// Return values: `"fill_color"`, `"stroke_shade_normal"`, `"padding_x"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemeAttr::Animate => "animate",
+            ThemeAttr::Cursor => "cursor",
+            ThemeAttr::CircleRadius => "circle_radius",
+            ThemeAttr::Extra => "extra",
+            ThemeAttr::FillColor => "fill_color",
+            ThemeAttr::FillColorNormal => "fill_color_normal",
+            ThemeAttr::FillColorFocus => "fill_color_focus",
+            ThemeAttr::FillColorHover => "fill_color_hover",
+            ThemeAttr::FillColorActive => "fill_color_active",
+            ThemeAttr::FillShade => "fill_shade",
+            ThemeAttr::FillShadeNormal => "fill_shade_normal",
+            ThemeAttr::FillShadeFocus => "fill_shade_focus",
+            ThemeAttr::FillShadeHover => "fill_shade_hover",
+            ThemeAttr::FillShadeActive => "fill_shade_active",
+            ThemeAttr::Gap => "gap",
+            ThemeAttr::Padding => "padding",
+            ThemeAttr::PaddingX => "padding_x",
+            ThemeAttr::PaddingY => "padding_y",
+            ThemeAttr::PaddingLeft => "padding_left",
+            ThemeAttr::PaddingRight => "padding_right",
+            ThemeAttr::PaddingTop => "padding_top",
+            ThemeAttr::PaddingBottom => "padding_bottom",
+            ThemeAttr::Margin => "margin",
+            ThemeAttr::MarginX => "margin_x",
+            ThemeAttr::MarginY => "margin_y",
+            ThemeAttr::MarginLeft => "margin_left",
+            ThemeAttr::MarginRight => "margin_right",
+            ThemeAttr::MarginTop => "margin_top",
+            ThemeAttr::MarginBottom => "margin_bottom",
+            ThemeAttr::Opacity => "opacity",
+            ThemeAttr::OutlineColor => "outline_color",
+            ThemeAttr::OutlineColorNormal => "outline_color_normal",
+            ThemeAttr::OutlineColorFocus => "outline_color_focus",
+            ThemeAttr::OutlineColorHover => "outline_color_hover",
+            ThemeAttr::OutlineColorActive => "outline_color_active",
+            ThemeAttr::OutlineShade => "outline_shade",
+            ThemeAttr::OutlineShadeNormal => "outline_shade_normal",
+            ThemeAttr::OutlineShadeFocus => "outline_shade_focus",
+            ThemeAttr::OutlineShadeHover => "outline_shade_hover",
+            ThemeAttr::OutlineShadeActive => "outline_shade_active",
+            ThemeAttr::OutlineWidth => "outline_width",
+            ThemeAttr::OutlineStyle => "outline_style",
+            ThemeAttr::OutlineStyleNormal => "outline_style_normal",
+            ThemeAttr::OutlineStyleFocus => "outline_style_focus",
+            ThemeAttr::OutlineStyleHover => "outline_style_hover",
+            ThemeAttr::OutlineStyleActive => "outline_style_active",
+            ThemeAttr::RadiusTopLeft => "radius_top_left",
+            ThemeAttr::RadiusTopRight => "radius_top_right",
+            ThemeAttr::RadiusBottomLeft => "radius_bottom_left",
+            ThemeAttr::RadiusBottomRight => "radius_bottom_right",
+            ThemeAttr::ShapeColor => "shape_color",
+            ThemeAttr::StrokeColor => "stroke_color",
+            ThemeAttr::StrokeColorNormal => "stroke_color_normal",
+            ThemeAttr::StrokeColorFocus => "stroke_color_focus",
+            ThemeAttr::StrokeColorHover => "stroke_color_hover",
+            ThemeAttr::StrokeColorActive => "stroke_color_active",
+            ThemeAttr::StrokeShade => "stroke_shade",
+            ThemeAttr::StrokeShadeNormal => "stroke_shade_normal",
+            ThemeAttr::StrokeShadeFocus => "stroke_shade_focus",
+            ThemeAttr::StrokeShadeHover => "stroke_shade_hover",
+            ThemeAttr::StrokeShadeActive => "stroke_shade_active",
+            ThemeAttr::StrokeWidth => "stroke_width",
+            ThemeAttr::StrokeStyle => "stroke_style",
+            ThemeAttr::StrokeStyleNormal => "stroke_style_normal",
+            ThemeAttr::StrokeStyleFocus => "stroke_style_focus",
+            ThemeAttr::StrokeStyleHover => "stroke_style_hover",
+            ThemeAttr::StrokeStyleActive => "stroke_style_active",
+            ThemeAttr::TextColor => "text_color",
+            ThemeAttr::TextShade => "text_shade",
+            ThemeAttr::Visibility => "visibility",
+        }
+    }
+
     /// Returns the suggested partial values for this attribute, for editor
     /// completion.
     ///
@@ -675,3 +758,9 @@ impl ThemeAttr {
         }
     }
 }
+
+impl Display for ThemeAttr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
@@ -61,6 +61,25 @@ impl<'s> ThingId<'s> {
     pub fn into_inner(self) -> Id<'s> {
         self.0
     }
+
+    /// Converts this `ThingId` into one with a `'static` lifetime.
+    ///
+    /// If the inner `Cow` is borrowed, this will clone the string to create
+    /// an owned version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_input_model::thing::ThingId;
+    ///
+    /// let thing_id = ThingId::new("example_id").unwrap();
+    /// let thing_id_static: ThingId<'static> = thing_id.into_static();
+    ///
+    /// assert_eq!(thing_id_static.as_str(), "example_id");
+    /// ```
+    pub fn into_static(self) -> ThingId<'static> {
+        ThingId(self.0.into_static())
+    }
 }
 
 impl<'s> From<Id<'s>> for ThingId<'s> {
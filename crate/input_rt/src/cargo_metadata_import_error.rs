@@ -0,0 +1,27 @@
+use miette::SourceSpan;
+
+/// Errors when reading `cargo metadata` output via `CargoMetadataImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum CargoMetadataImportError {
+    /// The output is not valid JSON, or is missing fields that are imported.
+    #[error("`cargo metadata` output is invalid: {detail}")]
+    #[diagnostic(
+        code(disposition::cargo_metadata_import::json_invalid),
+        help("Generate the output with `cargo metadata --format-version 1`.")
+    )]
+    JsonInvalid {
+        /// Description of what is invalid.
+        detail: String,
+        /// Location of the invalid JSON.
+        #[label("invalid")]
+        span: SourceSpan,
+    },
+
+    /// A workspace member that is not listed in `packages`.
+    #[error("workspace member `{package_id}` is not listed in `packages`.")]
+    #[diagnostic(code(disposition::cargo_metadata_import::workspace_member_not_found))]
+    WorkspaceMemberNotFound {
+        /// ID of the workspace member.
+        package_id: String,
+    },
+}
@@ -0,0 +1,26 @@
+use typed_builder::TypedBuilder;
+
+/// Options to import `cargo metadata` output via `CargoMetadataImporter`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use disposition_input_rt::CargoMetadataImportOptions;
+/// #
+/// let cargo_metadata_import_options = CargoMetadataImportOptions::builder()
+///     .with_external_crates_include(true)
+///     .build();
+///
+/// assert!(cargo_metadata_import_options.external_crates_include);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, TypedBuilder)]
+pub struct CargoMetadataImportOptions {
+    /// Whether to import crates from outside the workspace that workspace
+    /// members depend on directly.
+    ///
+    /// These are placed in an `external` thing, so that they are drawn apart
+    /// from the workspace's crates. The `external` thing is drawn expanded, as
+    /// input diagrams cannot collapse things.
+    #[builder(setter(prefix = "with_"), default = false)]
+    pub external_crates_include: bool,
+}
@@ -0,0 +1,370 @@
+use std::path::Path;
+
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind},
+    theme::ThemeAttr,
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{entity::EntityType, id, Id, Map, Set};
+
+use crate::{
+    cargo_metadata_importer::cargo_metadata::{CargoMetadata, CargoPackage},
+    import_diagram_insert::ImportDiagramInsert,
+    import_id_generator::ImportIdGenerator,
+    import_source_span::ImportSourceSpan,
+    CargoMetadataImportError, CargoMetadataImportOptions,
+};
+
+mod cargo_metadata;
+
+/// Name of the thing that contains crates from outside the workspace.
+const EXTERNAL_THING_ID: &str = "external";
+
+/// Imports `cargo metadata` output as an `InputDiagram` of the workspace's
+/// crates.
+///
+/// * Workspace members become things, nested in things for the directories they
+///   are in relative to the workspace root, e.g. `crate/model` becomes `model`
+///   within `crate`.
+/// * Each path dependency on another workspace member becomes a
+///   `thing_dependencies` `sequence` edge group from the dependency to the
+///   crate that depends on it, as the dependency is built first. Normal and
+///   build dependencies are imported, and dev dependencies are not, as test
+///   crates commonly depend on every other crate.
+/// * Crates are given the `type_crate_lib`, `type_crate_bin`, and
+///   `type_crate_proc_macro` entity types for their targets, with theme styles
+///   that distinguish binaries and procedural macros.
+/// * Package descriptions become tooltips.
+///
+/// When [`CargoMetadataImportOptions::external_crates_include`] is set, crates
+/// from outside the workspace that workspace members depend on directly are
+/// placed in an `external` thing with the `type_crate_external` type. Their
+/// own dependencies are not imported, and multiple versions of the same crate
+/// are imported as one thing.
+///
+/// Input diagrams cannot collapse things, so the `external` thing is drawn
+/// expanded, with each external crate within it. Leave
+/// `external_crates_include` unset for workspaces with many dependencies.
+#[derive(Clone, Copy, Debug)]
+pub struct CargoMetadataImporter;
+
+impl CargoMetadataImporter {
+    /// Returns the input diagram for the `cargo metadata --format-version 1`
+    /// JSON output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     CargoMetadataImportOptions, CargoMetadataImporter,
+    /// # };
+    /// #
+    /// let input_diagram = CargoMetadataImporter::import(
+    ///     r#"{
+    ///         "workspace_root": "/ws",
+    ///         "workspace_members": ["path+file:///ws/crate/app#0.1.0", "path+file:///ws/crate/model#0.1.0"],
+    ///         "packages": [
+    ///             {
+    ///                 "name": "app",
+    ///                 "id": "path+file:///ws/crate/app#0.1.0",
+    ///                 "manifest_path": "/ws/crate/app/Cargo.toml",
+    ///                 "dependencies": [{ "name": "model", "kind": null, "path": "/ws/crate/model" }],
+    ///                 "targets": [{ "kind": ["bin"] }]
+    ///             },
+    ///             {
+    ///                 "name": "model",
+    ///                 "id": "path+file:///ws/crate/model#0.1.0",
+    ///                 "manifest_path": "/ws/crate/model/Cargo.toml",
+    ///                 "targets": [{ "kind": ["lib"] }]
+    ///             }
+    ///         ]
+    ///     }"#,
+    ///     &CargoMetadataImportOptions::default(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let crate_dir = &input_diagram.things[&parse_thing_id("crate").unwrap()];
+    /// assert!(crate_dir.contains_key(&parse_thing_id("app").unwrap()));
+    /// assert!(crate_dir.contains_key(&parse_thing_id("model").unwrap()));
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_model__app").unwrap()));
+    /// ```
+    pub fn import(
+        cargo_metadata_json: &str,
+        cargo_metadata_import_options: &CargoMetadataImportOptions,
+    ) -> Result<InputDiagram<'static>, CargoMetadataImportError> {
+        let cargo_metadata = serde_json::from_str::<CargoMetadata>(cargo_metadata_json)
+            .map_err(|error| Self::json_invalid(cargo_metadata_json, &error))?;
+        let members = cargo_metadata
+            .workspace_members
+            .iter()
+            .map(|package_id| {
+                cargo_metadata
+                    .packages
+                    .iter()
+                    .find(|package| &package.id == package_id)
+                    .ok_or_else(|| CargoMetadataImportError::WorkspaceMemberNotFound {
+                        package_id: package_id.clone(),
+                    })
+            })
+            .collect::<Result<Vec<&CargoPackage>, CargoMetadataImportError>>()?;
+
+        let mut id_generator = ImportIdGenerator::new();
+        let member_thing_ids = id_generator.thing_ids_generate(
+            members
+                .iter()
+                .map(|package| (package.manifest_dir(), package.name.as_str())),
+        );
+        let mut ctx = CargoMetadataImportCtx {
+            cargo_metadata: &cargo_metadata,
+            input_diagram: InputDiagram::new(),
+            id_generator,
+            member_thing_ids,
+            dir_thing_ids: Map::new(),
+            external_thing_ids: Map::new(),
+        };
+
+        members
+            .iter()
+            .for_each(|package| ctx.member_import(package));
+        members
+            .iter()
+            .for_each(|package| ctx.dependencies_import(package, cargo_metadata_import_options));
+        ctx.external_crates_import();
+        ctx.theme_types_styles_import();
+
+        Ok(ctx.input_diagram)
+    }
+
+    /// Returns the error for invalid JSON, with the span of where it is
+    /// invalid.
+    fn json_invalid(
+        cargo_metadata_json: &str,
+        error: &serde_json::Error,
+    ) -> CargoMetadataImportError {
+        CargoMetadataImportError::JsonInvalid {
+            detail: error.to_string(),
+            span: ImportSourceSpan::from_line_column(
+                cargo_metadata_json,
+                error.line(),
+                error.column(),
+            ),
+        }
+    }
+}
+
+/// State while mapping [`CargoMetadata`] to an `InputDiagram`.
+struct CargoMetadataImportCtx<'m> {
+    /// The parsed `cargo metadata` output.
+    cargo_metadata: &'m CargoMetadata,
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Generates thing and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each workspace member, keyed by its manifest directory.
+    member_thing_ids: Map<&'m Path, ThingId<'static>>,
+    /// Thing ID of each directory that contains workspace members, keyed by
+    /// its path relative to the workspace root, e.g. `crate/model`.
+    dir_thing_ids: Map<String, ThingId<'static>>,
+    /// Thing ID of each crate from outside the workspace, keyed by its name.
+    external_thing_ids: Map<&'m str, ThingId<'static>>,
+}
+
+impl<'m> CargoMetadataImportCtx<'m> {
+    /// Adds the workspace member within the things for its directories.
+    fn member_import(&mut self, package: &'m CargoPackage) {
+        let thing_id = self.member_thing_ids[package.manifest_dir()].clone();
+
+        // Directories between the workspace root and the package's directory.
+        let dir_names = package
+            .manifest_dir()
+            .strip_prefix(&self.cargo_metadata.workspace_root)
+            .ok()
+            .and_then(Path::parent)
+            .map(|dir_relative| {
+                dir_relative
+                    .components()
+                    .filter_map(|component| component.as_os_str().to_str())
+                    .collect::<Vec<&str>>()
+            })
+            .unwrap_or_default();
+
+        let mut dir_path = String::new();
+        let dir_thing_ids = dir_names
+            .into_iter()
+            .map(|dir_name| {
+                if !dir_path.is_empty() {
+                    dir_path.push('/');
+                }
+                dir_path.push_str(dir_name);
+
+                match self.dir_thing_ids.get(&dir_path) {
+                    Some(dir_thing_id) => dir_thing_id.clone(),
+                    None => {
+                        let dir_thing_id = ThingId::from(self.id_generator.generate(&dir_path));
+                        ImportDiagramInsert::thing_name(
+                            &mut self.input_diagram,
+                            &dir_thing_id,
+                            &dir_path,
+                        );
+                        self.dir_thing_ids
+                            .insert(dir_path.clone(), dir_thing_id.clone());
+                        dir_thing_id
+                    }
+                }
+            })
+            .collect::<Vec<ThingId<'static>>>();
+        let thing_hierarchy = dir_thing_ids.into_iter().fold(
+            &mut self.input_diagram.things,
+            |thing_hierarchy, dir_thing_id| thing_hierarchy.entry(dir_thing_id).or_default(),
+        );
+        thing_hierarchy.insert(thing_id.clone(), ThingHierarchy::new());
+
+        self.package_details_import(&thing_id, package);
+    }
+
+    /// Adds the name, tooltip, and entity types of the package's thing.
+    fn package_details_import(&mut self, thing_id: &ThingId<'static>, package: &CargoPackage) {
+        ImportDiagramInsert::thing_name(&mut self.input_diagram, thing_id, &package.name);
+        if let Some(description) = package
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+        {
+            self.input_diagram
+                .entity_tooltips
+                .insert(thing_id.clone().into_inner(), description.to_string());
+        }
+        let entity_types = package.entity_types();
+        if !entity_types.is_empty() {
+            self.input_diagram
+                .entity_types
+                .insert(thing_id.clone().into_inner(), entity_types);
+        }
+    }
+
+    /// Adds an edge group for each of the package's normal and build
+    /// dependencies that is imported.
+    fn dependencies_import(
+        &mut self,
+        package: &'m CargoPackage,
+        cargo_metadata_import_options: &CargoMetadataImportOptions,
+    ) {
+        let thing_id = self.member_thing_ids[package.manifest_dir()].clone();
+        let mut thing_ids_dependency = Set::<ThingId<'static>>::new();
+        package
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.kind.as_deref() != Some("dev"))
+            .for_each(|dependency| {
+                let thing_id_member = dependency
+                    .path
+                    .as_deref()
+                    .and_then(|path| self.member_thing_ids.get(Path::new(path)))
+                    .cloned();
+                let thing_id_dependency = match thing_id_member {
+                    Some(thing_id_member) => thing_id_member,
+                    None if cargo_metadata_import_options.external_crates_include => {
+                        match self.external_thing_ids.get(dependency.name.as_str()) {
+                            Some(thing_id_external) => thing_id_external.clone(),
+                            None => {
+                                let thing_id_external =
+                                    ThingId::from(self.id_generator.generate(&dependency.name));
+                                self.external_thing_ids
+                                    .insert(&dependency.name, thing_id_external.clone());
+                                thing_id_external
+                            }
+                        }
+                    }
+                    None => return,
+                };
+                if thing_id_dependency != thing_id {
+                    thing_ids_dependency.insert(thing_id_dependency);
+                }
+            });
+
+        thing_ids_dependency
+            .into_iter()
+            .for_each(|thing_id_dependency| {
+                let things = vec![thing_id_dependency, thing_id.clone()];
+                let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+                self.input_diagram
+                    .thing_dependencies
+                    .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+            });
+    }
+
+    /// Adds the `external` thing with the crates from outside the workspace.
+    fn external_crates_import(&mut self) {
+        if self.external_thing_ids.is_empty() {
+            return;
+        }
+
+        let external_thing_id = ThingId::from(self.id_generator.generate(EXTERNAL_THING_ID));
+        let external_crates = self
+            .external_thing_ids
+            .iter()
+            .map(|(name, thing_id)| (*name, thing_id.clone()))
+            .collect::<Vec<(&str, ThingId<'static>)>>();
+        let thing_hierarchy = external_crates
+            .iter()
+            .map(|(_, thing_id)| (thing_id.clone(), ThingHierarchy::new()))
+            .collect::<ThingHierarchy<'static>>();
+        self.input_diagram
+            .things
+            .insert(external_thing_id, thing_hierarchy);
+
+        external_crates.into_iter().for_each(|(name, thing_id)| {
+            let package = self.cargo_metadata.packages.iter().find(|package| {
+                package.name == name && !self.member_thing_ids.contains_key(package.manifest_dir())
+            });
+            match package {
+                Some(package) => self.package_details_import(&thing_id, package),
+                None => ImportDiagramInsert::thing_name(&mut self.input_diagram, &thing_id, name),
+            }
+            self.input_diagram
+                .entity_types
+                .entry(thing_id.into_inner())
+                .or_default()
+                .insert(EntityType::Custom(id!("type_crate_external")));
+        });
+    }
+
+    /// Adds styles for the crate entity types that are used.
+    fn theme_types_styles_import(&mut self) {
+        let entity_types_used = self
+            .input_diagram
+            .entity_types
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Set<EntityType>>();
+
+        let type_styles: [(Id<'static>, ThemeAttr, &str); 3] = [
+            (id!("type_crate_bin"), ThemeAttr::ShapeColor, "emerald"),
+            (
+                id!("type_crate_proc_macro"),
+                ThemeAttr::ShapeColor,
+                "violet",
+            ),
+            (id!("type_crate_external"), ThemeAttr::StrokeStyle, "dashed"),
+        ];
+        type_styles
+            .into_iter()
+            .filter(|(type_id, _, _)| {
+                entity_types_used.contains(&EntityType::Custom(type_id.clone()))
+            })
+            .for_each(|(type_id, theme_attr, value)| {
+                ImportDiagramInsert::type_node_style(
+                    &mut self.input_diagram,
+                    type_id,
+                    theme_attr,
+                    value,
+                );
+            });
+    }
+}
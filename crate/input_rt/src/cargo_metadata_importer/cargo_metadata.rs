@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use disposition_model_common::{entity::EntityType, id, Id, Set};
+use serde::Deserialize;
+
+/// The parts of `cargo metadata --format-version 1` output that are imported.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct CargoMetadata {
+    /// Workspace members and, unless `--no-deps` is passed, their
+    /// dependencies.
+    pub(crate) packages: Vec<CargoPackage>,
+    /// Package IDs of the workspace members.
+    pub(crate) workspace_members: Vec<String>,
+    /// Directory of the workspace's `Cargo.toml`.
+    pub(crate) workspace_root: String,
+}
+
+/// A package in the `cargo metadata` output.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct CargoPackage {
+    /// Name of the package, e.g. `serde_json`.
+    pub(crate) name: String,
+    /// Opaque ID of the package, referenced by `workspace_members`.
+    pub(crate) id: String,
+    /// Description from the package's `Cargo.toml`.
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    /// Path to the package's `Cargo.toml`.
+    pub(crate) manifest_path: String,
+    /// Dependencies declared in the package's `Cargo.toml`.
+    #[serde(default)]
+    pub(crate) dependencies: Vec<CargoDependency>,
+    /// Library, binary, test, and other targets of the package.
+    #[serde(default)]
+    pub(crate) targets: Vec<CargoTarget>,
+}
+
+impl CargoPackage {
+    /// Returns the directory of the package's `Cargo.toml`.
+    pub(crate) fn manifest_dir(&self) -> &Path {
+        let manifest_path = Path::new(&self.manifest_path);
+        manifest_path.parent().unwrap_or(manifest_path)
+    }
+
+    /// Returns the entity types for the package's library, binary, and
+    /// procedural macro targets.
+    pub(crate) fn entity_types(&self) -> Set<EntityType> {
+        self.targets
+            .iter()
+            .flat_map(|target| target.kind.iter())
+            .filter_map(|kind| match kind.as_str() {
+                "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" => Some(id!("type_crate_lib")),
+                "bin" => Some(id!("type_crate_bin")),
+                "proc-macro" => Some(id!("type_crate_proc_macro")),
+                _ => None,
+            })
+            .map(EntityType::Custom)
+            .collect()
+    }
+}
+
+/// A dependency declared in a package's `Cargo.toml`.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct CargoDependency {
+    /// Name of the depended upon package.
+    pub(crate) name: String,
+    /// `dev` or `build`, `None` for normal dependencies.
+    #[serde(default)]
+    pub(crate) kind: Option<String>,
+    /// Directory of the depended upon package, for path dependencies.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+}
+
+/// A target of a package.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct CargoTarget {
+    /// Kinds of the target, e.g. `["lib"]`, `["bin"]`, `["proc-macro"]`.
+    #[serde(default)]
+    pub(crate) kind: Vec<String>,
+}
@@ -0,0 +1,31 @@
+use miette::SourceSpan;
+
+/// Errors when reading a `docker-compose.yml` file via
+/// `DockerComposeImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum DockerComposeImportError {
+    /// The file is not valid YAML, or does not have the shape of a compose
+    /// file.
+    #[error("compose file is invalid: {detail}")]
+    #[diagnostic(
+        code(disposition::docker_compose_import::yaml_invalid),
+        help("Services are declared under `services:`, keyed by service name.")
+    )]
+    YamlInvalid {
+        /// Description of what is invalid.
+        detail: String,
+        /// Location of the invalid YAML.
+        #[label("invalid")]
+        span: SourceSpan,
+    },
+
+    /// A `depends_on` entry that is not a service in the file.
+    #[error("service `{service}` depends on `{dependency}`, which is not a service.")]
+    #[diagnostic(code(disposition::docker_compose_import::depends_on_service_not_found))]
+    DependsOnServiceNotFound {
+        /// Name of the service with the `depends_on` entry.
+        service: String,
+        /// Name in the `depends_on` entry.
+        dependency: String,
+    },
+}
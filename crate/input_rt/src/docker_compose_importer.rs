@@ -0,0 +1,292 @@
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind},
+    theme::ThemeAttr,
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{entity::EntityType, Id, Map, Set};
+
+use crate::{
+    docker_compose_importer::docker_compose::{ComposeService, DockerCompose},
+    import_diagram_insert::ImportDiagramInsert,
+    import_id_generator::ImportIdGenerator,
+    import_source_span::ImportSourceSpan,
+    DockerComposeImportError,
+};
+
+mod docker_compose;
+
+/// Shape colors for well known image families, grouped by what the images
+/// are used for.
+const IMAGE_FAMILY_SHAPE_COLORS: &[(&[&str], &str)] = &[
+    // Databases
+    (
+        &[
+            "postgres",
+            "postgresql",
+            "postgis",
+            "mysql",
+            "mariadb",
+            "mongo",
+            "mongodb",
+            "mssql",
+            "cassandra",
+            "cockroach",
+        ],
+        "blue",
+    ),
+    // Caches
+    (&["redis", "valkey", "memcached", "keydb"], "red"),
+    // Message brokers
+    (
+        &[
+            "rabbitmq",
+            "kafka",
+            "nats",
+            "activemq",
+            "pulsar",
+            "zookeeper",
+        ],
+        "amber",
+    ),
+    // Reverse proxies and web servers
+    (
+        &["nginx", "traefik", "haproxy", "caddy", "envoy", "httpd"],
+        "emerald",
+    ),
+];
+
+/// Imports a `docker-compose.yml` file as an `InputDiagram` of its services.
+///
+/// * Services become things.
+/// * Each `depends_on` entry becomes a `thing_dependencies` `sequence` edge
+///   group from the dependency to the service, as the dependency is started
+///   first.
+/// * Services are nested in a thing for the first network they list under
+///   `networks`. Services that do not list networks are not nested.
+/// * Published `ports` become the service's description.
+/// * Services are given a `type_image_<family>` entity type for the family of
+///   their image, e.g. `type_image_postgres` for `postgres:16-alpine`. Well
+///   known databases, caches, message brokers, and reverse proxies are given a
+///   shape color per group.
+///
+/// Other service properties, and top level `networks`, `volumes`, `configs`,
+/// and `secrets` are not imported.
+#[derive(Clone, Copy, Debug)]
+pub struct DockerComposeImporter;
+
+impl DockerComposeImporter {
+    /// Returns the input diagram for the `docker-compose.yml` file's contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     DockerComposeImporter,
+    /// # };
+    /// #
+    /// let input_diagram = DockerComposeImporter::import(
+    ///     "\
+    /// services:
+    ///   web:
+    ///     image: nginx:1.27
+    ///     ports: ['8080:80']
+    ///     depends_on: [api]
+    ///   api:
+    ///     build: .
+    ///     networks: [backend]
+    ///   db:
+    ///     image: postgres:16
+    ///     networks: [backend]
+    /// ",
+    /// )
+    /// .unwrap();
+    ///
+    /// let backend = &input_diagram.things[&parse_thing_id("backend").unwrap()];
+    /// assert!(backend.contains_key(&parse_thing_id("api").unwrap()));
+    /// assert!(backend.contains_key(&parse_thing_id("db").unwrap()));
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_api__web").unwrap()));
+    /// assert_eq!(
+    ///     "Ports: `8080:80`",
+    ///     input_diagram.thing_descs[parse_thing_id("web").unwrap().as_ref()]
+    /// );
+    /// ```
+    pub fn import(
+        docker_compose_yaml: &str,
+    ) -> Result<InputDiagram<'static>, DockerComposeImportError> {
+        let docker_compose = serde_saphyr::from_str::<DockerCompose>(docker_compose_yaml)
+            .map_err(|error| Self::yaml_invalid(docker_compose_yaml, &error))?;
+
+        let mut id_generator = ImportIdGenerator::new();
+        let service_thing_ids = id_generator.thing_ids_generate(
+            docker_compose
+                .services
+                .keys()
+                .map(|service_name| (service_name.as_str(), service_name.as_str())),
+        );
+        let mut ctx = DockerComposeImportCtx {
+            input_diagram: InputDiagram::new(),
+            id_generator,
+            service_thing_ids,
+            network_thing_ids: Map::new(),
+        };
+
+        docker_compose
+            .services
+            .iter()
+            .for_each(|(service_name, service)| ctx.service_import(service_name, service));
+        docker_compose
+            .services
+            .iter()
+            .try_for_each(|(service_name, service)| ctx.depends_on_import(service_name, service))?;
+        ctx.theme_types_styles_import();
+
+        Ok(ctx.input_diagram)
+    }
+
+    /// Returns the error for invalid YAML, with the span of where it is
+    /// invalid.
+    fn yaml_invalid(
+        docker_compose_yaml: &str,
+        error: &serde_saphyr::Error,
+    ) -> DockerComposeImportError {
+        DockerComposeImportError::YamlInvalid {
+            detail: error.without_snippet().to_string(),
+            span: ImportSourceSpan::from_yaml_error(docker_compose_yaml, error),
+        }
+    }
+}
+
+/// State while mapping a [`DockerCompose`] file to an `InputDiagram`.
+struct DockerComposeImportCtx<'c> {
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Generates thing and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each service, keyed by its name.
+    service_thing_ids: Map<&'c str, ThingId<'static>>,
+    /// Thing ID of each network that contains services, keyed by its name.
+    network_thing_ids: Map<&'c str, ThingId<'static>>,
+}
+
+impl<'c> DockerComposeImportCtx<'c> {
+    /// Adds the service within the thing for its first network.
+    fn service_import(&mut self, service_name: &'c str, service: &'c ComposeService) {
+        let thing_id = self.service_thing_ids[service_name].clone();
+
+        let thing_hierarchy = match service.networks.names().first() {
+            Some(network_name) => {
+                let network_thing_id = match self.network_thing_ids.get(network_name) {
+                    Some(network_thing_id) => network_thing_id.clone(),
+                    None => {
+                        let network_thing_id =
+                            ThingId::from(self.id_generator.generate(network_name));
+                        ImportDiagramInsert::thing_name(
+                            &mut self.input_diagram,
+                            &network_thing_id,
+                            network_name,
+                        );
+                        self.network_thing_ids
+                            .insert(network_name, network_thing_id.clone());
+                        network_thing_id
+                    }
+                };
+                self.input_diagram
+                    .things
+                    .entry(network_thing_id)
+                    .or_default()
+            }
+            None => &mut self.input_diagram.things,
+        };
+        thing_hierarchy.insert(thing_id.clone(), ThingHierarchy::new());
+
+        ImportDiagramInsert::thing_name(&mut self.input_diagram, &thing_id, service_name);
+        if !service.ports.is_empty() {
+            let ports = service
+                .ports
+                .iter()
+                .map(|port| format!("`{port}`"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            self.input_diagram
+                .thing_descs
+                .insert(thing_id.clone().into_inner(), format!("Ports: {ports}"));
+        }
+        if let Some(image_family) = service.image_family() {
+            let entity_type = ImportIdGenerator::sanitize(&format!(
+                "type_image_{}",
+                image_family.to_ascii_lowercase()
+            ));
+            let entity_type =
+                Id::try_from(entity_type).expect("Expected sanitized ID to be valid.");
+            let mut entity_types = Set::new();
+            entity_types.insert(EntityType::Custom(entity_type));
+            self.input_diagram
+                .entity_types
+                .insert(thing_id.into_inner(), entity_types);
+        }
+    }
+
+    /// Adds an edge group for each of the service's `depends_on` entries.
+    fn depends_on_import(
+        &mut self,
+        service_name: &str,
+        service: &ComposeService,
+    ) -> Result<(), DockerComposeImportError> {
+        let thing_id = self.service_thing_ids[service_name].clone();
+        service
+            .depends_on
+            .names()
+            .into_iter()
+            .try_for_each(|dependency| {
+                let thing_id_dependency = self
+                    .service_thing_ids
+                    .get(dependency)
+                    .cloned()
+                    .ok_or_else(|| DockerComposeImportError::DependsOnServiceNotFound {
+                        service: service_name.to_string(),
+                        dependency: dependency.to_string(),
+                    })?;
+                let things = vec![thing_id_dependency, thing_id.clone()];
+                let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+                self.input_diagram
+                    .thing_dependencies
+                    .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+                Ok(())
+            })
+    }
+
+    /// Adds shape colors for the image family entity types that are used and
+    /// well known.
+    fn theme_types_styles_import(&mut self) {
+        let entity_types_used = self
+            .input_diagram
+            .entity_types
+            .values()
+            .flatten()
+            .filter_map(|entity_type| match entity_type {
+                EntityType::Custom(type_id) => Some(type_id.clone()),
+                _ => None,
+            })
+            .collect::<Set<Id<'static>>>();
+
+        entity_types_used.into_iter().for_each(|type_id| {
+            let image_family = type_id.as_str().trim_start_matches("type_image_");
+            let shape_color = IMAGE_FAMILY_SHAPE_COLORS
+                .iter()
+                .find(|(image_families, _)| image_families.contains(&image_family))
+                .map(|(_, shape_color)| *shape_color);
+            if let Some(shape_color) = shape_color {
+                ImportDiagramInsert::type_node_style(
+                    &mut self.input_diagram,
+                    type_id,
+                    ThemeAttr::ShapeColor,
+                    shape_color,
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+
+use disposition_model_common::Map;
+use serde::{de::IgnoredAny, Deserialize};
+
+/// The parts of a `docker-compose.yml` file that are imported.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct DockerCompose {
+    /// Services of the application, keyed by service name.
+    pub(crate) services: Map<String, ComposeService>,
+}
+
+/// A service in a `docker-compose.yml` file.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ComposeService {
+    /// Image the service's containers run, e.g. `postgres:16-alpine`.
+    pub(crate) image: Option<String>,
+    /// Services that are started before this service.
+    pub(crate) depends_on: ComposeNames,
+    /// Networks the service's containers are attached to.
+    pub(crate) networks: ComposeNames,
+    /// Ports that are published to the host.
+    pub(crate) ports: Vec<ComposePort>,
+}
+
+impl ComposeService {
+    /// Returns the family of the service's image, e.g. `postgres` for
+    /// `docker.io/library/postgres:16-alpine`.
+    ///
+    /// This is the image's name without its registry, namespace, tag, or
+    /// digest.
+    pub(crate) fn image_family(&self) -> Option<&str> {
+        let image = self.image.as_deref()?;
+        let image = image
+            .split_once('@')
+            .map_or(image, |(image, _digest)| image);
+        let name = image.rsplit('/').next().unwrap_or(image);
+        let family = name.split_once(':').map_or(name, |(family, _tag)| family);
+
+        Some(family).filter(|family| !family.is_empty())
+    }
+}
+
+/// Service or network names, written either as a list or as a map whose
+/// values configure each entry.
+///
+/// ```yaml
+/// depends_on: [db, cache]
+/// depends_on:
+///   db:
+///     condition: service_healthy
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ComposeNames {
+    /// Names written as a list.
+    List(Vec<String>),
+    /// Names written as the keys of a map.
+    Map(Map<String, IgnoredAny>),
+}
+
+impl ComposeNames {
+    /// Returns the names in the order they are written.
+    pub(crate) fn names(&self) -> Vec<&str> {
+        match self {
+            Self::List(names) => names.iter().map(String::as_str).collect(),
+            Self::Map(names) => names.keys().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl Default for ComposeNames {
+    fn default() -> Self {
+        Self::List(Vec::new())
+    }
+}
+
+/// A port mapping of a service.
+///
+/// Displays in the short syntax, e.g. `127.0.0.1:8080:80/tcp`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ComposePort {
+    /// Container port written as a number, e.g. `80`.
+    Number(u64),
+    /// Short syntax, e.g. `8080:80`.
+    Short(String),
+    /// Long syntax, with each part of the mapping as a field.
+    Long(ComposePortLong),
+}
+
+impl fmt::Display for ComposePort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(port) => write!(f, "{port}"),
+            Self::Short(port) => write!(f, "{port}"),
+            Self::Long(ComposePortLong {
+                target,
+                published,
+                host_ip,
+                protocol,
+            }) => {
+                if let Some(host_ip) = host_ip {
+                    write!(f, "{host_ip}:")?;
+                }
+                if let Some(published) = published {
+                    write!(f, "{published}:")?;
+                }
+                write!(f, "{target}")?;
+                if let Some(protocol) = protocol {
+                    write!(f, "/{protocol}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Port mapping written in the long syntax.
+///
+/// ```yaml
+/// ports:
+///   - target: 80
+///     published: "8080"
+///     protocol: tcp
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct ComposePortLong {
+    /// Port within the container.
+    pub(crate) target: ComposePortNumber,
+    /// Port or port range on the host.
+    #[serde(default)]
+    pub(crate) published: Option<ComposePortNumber>,
+    /// Host IP address the port is bound to.
+    #[serde(default)]
+    pub(crate) host_ip: Option<String>,
+    /// `tcp` or `udp`.
+    #[serde(default)]
+    pub(crate) protocol: Option<String>,
+}
+
+/// A port written as a number or a string, e.g. `80` or `"8000-8010"`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ComposePortNumber {
+    /// Port written as a number.
+    Number(u64),
+    /// Port or port range written as a string.
+    String(String),
+}
+
+impl fmt::Display for ComposePortNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(port) => write!(f, "{port}"),
+            Self::String(port) => write!(f, "{port}"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use miette::SourceSpan;
+
+/// Errors when reading a Graphviz DOT graph via `DotImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum DotImportError {
+    /// A character that cannot start a token.
+    #[error("unexpected character `{character}`.")]
+    #[diagnostic(code(disposition::dot_import::character_unexpected))]
+    CharacterUnexpected {
+        /// The unexpected character.
+        character: char,
+        /// Location of the character in the DOT source.
+        #[label("unexpected character")]
+        span: SourceSpan,
+    },
+
+    /// A double-quoted string, HTML string, or `/* */` comment is not closed.
+    #[error("{what} is not closed.")]
+    #[diagnostic(code(disposition::dot_import::unterminated))]
+    Unterminated {
+        /// What is not closed, e.g. `string`.
+        what: &'static str,
+        /// Location of the start of the unclosed value in the DOT source.
+        #[label("opened here")]
+        span: SourceSpan,
+    },
+
+    /// A token other than the expected one.
+    #[error("expected {expected}, found {found}.")]
+    #[diagnostic(code(disposition::dot_import::token_unexpected))]
+    TokenUnexpected {
+        /// Description of the expected token, e.g. `` `{` ``.
+        expected: &'static str,
+        /// Description of the token that was found.
+        found: String,
+        /// Location of the token in the DOT source.
+        #[label("expected {expected}")]
+        span: SourceSpan,
+    },
+
+    /// The DOT source ended before the graph was closed.
+    #[error("expected {expected}, found the end of the graph.")]
+    #[diagnostic(code(disposition::dot_import::end_unexpected))]
+    EndUnexpected {
+        /// Description of the expected token, e.g. `` `}` ``.
+        expected: &'static str,
+        /// Location of the end of the DOT source.
+        #[label("expected {expected}")]
+        span: SourceSpan,
+    },
+}
@@ -0,0 +1,112 @@
+use miette::SourceSpan;
+
+/// Parts of a Graphviz DOT graph that were not imported, or were imported
+/// differently, by `DotImporter`.
+///
+/// Every variant carries the `span` of the offending value in the DOT source.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum DotImportWarning {
+    /// An attribute that has no equivalent in the input diagram.
+    #[error("attribute `{attr_name}` is not supported, and was not imported.")]
+    #[diagnostic(code(disposition::dot_import::attr_unsupported), severity(Warning))]
+    AttrUnsupported {
+        /// Name of the attribute, e.g. `color`.
+        attr_name: String,
+        /// Location of the attribute in the DOT source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A supported attribute with a value that is not supported.
+    #[error("`{attr_name}` value `{value}` is not supported, and was not imported.")]
+    #[diagnostic(
+        code(disposition::dot_import::attr_value_unsupported),
+        severity(Warning)
+    )]
+    AttrValueUnsupported {
+        /// Name of the attribute, e.g. `rankdir`.
+        attr_name: String,
+        /// Value of the attribute.
+        value: String,
+        /// Location of the attribute in the DOT source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A node ID that is not a valid thing ID.
+    #[error("node `{dot_id}` was imported as `{thing_id}`.")]
+    #[diagnostic(
+        code(disposition::dot_import::id_renamed),
+        severity(Warning),
+        help("Thing IDs may only contain letters, numbers, and underscores.")
+    )]
+    IdRenamed {
+        /// ID of the node in the DOT source.
+        dot_id: String,
+        /// ID of the imported thing.
+        thing_id: String,
+        /// Location of the node's first appearance in the DOT source.
+        #[label("renamed")]
+        span: SourceSpan,
+    },
+
+    /// An HTML-like label, which is imported as plain text.
+    #[error("HTML label was imported as text.")]
+    #[diagnostic(code(disposition::dot_import::label_html), severity(Warning))]
+    LabelHtml {
+        /// Location of the label in the DOT source.
+        #[label("markup removed")]
+        span: SourceSpan,
+    },
+
+    /// A node that appears in clusters that are not within each other.
+    #[error("node `{node_id}` is in multiple clusters, and was imported into the first.")]
+    #[diagnostic(
+        code(disposition::dot_import::node_cluster_multiple),
+        severity(Warning)
+    )]
+    NodeClusterMultiple {
+        /// ID of the node in the DOT source.
+        node_id: String,
+        /// Location of the node's appearance in another cluster.
+        #[label("also in this cluster")]
+        span: SourceSpan,
+    },
+
+    /// A node with `style=invis`, which Graphviz uses to adjust layout.
+    #[error("node `{node_id}` is invisible, and was not imported.")]
+    #[diagnostic(code(disposition::dot_import::node_invisible), severity(Warning))]
+    NodeInvisible {
+        /// ID of the node in the DOT source.
+        node_id: String,
+        /// Location of the node's first appearance in the DOT source.
+        #[label("invisible node")]
+        span: SourceSpan,
+    },
+
+    /// An edge to a node that was not imported.
+    #[error("edge to invisible node `{node_id}` was not imported.")]
+    #[diagnostic(
+        code(disposition::dot_import::edge_node_invisible),
+        severity(Warning),
+        help("Set `lhead` / `ltail` to the cluster that the edge is drawn to.")
+    )]
+    EdgeNodeInvisible {
+        /// ID of the invisible node in the DOT source.
+        node_id: String,
+        /// Location of the edge statement in the DOT source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A node port, e.g. `a:p1`, which is imported as an edge to the node.
+    #[error("port on node `{node_id}` is not supported, and the edge was imported to the node.")]
+    #[diagnostic(code(disposition::dot_import::port_unsupported), severity(Warning))]
+    PortUnsupported {
+        /// ID of the node in the DOT source.
+        node_id: String,
+        /// Location of the node ID and port in the DOT source.
+        #[label("port not imported")]
+        span: SourceSpan,
+    },
+}
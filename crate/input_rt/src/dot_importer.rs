@@ -0,0 +1,522 @@
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind, EdgeLabel},
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{Map, RankDir, Set};
+use miette::SourceSpan;
+
+use crate::{
+    dot_importer::{
+        dot_graph::{DotAttr, DotAttrs, DotEdge, DotEntityRef, DotGraph},
+        dot_lexer::DotLexer,
+        dot_parser::DotParser,
+        dot_token::DotIdKind,
+    },
+    import_id_generator::ImportIdGenerator,
+    DotImportError, DotImportWarning,
+};
+
+pub use self::dot_import_outcome::DotImportOutcome;
+
+pub(crate) mod dot_graph;
+mod dot_import_outcome;
+pub(crate) mod dot_lexer;
+pub(crate) mod dot_parser;
+mod dot_token;
+
+/// Imports a Graphviz DOT graph as an `InputDiagram`.
+///
+/// * Nodes become things, and clusters (subgraphs whose name starts with
+///   `cluster`) become things that contain the things within them. Other
+///   subgraphs are flattened into the graph or cluster they are in.
+/// * Each edge statement becomes a `thing_dependencies` edge group, e.g. `a ->
+///   b -> c` becomes a `sequence` of `[a, b, c]`. `dir=back` reverses the
+///   sequence, and `dir=both` makes it `symmetric`. A self-loop becomes a
+///   `cyclic` edge group.
+/// * In an undirected `graph`, edges have no arrow heads, so `a -- b -- c`
+///   becomes a `symmetric` edge group of `[a, b, c]`. `dir=forward` and
+///   `dir=back` make it a `sequence`, as Graphviz then draws arrow heads.
+/// * `label` becomes the thing name or edge description, and `tooltip` the
+///   entity tooltip. `taillabel` / `headlabel` become the edge labels.
+/// * `rankdir` becomes `render_options.rank_dir`.
+/// * `lhead` / `ltail` connect the edge to the cluster instead of the node.
+///
+/// Nodes with `style=invis` are not imported, as Graphviz uses them to adjust
+/// layout. Attributes that have no equivalent in the input diagram, such as
+/// `color` or `shape`, are returned as [`DotImportWarning`]s.
+#[derive(Clone, Copy, Debug)]
+pub struct DotImporter;
+
+impl DotImporter {
+    /// Returns the input diagram for the DOT graph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     DotImporter,
+    /// # };
+    /// #
+    /// let dot_import_outcome = DotImporter::import(
+    ///     r#"digraph {
+    ///         rankdir=LR;
+    ///         subgraph cluster_aws { label="AWS"; db [label="Database"]; }
+    ///         app -> db;
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    /// let input_diagram = dot_import_outcome.input_diagram;
+    ///
+    /// assert_eq!(2, input_diagram.things.len());
+    /// assert!(input_diagram
+    ///     .things
+    ///     .contains_key(&parse_thing_id("aws").unwrap()));
+    /// assert_eq!(
+    ///     Some("Database"),
+    ///     input_diagram
+    ///         .thing_names
+    ///         .get(&parse_thing_id("db").unwrap())
+    ///         .map(String::as_str)
+    /// );
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_app__db").unwrap()));
+    /// assert!(dot_import_outcome.warnings.is_empty());
+    /// ```
+    pub fn import(dot: &str) -> Result<DotImportOutcome, DotImportError> {
+        let tokens = DotLexer::tokenize(dot)?;
+        let mut graph = DotParser::parse(&tokens, dot.len())?;
+        let graph_attrs = std::mem::take(&mut graph.attrs);
+        let edges = std::mem::take(&mut graph.edges);
+        let warnings = std::mem::take(&mut graph.warnings);
+
+        let mut ctx = DotImportCtx {
+            graph: &graph,
+            input_diagram: InputDiagram::new(),
+            warnings,
+            attr_spans_warned: Set::new(),
+            id_generator: ImportIdGenerator::new(),
+            node_thing_ids: Map::new(),
+            cluster_thing_ids: Vec::with_capacity(graph.clusters.len()),
+        };
+
+        ctx.graph_attrs_import(&graph_attrs);
+        ctx.thing_ids_assign();
+        ctx.input_diagram.things = ctx.thing_hierarchy_build(None);
+        ctx.clusters_import();
+        ctx.nodes_import();
+        edges.iter().for_each(|edge| ctx.edge_import(edge));
+
+        Ok(DotImportOutcome {
+            input_diagram: ctx.input_diagram,
+            warnings: ctx.warnings,
+        })
+    }
+}
+
+/// State while mapping a [`DotGraph`] to an `InputDiagram`.
+struct DotImportCtx<'g> {
+    /// The parsed graph, without its attributes and edges.
+    graph: &'g DotGraph,
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Warnings for the graph.
+    warnings: Vec<DotImportWarning>,
+    /// Attributes that have been reported, as defaults from `node [..]` and
+    /// `edge [..]` apply to many nodes and edges.
+    attr_spans_warned: Set<SourceSpan>,
+    /// Generates thing and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each visible node, keyed by its DOT ID.
+    node_thing_ids: Map<&'g str, ThingId<'static>>,
+    /// Thing ID of each cluster, by cluster index.
+    cluster_thing_ids: Vec<ThingId<'static>>,
+}
+
+impl<'g> DotImportCtx<'g> {
+    fn graph_attrs_import(&mut self, graph_attrs: &DotAttrs) {
+        graph_attrs.iter().for_each(|(attr_name, attr)| {
+            match attr_name.as_str() {
+                "rankdir" => match Self::rank_dir(&attr.value) {
+                    Some(rank_dir) => self.input_diagram.render_options.rank_dir = rank_dir,
+                    None => self.attr_value_unsupported(attr_name, attr),
+                },
+                // Only enables `lhead` / `ltail`, which are always supported.
+                "compound" => {}
+                _ => self.attr_unsupported(attr_name, attr),
+            }
+        });
+    }
+
+    /// Assigns a unique thing ID to each visible node and cluster, in the order
+    /// they first appear.
+    fn thing_ids_assign(&mut self) {
+        let graph = self.graph;
+        let node_ids_sanitized = graph
+            .nodes
+            .iter()
+            .filter(|(_, node)| !Self::is_invisible(&node.attrs))
+            .map(|(node_id, _)| ImportIdGenerator::sanitize(node_id))
+            .collect::<Set<String>>();
+
+        // Cluster IDs are assigned first, so that a node which happens to have
+        // the same ID as a cluster's name is the one that is renamed.
+        graph.clusters.iter().for_each(|cluster| {
+            let name_stripped = cluster.name[7..].trim_start_matches('_');
+            let id = ImportIdGenerator::sanitize(name_stripped);
+            let id = if name_stripped.is_empty() || node_ids_sanitized.contains(&id) {
+                ImportIdGenerator::sanitize(&cluster.name)
+            } else {
+                id
+            };
+            let thing_id = ThingId::from(self.id_generator.generate(&id));
+            self.cluster_thing_ids.push(thing_id);
+        });
+
+        graph.order.iter().for_each(|entity_ref| {
+            let DotEntityRef::Node(node_id) = entity_ref else {
+                return;
+            };
+            let node = &graph.nodes[node_id];
+            if Self::is_invisible(&node.attrs) {
+                self.warnings.push(DotImportWarning::NodeInvisible {
+                    node_id: node_id.clone(),
+                    span: node.span,
+                });
+                return;
+            }
+
+            let thing_id = ThingId::from(self.id_generator.generate(node_id));
+            if thing_id.as_str() != node_id {
+                self.warnings.push(DotImportWarning::IdRenamed {
+                    dot_id: node_id.clone(),
+                    thing_id: thing_id.to_string(),
+                    span: node.span,
+                });
+            }
+            self.node_thing_ids.insert(node_id, thing_id);
+        });
+    }
+
+    /// Returns the things within the cluster, or the root graph for `None`.
+    fn thing_hierarchy_build(&self, cluster: Option<usize>) -> ThingHierarchy<'static> {
+        self.graph
+            .order
+            .iter()
+            .filter_map(|entity_ref| match entity_ref {
+                DotEntityRef::Cluster(cluster_index) => {
+                    (self.graph.clusters[*cluster_index].parent == cluster).then(|| {
+                        (
+                            self.cluster_thing_ids[*cluster_index].clone(),
+                            self.thing_hierarchy_build(Some(*cluster_index)),
+                        )
+                    })
+                }
+                DotEntityRef::Node(node_id) => {
+                    let thing_id = self.node_thing_ids.get(node_id.as_str())?;
+                    (self.graph.nodes[node_id].cluster == cluster)
+                        .then(|| (thing_id.clone(), ThingHierarchy::new()))
+                }
+            })
+            .collect()
+    }
+
+    fn clusters_import(&mut self) {
+        let graph = self.graph;
+        graph
+            .clusters
+            .iter()
+            .enumerate()
+            .for_each(|(cluster_index, cluster)| {
+                let thing_id = self.cluster_thing_ids[cluster_index].clone();
+                cluster
+                    .attrs
+                    .iter()
+                    .for_each(|(attr_name, attr)| match attr_name.as_str() {
+                        "label" => {
+                            let name = self.label_text(attr, None);
+                            self.input_diagram
+                                .thing_names
+                                .insert(thing_id.clone(), name);
+                        }
+                        "tooltip" => {
+                            let tooltip = self.label_text(attr, None);
+                            self.input_diagram
+                                .entity_tooltips
+                                .insert(thing_id.clone().into_inner(), tooltip);
+                        }
+                        _ => self.attr_unsupported(attr_name, attr),
+                    });
+            });
+    }
+
+    fn nodes_import(&mut self) {
+        let graph = self.graph;
+        graph.nodes.iter().for_each(|(node_id, node)| {
+            let Some(thing_id) = self.node_thing_ids.get(node_id.as_str()).cloned() else {
+                return;
+            };
+
+            let mut name = (thing_id.as_str() != node_id).then(|| node_id.clone());
+            node.attrs
+                .iter()
+                .for_each(|(attr_name, attr)| match attr_name.as_str() {
+                    "label" => name = Some(self.label_text(attr, Some(node_id))),
+                    "tooltip" => {
+                        let tooltip = self.label_text(attr, Some(node_id));
+                        self.input_diagram
+                            .entity_tooltips
+                            .insert(thing_id.clone().into_inner(), tooltip);
+                    }
+                    _ => self.attr_unsupported(attr_name, attr),
+                });
+
+            if let Some(name) = name.filter(|name| name != thing_id.as_str()) {
+                self.input_diagram.thing_names.insert(thing_id, name);
+            }
+        });
+    }
+
+    /// Adds an edge group for each chain of connected edges in the edge
+    /// statement.
+    fn edge_import(&mut self, edge: &DotEdge) {
+        let mut edge_kind = if self.graph.directed {
+            EdgeKind::Sequence
+        } else {
+            EdgeKind::Symmetric
+        };
+        let mut is_reversed = false;
+        let mut cluster_head = None;
+        let mut cluster_tail = None;
+        let mut edge_desc = None;
+        let mut edge_label = EdgeLabel::default();
+        let mut tooltip = None;
+        edge.attrs
+            .iter()
+            .for_each(|(attr_name, attr)| match attr_name.as_str() {
+                "dir" => match attr.value.as_str() {
+                    "none" => {}
+                    "forward" => edge_kind = EdgeKind::Sequence,
+                    "back" => {
+                        edge_kind = EdgeKind::Sequence;
+                        is_reversed = true;
+                    }
+                    "both" => edge_kind = EdgeKind::Symmetric,
+                    _ => self.attr_value_unsupported(attr_name, attr),
+                },
+                "lhead" | "ltail" => {
+                    match self
+                        .graph
+                        .clusters
+                        .iter()
+                        .position(|cluster| cluster.name == attr.value)
+                    {
+                        Some(cluster_index) if attr_name == "lhead" => {
+                            cluster_head = Some(cluster_index)
+                        }
+                        Some(cluster_index) => cluster_tail = Some(cluster_index),
+                        None => self.attr_value_unsupported(attr_name, attr),
+                    }
+                }
+                "label" => edge_desc = Some(self.label_text(attr, None)),
+                "taillabel" => edge_label.from = self.label_text(attr, None),
+                "headlabel" => edge_label.to = self.label_text(attr, None),
+                "tooltip" => tooltip = Some(self.label_text(attr, None)),
+                _ => self.attr_unsupported(attr_name, attr),
+            });
+        if is_reversed {
+            std::mem::swap(&mut edge_label.from, &mut edge_label.to);
+        }
+
+        // Each pair of adjacent ends is an edge for every node in each end.
+        let hops = edge
+            .ends
+            .windows(2)
+            .flat_map(|ends| {
+                ends[0].iter().flat_map(move |node_id_tail| {
+                    ends[1]
+                        .iter()
+                        .map(move |node_id_head| (node_id_tail, node_id_head))
+                })
+            })
+            .filter_map(|(node_id_tail, node_id_head)| {
+                let thing_id_tail = self.edge_end_thing_id(node_id_tail, cluster_tail, edge.span);
+                let thing_id_head = self.edge_end_thing_id(node_id_head, cluster_head, edge.span);
+                thing_id_tail.zip(thing_id_head)
+            })
+            .collect::<Vec<(ThingId<'static>, ThingId<'static>)>>();
+
+        // Edges that continue from the previous edge are in the same group.
+        let mut chains: Vec<Vec<ThingId<'static>>> = Vec::new();
+        hops.into_iter()
+            .for_each(|(thing_id_tail, thing_id_head)| match chains.last_mut() {
+                Some(chain) if chain.last() == Some(&thing_id_tail) => chain.push(thing_id_head),
+                _ => chains.push(vec![thing_id_tail, thing_id_head]),
+            });
+
+        chains.into_iter().for_each(|mut things| {
+            if is_reversed {
+                things.reverse();
+            }
+            let edge_group = if things.len() == 2 && things[0] == things[1] {
+                things.pop();
+                EdgeGroup::new(EdgeKind::Cyclic, things)
+            } else {
+                EdgeGroup::new(edge_kind, things)
+            };
+
+            let edge_group_id = self.id_generator.edge_group_id_generate(&edge_group.things);
+
+            if let Some(edge_desc) = edge_desc.as_ref() {
+                self.input_diagram
+                    .edge_descs
+                    .insert(edge_group_id.clone().into_inner(), edge_desc.clone());
+            }
+            if !edge_label.from.is_empty() || !edge_label.to.is_empty() {
+                self.input_diagram.edge_labels.insert(
+                    edge_group_id.clone().into_inner().into(),
+                    edge_label.clone(),
+                );
+            }
+            if let Some(tooltip) = tooltip.as_ref() {
+                self.input_diagram
+                    .entity_tooltips
+                    .insert(edge_group_id.clone().into_inner(), tooltip.clone());
+            }
+            self.input_diagram
+                .thing_dependencies
+                .insert(edge_group_id, edge_group);
+        });
+    }
+
+    /// Returns the thing ID for an end of an edge, which is the cluster when
+    /// the edge is drawn to a cluster that the node is within.
+    fn edge_end_thing_id(
+        &mut self,
+        node_id: &str,
+        cluster: Option<usize>,
+        span: SourceSpan,
+    ) -> Option<ThingId<'static>> {
+        let node = &self.graph.nodes[node_id];
+        if let Some(cluster_index) = cluster
+            && self
+                .graph
+                .cluster_is_within(node.cluster, Some(cluster_index))
+        {
+            return Some(self.cluster_thing_ids[cluster_index].clone());
+        }
+
+        let thing_id = self.node_thing_ids.get(node_id).cloned();
+        if thing_id.is_none() {
+            self.warnings.push(DotImportWarning::EdgeNodeInvisible {
+                node_id: String::from(node_id),
+                span,
+            });
+        }
+        thing_id
+    }
+
+    /// Returns the text for a label or tooltip.
+    ///
+    /// Escape sequences are replaced, e.g. `\N` with the node ID, and `\n` /
+    /// `\l` / `\r` with a new line. HTML labels have their markup removed.
+    fn label_text(&mut self, attr: &DotAttr, node_id: Option<&str>) -> String {
+        if attr.value_kind == DotIdKind::Html {
+            self.warnings
+                .push(DotImportWarning::LabelHtml { span: attr.span });
+            return Self::html_text(&attr.value);
+        }
+
+        let mut text = String::with_capacity(attr.value.len());
+        let mut chars = attr.value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n' | 'l' | 'r') => text.push('\n'),
+                Some('N') => text.push_str(node_id.unwrap_or_default()),
+                Some('G') => text.push_str(self.graph.name.as_deref().unwrap_or_default()),
+                Some(c) => text.push(c),
+                None => text.push('\\'),
+            }
+        }
+        // Graphviz uses a trailing `\l` to left-justify the last line.
+        let len = text.trim_end_matches('\n').len();
+        text.truncate(len);
+        text
+    }
+
+    /// Returns the text within an HTML label, with `<br/>` as new lines.
+    fn html_text(html: &str) -> String {
+        let mut text = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(tag_start) = rest.find('<') {
+            text.push_str(&rest[..tag_start]);
+            let tag_end = rest[tag_start..]
+                .find('>')
+                .map(|tag_end| tag_start + tag_end + 1)
+                .unwrap_or(rest.len());
+            let tag_name = rest[tag_start + 1..tag_end]
+                .trim_start_matches('/')
+                .trim_end_matches('>');
+            if tag_name
+                .get(..2)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case("br"))
+            {
+                text.push('\n');
+            }
+            rest = &rest[tag_end..];
+        }
+        text.push_str(rest);
+
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&")
+            .trim()
+            .to_string()
+    }
+
+    fn rank_dir(value: &str) -> Option<RankDir> {
+        match value.to_ascii_uppercase().as_str() {
+            "TB" => Some(RankDir::TopToBottom),
+            "LR" => Some(RankDir::LeftToRight),
+            "BT" => Some(RankDir::BottomToTop),
+            "RL" => Some(RankDir::RightToLeft),
+            _ => None,
+        }
+    }
+
+    fn is_invisible(attrs: &DotAttrs) -> bool {
+        attrs.get("style").is_some_and(|style| {
+            style
+                .value
+                .split(',')
+                .any(|style| style.trim().eq_ignore_ascii_case("invis"))
+        })
+    }
+
+    fn attr_unsupported(&mut self, attr_name: &str, attr: &DotAttr) {
+        if self.attr_spans_warned.insert(attr.span) {
+            self.warnings.push(DotImportWarning::AttrUnsupported {
+                attr_name: String::from(attr_name),
+                span: attr.span,
+            });
+        }
+    }
+
+    fn attr_value_unsupported(&mut self, attr_name: &str, attr: &DotAttr) {
+        if self.attr_spans_warned.insert(attr.span) {
+            self.warnings.push(DotImportWarning::AttrValueUnsupported {
+                attr_name: String::from(attr_name),
+                value: attr.value.clone(),
+                span: attr.span,
+            });
+        }
+    }
+}
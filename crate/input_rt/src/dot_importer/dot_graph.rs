@@ -0,0 +1,108 @@
+use disposition_model_common::Map;
+use miette::SourceSpan;
+
+use crate::{dot_importer::dot_token::DotIdKind, DotImportWarning};
+
+/// Attributes of a graph, node, or edge, keyed by attribute name.
+///
+/// Later values for the same attribute replace earlier ones.
+pub(crate) type DotAttrs = Map<String, DotAttr>;
+
+/// A parsed DOT graph, with subgraphs other than clusters flattened.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DotGraph {
+    /// Name of the graph, e.g. `G` in `digraph G {}`.
+    pub(crate) name: Option<String>,
+    /// Whether the graph is a `digraph`.
+    pub(crate) directed: bool,
+    /// Attributes of the root graph, e.g. `rankdir`.
+    pub(crate) attrs: DotAttrs,
+    /// Subgraphs whose name starts with `cluster`.
+    pub(crate) clusters: Vec<DotCluster>,
+    /// Nodes keyed by their DOT ID, in the order they first appear.
+    pub(crate) nodes: Map<String, DotNode>,
+    /// Edge statements, in the order they appear.
+    pub(crate) edges: Vec<DotEdge>,
+    /// Clusters and nodes in the order they first appear.
+    pub(crate) order: Vec<DotEntityRef>,
+    /// Warnings found while parsing.
+    pub(crate) warnings: Vec<DotImportWarning>,
+}
+
+/// A subgraph whose name starts with `cluster`.
+#[derive(Clone, Debug)]
+pub(crate) struct DotCluster {
+    /// Name of the subgraph, e.g. `cluster_aws`.
+    pub(crate) name: String,
+    /// Index of the cluster that this cluster is within.
+    pub(crate) parent: Option<usize>,
+    /// Attributes of the cluster, e.g. `label`.
+    pub(crate) attrs: DotAttrs,
+}
+
+/// A node, with the attributes from every statement that declares it.
+#[derive(Clone, Debug)]
+pub(crate) struct DotNode {
+    /// Index of the innermost cluster that the node is within.
+    pub(crate) cluster: Option<usize>,
+    /// Attributes of the node, including defaults from `node [..]`.
+    pub(crate) attrs: DotAttrs,
+    /// Location where the node first appears in the DOT source.
+    pub(crate) span: SourceSpan,
+}
+
+/// An edge statement, e.g. `a -> b -> c [label="x"]`.
+#[derive(Clone, Debug)]
+pub(crate) struct DotEdge {
+    /// DOT IDs of the nodes at each end of the edge statement.
+    ///
+    /// Each end has one node, or every node within a subgraph, e.g. `{b c}`
+    /// in `a -> {b c}`.
+    pub(crate) ends: Vec<Vec<String>>,
+    /// Attributes of the edge, including defaults from `edge [..]`.
+    pub(crate) attrs: DotAttrs,
+    /// Location of the edge statement in the DOT source.
+    pub(crate) span: SourceSpan,
+}
+
+/// An attribute, e.g. `label="A"`.
+#[derive(Clone, Debug)]
+pub(crate) struct DotAttr {
+    /// Value of the attribute.
+    pub(crate) value: String,
+    /// How the value is written.
+    pub(crate) value_kind: DotIdKind,
+    /// Location of the attribute in the DOT source.
+    pub(crate) span: SourceSpan,
+}
+
+/// A cluster or node, used to keep the order that they first appear in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DotEntityRef {
+    /// Index of a cluster.
+    Cluster(usize),
+    /// DOT ID of a node.
+    Node(String),
+}
+
+impl DotGraph {
+    /// Returns whether `cluster` is `ancestor`, or is within it.
+    ///
+    /// `None` is the root graph, which every cluster is within.
+    pub(crate) fn cluster_is_within(
+        &self,
+        cluster: Option<usize>,
+        ancestor: Option<usize>,
+    ) -> bool {
+        let mut cluster = cluster;
+        loop {
+            if cluster == ancestor {
+                return true;
+            }
+            match cluster {
+                Some(index) => cluster = self.clusters[index].parent,
+                None => return false,
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use disposition_input_model::InputDiagram;
+
+use crate::DotImportWarning;
+
+/// The input diagram imported from a Graphviz DOT graph, and parts of the
+/// graph that were not imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DotImportOutcome {
+    /// The imported input diagram.
+    pub input_diagram: InputDiagram<'static>,
+    /// Attributes and nodes that were not imported, or were imported
+    /// differently.
+    pub warnings: Vec<DotImportWarning>,
+}
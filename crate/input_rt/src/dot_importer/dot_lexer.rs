@@ -0,0 +1,225 @@
+use miette::SourceSpan;
+
+use crate::{
+    dot_importer::dot_token::{DotIdKind, DotToken, DotTokenKind},
+    DotImportError,
+};
+
+/// Splits a DOT graph into tokens, skipping whitespace and comments.
+pub(crate) struct DotLexer<'src> {
+    /// The DOT source.
+    src: &'src str,
+    /// Byte offset of the next character to read.
+    offset: usize,
+}
+
+impl<'src> DotLexer<'src> {
+    /// Returns the tokens in the DOT source.
+    pub(crate) fn tokenize(src: &'src str) -> Result<Vec<DotToken>, DotImportError> {
+        let mut lexer = DotLexer { src, offset: 0 };
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.token_next()? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    fn token_next(&mut self) -> Result<Option<DotToken>, DotImportError> {
+        self.trivia_skip()?;
+        let start = self.offset;
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+
+        let kind = match c {
+            '{' => self.single(DotTokenKind::BraceOpen),
+            '}' => self.single(DotTokenKind::BraceClose),
+            '[' => self.single(DotTokenKind::BracketOpen),
+            ']' => self.single(DotTokenKind::BracketClose),
+            '=' => self.single(DotTokenKind::Equals),
+            ';' => self.single(DotTokenKind::Semicolon),
+            ',' => self.single(DotTokenKind::Comma),
+            ':' => self.single(DotTokenKind::Colon),
+            '-' if self.src[start..].starts_with("->") => {
+                self.offset += 2;
+                DotTokenKind::EdgeOp { directed: true }
+            }
+            '-' if self.src[start..].starts_with("--") => {
+                self.offset += 2;
+                DotTokenKind::EdgeOp { directed: false }
+            }
+            '"' => DotTokenKind::Id {
+                value: self.quoted_concat()?,
+                id_kind: DotIdKind::Quoted,
+            },
+            '<' => DotTokenKind::Id {
+                value: self.html()?,
+                id_kind: DotIdKind::Html,
+            },
+            c if c == '-' || c == '.' || c.is_ascii_digit() => DotTokenKind::Id {
+                value: self.numeral(),
+                id_kind: DotIdKind::Bare,
+            },
+            c if c == '_' || c.is_ascii_alphabetic() || !c.is_ascii() => DotTokenKind::Id {
+                value: self.bare(),
+                id_kind: DotIdKind::Bare,
+            },
+            character => {
+                return Err(DotImportError::CharacterUnexpected {
+                    character,
+                    span: SourceSpan::from((start, character.len_utf8())),
+                });
+            }
+        };
+
+        Ok(Some(DotToken {
+            kind,
+            span: SourceSpan::from((start, self.offset - start)),
+        }))
+    }
+
+    /// Skips whitespace, `//` and `/* */` comments, and lines starting with
+    /// `#`.
+    fn trivia_skip(&mut self) -> Result<(), DotImportError> {
+        loop {
+            let rest = &self.src[self.offset..];
+            let line_start = self.src[..self.offset]
+                .rsplit('\n')
+                .next()
+                .is_none_or(|line_before| line_before.trim().is_empty());
+
+            if let Some(c) = rest.chars().next()
+                && c.is_whitespace()
+            {
+                self.offset += c.len_utf8();
+            } else if rest.starts_with("//") || (line_start && rest.starts_with('#')) {
+                self.offset += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                match comment.find("*/") {
+                    Some(end) => self.offset += 2 + end + 2,
+                    None => {
+                        return Err(DotImportError::Unterminated {
+                            what: "comment",
+                            span: SourceSpan::from((self.offset, 2)),
+                        });
+                    }
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn single(&mut self, kind: DotTokenKind) -> DotTokenKind {
+        self.offset += 1;
+        kind
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.offset..].chars().next()
+    }
+
+    /// Reads a bare ID, e.g. `node_a`.
+    fn bare(&mut self) -> String {
+        let rest = &self.src[self.offset..];
+        let len = rest
+            .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric() || !c.is_ascii()))
+            .unwrap_or(rest.len());
+        self.offset += len;
+        String::from(&rest[..len])
+    }
+
+    /// Reads a numeral, e.g. `-1.5`.
+    fn numeral(&mut self) -> String {
+        let rest = &self.src[self.offset..];
+        let len = rest
+            .char_indices()
+            .find(|&(index, c)| !(c.is_ascii_digit() || c == '.' || (index == 0 && c == '-')))
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        self.offset += len;
+        String::from(&rest[..len])
+    }
+
+    /// Reads double-quoted strings joined with `+`, e.g. `"a" + "b"`.
+    fn quoted_concat(&mut self) -> Result<String, DotImportError> {
+        let mut value = self.quoted()?;
+        loop {
+            let offset = self.offset;
+            self.trivia_skip()?;
+            if self.peek() == Some('+') {
+                self.offset += 1;
+                self.trivia_skip()?;
+                if self.peek() == Some('"') {
+                    value.push_str(&self.quoted()?);
+                    continue;
+                }
+            }
+            self.offset = offset;
+            return Ok(value);
+        }
+    }
+
+    /// Reads a double-quoted string.
+    ///
+    /// Escaped quotes are unescaped, and escaped newlines are removed. Other
+    /// escapes are kept, as they are interpreted by the attribute that the
+    /// string is the value of, e.g. `\n` in labels.
+    fn quoted(&mut self) -> Result<String, DotImportError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut value = String::new();
+        let mut chars = self.src[self.offset..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.offset += index + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, '"')) => value.push('"'),
+                    Some((_, '\n')) => {}
+                    Some((_, '\r')) => {
+                        if let Some((_, '\n')) = chars.clone().next() {
+                            chars.next();
+                        }
+                    }
+                    Some((_, c)) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        Err(DotImportError::Unterminated {
+            what: "string",
+            span: SourceSpan::from((start, 1)),
+        })
+    }
+
+    /// Reads an HTML string, e.g. `<<b>a</b>>`, returning the text between
+    /// the outer angle brackets.
+    fn html(&mut self) -> Result<String, DotImportError> {
+        let start = self.offset;
+        let mut depth = 0usize;
+        for (index, c) in self.src[start..].char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.offset = start + index + 1;
+                        return Ok(String::from(&self.src[start + 1..start + index]));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err(DotImportError::Unterminated {
+            what: "HTML string",
+            span: SourceSpan::from((start, 1)),
+        })
+    }
+}
@@ -0,0 +1,476 @@
+use miette::SourceSpan;
+
+use crate::{
+    dot_importer::{
+        dot_graph::{DotAttr, DotAttrs, DotCluster, DotEdge, DotEntityRef, DotGraph, DotNode},
+        dot_token::{DotToken, DotTokenKind},
+    },
+    DotImportError, DotImportWarning,
+};
+
+/// Parses DOT tokens into a [`DotGraph`].
+pub(crate) struct DotParser<'t> {
+    /// Tokens of the DOT source.
+    tokens: &'t [DotToken],
+    /// Index of the next token to read.
+    index: usize,
+    /// Length of the DOT source, used to locate errors at its end.
+    src_len: usize,
+    /// The graph being built.
+    graph: DotGraph,
+    /// Subgraphs that are being parsed, innermost last.
+    scopes: Vec<DotScope>,
+}
+
+/// State of the graph or subgraph being parsed.
+#[derive(Clone, Debug, Default)]
+struct DotScope {
+    /// Index of the innermost cluster that the subgraph is within.
+    cluster: Option<usize>,
+    /// Whether the subgraph is the root graph or a cluster, whose graph
+    /// attributes are kept.
+    attrs_kept: bool,
+    /// Defaults from `node [..]`.
+    node_defaults: DotAttrs,
+    /// Defaults from `edge [..]`.
+    edge_defaults: DotAttrs,
+    /// DOT IDs of the nodes that appear in the subgraph.
+    node_ids: Vec<String>,
+}
+
+/// Kinds of `[..]` attribute statements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DotAttrTarget {
+    Graph,
+    Node,
+    Edge,
+}
+
+impl<'t> DotParser<'t> {
+    /// Returns the graph for the tokens.
+    pub(crate) fn parse(
+        tokens: &'t [DotToken],
+        src_len: usize,
+    ) -> Result<DotGraph, DotImportError> {
+        let mut parser = DotParser {
+            tokens,
+            index: 0,
+            src_len,
+            graph: DotGraph::default(),
+            scopes: Vec::new(),
+        };
+        parser.graph_parse()?;
+        Ok(parser.graph)
+    }
+
+    /// `[strict] (graph | digraph) [ID] '{' stmt_list '}'`
+    fn graph_parse(&mut self) -> Result<(), DotImportError> {
+        if self
+            .peek_kind()
+            .is_some_and(|kind| kind.is_keyword("strict"))
+        {
+            self.index += 1;
+        }
+        let token = self.next("`graph` or `digraph`")?;
+        if token.kind.is_keyword("digraph") {
+            self.graph.directed = true;
+        } else if !token.kind.is_keyword("graph") {
+            return Err(Self::unexpected("`graph` or `digraph`", token));
+        }
+        if let Some(DotTokenKind::Id { value, .. }) = self.peek_kind() {
+            self.graph.name = Some(value.clone());
+            self.index += 1;
+        }
+
+        self.scopes.push(DotScope {
+            attrs_kept: true,
+            ..DotScope::default()
+        });
+        self.expect(DotTokenKind::BraceOpen, "`{`")?;
+        self.stmt_list_parse()?;
+        self.expect(DotTokenKind::BraceClose, "`}`")?;
+
+        if let Some(token) = self.tokens.get(self.index) {
+            return Err(Self::unexpected("the end of the graph", token));
+        }
+        Ok(())
+    }
+
+    /// Parses statements until the closing `}`.
+    fn stmt_list_parse(&mut self) -> Result<(), DotImportError> {
+        while let Some(kind) = self.peek_kind() {
+            if *kind == DotTokenKind::BraceClose {
+                break;
+            }
+            self.stmt_parse()?;
+            if self.peek_kind() == Some(&DotTokenKind::Semicolon) {
+                self.index += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn stmt_parse(&mut self) -> Result<(), DotImportError> {
+        let token = self.next("a statement")?;
+        let attr_target = if token.kind.is_keyword("graph") {
+            Some(DotAttrTarget::Graph)
+        } else if token.kind.is_keyword("node") {
+            Some(DotAttrTarget::Node)
+        } else if token.kind.is_keyword("edge") {
+            Some(DotAttrTarget::Edge)
+        } else {
+            None
+        };
+        if let Some(attr_target) = attr_target {
+            let attrs = self.attr_lists_parse()?;
+            self.attr_stmt_apply(attr_target, attrs);
+            return Ok(());
+        }
+
+        if token.kind.is_keyword("subgraph") || token.kind == DotTokenKind::BraceOpen {
+            self.index -= 1;
+            let node_ids = self.subgraph_parse()?;
+            return self.edge_rhs_parse(node_ids, token.span);
+        }
+
+        let DotTokenKind::Id { .. } = &token.kind else {
+            return Err(Self::unexpected("a statement", token));
+        };
+        if self.peek_kind() == Some(&DotTokenKind::Equals) {
+            self.index += 1;
+            let value = self.next("a value")?;
+            let attr = Self::attr(token, value)?;
+            self.attr_stmt_apply(DotAttrTarget::Graph, DotAttrs::from_iter([attr]));
+            return Ok(());
+        }
+
+        self.index -= 1;
+        let (node_id, span) = self.node_id_parse()?;
+        if matches!(self.peek_kind(), Some(DotTokenKind::EdgeOp { .. })) {
+            self.node_touch(&node_id, span);
+            self.edge_rhs_parse(vec![node_id], span)
+        } else {
+            let attrs = if self.peek_kind() == Some(&DotTokenKind::BracketOpen) {
+                self.attr_lists_parse()?
+            } else {
+                DotAttrs::new()
+            };
+            self.node_touch(&node_id, span);
+            if let Some(node) = self.graph.nodes.get_mut(&node_id) {
+                node.attrs.extend(attrs);
+            }
+            Ok(())
+        }
+    }
+
+    /// Parses the `-> b -> c [..]` following the first end of an edge
+    /// statement, if any.
+    fn edge_rhs_parse(
+        &mut self,
+        first_end: Vec<String>,
+        span_start: SourceSpan,
+    ) -> Result<(), DotImportError> {
+        let mut ends = vec![first_end];
+        while let Some(DotTokenKind::EdgeOp { .. }) = self.peek_kind() {
+            self.index += 1;
+            let end = match self.peek_kind() {
+                Some(kind) if kind.is_keyword("subgraph") || *kind == DotTokenKind::BraceOpen => {
+                    self.subgraph_parse()?
+                }
+                _ => {
+                    let (node_id, span) = self.node_id_parse()?;
+                    self.node_touch(&node_id, span);
+                    vec![node_id]
+                }
+            };
+            ends.push(end);
+        }
+        if ends.len() == 1 {
+            return Ok(());
+        }
+
+        let mut attrs = self.scope().edge_defaults.clone();
+        if self.peek_kind() == Some(&DotTokenKind::BracketOpen) {
+            attrs.extend(self.attr_lists_parse()?);
+        }
+        let span_end = self.tokens[self.index - 1].span;
+        self.graph.edges.push(DotEdge {
+            ends,
+            attrs,
+            span: Self::span_join(span_start, span_end),
+        });
+        Ok(())
+    }
+
+    /// `[subgraph [ID]] '{' stmt_list '}'`
+    ///
+    /// Returns the DOT IDs of the nodes in the subgraph.
+    fn subgraph_parse(&mut self) -> Result<Vec<String>, DotImportError> {
+        let mut name = None;
+        if self
+            .peek_kind()
+            .is_some_and(|kind| kind.is_keyword("subgraph"))
+        {
+            self.index += 1;
+            if let Some(DotTokenKind::Id { value, .. }) = self.peek_kind() {
+                name = Some(value.clone());
+                self.index += 1;
+            }
+        }
+
+        let scope_outer = self.scope();
+        let mut scope = DotScope {
+            cluster: scope_outer.cluster,
+            attrs_kept: false,
+            node_defaults: scope_outer.node_defaults.clone(),
+            edge_defaults: scope_outer.edge_defaults.clone(),
+            node_ids: Vec::new(),
+        };
+        if let Some(name) = name.filter(|name| Self::is_cluster_name(name)) {
+            let cluster_index = self
+                .graph
+                .clusters
+                .iter()
+                .position(|cluster| cluster.name == name)
+                .unwrap_or_else(|| {
+                    self.graph.clusters.push(DotCluster {
+                        name,
+                        parent: scope.cluster,
+                        attrs: DotAttrs::new(),
+                    });
+                    let cluster_index = self.graph.clusters.len() - 1;
+                    self.graph.order.push(DotEntityRef::Cluster(cluster_index));
+                    cluster_index
+                });
+            scope.cluster = Some(cluster_index);
+            scope.attrs_kept = true;
+        }
+
+        self.scopes.push(scope);
+        self.expect(DotTokenKind::BraceOpen, "`{`")?;
+        self.stmt_list_parse()?;
+        self.expect(DotTokenKind::BraceClose, "`}`")?;
+        let scope = self.scopes.pop().unwrap_or_default();
+
+        let node_ids = scope.node_ids;
+        let scope_outer = self.scope_mut();
+        node_ids.iter().for_each(|node_id| {
+            if !scope_outer.node_ids.contains(node_id) {
+                scope_outer.node_ids.push(node_id.clone());
+            }
+        });
+        Ok(node_ids)
+    }
+
+    /// `ID [':' ID [':' ID]]`
+    ///
+    /// Ports are not supported, so they are reported and skipped.
+    fn node_id_parse(&mut self) -> Result<(String, SourceSpan), DotImportError> {
+        let token = self.next("a node ID")?;
+        let DotTokenKind::Id { value, .. } = &token.kind else {
+            return Err(Self::unexpected("a node ID", token));
+        };
+        let node_id = value.clone();
+        let span = token.span;
+
+        let mut span_port = None;
+        while self.peek_kind() == Some(&DotTokenKind::Colon) {
+            self.index += 1;
+            let port = self.next("a port")?;
+            if !matches!(port.kind, DotTokenKind::Id { .. }) {
+                return Err(Self::unexpected("a port", port));
+            }
+            span_port = Some(port.span);
+        }
+        if let Some(span_port) = span_port {
+            self.graph.warnings.push(DotImportWarning::PortUnsupported {
+                node_id: node_id.clone(),
+                span: Self::span_join(span, span_port),
+            });
+        }
+
+        Ok((node_id, span))
+    }
+
+    /// `'[' [ID '=' ID [';' | ','] ...] ']' ['[' ... ']' ...]`
+    fn attr_lists_parse(&mut self) -> Result<DotAttrs, DotImportError> {
+        let mut attrs = DotAttrs::new();
+        self.expect(DotTokenKind::BracketOpen, "`[`")?;
+        loop {
+            let token = self.next("an attribute or `]`")?;
+            match &token.kind {
+                DotTokenKind::BracketClose => {
+                    if self.peek_kind() == Some(&DotTokenKind::BracketOpen) {
+                        self.index += 1;
+                        continue;
+                    }
+                    return Ok(attrs);
+                }
+                DotTokenKind::Semicolon | DotTokenKind::Comma => {}
+                DotTokenKind::Id { .. } => {
+                    self.expect(DotTokenKind::Equals, "`=`")?;
+                    let value = self.next("a value")?;
+                    let (attr_name, attr) = Self::attr(token, value)?;
+                    attrs.insert(attr_name, attr);
+                }
+                _ => return Err(Self::unexpected("an attribute or `]`", token)),
+            }
+        }
+    }
+
+    /// Returns the attribute for the `name` and `value` tokens.
+    fn attr(name: &DotToken, value: &DotToken) -> Result<(String, DotAttr), DotImportError> {
+        let (
+            DotTokenKind::Id {
+                value: name_str, ..
+            },
+            DotTokenKind::Id {
+                value: value_str,
+                id_kind,
+            },
+        ) = (&name.kind, &value.kind)
+        else {
+            return Err(Self::unexpected("a value", value));
+        };
+        Ok((
+            name_str.clone(),
+            DotAttr {
+                value: value_str.clone(),
+                value_kind: *id_kind,
+                span: Self::span_join(name.span, value.span),
+            },
+        ))
+    }
+
+    fn attr_stmt_apply(&mut self, attr_target: DotAttrTarget, attrs: DotAttrs) {
+        let scope_cluster = self.scope().cluster;
+        let scope_attrs_kept = self.scope().attrs_kept;
+        match attr_target {
+            DotAttrTarget::Graph if scope_attrs_kept => {
+                let graph_attrs = match scope_cluster {
+                    Some(cluster_index) => &mut self.graph.clusters[cluster_index].attrs,
+                    None => &mut self.graph.attrs,
+                };
+                graph_attrs.extend(attrs);
+            }
+            // Attributes of subgraphs other than clusters, such as
+            // `rank=same`, only affect Graphviz's layout.
+            DotAttrTarget::Graph => {
+                attrs.into_iter().for_each(|(attr_name, attr)| {
+                    self.graph.warnings.push(DotImportWarning::AttrUnsupported {
+                        attr_name,
+                        span: attr.span,
+                    });
+                });
+            }
+            DotAttrTarget::Node => self.scope_mut().node_defaults.extend(attrs),
+            DotAttrTarget::Edge => self.scope_mut().edge_defaults.extend(attrs),
+        }
+    }
+
+    /// Records that the node appears in the current subgraph, adding it to the
+    /// graph if it is new.
+    fn node_touch(&mut self, node_id: &str, span: SourceSpan) {
+        let scope = self.scopes.last().expect("Expected a scope to be pushed.");
+        let cluster = scope.cluster;
+        let node_defaults = scope.node_defaults.clone();
+
+        match self.graph.nodes.get(node_id).map(|node| node.cluster) {
+            None => {
+                self.graph.nodes.insert(
+                    String::from(node_id),
+                    DotNode {
+                        cluster,
+                        attrs: node_defaults,
+                        span,
+                    },
+                );
+                self.graph
+                    .order
+                    .push(DotEntityRef::Node(String::from(node_id)));
+            }
+            Some(node_cluster) => {
+                // A node that is declared before it appears in a cluster is
+                // moved into the cluster.
+                if node_cluster != cluster && self.graph.cluster_is_within(cluster, node_cluster) {
+                    if let Some(node) = self.graph.nodes.get_mut(node_id) {
+                        node.cluster = cluster;
+                    }
+                } else if !self.graph.cluster_is_within(node_cluster, cluster) {
+                    self.graph
+                        .warnings
+                        .push(DotImportWarning::NodeClusterMultiple {
+                            node_id: String::from(node_id),
+                            span,
+                        });
+                }
+            }
+        }
+
+        let scope = self.scope_mut();
+        if !scope
+            .node_ids
+            .iter()
+            .any(|scope_node_id| scope_node_id == node_id)
+        {
+            scope.node_ids.push(String::from(node_id));
+        }
+    }
+
+    /// Returns whether the subgraph name marks it as a cluster.
+    fn is_cluster_name(name: &str) -> bool {
+        name.get(..7)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("cluster"))
+    }
+
+    fn scope(&self) -> &DotScope {
+        self.scopes.last().expect("Expected a scope to be pushed.")
+    }
+
+    fn scope_mut(&mut self) -> &mut DotScope {
+        self.scopes
+            .last_mut()
+            .expect("Expected a scope to be pushed.")
+    }
+
+    fn peek_kind(&self) -> Option<&'t DotTokenKind> {
+        self.tokens.get(self.index).map(|token| &token.kind)
+    }
+
+    /// Returns the next token, or an error if there are no more tokens.
+    fn next(&mut self, expected: &'static str) -> Result<&'t DotToken, DotImportError> {
+        match self.tokens.get(self.index) {
+            Some(token) => {
+                self.index += 1;
+                Ok(token)
+            }
+            None => Err(DotImportError::EndUnexpected {
+                expected,
+                span: SourceSpan::from((self.src_len, 0)),
+            }),
+        }
+    }
+
+    fn expect(&mut self, kind: DotTokenKind, expected: &'static str) -> Result<(), DotImportError> {
+        let token = self.next(expected)?;
+        if token.kind == kind {
+            Ok(())
+        } else {
+            Err(Self::unexpected(expected, token))
+        }
+    }
+
+    fn unexpected(expected: &'static str, token: &DotToken) -> DotImportError {
+        DotImportError::TokenUnexpected {
+            expected,
+            found: token.kind.describe(),
+            span: token.span,
+        }
+    }
+
+    /// Returns the span from the start of `start` to the end of `end`.
+    fn span_join(start: SourceSpan, end: SourceSpan) -> SourceSpan {
+        let offset = start.offset();
+        SourceSpan::from((offset, end.offset() + end.len() - offset))
+    }
+}
@@ -0,0 +1,89 @@
+use miette::SourceSpan;
+
+/// A token in a DOT graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DotToken {
+    /// The kind of token, with its value for IDs.
+    pub(crate) kind: DotTokenKind,
+    /// Location of the token in the DOT source.
+    pub(crate) span: SourceSpan,
+}
+
+/// Kinds of tokens in a DOT graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DotTokenKind {
+    /// An ID, with quotes and escaped quotes removed.
+    ///
+    /// Quoted strings joined with `+` are a single ID.
+    Id {
+        /// Value of the ID.
+        value: String,
+        /// How the ID is written.
+        id_kind: DotIdKind,
+    },
+    /// `{`
+    BraceOpen,
+    /// `}`
+    BraceClose,
+    /// `[`
+    BracketOpen,
+    /// `]`
+    BracketClose,
+    /// `=`
+    Equals,
+    /// `;`
+    Semicolon,
+    /// `,`
+    Comma,
+    /// `:`
+    Colon,
+    /// `->` when `directed`, or `--`.
+    EdgeOp {
+        /// Whether the operator is `->`.
+        directed: bool,
+    },
+}
+
+/// How an ID is written in a DOT graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DotIdKind {
+    /// Alphanumeric characters and underscores, or a number, e.g. `node_a`.
+    ///
+    /// Only bare IDs can be keywords.
+    Bare,
+    /// A double-quoted string, e.g. `"node a"`.
+    Quoted,
+    /// An HTML string, e.g. `<<b>node</b> a>`.
+    Html,
+}
+
+impl DotTokenKind {
+    /// Returns whether this is a bare ID that matches the keyword, ignoring
+    /// case.
+    pub(crate) fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(
+            self,
+            DotTokenKind::Id {
+                value,
+                id_kind: DotIdKind::Bare,
+            } if value.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    /// Returns a description of the token for error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            DotTokenKind::Id { value, .. } => format!("`{value}`"),
+            DotTokenKind::BraceOpen => String::from("`{`"),
+            DotTokenKind::BraceClose => String::from("`}`"),
+            DotTokenKind::BracketOpen => String::from("`[`"),
+            DotTokenKind::BracketClose => String::from("`]`"),
+            DotTokenKind::Equals => String::from("`=`"),
+            DotTokenKind::Semicolon => String::from("`;`"),
+            DotTokenKind::Comma => String::from("`,`"),
+            DotTokenKind::Colon => String::from("`:`"),
+            DotTokenKind::EdgeOp { directed: true } => String::from("`->`"),
+            DotTokenKind::EdgeOp { directed: false } => String::from("`--`"),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use disposition_input_model::{
+    theme::{CssClassPartials, IdOrDefaults, ThemeAttr, ThemeStyles},
+    thing::ThingId,
+    InputDiagram,
+};
+use disposition_model_common::{entity::EntityTypeId, Id};
+
+/// Inserts entries that importers of other diagram formats have in common
+/// into an `InputDiagram`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ImportDiagramInsert;
+
+impl ImportDiagramInsert {
+    /// Adds the name of the thing if it differs from its ID.
+    pub(crate) fn thing_name(
+        input_diagram: &mut InputDiagram<'static>,
+        thing_id: &ThingId<'static>,
+        name: &str,
+    ) {
+        if thing_id.as_str() != name {
+            input_diagram
+                .thing_names
+                .insert(thing_id.clone(), name.to_string());
+        }
+    }
+
+    /// Adds a node style for the entity type, e.g. a shape color for
+    /// `type_crate_bin`.
+    pub(crate) fn type_node_style(
+        input_diagram: &mut InputDiagram<'static>,
+        type_id: Id<'static>,
+        theme_attr: ThemeAttr,
+        value: &str,
+    ) {
+        let mut css_class_partials = CssClassPartials::new();
+        css_class_partials.insert(theme_attr, value.to_string());
+        let mut theme_styles = ThemeStyles::new();
+        theme_styles.insert(IdOrDefaults::NodeDefaults, css_class_partials);
+        input_diagram
+            .theme_types_styles
+            .insert(EntityTypeId::from(type_id), theme_styles);
+    }
+}
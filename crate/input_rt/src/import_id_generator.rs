@@ -0,0 +1,87 @@
+use std::hash::Hash;
+
+use disposition_input_model::thing::ThingId;
+use disposition_model_common::{edge::EdgeGroupId, Id, Map, Set};
+
+/// Generates unique IDs for things and edge groups imported from other
+/// diagram formats.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ImportIdGenerator {
+    /// IDs that have been generated.
+    ids_used: Set<String>,
+}
+
+impl ImportIdGenerator {
+    /// Returns a new `ImportIdGenerator`.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the ID with characters that are not valid in IDs replaced with
+    /// `_`, prefixed with `t_` if it is empty or starts with a digit.
+    pub(crate) fn sanitize(id: &str) -> String {
+        let mut id_sanitized = String::with_capacity(id.len() + 2);
+        if id.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+            id_sanitized.push_str("t_");
+        }
+        id.chars().for_each(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                id_sanitized.push(c);
+            } else {
+                id_sanitized.push('_');
+            }
+        });
+        id_sanitized
+    }
+
+    /// Returns the sanitized ID, with a `_2`, `_3`, ... suffix if it has
+    /// already been generated.
+    pub(crate) fn generate(&mut self, id: &str) -> Id<'static> {
+        let id = Self::sanitize(id);
+        let mut id_unique = id.clone();
+        let mut suffix = 2;
+        while self.ids_used.contains(&id_unique) {
+            id_unique = format!("{id}_{suffix}");
+            suffix += 1;
+        }
+        self.ids_used.insert(id_unique.clone());
+        Id::try_from(id_unique).expect("Expected sanitized ID to be valid.")
+    }
+
+    /// Returns a thing ID generated from the name of each key, keeping the
+    /// ID of a key's first occurrence if it appears more than once.
+    ///
+    /// Importers generate the IDs of their main things, e.g. services or
+    /// crates, with this before the IDs of the things that group them, e.g.
+    /// networks or directories, so that a grouping thing which happens to
+    /// have the same name as a main thing is the one that is renamed.
+    pub(crate) fn thing_ids_generate<'n, K>(
+        &mut self,
+        key_names: impl IntoIterator<Item = (K, &'n str)>,
+    ) -> Map<K, ThingId<'static>>
+    where
+        K: Eq + Hash,
+    {
+        let mut thing_ids = Map::new();
+        key_names.into_iter().for_each(|(key, name)| {
+            if !thing_ids.contains_key(&key) {
+                let thing_id = ThingId::from(self.generate(name));
+                thing_ids.insert(key, thing_id);
+            }
+        });
+        thing_ids
+    }
+
+    /// Returns a unique edge group ID for the things, e.g. `edge_a__b`.
+    pub(crate) fn edge_group_id_generate(
+        &mut self,
+        thing_ids: &[ThingId<'static>],
+    ) -> EdgeGroupId<'static> {
+        let thing_ids = thing_ids
+            .iter()
+            .map(|thing_id| thing_id.as_str())
+            .collect::<Vec<&str>>()
+            .join("__");
+        EdgeGroupId::from(self.generate(&format!("edge_{thing_ids}")))
+    }
+}
@@ -0,0 +1,47 @@
+use miette::SourceSpan;
+
+/// Calculates spans in the source of input diagrams, and of diagrams imported
+/// from other formats.
+#[derive(Clone, Copy, Debug)]
+pub struct ImportSourceSpan;
+
+impl ImportSourceSpan {
+    /// Returns the empty span at the 1-based line and column.
+    ///
+    /// Columns are counted in characters. Column `0` is treated as the start
+    /// of the line, and positions past the end of the source are clamped to
+    /// the end.
+    pub fn from_line_column(source: &str, line: usize, column: usize) -> SourceSpan {
+        let line_offset = source
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>()
+            .min(source.len());
+        let line_text = &source[line_offset..];
+        let line_text = line_text.split_inclusive('\n').next().unwrap_or(line_text);
+        let column_offset = line_text
+            .char_indices()
+            .map(|(char_offset, _)| char_offset)
+            .chain(std::iter::once(line_text.len()))
+            .nth(column.saturating_sub(1))
+            .unwrap_or(line_text.len());
+
+        SourceSpan::from((line_offset + column_offset, 0))
+    }
+
+    /// Returns the empty span at the location of the YAML error, or at the
+    /// start of the source if the error has no location.
+    pub(crate) fn from_yaml_error(source: &str, error: &serde_saphyr::Error) -> SourceSpan {
+        error
+            .location()
+            .map(|location| {
+                Self::from_line_column(
+                    source,
+                    usize::try_from(location.line()).unwrap_or(usize::MAX),
+                    usize::try_from(location.column()).unwrap_or(usize::MAX),
+                )
+            })
+            .unwrap_or_else(|| SourceSpan::from((0, 0)))
+    }
+}
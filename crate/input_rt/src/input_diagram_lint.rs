@@ -0,0 +1,189 @@
+use disposition_input_model::{
+    entity::EntityTypeId,
+    process::{ProcessId, ProcessStepId},
+    tag::TagId,
+    theme::StyleAlias,
+    thing::ThingId,
+    InputDiagramSpans,
+};
+use miette::SourceSpan;
+
+use crate::InputDiagramLintFix;
+
+/// Dead or likely unintended configuration in an `InputDiagram`.
+///
+/// Unlike `ModelToIrIssue`s, lints are about values that are valid, but have
+/// no effect on the diagram, or leave parts of it unconnected. They are
+/// reported as warnings, and most have a suggested [`InputDiagramLintFix`].
+///
+/// Every variant carries the `field_path` of the offending value, using `.`
+/// to separate map keys and `[n]` for sequence indices, e.g.
+/// `theme_default.style_aliases.my_alias`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum InputDiagramLint {
+    /// A custom style alias is not in any `style_aliases_applied` list.
+    #[error("`{field_path}`: style alias `{style_alias}` is not applied anywhere.")]
+    #[diagnostic(
+        code(disposition::lint::style_alias_unused),
+        severity(Warning),
+        help(
+            "Remove `{style_alias}` from `theme_default.style_aliases`, or add it to a \
+             `style_aliases_applied` list."
+        )
+    )]
+    StyleAliasUnused {
+        /// Path to the offending key, e.g.
+        /// `theme_default.style_aliases.my_alias`.
+        field_path: String,
+        /// The unused style alias.
+        style_alias: StyleAlias<'static>,
+        /// Location of the offending key in the input YAML, if known.
+        #[label("unused style alias")]
+        span: Option<SourceSpan>,
+    },
+
+    /// A custom entity type is styled in `theme_types_styles`, but no entity in
+    /// `entity_types` has that type.
+    #[error("`{field_path}`: no entity has type `{entity_type_id}`.")]
+    #[diagnostic(
+        code(disposition::lint::theme_types_styles_type_unused),
+        severity(Warning),
+        help(
+            "Remove `{entity_type_id}` from `theme_types_styles`, or add it to an entity in \
+             `entity_types`."
+        )
+    )]
+    ThemeTypesStylesTypeUnused {
+        /// Path to the offending key, e.g. `theme_types_styles.type_server`.
+        field_path: String,
+        /// The unused entity type.
+        entity_type_id: EntityTypeId<'static>,
+        /// Location of the offending key in the input YAML, if known.
+        #[label("unused entity type")]
+        span: Option<SourceSpan>,
+    },
+
+    /// A tag has no things in `tag_things`.
+    #[error("`{field_path}`: tag `{tag_id}` has no things.")]
+    #[diagnostic(
+        code(disposition::lint::tag_things_empty),
+        severity(Warning),
+        help("Add things to `tag_things.{tag_id}`, or remove the tag.")
+    )]
+    TagThingsEmpty {
+        /// Path to the offending value, e.g. `tag_things.tag_a`, or
+        /// `tags.tag_a` if the tag has no `tag_things` entry.
+        field_path: String,
+        /// ID of the empty tag.
+        tag_id: TagId<'static>,
+        /// Location of the offending value in the input YAML, if known.
+        #[label("tag has no things")]
+        span: Option<SourceSpan>,
+    },
+
+    /// A thing with no nested things has no dependency, interaction, or layout
+    /// edges.
+    #[error("`{field_path}`: thing `{thing_id}` is not connected to anything.")]
+    #[diagnostic(
+        code(disposition::lint::thing_edgeless),
+        severity(Warning),
+        help(
+            "Add `{thing_id}` to an edge group in `thing_dependencies` or \
+             `thing_interactions`, or remove the thing."
+        )
+    )]
+    ThingEdgeless {
+        /// Path to the offending key, e.g. `things.t_parent.t_child`.
+        field_path: String,
+        /// ID of the thing without edges.
+        thing_id: ThingId<'static>,
+        /// Location of the offending key in the input YAML, if known.
+        #[label("thing has no edges")]
+        span: Option<SourceSpan>,
+    },
+
+    /// A process step is not in its process's `step_thing_interactions`.
+    #[error(
+        "`{field_path}`: step `{process_step_id}` has no `step_thing_interactions` in process \
+         `{process_id}`."
+    )]
+    #[diagnostic(
+        code(disposition::lint::process_step_interactions_missing),
+        severity(Warning),
+        help(
+            "Add `{process_step_id}` to `processes.{process_id}.step_thing_interactions` with the \
+             edge groups it interacts with."
+        )
+    )]
+    ProcessStepInteractionsMissing {
+        /// Path to the offending key, e.g.
+        /// `processes.proc_a.steps.proc_a_step_1`.
+        field_path: String,
+        /// ID of the process that the step belongs to.
+        process_id: ProcessId<'static>,
+        /// ID of the process step without interactions.
+        process_step_id: ProcessStepId<'static>,
+        /// Location of the offending key in the input YAML, if known.
+        #[label("step has no interactions")]
+        span: Option<SourceSpan>,
+    },
+}
+
+impl InputDiagramLint {
+    /// Returns the path to the offending value in the input diagram.
+    ///
+    /// Map keys are separated by `.`, and sequence items are suffixed with
+    /// `[n]`, e.g. `theme_default.style_aliases.my_alias`.
+    pub fn field_path(&self) -> &str {
+        match self {
+            InputDiagramLint::StyleAliasUnused { field_path, .. }
+            | InputDiagramLint::ThemeTypesStylesTypeUnused { field_path, .. }
+            | InputDiagramLint::TagThingsEmpty { field_path, .. }
+            | InputDiagramLint::ThingEdgeless { field_path, .. }
+            | InputDiagramLint::ProcessStepInteractionsMissing { field_path, .. } => field_path,
+        }
+    }
+
+    /// Returns the suggested fix for this lint, if there is one that can be
+    /// applied automatically.
+    pub fn fix(&self) -> Option<InputDiagramLintFix> {
+        match self {
+            InputDiagramLint::StyleAliasUnused { style_alias, .. } => {
+                Some(InputDiagramLintFix::StyleAliasRemove(style_alias.clone()))
+            }
+            InputDiagramLint::ThemeTypesStylesTypeUnused { entity_type_id, .. } => Some(
+                InputDiagramLintFix::ThemeTypesStylesRemove(entity_type_id.clone()),
+            ),
+            InputDiagramLint::TagThingsEmpty { tag_id, .. } => {
+                Some(InputDiagramLintFix::TagRemove(tag_id.clone()))
+            }
+            InputDiagramLint::ThingEdgeless { thing_id, .. } => {
+                Some(InputDiagramLintFix::ThingRemove(thing_id.clone()))
+            }
+            // Which edge groups a step interacts with can't be guessed.
+            InputDiagramLint::ProcessStepInteractionsMissing { .. } => None,
+        }
+    }
+
+    /// Sets the span of this lint to the location of its `field_path` in the
+    /// input YAML, so that it is rendered as a label on the source snippet.
+    ///
+    /// The span is left unchanged if `input_diagram_spans` does not contain
+    /// the field path, e.g. when the value comes from the base diagram.
+    pub fn span_attach(&mut self, input_diagram_spans: &InputDiagramSpans) {
+        let Some(field_span) = input_diagram_spans.get(self.field_path()) else {
+            return;
+        };
+        let span_new = SourceSpan::from(field_span.key_or_value());
+
+        match self {
+            InputDiagramLint::StyleAliasUnused { span, .. }
+            | InputDiagramLint::ThemeTypesStylesTypeUnused { span, .. }
+            | InputDiagramLint::TagThingsEmpty { span, .. }
+            | InputDiagramLint::ThingEdgeless { span, .. }
+            | InputDiagramLint::ProcessStepInteractionsMissing { span, .. } => {
+                *span = Some(span_new)
+            }
+        }
+    }
+}
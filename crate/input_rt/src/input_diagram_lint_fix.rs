@@ -0,0 +1,67 @@
+use std::fmt;
+
+use disposition_input_model::{
+    entity::EntityTypeId, tag::TagId, theme::StyleAlias, thing::ThingId, InputDiagram,
+};
+use disposition_model_common::MapOrderedRemove;
+
+use crate::{TagsPageOps, ThingsPageOps};
+
+/// A suggested fix for an [`InputDiagramLint`].
+///
+/// Fixes are applied to the user's (unmerged) `InputDiagram`, and preserve
+/// the order of the remaining entries.
+///
+/// [`InputDiagramLint`]: crate::InputDiagramLint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputDiagramLintFix {
+    /// Remove the style alias from `theme_default.style_aliases`.
+    StyleAliasRemove(StyleAlias<'static>),
+    /// Remove the entity type's entry from `theme_types_styles`.
+    ThemeTypesStylesRemove(EntityTypeId<'static>),
+    /// Remove the tag from `tags` and `tag_things`.
+    TagRemove(TagId<'static>),
+    /// Remove the thing and all references to it.
+    ThingRemove(ThingId<'static>),
+}
+
+impl InputDiagramLintFix {
+    /// Applies this fix to the given input diagram.
+    pub fn apply(&self, input_diagram: &mut InputDiagram<'static>) {
+        match self {
+            InputDiagramLintFix::StyleAliasRemove(style_alias) => {
+                input_diagram
+                    .theme_default
+                    .style_aliases
+                    .remove_ordered(style_alias);
+            }
+            InputDiagramLintFix::ThemeTypesStylesRemove(entity_type_id) => {
+                input_diagram
+                    .theme_types_styles
+                    .remove_ordered(entity_type_id);
+            }
+            InputDiagramLintFix::TagRemove(tag_id) => {
+                TagsPageOps::tag_remove(input_diagram, tag_id.as_str());
+                TagsPageOps::tag_things_entry_remove(input_diagram, tag_id.as_str());
+            }
+            InputDiagramLintFix::ThingRemove(thing_id) => {
+                ThingsPageOps::thing_remove(input_diagram, thing_id.as_str());
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputDiagramLintFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputDiagramLintFix::StyleAliasRemove(style_alias) => {
+                write!(f, "Remove style alias `{style_alias}`")
+            }
+            InputDiagramLintFix::ThemeTypesStylesRemove(entity_type_id) => {
+                write!(f, "Remove styles for type `{entity_type_id}`")
+            }
+            InputDiagramLintFix::TagRemove(tag_id) => write!(f, "Remove tag `{tag_id}`"),
+            InputDiagramLintFix::ThingRemove(thing_id) => write!(f, "Remove thing `{thing_id}`"),
+        }
+    }
+}
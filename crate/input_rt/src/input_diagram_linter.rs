@@ -0,0 +1,212 @@
+use disposition_input_model::{
+    entity::EntityType,
+    theme::{CssClassPartials, StyleAlias, ThemeStyles},
+    thing::ThingHierarchy,
+    InputDiagram,
+};
+use disposition_model_common::Set;
+
+use crate::InputDiagramLint;
+
+/// Finds dead or likely unintended configuration in an `InputDiagram`.
+///
+/// The linter should be run on the merged diagram (from
+/// `InputDiagramMerger::merge`), so that style aliases and entity types
+/// defined in the base diagram are resolved the same way as during
+/// generation. Lints are returned in the order:
+///
+/// 1. Unused style aliases.
+/// 2. Unused `theme_types_styles` keys.
+/// 3. Tags with no things.
+/// 4. Things with no edges.
+/// 5. Process steps missing from `step_thing_interactions`.
+pub struct InputDiagramLinter;
+
+impl InputDiagramLinter {
+    /// Returns the lints for the given input diagram.
+    pub fn lint(input_diagram: &InputDiagram<'_>) -> Vec<InputDiagramLint> {
+        let mut lints = Vec::new();
+
+        Self::style_aliases_lint(input_diagram, &mut lints);
+        Self::theme_types_styles_lint(input_diagram, &mut lints);
+        Self::tags_lint(input_diagram, &mut lints);
+        Self::things_lint(input_diagram, &mut lints);
+        Self::process_steps_lint(input_diagram, &mut lints);
+
+        lints
+    }
+
+    /// Reports custom style aliases that are not applied by any style.
+    ///
+    /// Built-in aliases are not reported, since they are always defined.
+    fn style_aliases_lint(input_diagram: &InputDiagram<'_>, lints: &mut Vec<InputDiagramLint>) {
+        let style_aliases_applied = Self::style_aliases_applied(input_diagram);
+
+        input_diagram
+            .theme_default
+            .style_aliases
+            .keys()
+            .filter(|style_alias| style_alias.custom_id().is_some())
+            .filter(|style_alias| !style_aliases_applied.contains(style_alias.as_str()))
+            .for_each(|style_alias| {
+                lints.push(InputDiagramLint::StyleAliasUnused {
+                    field_path: format!("theme_default.style_aliases.{style_alias}"),
+                    style_alias: style_alias.clone().into_static(),
+                    span: None,
+                });
+            });
+    }
+
+    /// Returns the names of every style alias in a `style_aliases_applied`
+    /// list.
+    fn style_aliases_applied<'f>(input_diagram: &'f InputDiagram<'_>) -> Set<&'f str> {
+        let theme_default = &input_diagram.theme_default;
+        let theme_thing_dependencies_styles = &input_diagram.theme_thing_dependencies_styles;
+
+        let css_class_partials_from_theme_styles = [
+            &theme_default.base_styles,
+            &theme_default.process_step_selected_styles,
+            &theme_thing_dependencies_styles.things_included_styles,
+            &theme_thing_dependencies_styles.things_excluded_styles,
+        ]
+        .into_iter()
+        .chain(input_diagram.theme_types_styles.values())
+        .chain(input_diagram.theme_tag_things_focus.values())
+        .flat_map(|theme_styles: &ThemeStyles<'_>| theme_styles.values());
+
+        theme_default
+            .style_aliases
+            .values()
+            .chain(css_class_partials_from_theme_styles)
+            .flat_map(|css_class_partials: &CssClassPartials<'_>| {
+                css_class_partials.style_aliases_applied.iter()
+            })
+            .map(StyleAlias::as_str)
+            .collect()
+    }
+
+    /// Reports custom entity types in `theme_types_styles` that no entity
+    /// has.
+    ///
+    /// Built-in types are not reported, since they are assigned to entities
+    /// automatically.
+    fn theme_types_styles_lint(
+        input_diagram: &InputDiagram<'_>,
+        lints: &mut Vec<InputDiagramLint>,
+    ) {
+        let entity_types_used = input_diagram
+            .entity_types
+            .values()
+            .flatten()
+            .map(EntityType::as_str)
+            .collect::<Set<&str>>();
+
+        input_diagram
+            .theme_types_styles
+            .keys()
+            .filter(|entity_type_id| {
+                let entity_type =
+                    EntityType::from((*entity_type_id).clone().into_static().into_inner());
+                !entity_type.is_built_in()
+            })
+            .filter(|entity_type_id| !entity_types_used.contains(entity_type_id.as_str()))
+            .for_each(|entity_type_id| {
+                lints.push(InputDiagramLint::ThemeTypesStylesTypeUnused {
+                    field_path: format!("theme_types_styles.{entity_type_id}"),
+                    entity_type_id: entity_type_id.clone().into_static(),
+                    span: None,
+                });
+            });
+    }
+
+    /// Reports tags that have no things in `tag_things`.
+    fn tags_lint(input_diagram: &InputDiagram<'_>, lints: &mut Vec<InputDiagramLint>) {
+        input_diagram.tags.keys().for_each(|tag_id| {
+            let field_path = match input_diagram.tag_things.get(tag_id) {
+                Some(thing_ids) if !thing_ids.is_empty() => return,
+                Some(_) => format!("tag_things.{tag_id}"),
+                None => format!("tags.{tag_id}"),
+            };
+            lints.push(InputDiagramLint::TagThingsEmpty {
+                field_path,
+                tag_id: tag_id.clone().into_static(),
+                span: None,
+            });
+        });
+    }
+
+    /// Reports leaf things that are not in any dependency, interaction, or
+    /// layout edge.
+    ///
+    /// Container things are not reported, since they are connected through
+    /// their children.
+    fn things_lint(input_diagram: &InputDiagram<'_>, lints: &mut Vec<InputDiagramLint>) {
+        let ids_with_edges = input_diagram
+            .thing_dependencies
+            .values()
+            .chain(input_diagram.thing_interactions.values())
+            .flat_map(|edge_group| edge_group.things.iter().map(|thing_id| thing_id.as_str()))
+            .chain(
+                input_diagram
+                    .thing_layout_edges
+                    .values()
+                    .flat_map(|layout_edge| [layout_edge.from.as_str(), layout_edge.to.as_str()]),
+            )
+            .collect::<Set<&str>>();
+
+        Self::things_lint_hierarchy(&input_diagram.things, "things", &ids_with_edges, lints);
+    }
+
+    fn things_lint_hierarchy(
+        thing_hierarchy: &ThingHierarchy<'_>,
+        field_path_parent: &str,
+        ids_with_edges: &Set<&str>,
+        lints: &mut Vec<InputDiagramLint>,
+    ) {
+        thing_hierarchy
+            .iter()
+            .for_each(|(thing_id, thing_hierarchy_child)| {
+                let field_path = format!("{field_path_parent}.{thing_id}");
+                if !thing_hierarchy_child.is_empty() {
+                    Self::things_lint_hierarchy(
+                        thing_hierarchy_child,
+                        &field_path,
+                        ids_with_edges,
+                        lints,
+                    );
+                } else if !ids_with_edges.contains(thing_id.as_str()) {
+                    lints.push(InputDiagramLint::ThingEdgeless {
+                        field_path,
+                        thing_id: thing_id.clone().into_static(),
+                        span: None,
+                    });
+                }
+            });
+    }
+
+    /// Reports process steps that have no entry in their process's
+    /// `step_thing_interactions`.
+    fn process_steps_lint(input_diagram: &InputDiagram<'_>, lints: &mut Vec<InputDiagramLint>) {
+        input_diagram
+            .processes
+            .iter()
+            .for_each(|(process_id, process_diagram)| {
+                process_diagram
+                    .steps
+                    .keys()
+                    .filter(|process_step_id| {
+                        !process_diagram
+                            .step_thing_interactions
+                            .contains_key(*process_step_id)
+                    })
+                    .for_each(|process_step_id| {
+                        lints.push(InputDiagramLint::ProcessStepInteractionsMissing {
+                            field_path: format!("processes.{process_id}.steps.{process_step_id}"),
+                            process_id: process_id.clone().into_static(),
+                            process_step_id: process_step_id.clone().into_static(),
+                            span: None,
+                        });
+                    });
+            });
+    }
+}
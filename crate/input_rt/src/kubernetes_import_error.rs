@@ -0,0 +1,20 @@
+use miette::SourceSpan;
+
+/// Errors when reading Kubernetes manifests via `KubernetesManifests`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum KubernetesImportError {
+    /// The manifest is not valid YAML, or a document does not have the shape
+    /// of a Kubernetes object.
+    #[error("Kubernetes manifest is invalid: {detail}")]
+    #[diagnostic(
+        code(disposition::kubernetes_import::yaml_invalid),
+        help("Each YAML document must be an object with a `kind`.")
+    )]
+    YamlInvalid {
+        /// Description of what is invalid.
+        detail: String,
+        /// Location of the invalid YAML.
+        #[label("invalid")]
+        span: SourceSpan,
+    },
+}
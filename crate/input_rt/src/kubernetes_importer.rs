@@ -0,0 +1,422 @@
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind},
+    tag::TagId,
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{Map, Set};
+
+use crate::{
+    import_id_generator::ImportIdGenerator,
+    kubernetes_importer::kubernetes_object::{
+        ConfigRef, KubernetesObject, ObjectMeta, PodSpec, PodTemplateSpec,
+    },
+};
+
+pub use self::kubernetes_manifests::KubernetesManifests;
+
+mod kubernetes_manifests;
+mod kubernetes_object;
+
+/// Namespace of objects that do not specify one.
+const NAMESPACE_DEFAULT: &str = "default";
+
+/// Labels that tags are derived from, with the prefix of the tag ID and name
+/// for each label.
+///
+/// Labels with the same prefix share tags, e.g. `app: web` and
+/// `app.kubernetes.io/name: web` are both tagged `app: web`.
+const TAG_LABELS: &[(&str, &str)] = &[
+    ("app.kubernetes.io/part-of", "part of"),
+    ("app.kubernetes.io/name", "app"),
+    ("app", "app"),
+    ("team", "team"),
+];
+
+/// Imports Kubernetes manifests as an `InputDiagram` of the objects in each
+/// namespace.
+///
+/// * Namespaces become things that contain the things for their objects, which
+///   are named `<kind>/<name>`, e.g. `deployment/web`.
+/// * `Deployment`s, `StatefulSet`s, `DaemonSet`s, `ReplicaSet`s, `Job`s, and
+///   `CronJob`s contain a `pod template` thing, whose description lists its
+///   containers' images. `Pod`s are imported the same way as pod templates.
+/// * Each `Service` whose selector matches a workload's pod template labels
+///   becomes a `thing_interactions` edge group from the service to the
+///   workload.
+/// * Each `Ingress` that routes to a `Service` becomes a `thing_interactions`
+///   edge group from the ingress to the service, described by the hosts and
+///   paths that are routed.
+/// * Each `ConfigMap` and `Secret` that a pod mounts or reads environment
+///   variables from becomes a `thing_dependencies` `sequence` edge group to the
+///   pod template. Referenced objects that are not in the manifests are added,
+///   as they are commonly generated.
+/// * `app.kubernetes.io/part-of`, `app.kubernetes.io/name`, `app`, and `team`
+///   labels become tags, e.g. `app: web`, so each app or team can be focused
+///   on.
+///
+/// Other kinds are not imported.
+#[derive(Clone, Copy, Debug)]
+pub struct KubernetesImporter;
+
+impl KubernetesImporter {
+    /// Returns the input diagram for the objects in the manifests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     KubernetesImporter, KubernetesManifests,
+    /// # };
+    /// #
+    /// let mut kubernetes_manifests = KubernetesManifests::new();
+    /// kubernetes_manifests
+    ///     .extend_from_yaml(
+    ///         "\
+    /// kind: Deployment
+    /// metadata: { name: web, namespace: shop }
+    /// spec:
+    ///   template:
+    ///     metadata: { labels: { app: web } }
+    ///     spec:
+    ///       containers: [{ name: web, image: 'nginx:1.27' }]
+    /// ---
+    /// kind: Service
+    /// metadata: { name: web, namespace: shop }
+    /// spec:
+    ///   selector: { app: web }
+    /// ",
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let input_diagram = KubernetesImporter::import(&kubernetes_manifests);
+    ///
+    /// let shop = &input_diagram.things[&parse_thing_id("namespace_shop").unwrap()];
+    /// let deployment = &shop[&parse_thing_id("deployment_web").unwrap()];
+    /// assert!(deployment.contains_key(&parse_thing_id("deployment_web_pod").unwrap()));
+    /// assert!(input_diagram
+    ///     .thing_interactions
+    ///     .contains_key(&parse_edge_group_id("edge_service_web__deployment_web").unwrap()));
+    /// ```
+    pub fn import(kubernetes_manifests: &KubernetesManifests) -> InputDiagram<'static> {
+        let mut ctx = KubernetesImportCtx {
+            input_diagram: InputDiagram::new(),
+            id_generator: ImportIdGenerator::new(),
+            namespace_thing_ids: Map::new(),
+            object_thing_ids: Map::new(),
+            tag_ids: Map::new(),
+            workloads: Vec::new(),
+        };
+
+        kubernetes_manifests
+            .objects
+            .iter()
+            .for_each(|object| ctx.object_import(object));
+        kubernetes_manifests
+            .objects
+            .iter()
+            .for_each(|object| match object {
+                KubernetesObject::Service(service) => {
+                    ctx.service_interactions_import(&service.metadata, &service.spec.selector)
+                }
+                KubernetesObject::Ingress(ingress) => {
+                    ctx.ingress_interactions_import(&ingress.metadata, ingress.spec.routes())
+                }
+                _ => {}
+            });
+        let workloads = std::mem::take(&mut ctx.workloads);
+        workloads
+            .iter()
+            .for_each(|workload| ctx.config_dependencies_import(workload));
+
+        ctx.input_diagram
+    }
+}
+
+/// A workload or pod that has been imported.
+struct KubernetesWorkloadImported<'m> {
+    /// Namespace of the workload.
+    namespace: &'m str,
+    /// Thing ID of the workload.
+    thing_id: ThingId<'static>,
+    /// Thing ID of the pod template, which is the workload's thing ID for
+    /// pods.
+    pod_thing_id: ThingId<'static>,
+    /// Labels of the pods, which services select.
+    pod_labels: &'m Map<String, String>,
+    /// Containers and volumes of the pods.
+    pod_spec: &'m PodSpec,
+}
+
+/// State while mapping `KubernetesManifests` to an `InputDiagram`.
+struct KubernetesImportCtx<'m> {
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Generates thing, tag, and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each namespace, keyed by its name.
+    namespace_thing_ids: Map<&'m str, ThingId<'static>>,
+    /// Thing ID of each object, keyed by its namespace, kind, and name.
+    object_thing_ids: Map<(&'m str, &'static str, &'m str), ThingId<'static>>,
+    /// Tag ID of each label derived tag, keyed by its name, e.g. `app: web`.
+    tag_ids: Map<String, TagId<'static>>,
+    /// Workloads and pods, whose pods may be selected by services and depend
+    /// on config.
+    workloads: Vec<KubernetesWorkloadImported<'m>>,
+}
+
+impl<'m> KubernetesImportCtx<'m> {
+    /// Adds the thing for the object within its namespace's thing.
+    fn object_import(&mut self, object: &'m KubernetesObject) {
+        let (Some(kind_name), Some(metadata)) = (object.kind_name(), object.metadata()) else {
+            return;
+        };
+
+        if let KubernetesObject::Namespace(_) = object {
+            let namespace_thing_id = self.namespace_thing_id(&metadata.name);
+            self.tags_import(&namespace_thing_id, &metadata.labels);
+            return;
+        }
+
+        let namespace = metadata.namespace.as_deref().unwrap_or(NAMESPACE_DEFAULT);
+        let thing_id = self.object_thing_id(namespace, kind_name, &metadata.name);
+        self.tags_import(&thing_id, &metadata.labels);
+
+        if let Some(PodTemplateSpec {
+            metadata: pod_metadata,
+            spec: pod_spec,
+        }) = object.pod_template()
+        {
+            let pod_thing_id = ThingId::from(
+                self.id_generator
+                    .generate(&format!("{}_pod", thing_id.as_str())),
+            );
+            let namespace_thing_id = self.namespace_thing_id(namespace);
+            self.input_diagram.things[&namespace_thing_id]
+                .entry(thing_id.clone())
+                .or_default()
+                .insert(pod_thing_id.clone(), ThingHierarchy::new());
+            self.input_diagram
+                .thing_names
+                .insert(pod_thing_id.clone(), String::from("pod template"));
+            self.pod_details_import(&pod_thing_id, pod_metadata, pod_spec);
+
+            self.workloads.push(KubernetesWorkloadImported {
+                namespace,
+                thing_id,
+                pod_thing_id,
+                pod_labels: &pod_metadata.labels,
+                pod_spec,
+            });
+        } else if let KubernetesObject::Pod(pod) = object {
+            self.pod_details_import(&thing_id, &ObjectMeta::default(), &pod.spec);
+
+            self.workloads.push(KubernetesWorkloadImported {
+                namespace,
+                thing_id: thing_id.clone(),
+                pod_thing_id: thing_id,
+                pod_labels: &pod.metadata.labels,
+                pod_spec: &pod.spec,
+            });
+        }
+    }
+
+    /// Returns the thing ID of the namespace, adding its thing if it has not
+    /// been added.
+    fn namespace_thing_id(&mut self, namespace: &'m str) -> ThingId<'static> {
+        if let Some(namespace_thing_id) = self.namespace_thing_ids.get(namespace) {
+            return namespace_thing_id.clone();
+        }
+
+        let namespace_thing_id = ThingId::from(
+            self.id_generator
+                .generate(&format!("namespace_{namespace}")),
+        );
+        self.input_diagram
+            .things
+            .insert(namespace_thing_id.clone(), ThingHierarchy::new());
+        self.input_diagram
+            .thing_names
+            .insert(namespace_thing_id.clone(), format!("namespace/{namespace}"));
+        self.namespace_thing_ids
+            .insert(namespace, namespace_thing_id.clone());
+        namespace_thing_id
+    }
+
+    /// Returns the thing ID of the object, adding its thing within its
+    /// namespace's thing if it has not been added.
+    fn object_thing_id(
+        &mut self,
+        namespace: &'m str,
+        kind_name: &'static str,
+        name: &'m str,
+    ) -> ThingId<'static> {
+        if let Some(thing_id) = self.object_thing_ids.get(&(namespace, kind_name, name)) {
+            return thing_id.clone();
+        }
+
+        let namespace_thing_id = self.namespace_thing_id(namespace);
+        let thing_id = ThingId::from(self.id_generator.generate(&format!("{kind_name}_{name}")));
+        self.input_diagram.things[&namespace_thing_id]
+            .insert(thing_id.clone(), ThingHierarchy::new());
+        self.input_diagram
+            .thing_names
+            .insert(thing_id.clone(), format!("{kind_name}/{name}"));
+        self.object_thing_ids
+            .insert((namespace, kind_name, name), thing_id.clone());
+        thing_id
+    }
+
+    /// Adds the pod's labels as tags, and its containers' images as its
+    /// description.
+    fn pod_details_import(
+        &mut self,
+        pod_thing_id: &ThingId<'static>,
+        pod_metadata: &ObjectMeta,
+        pod_spec: &PodSpec,
+    ) {
+        self.tags_import(pod_thing_id, &pod_metadata.labels);
+
+        let containers_desc = pod_spec
+            .containers
+            .iter()
+            .map(|container| match container.image.as_deref() {
+                Some(image) => format!("* `{}`: `{image}`", container.name),
+                None => format!("* `{}`", container.name),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        if !containers_desc.is_empty() {
+            self.input_diagram
+                .thing_descs
+                .insert(pod_thing_id.clone().into_inner(), containers_desc);
+        }
+    }
+
+    /// Adds the thing to the tags derived from its labels.
+    fn tags_import(&mut self, thing_id: &ThingId<'static>, labels: &Map<String, String>) {
+        TAG_LABELS.iter().for_each(|(label_key, tag_prefix)| {
+            let Some(label_value) = labels
+                .get(*label_key)
+                .filter(|label_value| !label_value.is_empty())
+            else {
+                return;
+            };
+
+            let tag_name = format!("{tag_prefix}: {label_value}");
+            let tag_id = match self.tag_ids.get(&tag_name) {
+                Some(tag_id) => tag_id.clone(),
+                None => {
+                    let tag_id = TagId::from(self.id_generator.generate(&format!(
+                        "tag_{}_{label_value}",
+                        tag_prefix.replace(' ', "_")
+                    )));
+                    self.input_diagram
+                        .tags
+                        .insert(tag_id.clone(), tag_name.clone());
+                    self.tag_ids.insert(tag_name, tag_id.clone());
+                    tag_id
+                }
+            };
+            self.input_diagram
+                .tag_things
+                .entry(tag_id)
+                .or_default()
+                .insert(thing_id.clone());
+        });
+    }
+
+    /// Adds an interaction from the service to each workload whose pods it
+    /// selects.
+    fn service_interactions_import(
+        &mut self,
+        metadata: &'m ObjectMeta,
+        selector: &Map<String, String>,
+    ) {
+        if selector.is_empty() {
+            return;
+        }
+
+        let namespace = metadata.namespace.as_deref().unwrap_or(NAMESPACE_DEFAULT);
+        let service_thing_id = self.object_thing_id(namespace, "service", &metadata.name);
+        let workload_thing_ids = self
+            .workloads
+            .iter()
+            .filter(|workload| workload.namespace == namespace)
+            .filter(|workload| {
+                selector
+                    .iter()
+                    .all(|(key, value)| workload.pod_labels.get(key) == Some(value))
+            })
+            .map(|workload| workload.thing_id.clone())
+            .collect::<Vec<ThingId<'static>>>();
+
+        workload_thing_ids
+            .into_iter()
+            .for_each(|workload_thing_id| {
+                let things = vec![service_thing_id.clone(), workload_thing_id];
+                let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+                self.input_diagram
+                    .thing_interactions
+                    .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+            });
+    }
+
+    /// Adds an interaction from the ingress to each service it routes to,
+    /// described by the routes.
+    fn ingress_interactions_import(
+        &mut self,
+        metadata: &'m ObjectMeta,
+        routes: Vec<(&'m str, String)>,
+    ) {
+        let namespace = metadata.namespace.as_deref().unwrap_or(NAMESPACE_DEFAULT);
+        let ingress_thing_id = self.object_thing_id(namespace, "ingress", &metadata.name);
+
+        let mut service_routes = Map::<&'m str, Vec<String>>::new();
+        routes.into_iter().for_each(|(service_name, route)| {
+            let routes = service_routes.entry(service_name).or_default();
+            if !routes.contains(&route) {
+                routes.push(route);
+            }
+        });
+        service_routes
+            .into_iter()
+            .for_each(|(service_name, routes)| {
+                let service_thing_id = self.object_thing_id(namespace, "service", service_name);
+                let things = vec![ingress_thing_id.clone(), service_thing_id];
+                let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+                self.input_diagram
+                    .edge_descs
+                    .insert(edge_group_id.clone().into_inner(), routes.join(", "));
+                self.input_diagram
+                    .thing_interactions
+                    .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+            });
+    }
+
+    /// Adds a dependency from each `ConfigMap` and `Secret` that the
+    /// workload's pods reference to the pod template.
+    fn config_dependencies_import(&mut self, workload: &KubernetesWorkloadImported<'m>) {
+        let config_thing_ids = workload
+            .pod_spec
+            .config_refs()
+            .into_iter()
+            .map(|config_ref| {
+                let kind_name = match config_ref {
+                    ConfigRef::ConfigMap(_) => "configmap",
+                    ConfigRef::Secret(_) => "secret",
+                };
+                self.object_thing_id(workload.namespace, kind_name, config_ref.name())
+            })
+            .collect::<Set<ThingId<'static>>>();
+
+        config_thing_ids.into_iter().for_each(|config_thing_id| {
+            let things = vec![config_thing_id, workload.pod_thing_id.clone()];
+            let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+            self.input_diagram
+                .thing_dependencies
+                .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+        });
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    import_source_span::ImportSourceSpan, kubernetes_importer::kubernetes_object::KubernetesObject,
+    KubernetesImportError,
+};
+
+/// Kubernetes objects read from one or more manifests, to import via
+/// `KubernetesImporter`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use disposition_input_rt::KubernetesManifests;
+/// #
+/// let mut kubernetes_manifests = KubernetesManifests::new();
+/// kubernetes_manifests
+///     .extend_from_yaml(
+///         "\
+/// kind: ConfigMap
+/// metadata: { name: app-config }
+/// ---
+/// kind: Secret
+/// metadata: { name: app-secret }
+/// ",
+///     )
+///     .unwrap();
+///
+/// assert_eq!(2, kubernetes_manifests.len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KubernetesManifests {
+    /// Objects in the order they were read.
+    pub(crate) objects: Vec<KubernetesObject>,
+}
+
+impl KubernetesManifests {
+    /// Returns a new empty `KubernetesManifests`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the objects from a manifest, which may contain multiple YAML
+    /// documents separated by `---`.
+    ///
+    /// Objects of kinds that are not imported are read, but ignored.
+    pub fn extend_from_yaml(&mut self, manifest_yaml: &str) -> Result<(), KubernetesImportError> {
+        let objects =
+            serde_saphyr::from_multiple::<KubernetesObject>(manifest_yaml).map_err(|error| {
+                KubernetesImportError::YamlInvalid {
+                    detail: error.without_snippet().to_string(),
+                    span: ImportSourceSpan::from_yaml_error(manifest_yaml, &error),
+                }
+            })?;
+        self.objects.extend(objects);
+
+        Ok(())
+    }
+
+    /// Returns the number of objects that have been read.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns whether no objects have been read.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
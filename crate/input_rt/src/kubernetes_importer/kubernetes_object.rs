@@ -0,0 +1,487 @@
+use disposition_model_common::Map;
+use serde::Deserialize;
+
+/// The parts of a Kubernetes manifest document that are imported, by `kind`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "kind")]
+pub(crate) enum KubernetesObject {
+    /// `Deployment` workload.
+    Deployment(KubernetesWorkload),
+    /// `StatefulSet` workload.
+    StatefulSet(KubernetesWorkload),
+    /// `DaemonSet` workload.
+    DaemonSet(KubernetesWorkload),
+    /// `ReplicaSet` workload.
+    ReplicaSet(KubernetesWorkload),
+    /// `Job` workload.
+    Job(KubernetesWorkload),
+    /// `CronJob` workload, whose pod template is within its job template.
+    CronJob(KubernetesCronJob),
+    /// `Pod` that is not managed by a workload.
+    Pod(KubernetesPod),
+    /// `Service` that routes to pods by label selector.
+    Service(KubernetesService),
+    /// `Ingress` that routes to services by host and path.
+    Ingress(KubernetesIngress),
+    /// `ConfigMap` that pods mount or read environment variables from.
+    ConfigMap(KubernetesNamed),
+    /// `Secret` that pods mount or read environment variables from.
+    Secret(KubernetesNamed),
+    /// `Namespace` that contains other objects.
+    Namespace(KubernetesNamed),
+    /// Kinds that are not imported.
+    #[serde(other)]
+    Other,
+}
+
+impl KubernetesObject {
+    /// Returns the lowercase kind of the object, e.g. `deployment`, or `None`
+    /// for kinds that are not imported.
+    pub(crate) fn kind_name(&self) -> Option<&'static str> {
+        let kind_name = match self {
+            Self::Deployment(_) => "deployment",
+            Self::StatefulSet(_) => "statefulset",
+            Self::DaemonSet(_) => "daemonset",
+            Self::ReplicaSet(_) => "replicaset",
+            Self::Job(_) => "job",
+            Self::CronJob(_) => "cronjob",
+            Self::Pod(_) => "pod",
+            Self::Service(_) => "service",
+            Self::Ingress(_) => "ingress",
+            Self::ConfigMap(_) => "configmap",
+            Self::Secret(_) => "secret",
+            Self::Namespace(_) => "namespace",
+            Self::Other => return None,
+        };
+        Some(kind_name)
+    }
+
+    /// Returns the name, namespace, and labels of the object, or `None` for
+    /// kinds that are not imported.
+    pub(crate) fn metadata(&self) -> Option<&ObjectMeta> {
+        match self {
+            Self::Deployment(workload)
+            | Self::StatefulSet(workload)
+            | Self::DaemonSet(workload)
+            | Self::ReplicaSet(workload)
+            | Self::Job(workload) => Some(&workload.metadata),
+            Self::CronJob(cron_job) => Some(&cron_job.metadata),
+            Self::Pod(pod) => Some(&pod.metadata),
+            Self::Service(service) => Some(&service.metadata),
+            Self::Ingress(ingress) => Some(&ingress.metadata),
+            Self::ConfigMap(named) | Self::Secret(named) | Self::Namespace(named) => {
+                Some(&named.metadata)
+            }
+            Self::Other => None,
+        }
+    }
+
+    /// Returns the pod template of a workload, or `None` for other kinds.
+    pub(crate) fn pod_template(&self) -> Option<&PodTemplateSpec> {
+        match self {
+            Self::Deployment(workload)
+            | Self::StatefulSet(workload)
+            | Self::DaemonSet(workload)
+            | Self::ReplicaSet(workload)
+            | Self::Job(workload) => Some(&workload.spec.template),
+            Self::CronJob(cron_job) => Some(&cron_job.spec.job_template.spec.template),
+            _ => None,
+        }
+    }
+}
+
+/// Name, namespace, and labels of an object.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ObjectMeta {
+    /// Name of the object, unique per kind within its namespace.
+    pub(crate) name: String,
+    /// Namespace of the object, `None` for the `default` namespace.
+    pub(crate) namespace: Option<String>,
+    /// Labels used to select and group objects.
+    pub(crate) labels: Map<String, String>,
+}
+
+/// An object whose only imported part is its metadata.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesNamed {
+    /// Name, namespace, and labels of the object.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+}
+
+/// A workload that runs pods from a pod template.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesWorkload {
+    /// Name, namespace, and labels of the workload.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+    /// Desired state of the workload.
+    #[serde(default)]
+    pub(crate) spec: WorkloadSpec,
+}
+
+/// Desired state of a workload.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct WorkloadSpec {
+    /// Template of the pods the workload runs.
+    pub(crate) template: PodTemplateSpec,
+}
+
+/// A `CronJob`, which runs a job on a schedule.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesCronJob {
+    /// Name, namespace, and labels of the cron job.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+    /// Desired state of the cron job.
+    #[serde(default)]
+    pub(crate) spec: CronJobSpec,
+}
+
+/// Desired state of a `CronJob`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct CronJobSpec {
+    /// Template of the job that is run.
+    pub(crate) job_template: JobTemplateSpec,
+}
+
+/// Template of a job run by a `CronJob`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct JobTemplateSpec {
+    /// Desired state of the job.
+    pub(crate) spec: WorkloadSpec,
+}
+
+/// A `Pod` that is not managed by a workload.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesPod {
+    /// Name, namespace, and labels of the pod.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+    /// Containers and volumes of the pod.
+    #[serde(default)]
+    pub(crate) spec: PodSpec,
+}
+
+/// Template of the pods a workload runs.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct PodTemplateSpec {
+    /// Labels of the pods, which services select.
+    pub(crate) metadata: ObjectMeta,
+    /// Containers and volumes of the pods.
+    pub(crate) spec: PodSpec,
+}
+
+/// Containers and volumes of a pod.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct PodSpec {
+    /// Containers that run for the lifetime of the pod.
+    pub(crate) containers: Vec<Container>,
+    /// Containers that run to completion before `containers` start.
+    pub(crate) init_containers: Vec<Container>,
+    /// Volumes that containers may mount.
+    pub(crate) volumes: Vec<Volume>,
+}
+
+impl PodSpec {
+    /// Returns the names of the `ConfigMap`s and `Secret`s that the pod mounts
+    /// or reads environment variables from, in the order they are first
+    /// referenced.
+    pub(crate) fn config_refs(&self) -> Vec<ConfigRef<'_>> {
+        let volume_refs = self.volumes.iter().flat_map(|volume| {
+            let projected_refs = volume
+                .projected
+                .iter()
+                .flat_map(|projected| projected.sources.iter())
+                .flat_map(|source| {
+                    let config_map = source
+                        .config_map
+                        .as_ref()
+                        .map(|config_map| ConfigRef::ConfigMap(config_map.name.as_str()));
+                    let secret = source
+                        .secret
+                        .as_ref()
+                        .map(|secret| ConfigRef::Secret(secret.name.as_str()));
+                    config_map.into_iter().chain(secret)
+                });
+            let config_map = volume
+                .config_map
+                .as_ref()
+                .map(|config_map| ConfigRef::ConfigMap(config_map.name.as_str()));
+            let secret = volume
+                .secret
+                .as_ref()
+                .map(|secret| ConfigRef::Secret(secret.secret_name.as_str()));
+
+            config_map.into_iter().chain(secret).chain(projected_refs)
+        });
+        let container_refs = self
+            .init_containers
+            .iter()
+            .chain(self.containers.iter())
+            .flat_map(|container| {
+                let env_from_refs = container.env_from.iter().flat_map(|env_from| {
+                    let config_map = env_from
+                        .config_map_ref
+                        .as_ref()
+                        .map(|config_map| ConfigRef::ConfigMap(config_map.name.as_str()));
+                    let secret = env_from
+                        .secret_ref
+                        .as_ref()
+                        .map(|secret| ConfigRef::Secret(secret.name.as_str()));
+                    config_map.into_iter().chain(secret)
+                });
+                let env_refs = container
+                    .env
+                    .iter()
+                    .filter_map(|env_var| env_var.value_from.as_ref())
+                    .flat_map(|value_from| {
+                        let config_map = value_from
+                            .config_map_key_ref
+                            .as_ref()
+                            .map(|config_map| ConfigRef::ConfigMap(config_map.name.as_str()));
+                        let secret = value_from
+                            .secret_key_ref
+                            .as_ref()
+                            .map(|secret| ConfigRef::Secret(secret.name.as_str()));
+                        config_map.into_iter().chain(secret)
+                    });
+                env_from_refs.chain(env_refs)
+            });
+
+        let mut config_refs = Vec::new();
+        volume_refs
+            .chain(container_refs)
+            .filter(|config_ref| !config_ref.name().is_empty())
+            .for_each(|config_ref| {
+                if !config_refs.contains(&config_ref) {
+                    config_refs.push(config_ref);
+                }
+            });
+        config_refs
+    }
+}
+
+/// A `ConfigMap` or `Secret` referenced by a pod.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ConfigRef<'p> {
+    /// Name of a `ConfigMap`.
+    ConfigMap(&'p str),
+    /// Name of a `Secret`.
+    Secret(&'p str),
+}
+
+impl<'p> ConfigRef<'p> {
+    /// Returns the name of the referenced object.
+    pub(crate) fn name(self) -> &'p str {
+        match self {
+            Self::ConfigMap(name) | Self::Secret(name) => name,
+        }
+    }
+}
+
+/// A container in a pod.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct Container {
+    /// Name of the container.
+    pub(crate) name: String,
+    /// Image the container runs.
+    pub(crate) image: Option<String>,
+    /// Sources of environment variables for every key in an object.
+    pub(crate) env_from: Vec<EnvFromSource>,
+    /// Environment variables, which may be read from an object's key.
+    pub(crate) env: Vec<EnvVar>,
+}
+
+/// Source of environment variables for every key in an object.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct EnvFromSource {
+    /// `ConfigMap` to read the environment variables from.
+    pub(crate) config_map_ref: Option<NameRef>,
+    /// `Secret` to read the environment variables from.
+    pub(crate) secret_ref: Option<NameRef>,
+}
+
+/// An environment variable of a container.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct EnvVar {
+    /// Source of the value, when it is not written inline.
+    pub(crate) value_from: Option<EnvVarSource>,
+}
+
+/// Source of an environment variable's value.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct EnvVarSource {
+    /// `ConfigMap` key to read the value from.
+    pub(crate) config_map_key_ref: Option<NameRef>,
+    /// `Secret` key to read the value from.
+    pub(crate) secret_key_ref: Option<NameRef>,
+}
+
+/// A volume that containers may mount.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct Volume {
+    /// `ConfigMap` whose keys are mounted as files.
+    pub(crate) config_map: Option<NameRef>,
+    /// `Secret` whose keys are mounted as files.
+    pub(crate) secret: Option<SecretVolumeSource>,
+    /// Multiple sources mounted in one directory.
+    pub(crate) projected: Option<ProjectedVolumeSource>,
+}
+
+/// `Secret` mounted as a volume.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SecretVolumeSource {
+    /// Name of the `Secret`.
+    pub(crate) secret_name: String,
+}
+
+/// Multiple sources mounted in one directory.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ProjectedVolumeSource {
+    /// The sources that are mounted.
+    pub(crate) sources: Vec<VolumeProjection>,
+}
+
+/// A source of a projected volume.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct VolumeProjection {
+    /// `ConfigMap` whose keys are mounted as files.
+    pub(crate) config_map: Option<NameRef>,
+    /// `Secret` whose keys are mounted as files.
+    pub(crate) secret: Option<NameRef>,
+}
+
+/// Reference to an object in the same namespace by name.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct NameRef {
+    /// Name of the referenced object.
+    pub(crate) name: String,
+}
+
+/// A `Service`, which routes to pods by label selector.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesService {
+    /// Name, namespace, and labels of the service.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+    /// Desired state of the service.
+    #[serde(default)]
+    pub(crate) spec: ServiceSpec,
+}
+
+/// Desired state of a `Service`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ServiceSpec {
+    /// Labels of the pods that the service routes to.
+    pub(crate) selector: Map<String, String>,
+}
+
+/// An `Ingress`, which routes to services by host and path.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct KubernetesIngress {
+    /// Name, namespace, and labels of the ingress.
+    #[serde(default)]
+    pub(crate) metadata: ObjectMeta,
+    /// Desired state of the ingress.
+    #[serde(default)]
+    pub(crate) spec: IngressSpec,
+}
+
+/// Desired state of an `Ingress`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct IngressSpec {
+    /// Backend for requests that do not match any rule.
+    pub(crate) default_backend: Option<IngressBackend>,
+    /// Rules that route requests by host and path.
+    pub(crate) rules: Vec<IngressRule>,
+}
+
+impl IngressSpec {
+    /// Returns the routes of the ingress as `(service_name, route)` pairs,
+    /// where `route` is the host and path, e.g. `example.com/api`, or `*` for
+    /// the default backend.
+    pub(crate) fn routes(&self) -> Vec<(&str, String)> {
+        let default_route = self
+            .default_backend
+            .as_ref()
+            .and_then(IngressBackend::service_name)
+            .map(|service_name| (service_name, String::from("*")));
+        let rule_routes = self.rules.iter().flat_map(|rule| {
+            let host = rule.host.as_deref().unwrap_or_default();
+            rule.http
+                .iter()
+                .flat_map(|http| http.paths.iter())
+                .filter_map(move |path| {
+                    let service_name = path.backend.service_name()?;
+                    let path = path.path.as_deref().unwrap_or("/");
+                    Some((service_name, format!("{host}{path}")))
+                })
+        });
+
+        default_route.into_iter().chain(rule_routes).collect()
+    }
+}
+
+/// A rule of an `Ingress`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct IngressRule {
+    /// Host the rule applies to, or `None` for every host.
+    pub(crate) host: Option<String>,
+    /// Paths of the rule.
+    pub(crate) http: Option<HttpIngressRuleValue>,
+}
+
+/// Paths of an `Ingress` rule.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct HttpIngressRuleValue {
+    /// Paths and the backends they route to.
+    pub(crate) paths: Vec<HttpIngressPath>,
+}
+
+/// A path and the backend it routes to.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct HttpIngressPath {
+    /// Path that requests match, `None` for every path.
+    pub(crate) path: Option<String>,
+    /// Backend that matching requests are routed to.
+    pub(crate) backend: IngressBackend,
+}
+
+/// Backend of an `Ingress` route.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct IngressBackend {
+    /// Service to route to, `None` for resource backends.
+    pub(crate) service: Option<NameRef>,
+}
+
+impl IngressBackend {
+    /// Returns the name of the service to route to.
+    pub(crate) fn service_name(&self) -> Option<&str> {
+        self.service
+            .as_ref()
+            .map(|service| service.name.as_str())
+            .filter(|service_name| !service_name.is_empty())
+    }
+}
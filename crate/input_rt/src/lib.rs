@@ -7,31 +7,82 @@
 //! across different frontends.
 
 pub use crate::{
-    edge_group_card_ops::EdgeGroupCardOps, edge_labels_page_ops::EdgeLabelsPageOps,
-    entity_page_ops::EntityPageOps, entity_types_page_ops::EntityTypesPageOps,
-    flat_entry::FlatEntry, map_target::MapTarget, on_change_target::OnChangeTarget,
-    process_card_ops::ProcessCardOps, processes_page_ops::ProcessesPageOps,
+    cargo_metadata_import_error::CargoMetadataImportError,
+    cargo_metadata_import_options::CargoMetadataImportOptions,
+    cargo_metadata_importer::CargoMetadataImporter,
+    docker_compose_import_error::DockerComposeImportError,
+    docker_compose_importer::DockerComposeImporter,
+    dot_import_error::DotImportError,
+    dot_import_warning::DotImportWarning,
+    dot_importer::{DotImportOutcome, DotImporter},
+    edge_group_card_ops::EdgeGroupCardOps,
+    edge_labels_page_ops::EdgeLabelsPageOps,
+    entity_page_ops::EntityPageOps,
+    entity_types_page_ops::EntityTypesPageOps,
+    flat_entry::FlatEntry,
+    import_source_span::ImportSourceSpan,
+    input_diagram_lint::InputDiagramLint,
+    input_diagram_lint_fix::InputDiagramLintFix,
+    input_diagram_linter::InputDiagramLinter,
+    kubernetes_import_error::KubernetesImportError,
+    kubernetes_importer::{KubernetesImporter, KubernetesManifests},
+    map_target::MapTarget,
+    mermaid_import_error::MermaidImportError,
+    mermaid_import_warning::MermaidImportWarning,
+    mermaid_importer::{MermaidImportOutcome, MermaidImporter},
+    on_change_target::OnChangeTarget,
+    otlp_trace_import_error::OtlpTraceImportError,
+    otlp_trace_importer::OtlpTraceImporter,
+    process_card_ops::ProcessCardOps,
+    processes_page_ops::ProcessesPageOps,
     step_dependency_card_ops::StepDependencyCardOps,
     step_interaction_card_ops::StepInteractionCardOps,
-    style_aliases_section_ops::StyleAliasesSectionOps, tags_page_ops::TagsPageOps,
-    thing_layout_ops::ThingLayoutOps, things_page_ops::ThingsPageOps,
+    style_aliases_section_ops::StyleAliasesSectionOps,
+    tags_page_ops::TagsPageOps,
+    terraform_import_error::TerraformImportError,
+    terraform_importer::TerraformImporter,
+    thing_layout_ops::ThingLayoutOps,
+    things_page_ops::ThingsPageOps,
 };
 
 pub mod flat_entry;
 pub mod id_parse;
 pub mod id_rename;
 
+mod cargo_metadata_import_error;
+mod cargo_metadata_import_options;
+mod cargo_metadata_importer;
+mod docker_compose_import_error;
+mod docker_compose_importer;
+mod dot_import_error;
+mod dot_import_warning;
+mod dot_importer;
 mod edge_group_card_ops;
 mod edge_labels_page_ops;
 mod entity_page_ops;
 mod entity_types_page_ops;
+mod import_diagram_insert;
+mod import_id_generator;
+mod import_source_span;
+mod input_diagram_lint;
+mod input_diagram_lint_fix;
+mod input_diagram_linter;
+mod kubernetes_import_error;
+mod kubernetes_importer;
 mod map_target;
+mod mermaid_import_error;
+mod mermaid_import_warning;
+mod mermaid_importer;
 mod on_change_target;
+mod otlp_trace_import_error;
+mod otlp_trace_importer;
 mod process_card_ops;
 mod processes_page_ops;
 mod step_dependency_card_ops;
 mod step_interaction_card_ops;
 mod style_aliases_section_ops;
 mod tags_page_ops;
+mod terraform_import_error;
+mod terraform_importer;
 mod thing_layout_ops;
 mod things_page_ops;
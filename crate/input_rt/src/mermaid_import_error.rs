@@ -0,0 +1,66 @@
+use miette::SourceSpan;
+
+/// Errors when reading a Mermaid flowchart via `MermaidImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum MermaidImportError {
+    /// The diagram does not start with `flowchart` or `graph`.
+    #[error("expected `flowchart` or `graph`, found {found}.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::header_invalid),
+        help("Only Mermaid flowcharts can be imported.")
+    )]
+    HeaderInvalid {
+        /// Description of what was found, e.g. `` `sequenceDiagram` ``.
+        found: String,
+        /// Location of the diagram type in the Mermaid source.
+        #[label("expected `flowchart` or `graph`")]
+        span: SourceSpan,
+    },
+
+    /// Node text, link text, or front matter that is not closed.
+    #[error("{what} is not closed.")]
+    #[diagnostic(code(disposition::mermaid_import::unterminated))]
+    Unterminated {
+        /// What is not closed, e.g. `node text`.
+        what: &'static str,
+        /// Location of the start of the unclosed value in the Mermaid source.
+        #[label("opened here")]
+        span: SourceSpan,
+    },
+
+    /// Text other than what is expected.
+    #[error("expected {expected}, found {found}.")]
+    #[diagnostic(code(disposition::mermaid_import::token_unexpected))]
+    TokenUnexpected {
+        /// Description of what is expected, e.g. `node ID`.
+        expected: &'static str,
+        /// Description of what was found.
+        found: String,
+        /// Location of the unexpected text in the Mermaid source.
+        #[label("expected {expected}")]
+        span: SourceSpan,
+    },
+
+    /// An `end` without a `subgraph` to close.
+    #[error("`end` does not close a `subgraph`.")]
+    #[diagnostic(code(disposition::mermaid_import::subgraph_end_unexpected))]
+    SubgraphEndUnexpected {
+        /// Location of the `end` in the Mermaid source.
+        #[label("no subgraph to close")]
+        span: SourceSpan,
+    },
+
+    /// A `subgraph` without an `end`.
+    #[error("`subgraph` `{subgraph_id}` is not closed.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::subgraph_not_closed),
+        help("Add `end` after the subgraph's nodes and links.")
+    )]
+    SubgraphNotClosed {
+        /// ID of the subgraph.
+        subgraph_id: String,
+        /// Location of the `subgraph` in the Mermaid source.
+        #[label("opened here")]
+        span: SourceSpan,
+    },
+}
@@ -0,0 +1,130 @@
+use miette::SourceSpan;
+
+/// Parts of a Mermaid flowchart that were not imported, or were imported
+/// differently, by `MermaidImporter`.
+///
+/// Every variant carries the `span` of the offending text in the Mermaid
+/// source.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum MermaidImportWarning {
+    /// A statement that has no equivalent in the input diagram, e.g.
+    /// `classDef`.
+    #[error("`{statement}` statements are not supported, and were not imported.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::statement_unsupported),
+        severity(Warning)
+    )]
+    StatementUnsupported {
+        /// Keyword of the statement, e.g. `style`.
+        statement: String,
+        /// Location of the statement in the Mermaid source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A `direction` within a subgraph.
+    #[error("subgraph `direction` is not supported, and was not imported.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::direction_subgraph_unsupported),
+        severity(Warning),
+        help("The flowchart's direction applies to every subgraph.")
+    )]
+    DirectionSubgraphUnsupported {
+        /// Location of the `direction` statement in the Mermaid source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A node ID that is not a valid thing ID.
+    #[error("node `{mermaid_id}` was imported as `{thing_id}`.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::id_renamed),
+        severity(Warning),
+        help("Thing IDs may only contain letters, numbers, and underscores.")
+    )]
+    IdRenamed {
+        /// ID of the node or subgraph in the Mermaid source.
+        mermaid_id: String,
+        /// ID of the imported thing.
+        thing_id: String,
+        /// Location of the node's first appearance in the Mermaid source.
+        #[label("renamed")]
+        span: SourceSpan,
+    },
+
+    /// A node shape other than a rectangle or rounded rectangle.
+    #[error(
+        "{shape} shape of node `{node_id}` is not supported, and was imported as a rectangle."
+    )]
+    #[diagnostic(
+        code(disposition::mermaid_import::node_shape_unsupported),
+        severity(Warning)
+    )]
+    NodeShapeUnsupported {
+        /// ID of the node in the Mermaid source.
+        node_id: String,
+        /// Name of the shape, e.g. `rhombus`.
+        shape: &'static str,
+        /// Location of the node in the Mermaid source.
+        #[label("imported as a rectangle")]
+        span: SourceSpan,
+    },
+
+    /// A class applied with `:::`.
+    #[error("`:::` classes are not supported, and were not imported.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::class_unsupported),
+        severity(Warning)
+    )]
+    ClassUnsupported {
+        /// Location of the class in the Mermaid source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+
+    /// A Markdown string, which is imported as plain text.
+    #[error("Markdown text was imported as plain text.")]
+    #[diagnostic(code(disposition::mermaid_import::label_markdown), severity(Warning))]
+    LabelMarkdown {
+        /// Location of the text in the Mermaid source.
+        #[label("backticks removed")]
+        span: SourceSpan,
+    },
+
+    /// A node that appears in subgraphs that are not within each other.
+    #[error("node `{node_id}` is in multiple subgraphs, and was imported into the first.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::node_subgraph_multiple),
+        severity(Warning)
+    )]
+    NodeSubgraphMultiple {
+        /// ID of the node in the Mermaid source.
+        node_id: String,
+        /// Location of the node's appearance in another subgraph.
+        #[label("also in this subgraph")]
+        span: SourceSpan,
+    },
+
+    /// A link style other than a solid line with an arrow or no arrow head.
+    #[error("{style} links are not supported, and were imported as solid links.")]
+    #[diagnostic(
+        code(disposition::mermaid_import::link_style_unsupported),
+        severity(Warning)
+    )]
+    LinkStyleUnsupported {
+        /// Name of the style, e.g. `dotted`.
+        style: &'static str,
+        /// Location of the link in the Mermaid source.
+        #[label("imported as a solid link")]
+        span: SourceSpan,
+    },
+
+    /// An invisible link (`~~~`), which Mermaid uses to adjust layout.
+    #[error("invisible link was not imported.")]
+    #[diagnostic(code(disposition::mermaid_import::link_invisible), severity(Warning))]
+    LinkInvisible {
+        /// Location of the link in the Mermaid source.
+        #[label("not imported")]
+        span: SourceSpan,
+    },
+}
@@ -0,0 +1,280 @@
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind, EdgeLabel},
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{
+    edge::{EdgeId, EdgeIdGenerator},
+    Map,
+};
+
+use crate::{
+    import_id_generator::ImportIdGenerator,
+    mermaid_importer::{
+        mermaid_flowchart::{MermaidFlowchart, MermaidLinkKind, MermaidLinkStatement},
+        mermaid_parser::MermaidParser,
+    },
+    MermaidImportError, MermaidImportWarning,
+};
+
+pub use self::mermaid_import_outcome::MermaidImportOutcome;
+
+mod mermaid_flowchart;
+mod mermaid_import_outcome;
+mod mermaid_parser;
+
+/// Imports a Mermaid flowchart as an `InputDiagram`.
+///
+/// * Nodes become things, and subgraphs become things that contain the things
+///   within them. Node text and subgraph titles become thing names.
+/// * Each link statement becomes `thing_dependencies` edge groups, e.g. `a -->
+///   b --> c` becomes a `sequence` of `[a, b, c]`. `<-->` links become
+///   `symmetric` edge groups, and a link from a node to itself becomes a
+///   `cyclic` edge group.
+/// * Link text becomes the `from` label in `edge_labels`, for the edge group
+///   when every link in the group has the same text, otherwise for each edge.
+/// * The flowchart direction becomes `render_options.rank_dir`.
+///
+/// Links without arrow heads (`---`) are imported as `sequence` edge groups,
+/// as edges in the input diagram always have a direction. Invisible links
+/// (`~~~`) are not imported, as Mermaid uses them to adjust layout. Shapes,
+/// link styles, and statements that have no equivalent in the input diagram,
+/// such as `classDef`, are returned as [`MermaidImportWarning`]s.
+#[derive(Clone, Copy, Debug)]
+pub struct MermaidImporter;
+
+impl MermaidImporter {
+    /// Returns the input diagram for the Mermaid flowchart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     MermaidImporter,
+    /// # };
+    /// #
+    /// let mermaid_import_outcome = MermaidImporter::import(
+    ///     "flowchart LR
+    ///         subgraph aws [AWS]
+    ///             db[(Database)]
+    ///         end
+    ///         app[App] -->|queries| db",
+    /// )
+    /// .unwrap();
+    /// let input_diagram = mermaid_import_outcome.input_diagram;
+    ///
+    /// assert_eq!(2, input_diagram.things.len());
+    /// assert!(input_diagram
+    ///     .things
+    ///     .contains_key(&parse_thing_id("aws").unwrap()));
+    /// assert_eq!(
+    ///     Some("Database"),
+    ///     input_diagram
+    ///         .thing_names
+    ///         .get(&parse_thing_id("db").unwrap())
+    ///         .map(String::as_str)
+    /// );
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_app__db").unwrap()));
+    /// // `db[(Database)]` is a cylinder, which is imported as a rectangle.
+    /// assert_eq!(1, mermaid_import_outcome.warnings.len());
+    /// ```
+    pub fn import(mermaid: &str) -> Result<MermaidImportOutcome, MermaidImportError> {
+        let mut flowchart = MermaidParser::parse(mermaid)?;
+        let link_statements = std::mem::take(&mut flowchart.link_statements);
+        let warnings = std::mem::take(&mut flowchart.warnings);
+
+        let mut ctx = MermaidImportCtx {
+            flowchart: &flowchart,
+            input_diagram: InputDiagram::new(),
+            warnings,
+            id_generator: ImportIdGenerator::new(),
+            thing_ids: Map::with_capacity(flowchart.nodes.len()),
+        };
+
+        ctx.input_diagram.render_options.rank_dir = flowchart.rank_dir;
+        ctx.things_import();
+        ctx.input_diagram.things = ctx.thing_hierarchy_build(None);
+        link_statements
+            .iter()
+            .for_each(|link_statement| ctx.link_statement_import(link_statement));
+
+        Ok(MermaidImportOutcome {
+            input_diagram: ctx.input_diagram,
+            warnings: ctx.warnings,
+        })
+    }
+}
+
+/// State while mapping a [`MermaidFlowchart`] to an `InputDiagram`.
+struct MermaidImportCtx<'f> {
+    /// The parsed flowchart, without its link statements.
+    flowchart: &'f MermaidFlowchart,
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Warnings for the flowchart.
+    warnings: Vec<MermaidImportWarning>,
+    /// Generates thing and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each node and subgraph, keyed by its Mermaid ID.
+    thing_ids: Map<&'f str, ThingId<'static>>,
+}
+
+/// An edge group being built from the links in a link statement.
+struct EdgeChain<'f> {
+    /// Kind of the edge group.
+    kind: EdgeKind,
+    /// Things that the edges connect.
+    things: Vec<ThingId<'static>>,
+    /// Text of each edge.
+    labels: Vec<Option<&'f str>>,
+}
+
+impl<'f> MermaidImportCtx<'f> {
+    /// Assigns a unique thing ID and name to each node and subgraph, in the
+    /// order they first appear.
+    fn things_import(&mut self) {
+        let flowchart = self.flowchart;
+        flowchart.nodes.iter().for_each(|(mermaid_id, node)| {
+            let thing_id = ThingId::from(self.id_generator.generate(mermaid_id));
+            let is_renamed = thing_id.as_str() != mermaid_id;
+            if is_renamed {
+                self.warnings.push(MermaidImportWarning::IdRenamed {
+                    mermaid_id: mermaid_id.clone(),
+                    thing_id: thing_id.to_string(),
+                    span: node.span,
+                });
+            }
+
+            let name = node
+                .label
+                .clone()
+                .or_else(|| is_renamed.then(|| mermaid_id.clone()));
+            if let Some(name) = name.filter(|name| name != thing_id.as_str()) {
+                self.input_diagram
+                    .thing_names
+                    .insert(thing_id.clone(), name);
+            }
+            self.thing_ids.insert(mermaid_id, thing_id);
+        });
+    }
+
+    /// Returns the things within the subgraph, or the flowchart for `None`.
+    fn thing_hierarchy_build(&self, subgraph: Option<&str>) -> ThingHierarchy<'static> {
+        self.flowchart
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.subgraph.as_deref() == subgraph)
+            .map(|(mermaid_id, node)| {
+                let thing_hierarchy = if node.is_subgraph {
+                    self.thing_hierarchy_build(Some(mermaid_id))
+                } else {
+                    ThingHierarchy::new()
+                };
+                (self.thing_ids[mermaid_id.as_str()].clone(), thing_hierarchy)
+            })
+            .collect()
+    }
+
+    /// Adds an edge group for each chain of connected links in the link
+    /// statement.
+    fn link_statement_import(&mut self, link_statement: &'f MermaidLinkStatement) {
+        let MermaidLinkStatement { ends, links } = link_statement;
+        links
+            .iter()
+            .filter(|link| link.kind == MermaidLinkKind::Invisible)
+            .for_each(|link| {
+                self.warnings
+                    .push(MermaidImportWarning::LinkInvisible { span: link.span });
+            });
+
+        // Links that continue from the previous link are in the same group.
+        let mut chains: Vec<EdgeChain<'f>> = Vec::new();
+        links
+            .iter()
+            .enumerate()
+            .filter(|(_, link)| link.kind != MermaidLinkKind::Invisible)
+            .for_each(|(link_index, link)| {
+                let kind = if link.kind == MermaidLinkKind::ArrowBoth {
+                    EdgeKind::Symmetric
+                } else {
+                    EdgeKind::Sequence
+                };
+                let label = link.label.as_deref();
+                ends[link_index].iter().for_each(|mermaid_id_tail| {
+                    ends[link_index + 1].iter().for_each(|mermaid_id_head| {
+                        let thing_id_tail = &self.thing_ids[mermaid_id_tail.as_str()];
+                        let thing_id_head = &self.thing_ids[mermaid_id_head.as_str()];
+                        match chains.last_mut() {
+                            Some(chain)
+                                if chain.kind == kind
+                                    && chain.things.last() == Some(thing_id_tail) =>
+                            {
+                                chain.things.push(thing_id_head.clone());
+                                chain.labels.push(label);
+                            }
+                            _ => chains.push(EdgeChain {
+                                kind,
+                                things: vec![thing_id_tail.clone(), thing_id_head.clone()],
+                                labels: vec![label],
+                            }),
+                        }
+                    });
+                });
+            });
+
+        chains
+            .into_iter()
+            .for_each(|chain| self.edge_chain_import(chain));
+    }
+
+    fn edge_chain_import(&mut self, chain: EdgeChain<'f>) {
+        let EdgeChain {
+            mut kind,
+            mut things,
+            labels,
+        } = chain;
+        if things.len() == 2 && things[0] == things[1] {
+            things.pop();
+            kind = EdgeKind::Cyclic;
+        }
+        let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+
+        // A label for the edge group applies to both directions of a symmetric
+        // edge group, so it is only used when each edge is drawn once.
+        let label_group = labels.first().copied().flatten().filter(|label| {
+            kind != EdgeKind::Symmetric && labels.iter().all(|l| *l == Some(label))
+        });
+        match label_group {
+            Some(label) => {
+                self.input_diagram.edge_labels.insert(
+                    EdgeId::from(edge_group_id.clone().into_inner()),
+                    Self::edge_label(label),
+                );
+            }
+            None => labels
+                .iter()
+                .enumerate()
+                .filter_map(|(edge_index, label)| label.map(|label| (edge_index, label)))
+                .for_each(|(edge_index, label)| {
+                    let edge_id = EdgeIdGenerator::generate(&edge_group_id, edge_index);
+                    self.input_diagram
+                        .edge_labels
+                        .insert(edge_id, Self::edge_label(label));
+                }),
+        }
+
+        self.input_diagram
+            .thing_dependencies
+            .insert(edge_group_id, EdgeGroup::new(kind, things));
+    }
+
+    fn edge_label(label: &str) -> EdgeLabel {
+        EdgeLabel {
+            from: String::from(label),
+            to: String::new(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use disposition_model_common::{Map, RankDir};
+use miette::SourceSpan;
+
+use crate::MermaidImportWarning;
+
+/// A parsed Mermaid flowchart.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MermaidFlowchart {
+    /// Direction of the flowchart, e.g. `LR` in `flowchart LR`.
+    pub(crate) rank_dir: RankDir,
+    /// Nodes and subgraphs keyed by their Mermaid ID, in the order they first
+    /// appear.
+    pub(crate) nodes: Map<String, MermaidNode>,
+    /// Link statements, in the order they appear.
+    pub(crate) link_statements: Vec<MermaidLinkStatement>,
+    /// Warnings found while parsing.
+    pub(crate) warnings: Vec<MermaidImportWarning>,
+}
+
+/// A node or subgraph.
+///
+/// Subgraphs share IDs with nodes, as links may connect to a subgraph.
+#[derive(Clone, Debug)]
+pub(crate) struct MermaidNode {
+    /// ID of the innermost subgraph that the node is within.
+    pub(crate) subgraph: Option<String>,
+    /// Whether this is a subgraph.
+    pub(crate) is_subgraph: bool,
+    /// Text of the node, or title of the subgraph.
+    pub(crate) label: Option<String>,
+    /// Location where the node first appears in the Mermaid source.
+    pub(crate) span: SourceSpan,
+}
+
+/// A statement of nodes joined by links, e.g. `a --> b & c -.- d`.
+#[derive(Clone, Debug)]
+pub(crate) struct MermaidLinkStatement {
+    /// Mermaid IDs of the nodes at each end of each link.
+    ///
+    /// Each end has one or more nodes joined by `&`.
+    pub(crate) ends: Vec<Vec<String>>,
+    /// Links between each end, so there is one fewer link than ends.
+    pub(crate) links: Vec<MermaidLink>,
+}
+
+/// A link between two ends of a link statement, e.g. `-->|text|`.
+#[derive(Clone, Debug)]
+pub(crate) struct MermaidLink {
+    /// Arrow heads of the link.
+    pub(crate) kind: MermaidLinkKind,
+    /// Text of the link.
+    pub(crate) label: Option<String>,
+    /// Location of the link in the Mermaid source.
+    pub(crate) span: SourceSpan,
+}
+
+/// Arrow heads of a link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MermaidLinkKind {
+    /// No arrow head, e.g. `---`.
+    Open,
+    /// Arrow head at the end, e.g. `-->`.
+    Arrow,
+    /// Arrow head at both ends, e.g. `<-->`.
+    ArrowBoth,
+    /// Invisible link, `~~~`.
+    Invisible,
+}
+
+impl MermaidFlowchart {
+    /// Returns whether `subgraph` is `ancestor`, or is within it.
+    ///
+    /// `None` is the flowchart, which every subgraph is within.
+    pub(crate) fn subgraph_is_within(
+        &self,
+        subgraph: Option<&str>,
+        ancestor: Option<&str>,
+    ) -> bool {
+        let mut subgraph = subgraph;
+        loop {
+            if subgraph == ancestor {
+                return true;
+            }
+            match subgraph {
+                Some(subgraph_id) => {
+                    subgraph = self
+                        .nodes
+                        .get(subgraph_id)
+                        .and_then(|node| node.subgraph.as_deref())
+                }
+                None => return false,
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use disposition_input_model::InputDiagram;
+
+use crate::MermaidImportWarning;
+
+/// The input diagram imported from a Mermaid flowchart, and parts of the
+/// flowchart that were not imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MermaidImportOutcome {
+    /// The imported input diagram.
+    pub input_diagram: InputDiagram<'static>,
+    /// Statements, shapes, and link styles that were not imported, or were
+    /// imported differently.
+    pub warnings: Vec<MermaidImportWarning>,
+}
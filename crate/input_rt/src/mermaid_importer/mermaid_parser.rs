@@ -0,0 +1,718 @@
+use disposition_model_common::RankDir;
+use miette::SourceSpan;
+
+use crate::{
+    mermaid_importer::mermaid_flowchart::{
+        MermaidFlowchart, MermaidLink, MermaidLinkKind, MermaidLinkStatement, MermaidNode,
+    },
+    MermaidImportError, MermaidImportWarning,
+};
+
+/// Node shapes, as their opening and closing delimiters.
+///
+/// Longer delimiters are listed before the delimiters they start with. Shapes
+/// with a name are not supported.
+const NODE_SHAPES: &[(&str, &str, Option<&str>)] = &[
+    ("(((", ")))", Some("double circle")),
+    ("((", "))", Some("circle")),
+    ("([", "])", Some("stadium")),
+    ("[[", "]]", Some("subroutine")),
+    ("[(", ")]", Some("cylinder")),
+    ("[/", "/]", Some("parallelogram")),
+    ("[/", "\\]", Some("trapezoid")),
+    ("[\\", "\\]", Some("parallelogram")),
+    ("[\\", "/]", Some("trapezoid")),
+    ("{{", "}}", Some("hexagon")),
+    ("(", ")", None),
+    ("[", "]", None),
+    ("{", "}", Some("rhombus")),
+    (">", "]", Some("asymmetric")),
+];
+
+/// Statements that have no equivalent in the input diagram.
+const STATEMENTS_UNSUPPORTED: &[&str] = &[
+    "accDescr",
+    "accTitle",
+    "class",
+    "classDef",
+    "click",
+    "linkStyle",
+    "style",
+];
+
+/// Parses a Mermaid flowchart.
+///
+/// Mermaid flowcharts are line based, so this reads the source directly
+/// instead of splitting it into tokens first.
+pub(crate) struct MermaidParser<'src> {
+    /// The Mermaid source.
+    src: &'src str,
+    /// Byte offset of the next character to read.
+    offset: usize,
+    /// IDs and `subgraph` keyword spans of the subgraphs being parsed.
+    subgraphs: Vec<(String, SourceSpan)>,
+    /// The flowchart being built.
+    flowchart: MermaidFlowchart,
+}
+
+impl<'src> MermaidParser<'src> {
+    /// Returns the flowchart in the Mermaid source.
+    pub(crate) fn parse(src: &'src str) -> Result<MermaidFlowchart, MermaidImportError> {
+        let mut parser = MermaidParser {
+            src,
+            offset: 0,
+            subgraphs: Vec::new(),
+            flowchart: MermaidFlowchart::default(),
+        };
+
+        parser.front_matter_skip()?;
+        parser.header_parse()?;
+        loop {
+            parser.trivia_skip();
+            if parser.rest().is_empty() {
+                break;
+            }
+            parser.statement_parse()?;
+        }
+
+        if let Some((subgraph_id, span)) = parser.subgraphs.pop() {
+            return Err(MermaidImportError::SubgraphNotClosed { subgraph_id, span });
+        }
+
+        Ok(parser.flowchart)
+    }
+
+    /// Skips YAML front matter, e.g. `---\ntitle: Example\n---`.
+    fn front_matter_skip(&mut self) -> Result<(), MermaidImportError> {
+        self.trivia_skip();
+        let rest = self.rest();
+        if !rest.starts_with("---") {
+            return Ok(());
+        }
+        match rest[3..].find("\n---") {
+            Some(end) => {
+                self.offset += 3 + end + 4;
+                self.line_skip();
+                Ok(())
+            }
+            None => Err(MermaidImportError::Unterminated {
+                what: "front matter",
+                span: SourceSpan::from((self.offset, 3)),
+            }),
+        }
+    }
+
+    /// `(flowchart | graph) [direction]`
+    fn header_parse(&mut self) -> Result<(), MermaidImportError> {
+        self.trivia_skip();
+        let start = self.offset;
+        let word = self.word();
+        if !(word.eq_ignore_ascii_case("flowchart") || word.eq_ignore_ascii_case("graph")) {
+            self.offset = start;
+            return Err(MermaidImportError::HeaderInvalid {
+                found: self.found_describe(),
+                span: self.found_span(),
+            });
+        }
+
+        self.inline_skip();
+        if !self.is_statement_end() {
+            self.flowchart.rank_dir = self.direction_parse()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the direction, e.g. `LR`.
+    fn direction_parse(&mut self) -> Result<RankDir, MermaidImportError> {
+        let start = self.offset;
+        let direction = match self.rest().chars().next() {
+            Some(c @ ('<' | '>' | '^')) => {
+                self.offset += 1;
+                String::from(c)
+            }
+            _ => String::from(self.word()),
+        };
+        match direction.as_str() {
+            "TB" | "TD" | "v" => Ok(RankDir::TopToBottom),
+            "BT" | "^" => Ok(RankDir::BottomToTop),
+            "LR" | ">" => Ok(RankDir::LeftToRight),
+            "RL" | "<" => Ok(RankDir::RightToLeft),
+            _ => {
+                self.offset = start;
+                Err(MermaidImportError::TokenUnexpected {
+                    expected: "`TB`, `TD`, `BT`, `LR`, or `RL`",
+                    found: self.found_describe(),
+                    span: self.found_span(),
+                })
+            }
+        }
+    }
+
+    fn statement_parse(&mut self) -> Result<(), MermaidImportError> {
+        let start = self.offset;
+        let word = self.word();
+        let span = SourceSpan::from((start, word.len()));
+        match word {
+            "subgraph" => self.subgraph_parse(span),
+            "end" => match self.subgraphs.pop() {
+                Some(_) => Ok(()),
+                None => Err(MermaidImportError::SubgraphEndUnexpected { span }),
+            },
+            "direction" => {
+                self.inline_skip();
+                let rank_dir = self.direction_parse()?;
+                if self.subgraphs.is_empty() {
+                    self.flowchart.rank_dir = rank_dir;
+                } else {
+                    self.flowchart.warnings.push(
+                        MermaidImportWarning::DirectionSubgraphUnsupported {
+                            span: SourceSpan::from((start, self.offset - start)),
+                        },
+                    );
+                }
+                Ok(())
+            }
+            statement if STATEMENTS_UNSUPPORTED.contains(&statement) => {
+                let statement = String::from(statement);
+                self.line_skip();
+                let len = self.src[start..self.offset].trim_end().len();
+                self.flowchart
+                    .warnings
+                    .push(MermaidImportWarning::StatementUnsupported {
+                        statement,
+                        span: SourceSpan::from((start, len)),
+                    });
+                Ok(())
+            }
+            _ => {
+                self.offset = start;
+                self.link_statement_parse()
+            }
+        }
+    }
+
+    /// `subgraph id [title]`, `subgraph id["title"]`, or `subgraph title`.
+    fn subgraph_parse(&mut self, span: SourceSpan) -> Result<(), MermaidImportError> {
+        self.inline_skip();
+        let start = self.offset;
+        let rest = self.rest();
+        let line = &rest[..rest.find(['\n', ';']).unwrap_or(rest.len())];
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Err(MermaidImportError::TokenUnexpected {
+                expected: "subgraph ID",
+                found: self.found_describe(),
+                span: self.found_span(),
+            });
+        }
+        self.offset += line.len();
+
+        let (subgraph_id, title) = match line.find('[') {
+            Some(bracket_start) if line.ends_with(']') => {
+                let title = &line[bracket_start + 1..line.len() - 1];
+                (
+                    line[..bracket_start].trim_end(),
+                    Some(self.text_normalize(title, start + bracket_start + 1)),
+                )
+            }
+            _ if line.len() >= 2 && line.starts_with('"') && line.ends_with('"') => {
+                let title = &line[1..line.len() - 1];
+                (title, Some(self.text_normalize(title, start + 1)))
+            }
+            _ => (line, None),
+        };
+        let subgraph_id = String::from(subgraph_id);
+        let subgraph_span = SourceSpan::from((start, line.len()));
+
+        let subgraph_parent = self.subgraph_current();
+        match self.flowchart.nodes.get_mut(&subgraph_id) {
+            Some(node) => {
+                if !node.is_subgraph {
+                    node.is_subgraph = true;
+                    node.subgraph = subgraph_parent;
+                }
+                if title.is_some() {
+                    node.label = title;
+                }
+            }
+            None => {
+                self.flowchart.nodes.insert(
+                    subgraph_id.clone(),
+                    MermaidNode {
+                        subgraph: subgraph_parent,
+                        is_subgraph: true,
+                        label: title,
+                        span: subgraph_span,
+                    },
+                );
+            }
+        }
+        self.subgraphs.push((subgraph_id, span));
+        Ok(())
+    }
+
+    /// `nodes (link nodes)*`, where `nodes` is `node (& node)*`.
+    fn link_statement_parse(&mut self) -> Result<(), MermaidImportError> {
+        let mut ends = vec![self.nodes_parse()?];
+        let mut links = Vec::new();
+        loop {
+            self.inline_skip();
+            if self.is_statement_end() {
+                break;
+            }
+            let Some(link) = self.link_parse()? else {
+                return Err(MermaidImportError::TokenUnexpected {
+                    expected: "a link, `&`, or the end of the statement",
+                    found: self.found_describe(),
+                    span: self.found_span(),
+                });
+            };
+            links.push(link);
+            self.inline_skip();
+            ends.push(self.nodes_parse()?);
+        }
+
+        if !links.is_empty() {
+            self.flowchart
+                .link_statements
+                .push(MermaidLinkStatement { ends, links });
+        }
+        Ok(())
+    }
+
+    /// `node (& node)*`
+    fn nodes_parse(&mut self) -> Result<Vec<String>, MermaidImportError> {
+        let mut node_ids = vec![self.node_parse()?];
+        loop {
+            let offset = self.offset;
+            self.inline_skip();
+            if self.rest().starts_with('&') {
+                self.offset += 1;
+                self.inline_skip();
+                node_ids.push(self.node_parse()?);
+            } else {
+                self.offset = offset;
+                return Ok(node_ids);
+            }
+        }
+    }
+
+    /// `id [shape_open text shape_close] [:::class]`
+    fn node_parse(&mut self) -> Result<String, MermaidImportError> {
+        let start = self.offset;
+        let node_id = String::from(self.word());
+        if node_id.is_empty() {
+            return Err(MermaidImportError::TokenUnexpected {
+                expected: "node ID",
+                found: self.found_describe(),
+                span: self.found_span(),
+            });
+        }
+
+        let mut label = None;
+        let rest = self.rest();
+        let node_shape = NODE_SHAPES
+            .iter()
+            .filter(|(open, _, _)| rest.starts_with(open))
+            .find(|(open, close, _)| {
+                // Shapes that share an opening delimiter are told apart by their
+                // closing delimiter.
+                let text = &rest[open.len()..];
+                let text_len = Self::text_len(text, close);
+                text_len.is_some_and(|text_len| text[text_len..].starts_with(close))
+            });
+        if let Some((open, close, shape)) = node_shape {
+            let text_start = self.offset + open.len();
+            let text_len = Self::text_len(&self.src[text_start..], close).unwrap_or_default();
+            let text = &self.src[text_start..text_start + text_len];
+            label = Some(self.text_normalize(text, text_start));
+            self.offset = text_start + text_len + close.len();
+
+            if let Some(shape) = shape {
+                self.flowchart
+                    .warnings
+                    .push(MermaidImportWarning::NodeShapeUnsupported {
+                        node_id: node_id.clone(),
+                        shape,
+                        span: SourceSpan::from((start, self.offset - start)),
+                    });
+            }
+        } else if let Some(open) = NODE_SHAPES
+            .iter()
+            .map(|(open, _, _)| *open)
+            .find(|open| rest.starts_with(open))
+        {
+            return Err(MermaidImportError::Unterminated {
+                what: "node text",
+                span: SourceSpan::from((self.offset, open.len())),
+            });
+        }
+
+        if self.rest().starts_with(":::") {
+            let class_start = self.offset;
+            self.offset += 3;
+            self.word();
+            self.flowchart
+                .warnings
+                .push(MermaidImportWarning::ClassUnsupported {
+                    span: SourceSpan::from((class_start, self.offset - class_start)),
+                });
+        }
+
+        self.node_register(&node_id, label, SourceSpan::from((start, node_id.len())));
+        Ok(node_id)
+    }
+
+    /// Records that the node appears in the current subgraph.
+    ///
+    /// A node that first appears outside a subgraph is moved into the
+    /// subgraph when it appears within one.
+    fn node_register(&mut self, node_id: &str, label: Option<String>, span: SourceSpan) {
+        let subgraph_current = self.subgraph_current();
+        let Some(node) = self.flowchart.nodes.get(node_id) else {
+            self.flowchart.nodes.insert(
+                String::from(node_id),
+                MermaidNode {
+                    subgraph: subgraph_current,
+                    is_subgraph: false,
+                    label,
+                    span,
+                },
+            );
+            return;
+        };
+
+        let subgraph_node = node.subgraph.clone();
+        let is_subgraph = node.is_subgraph;
+        let is_deeper = self
+            .flowchart
+            .subgraph_is_within(subgraph_current.as_deref(), subgraph_node.as_deref());
+        let is_shallower = self
+            .flowchart
+            .subgraph_is_within(subgraph_node.as_deref(), subgraph_current.as_deref());
+
+        let node = &mut self.flowchart.nodes[node_id];
+        if label.is_some() {
+            node.label = label;
+        }
+        if is_subgraph || subgraph_node == subgraph_current {
+            return;
+        }
+        if is_deeper {
+            node.subgraph = subgraph_current;
+        } else if !is_shallower {
+            self.flowchart
+                .warnings
+                .push(MermaidImportWarning::NodeSubgraphMultiple {
+                    node_id: String::from(node_id),
+                    span,
+                });
+        }
+    }
+
+    /// Parses a link, e.g. `-->`, `-- text -->`, or `-.->|text|`.
+    ///
+    /// Returns `None` if there is no link at the current offset.
+    fn link_parse(&mut self) -> Result<Option<MermaidLink>, MermaidImportError> {
+        let start = self.offset;
+        let rest = self.rest();
+
+        if rest.starts_with("~~~") {
+            self.offset += rest.find(|c| c != '~').unwrap_or(rest.len());
+            let label = self.link_label_parse()?;
+            return Ok(Some(MermaidLink {
+                kind: MermaidLinkKind::Invisible,
+                label,
+                span: SourceSpan::from((start, self.offset - start)),
+            }));
+        }
+
+        let arrow_start = rest.starts_with('<');
+        if arrow_start {
+            self.offset += 1;
+        }
+        let mut line = String::from(self.link_line());
+        if line.len() < 2 {
+            self.offset = start;
+            return Ok(None);
+        }
+
+        // `-- text -->`, `-. text .->`, or `== text ==>`.
+        let mut label = None;
+        let line_text_close = match line.as_str() {
+            "--" => Some("--"),
+            "-." => Some(".-"),
+            "==" => Some("=="),
+            _ => None,
+        };
+        if let Some(text_close) = line_text_close
+            && self.rest().starts_with([' ', '\t'])
+        {
+            let text_start = self.offset;
+            let rest = self.rest();
+            let text_len = rest[..rest.find('\n').unwrap_or(rest.len())]
+                .find(text_close)
+                .ok_or(MermaidImportError::Unterminated {
+                    what: "link text",
+                    span: SourceSpan::from((start, self.offset - start)),
+                })?;
+            label = Some(self.text_normalize(&rest[..text_len], text_start));
+            self.offset = text_start + text_len;
+            line.push_str(self.link_line());
+        }
+
+        let rest = self.rest();
+        let mut head_unsupported = None;
+        let arrow_end = rest.starts_with('>');
+        if arrow_end {
+            self.offset += 1;
+        } else if let Some(head) = rest.chars().next().filter(|c| matches!(c, 'o' | 'x'))
+            && rest[1..].starts_with(|c: char| c.is_whitespace())
+        {
+            self.offset += 1;
+            head_unsupported = Some(if head == 'o' {
+                "circle arrow head"
+            } else {
+                "cross arrow head"
+            });
+        }
+        let span = SourceSpan::from((start, self.offset - start));
+
+        let style_unsupported = if line.contains('.') {
+            Some("dotted")
+        } else if line.contains('=') {
+            Some("thick")
+        } else {
+            None
+        };
+        [style_unsupported, head_unsupported]
+            .into_iter()
+            .flatten()
+            .for_each(|style| {
+                self.flowchart
+                    .warnings
+                    .push(MermaidImportWarning::LinkStyleUnsupported { style, span });
+            });
+
+        if let Some(label_pipe) = self.link_label_parse()? {
+            label = Some(label_pipe);
+        }
+        let kind = match (arrow_start, arrow_end) {
+            (true, true) => MermaidLinkKind::ArrowBoth,
+            (false, true) => MermaidLinkKind::Arrow,
+            _ => MermaidLinkKind::Open,
+        };
+        Ok(Some(MermaidLink { kind, label, span }))
+    }
+
+    /// Reads the line of a link, e.g. `--` in `-->`.
+    fn link_line(&mut self) -> &'src str {
+        let rest = &self.src[self.offset..];
+        let len = rest
+            .find(|c| !matches!(c, '-' | '.' | '='))
+            .unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    /// Reads the text of a link written after it, e.g. `|text|` in
+    /// `-->|text|`.
+    fn link_label_parse(&mut self) -> Result<Option<String>, MermaidImportError> {
+        let offset = self.offset;
+        self.inline_skip();
+        if !self.rest().starts_with('|') {
+            self.offset = offset;
+            return Ok(None);
+        }
+
+        let text_start = self.offset + 1;
+        let rest = &self.src[text_start..];
+        let text_len = rest[..rest.find('\n').unwrap_or(rest.len())]
+            .find('|')
+            .ok_or(MermaidImportError::Unterminated {
+                what: "link text",
+                span: SourceSpan::from((self.offset, 1)),
+            })?;
+        let text = &rest[..text_len];
+        self.offset = text_start + text_len + 1;
+        Ok(Some(self.text_normalize(text, text_start)))
+    }
+
+    /// Returns the length of node text before the closing delimiter, which
+    /// may be quoted.
+    fn text_len(text: &str, close: &str) -> Option<usize> {
+        if let Some(quoted) = text.strip_prefix('"') {
+            let quote_end = quoted.find('"')? + 2;
+            let close_start =
+                quote_end + (text[quote_end..].len() - text[quote_end..].trim_start().len());
+            text[close_start..]
+                .starts_with(close)
+                .then_some(close_start)
+        } else {
+            text.find(close)
+        }
+    }
+
+    /// Returns the text with quotes and backticks removed, `<br>` as new
+    /// lines, and entity codes such as `#quot;` replaced.
+    ///
+    /// `text_start` is the byte offset of `text` in the Mermaid source, used
+    /// to report where Markdown labels are.
+    fn text_normalize(&mut self, text: &str, text_start: usize) -> String {
+        let text_trimmed = text.trim_start();
+        let mut text_offset = text_start + (text.len() - text_trimmed.len());
+        let text = text_trimmed.trim_end();
+        let text = match text
+            .strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+        {
+            Some(text_quoted) => {
+                text_offset += 1;
+                text_quoted
+            }
+            None => text,
+        };
+        let text = match text
+            .strip_prefix('`')
+            .and_then(|text| text.strip_suffix('`'))
+        {
+            Some(text_markdown) => {
+                self.flowchart
+                    .warnings
+                    .push(MermaidImportWarning::LabelMarkdown {
+                        span: SourceSpan::from((text_offset, text.len())),
+                    });
+                text_markdown
+            }
+            None => text,
+        };
+
+        let mut text_normalized = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(index) = rest.find(['<', '#']) {
+            text_normalized.push_str(&rest[..index]);
+            rest = &rest[index..];
+
+            let br_len = ["<br>", "<br/>", "<br />"].iter().find_map(|br| {
+                rest.get(..br.len())
+                    .filter(|tag| tag.eq_ignore_ascii_case(br))
+                    .map(|_| br.len())
+            });
+            let entity = rest
+                .strip_prefix('#')
+                .and_then(|entity| Some(&entity[..entity.find(';')?]))
+                .and_then(|entity| Some((Self::entity_char(entity)?, entity.len() + 2)));
+
+            if let Some(br_len) = br_len {
+                text_normalized.push('\n');
+                rest = &rest[br_len..];
+            } else if let Some((c, entity_len)) = entity {
+                text_normalized.push(c);
+                rest = &rest[entity_len..];
+            } else {
+                text_normalized.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+        text_normalized.push_str(rest);
+        text_normalized
+    }
+
+    /// Returns the character for an entity code, e.g. `quot` or `35`.
+    fn entity_char(entity: &str) -> Option<char> {
+        match entity {
+            "quot" => Some('"'),
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "nbsp" => Some(' '),
+            _ => entity.parse::<u32>().ok().and_then(char::from_u32),
+        }
+    }
+
+    fn subgraph_current(&self) -> Option<String> {
+        self.subgraphs
+            .last()
+            .map(|(subgraph_id, _)| subgraph_id.clone())
+    }
+
+    fn rest(&self) -> &'src str {
+        &self.src[self.offset..]
+    }
+
+    /// Reads a word, e.g. a node ID or keyword.
+    fn word(&mut self) -> &'src str {
+        let rest = &self.src[self.offset..];
+        let len = rest
+            .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+            .unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    /// Skips whitespace, new lines, `;`, and `%%` comments.
+    fn trivia_skip(&mut self) {
+        loop {
+            let rest = self.rest();
+            if let Some(c) = rest
+                .chars()
+                .next()
+                .filter(|c| c.is_whitespace() || *c == ';')
+            {
+                self.offset += c.len_utf8();
+            } else if rest.starts_with("%%") {
+                self.line_skip();
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// Skips spaces and tabs.
+    fn inline_skip(&mut self) {
+        let rest = self.rest();
+        self.offset += rest
+            .find(|c| !matches!(c, ' ' | '\t' | '\r'))
+            .unwrap_or(rest.len());
+    }
+
+    /// Skips to the end of the line.
+    fn line_skip(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.find('\n').unwrap_or(rest.len());
+    }
+
+    fn is_statement_end(&self) -> bool {
+        let rest = self.rest();
+        rest.is_empty() || rest.starts_with(['\n', ';']) || rest.starts_with("%%")
+    }
+
+    /// Returns a description of the text at the current offset.
+    fn found_describe(&self) -> String {
+        let rest = self.rest();
+        match rest.chars().next() {
+            None => String::from("the end of the flowchart"),
+            Some('\n') => String::from("the end of the line"),
+            Some(c) if c == '_' || c.is_alphanumeric() => {
+                let len = rest
+                    .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+                    .unwrap_or(rest.len());
+                format!("`{}`", &rest[..len])
+            }
+            Some(c) => format!("`{c}`"),
+        }
+    }
+
+    /// Returns the span of the text described by [`Self::found_describe`].
+    fn found_span(&self) -> SourceSpan {
+        let rest = self.rest();
+        let len = match rest.chars().next() {
+            None | Some('\n') => 0,
+            Some(c) if c == '_' || c.is_alphanumeric() => rest
+                .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+                .unwrap_or(rest.len()),
+            Some(c) => c.len_utf8(),
+        };
+        SourceSpan::from((self.offset, len))
+    }
+}
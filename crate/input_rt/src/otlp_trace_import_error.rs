@@ -0,0 +1,20 @@
+use miette::SourceSpan;
+
+/// Errors when reading an OTLP/JSON trace export via `OtlpTraceImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum OtlpTraceImportError {
+    /// The export is not valid JSON, or does not have the shape of an
+    /// OTLP/JSON trace export.
+    #[error("OTLP trace export is invalid: {detail}")]
+    #[diagnostic(
+        code(disposition::otlp_trace_import::json_invalid),
+        help("Export the traces with the OpenTelemetry collector's `file` exporter.")
+    )]
+    JsonInvalid {
+        /// Description of what is invalid.
+        detail: String,
+        /// Location of the invalid JSON.
+        #[label("invalid")]
+        span: SourceSpan,
+    },
+}
@@ -0,0 +1,404 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind},
+    process::{ProcessDiagram, ProcessId, ProcessStepId},
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{edge::EdgeGroupId, Map, Set};
+
+use crate::{
+    import_diagram_insert::ImportDiagramInsert,
+    import_id_generator::ImportIdGenerator,
+    import_source_span::ImportSourceSpan,
+    otlp_trace_importer::otlp_traces::{OtlpSpan, OtlpTraces},
+    OtlpTraceImportError,
+};
+
+mod otlp_traces;
+
+/// Service name that the OpenTelemetry SDKs use when `service.name` is not
+/// set.
+const SERVICE_NAME_UNKNOWN: &str = "unknown_service";
+
+/// Imports an OTLP/JSON trace export as an `InputDiagram` of the services in
+/// the traces, and a process per trace.
+///
+/// * Each service, identified by its `service.name` resource attribute, becomes
+///   a thing.
+/// * Services are nested in a thing for their `service.namespace` resource
+///   attribute. Services without a namespace are not nested.
+/// * Each span whose parent span is in a different service becomes a
+///   `thing_interactions` `sequence` edge group from the parent's service to
+///   the span's service. Calls between the same pair of services share one edge
+///   group.
+/// * Each trace becomes a process named after its root span, whose steps are
+///   the spans that cross services, in the order they started, with each span
+///   after its parent span. Each step highlights the edge group for its call,
+///   and depends on the step of its nearest ancestor span that crosses
+///   services. Traces that stay within one service are not imported as
+///   processes.
+/// * Each step's tooltip names the services it is between and how long the span
+///   took.
+///
+/// The export may contain multiple `ExportTraceServiceRequest` objects one
+/// after another, as the OpenTelemetry collector's `file` exporter writes one
+/// per line.
+///
+/// Span attributes, events, links, and status are not imported.
+#[derive(Clone, Copy, Debug)]
+pub struct OtlpTraceImporter;
+
+impl OtlpTraceImporter {
+    /// Returns the input diagram for the OTLP/JSON trace export.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     OtlpTraceImporter,
+    /// # };
+    /// #
+    /// let input_diagram = OtlpTraceImporter::import(
+    ///     r#"{
+    ///   "resourceSpans": [
+    ///     {
+    ///       "resource": {
+    ///         "attributes": [{ "key": "service.name", "value": { "stringValue": "web" } }]
+    ///       },
+    ///       "scopeSpans": [{ "spans": [{
+    ///         "traceId": "5b8efff798038103d269b633813fc60c",
+    ///         "spanId": "eee19b7ec3c1b174",
+    ///         "name": "GET /cart",
+    ///         "startTimeUnixNano": "1700000000000000000",
+    ///         "endTimeUnixNano": "1700000000020000000"
+    ///       }] }]
+    ///     },
+    ///     {
+    ///       "resource": {
+    ///         "attributes": [{ "key": "service.name", "value": { "stringValue": "cart" } }]
+    ///       },
+    ///       "scopeSpans": [{ "spans": [{
+    ///         "traceId": "5b8efff798038103d269b633813fc60c",
+    ///         "spanId": "eee19b7ec3c1b175",
+    ///         "parentSpanId": "eee19b7ec3c1b174",
+    ///         "name": "CartService/GetCart",
+    ///         "startTimeUnixNano": "1700000000005000000",
+    ///         "endTimeUnixNano": "1700000000015000000"
+    ///       }] }]
+    ///     }
+    ///   ]
+    /// }"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(input_diagram
+    ///     .things
+    ///     .contains_key(&parse_thing_id("cart").unwrap()));
+    /// assert!(input_diagram
+    ///     .thing_interactions
+    ///     .contains_key(&parse_edge_group_id("edge_web__cart").unwrap()));
+    ///
+    /// let process = input_diagram.processes.values().next().unwrap();
+    /// assert_eq!(Some("GET /cart"), process.name.as_deref());
+    /// assert_eq!(
+    ///     vec!["CartService/GetCart"],
+    ///     process.steps.values().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn import(otlp_traces_json: &str) -> Result<InputDiagram<'static>, OtlpTraceImportError> {
+        let otlp_traces_all = serde_json::Deserializer::from_str(otlp_traces_json)
+            .into_iter::<OtlpTraces>()
+            .collect::<Result<Vec<OtlpTraces>, serde_json::Error>>()
+            .map_err(|error| Self::json_invalid(otlp_traces_json, &error))?;
+
+        let mut service_spans = otlp_traces_all
+            .iter()
+            .flat_map(|otlp_traces| otlp_traces.resource_spans.iter())
+            .flat_map(|resource_spans| {
+                let resource = &resource_spans.resource;
+                let service = OtlpService {
+                    namespace: resource.attribute_str("service.namespace"),
+                    name: resource
+                        .attribute_str("service.name")
+                        .unwrap_or(SERVICE_NAME_UNKNOWN),
+                };
+                resource_spans
+                    .scope_spans
+                    .iter()
+                    .flat_map(|scope_spans| scope_spans.spans.iter())
+                    .map(move |span| ServiceSpan { service, span })
+            })
+            .collect::<Vec<ServiceSpan<'_>>>();
+        service_spans.sort_by_key(|service_span| service_span.span.start_time_unix_nano);
+
+        let mut id_generator = ImportIdGenerator::new();
+        let service_thing_ids = id_generator.thing_ids_generate(
+            service_spans
+                .iter()
+                .map(|service_span| (service_span.service, service_span.service.name)),
+        );
+        let mut ctx = OtlpTraceImportCtx {
+            input_diagram: InputDiagram::new(),
+            id_generator,
+            service_thing_ids,
+            namespace_thing_ids: Map::new(),
+            interaction_edge_group_ids: Map::new(),
+        };
+
+        let services = ctx
+            .service_thing_ids
+            .keys()
+            .copied()
+            .collect::<Vec<OtlpService<'_>>>();
+        services
+            .into_iter()
+            .for_each(|service| ctx.service_import(service));
+
+        let mut trace_spans = Map::<&str, Vec<ServiceSpan<'_>>>::new();
+        service_spans.into_iter().for_each(|service_span| {
+            trace_spans
+                .entry(service_span.span.trace_id.as_str())
+                .or_default()
+                .push(service_span);
+        });
+        trace_spans
+            .into_iter()
+            .for_each(|(trace_id, service_spans)| ctx.trace_import(trace_id, &service_spans));
+
+        Ok(ctx.input_diagram)
+    }
+
+    /// Returns the error for invalid JSON, with the span of where it is
+    /// invalid.
+    fn json_invalid(otlp_traces_json: &str, error: &serde_json::Error) -> OtlpTraceImportError {
+        OtlpTraceImportError::JsonInvalid {
+            detail: error.to_string(),
+            span: ImportSourceSpan::from_line_column(
+                otlp_traces_json,
+                error.line(),
+                error.column(),
+            ),
+        }
+    }
+}
+
+/// A service that emits spans, identified by its `service.namespace` and
+/// `service.name` resource attributes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct OtlpService<'t> {
+    /// Value of the `service.namespace` resource attribute.
+    namespace: Option<&'t str>,
+    /// Value of the `service.name` resource attribute.
+    name: &'t str,
+}
+
+/// A span and the service that emitted it.
+#[derive(Clone, Copy, Debug)]
+struct ServiceSpan<'t> {
+    /// The service that emitted the span.
+    service: OtlpService<'t>,
+    /// The span.
+    span: &'t OtlpSpan,
+}
+
+/// State while mapping [`OtlpTraces`] to an `InputDiagram`.
+struct OtlpTraceImportCtx<'t> {
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Generates thing, edge group, process, and step IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each service, in the order the services first emitted a
+    /// span.
+    service_thing_ids: Map<OtlpService<'t>, ThingId<'static>>,
+    /// Thing ID of each namespace that contains services, keyed by its name.
+    namespace_thing_ids: Map<&'t str, ThingId<'static>>,
+    /// Edge group ID of the interaction between each pair of services, keyed
+    /// by the calling service's thing ID then the called service's thing ID.
+    interaction_edge_group_ids: Map<(ThingId<'static>, ThingId<'static>), EdgeGroupId<'static>>,
+}
+
+impl<'t> OtlpTraceImportCtx<'t> {
+    /// Adds the service within the thing for its namespace.
+    fn service_import(&mut self, service: OtlpService<'t>) {
+        let thing_id = self.service_thing_ids[&service].clone();
+
+        let thing_hierarchy = match service.namespace {
+            Some(namespace) => {
+                let namespace_thing_id = match self.namespace_thing_ids.get(namespace) {
+                    Some(namespace_thing_id) => namespace_thing_id.clone(),
+                    None => {
+                        let namespace_thing_id =
+                            ThingId::from(self.id_generator.generate(namespace));
+                        ImportDiagramInsert::thing_name(
+                            &mut self.input_diagram,
+                            &namespace_thing_id,
+                            namespace,
+                        );
+                        self.namespace_thing_ids
+                            .insert(namespace, namespace_thing_id.clone());
+                        namespace_thing_id
+                    }
+                };
+                self.input_diagram
+                    .things
+                    .entry(namespace_thing_id)
+                    .or_default()
+            }
+            None => &mut self.input_diagram.things,
+        };
+        thing_hierarchy.insert(thing_id.clone(), ThingHierarchy::new());
+
+        ImportDiagramInsert::thing_name(&mut self.input_diagram, &thing_id, service.name);
+    }
+
+    /// Adds a process for the trace, with a step for each span that is called
+    /// from a different service.
+    ///
+    /// The spans must be sorted by when they started. Steps are added in that
+    /// order, except that a span is always added after its parent span, as
+    /// clocks on different hosts may be skewed so that a span appears to start
+    /// before the span that called it.
+    fn trace_import(&mut self, trace_id: &str, service_spans: &[ServiceSpan<'t>]) {
+        let span_indices = service_spans
+            .iter()
+            .enumerate()
+            .map(|(span_index, service_span)| (service_span.span.span_id.as_str(), span_index))
+            .collect::<Map<&str, usize>>();
+        let parent_index = |service_span: &ServiceSpan<'t>| {
+            span_indices
+                .get(service_span.span.parent_span_id.as_str())
+                .copied()
+        };
+
+        let process_name = service_spans
+            .iter()
+            .find(|service_span| parent_index(service_span).is_none())
+            .map_or(trace_id, |service_span| service_span.span.name.as_str());
+        let process_id = ProcessId::from(
+            self.id_generator
+                .generate(&format!("proc_{}", process_name.to_ascii_lowercase())),
+        );
+        let mut process_diagram = ProcessDiagram::new();
+        process_diagram.name = Some(process_name.to_string());
+        process_diagram.desc = Some(format!("Trace `{trace_id}`."));
+
+        // Step of each span, or of its nearest ancestor span that has a step.
+        let mut span_step_ids = Map::<usize, ProcessStepId<'static>>::new();
+        Self::span_indices_parent_first(service_spans, parent_index)
+            .into_iter()
+            .for_each(|span_index| {
+                let service_span = &service_spans[span_index];
+                let Some(parent_index) = parent_index(service_span) else {
+                    return;
+                };
+                let parent_service_span = &service_spans[parent_index];
+                let step_id_ancestor = span_step_ids.get(&parent_index).cloned();
+                if parent_service_span.service == service_span.service {
+                    if let Some(step_id_ancestor) = step_id_ancestor {
+                        span_step_ids.insert(span_index, step_id_ancestor);
+                    }
+                    return;
+                }
+
+                let edge_group_id =
+                    self.interaction_import(parent_service_span.service, service_span.service);
+                let step_id = ProcessStepId::from(self.id_generator.generate(&format!(
+                    "{process_id}_step_{}",
+                    service_span.span.name.to_ascii_lowercase()
+                )));
+                process_diagram
+                    .steps
+                    .insert(step_id.clone(), service_span.span.name.clone());
+                process_diagram
+                    .step_thing_interactions
+                    .insert(step_id.clone(), vec![edge_group_id]);
+                if let Some(step_id_ancestor) = step_id_ancestor {
+                    let mut step_ids_dependency = Set::new();
+                    step_ids_dependency.insert(step_id_ancestor);
+                    process_diagram
+                        .process_step_dependencies
+                        .insert(step_id.clone(), step_ids_dependency);
+                }
+
+                let mut tooltip = format!(
+                    "`{}` → `{}`",
+                    parent_service_span.service.name, service_span.service.name
+                );
+                if let Some(duration_ms) = service_span.span.duration_ms() {
+                    tooltip.push_str(&format!(", {duration_ms:.1} ms"));
+                }
+                self.input_diagram
+                    .entity_tooltips
+                    .insert(step_id.clone().into_inner(), tooltip);
+
+                span_step_ids.insert(span_index, step_id);
+            });
+
+        if !process_diagram.steps.is_empty() {
+            self.input_diagram
+                .processes
+                .insert(process_id, process_diagram);
+        }
+    }
+
+    /// Returns the indices of the spans with each span after its parent span,
+    /// and otherwise in the order they started.
+    ///
+    /// Spans whose parent is not in the trace come first. Spans in a parent
+    /// cycle, which only malformed exports have, are not returned.
+    fn span_indices_parent_first(
+        service_spans: &[ServiceSpan<'t>],
+        parent_index: impl Fn(&ServiceSpan<'t>) -> Option<usize>,
+    ) -> Vec<usize> {
+        let mut span_indices_ready = BinaryHeap::new();
+        let mut span_indices_child = vec![Vec::new(); service_spans.len()];
+        service_spans
+            .iter()
+            .enumerate()
+            .for_each(
+                |(span_index, service_span)| match parent_index(service_span) {
+                    Some(parent_index) => span_indices_child[parent_index].push(span_index),
+                    None => span_indices_ready.push(Reverse(span_index)),
+                },
+            );
+
+        // Span indices are in start order, so the earliest started span whose
+        // parent has been returned is returned next.
+        let mut span_indices = Vec::with_capacity(service_spans.len());
+        while let Some(Reverse(span_index)) = span_indices_ready.pop() {
+            span_indices.push(span_index);
+            let span_indices_child = std::mem::take(&mut span_indices_child[span_index]);
+            span_indices_ready.extend(span_indices_child.into_iter().map(Reverse));
+        }
+        span_indices
+    }
+
+    /// Returns the edge group for the interaction from the calling service to
+    /// the called service, adding it if it does not exist.
+    fn interaction_import(
+        &mut self,
+        service_from: OtlpService<'t>,
+        service_to: OtlpService<'t>,
+    ) -> EdgeGroupId<'static> {
+        let thing_id_from = self.service_thing_ids[&service_from].clone();
+        let thing_id_to = self.service_thing_ids[&service_to].clone();
+        let edge_group_key = (thing_id_from, thing_id_to);
+        if let Some(edge_group_id) = self.interaction_edge_group_ids.get(&edge_group_key) {
+            return edge_group_id.clone();
+        }
+
+        let things = vec![edge_group_key.0.clone(), edge_group_key.1.clone()];
+        let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+        self.input_diagram.thing_interactions.insert(
+            edge_group_id.clone(),
+            EdgeGroup::new(EdgeKind::Sequence, things),
+        );
+        self.interaction_edge_group_ids
+            .insert(edge_group_key, edge_group_id.clone());
+
+        edge_group_id
+    }
+}
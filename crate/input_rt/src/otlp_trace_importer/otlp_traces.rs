@@ -0,0 +1,122 @@
+use serde::{Deserialize, Deserializer};
+
+/// The parts of an OTLP/JSON trace export that are imported.
+///
+/// This is the JSON encoding of an `ExportTraceServiceRequest`, as written by
+/// the OpenTelemetry collector's `file` exporter and by OTLP/HTTP clients.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct OtlpTraces {
+    /// Spans grouped by the resource, i.e. service instance, that emitted
+    /// them.
+    pub(crate) resource_spans: Vec<OtlpResourceSpans>,
+}
+
+/// Spans emitted by one resource.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct OtlpResourceSpans {
+    /// The resource that emitted the spans.
+    pub(crate) resource: OtlpResource,
+    /// Spans grouped by instrumentation scope.
+    ///
+    /// Exports before OTLP 0.15 name this `instrumentationLibrarySpans`.
+    #[serde(alias = "instrumentationLibrarySpans")]
+    pub(crate) scope_spans: Vec<OtlpScopeSpans>,
+}
+
+/// A resource that emits spans.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct OtlpResource {
+    /// Attributes of the resource, e.g. `service.name`.
+    pub(crate) attributes: Vec<OtlpKeyValue>,
+}
+
+impl OtlpResource {
+    /// Returns the string value of the attribute with the given key.
+    pub(crate) fn attribute_str(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .and_then(|attribute| attribute.value.string_value.as_deref())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// An attribute of a resource.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct OtlpKeyValue {
+    /// Key of the attribute, e.g. `service.name`.
+    pub(crate) key: String,
+    /// Value of the attribute.
+    pub(crate) value: OtlpAnyValue,
+}
+
+/// Value of an attribute.
+///
+/// Only string values are imported.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct OtlpAnyValue {
+    /// The value, if it is a string.
+    pub(crate) string_value: Option<String>,
+}
+
+/// Spans emitted by one instrumentation scope.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct OtlpScopeSpans {
+    /// Spans emitted by the scope.
+    pub(crate) spans: Vec<OtlpSpan>,
+}
+
+/// A span within a trace.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct OtlpSpan {
+    /// Hex encoded ID of the trace the span belongs to.
+    pub(crate) trace_id: String,
+    /// Hex encoded ID of the span.
+    pub(crate) span_id: String,
+    /// Hex encoded ID of the span's parent, empty for root spans.
+    pub(crate) parent_span_id: String,
+    /// Name of the operation, e.g. `GET /cart`.
+    pub(crate) name: String,
+    /// Time the span started, in nanoseconds since the Unix epoch.
+    #[serde(deserialize_with = "unix_nano_deserialize")]
+    pub(crate) start_time_unix_nano: u64,
+    /// Time the span ended, in nanoseconds since the Unix epoch.
+    #[serde(deserialize_with = "unix_nano_deserialize")]
+    pub(crate) end_time_unix_nano: u64,
+}
+
+impl OtlpSpan {
+    /// Returns how long the span took in milliseconds, if it has ended.
+    pub(crate) fn duration_ms(&self) -> Option<f64> {
+        self.end_time_unix_nano
+            .checked_sub(self.start_time_unix_nano)
+            .filter(|_| self.end_time_unix_nano != 0)
+            .map(|duration_nanos| duration_nanos as f64 / 1_000_000.0)
+    }
+}
+
+/// Deserializes a timestamp that is written either as a number, or as a
+/// string as the OTLP/JSON encoding of 64 bit integers requires.
+fn unix_nano_deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum UnixNano {
+        Number(u64),
+        String(String),
+    }
+
+    match UnixNano::deserialize(deserializer)? {
+        UnixNano::Number(unix_nano) => Ok(unix_nano),
+        UnixNano::String(unix_nano) => unix_nano.parse::<u64>().map_err(serde::de::Error::custom),
+    }
+}
@@ -0,0 +1,27 @@
+use miette::SourceSpan;
+
+use crate::DotImportError;
+
+/// Errors when reading Terraform output via `TerraformImporter`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+pub enum TerraformImportError {
+    /// The output is not valid JSON, or does not have the shape of `terraform
+    /// show -json` output.
+    #[error("`terraform show -json` output is invalid: {detail}")]
+    #[diagnostic(
+        code(disposition::terraform_import::json_invalid),
+        help("Generate the output with `terraform show -json` or `terraform show -json <plan>`.")
+    )]
+    JsonInvalid {
+        /// Description of what is invalid.
+        detail: String,
+        /// Location of the invalid JSON.
+        #[label("invalid")]
+        span: SourceSpan,
+    },
+
+    /// The `terraform graph` output is not a valid DOT graph.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    GraphDotInvalid(#[from] DotImportError),
+}
@@ -0,0 +1,334 @@
+use disposition_input_model::{
+    edge::{EdgeGroup, EdgeKind},
+    tag::TagId,
+    theme::ThemeAttr,
+    thing::{ThingHierarchy, ThingId},
+    InputDiagram,
+};
+use disposition_model_common::{entity::EntityType, id, Id, Map, Set};
+
+use crate::{
+    dot_importer::{dot_lexer::DotLexer, dot_parser::DotParser},
+    import_diagram_insert::ImportDiagramInsert,
+    import_id_generator::ImportIdGenerator,
+    import_source_span::ImportSourceSpan,
+    terraform_importer::{
+        terraform_address::TerraformAddress,
+        terraform_dot::TerraformDot,
+        terraform_graph::{TerraformAction, TerraformGraph, TerraformResource},
+        terraform_json::TerraformJson,
+    },
+    TerraformImportError,
+};
+
+mod terraform_address;
+mod terraform_dot;
+mod terraform_graph;
+mod terraform_json;
+
+/// Imports Terraform state, plans, and dependency graphs as an `InputDiagram`
+/// of their resources.
+///
+/// * Modules become things that contain the things for their resources and the
+///   modules they call, e.g. `module.vpc.aws_subnet.main` becomes
+///   `aws_subnet.main` within `module.vpc`. Instances created with `count` or
+///   `for_each` are imported as one thing.
+/// * Resources are given a `type_provider_<provider>` entity type for their
+///   provider, and a `type_resource_<type>` entity type for their resource
+///   type, e.g. `type_provider_aws` and `type_resource_aws_instance`. Data
+///   sources are also given the `type_data_source` entity type, which is drawn
+///   with a dashed outline.
+/// * Each resource that another resource or module refers to becomes a
+///   `thing_dependencies` `sequence` edge group from the referenced resource to
+///   the one that refers to it, as the referenced resource is created first.
+/// * For plans, resources that are created, updated, replaced, deleted, or read
+///   are placed in the `tag_create`, `tag_update`, `tag_replace`, `tag_delete`,
+///   and `tag_read` tags, so that each kind of change can be focused on.
+#[derive(Clone, Copy, Debug)]
+pub struct TerraformImporter;
+
+impl TerraformImporter {
+    /// Returns the input diagram for `terraform show -json` output, for
+    /// either the state or a plan.
+    ///
+    /// For plans, references between resources are read from the
+    /// configuration. For the state, dependencies recorded when resources
+    /// were last applied are used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_tag_id, parse_thing_id},
+    /// #     TerraformImporter,
+    /// # };
+    /// #
+    /// let input_diagram = TerraformImporter::import_json(
+    ///     r#"{
+    ///         "resource_changes": [
+    ///             { "address": "aws_instance.web", "change": { "actions": ["create"] } },
+    ///             { "address": "module.vpc.aws_subnet.main", "change": { "actions": ["no-op"] } }
+    ///         ],
+    ///         "configuration": {
+    ///             "root_module": {
+    ///                 "resources": [{
+    ///                     "address": "aws_instance.web",
+    ///                     "expressions": { "subnet_id": { "references": ["module.vpc.subnet_id", "module.vpc"] } }
+    ///                 }]
+    ///             }
+    ///         }
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let vpc = &input_diagram.things[&parse_thing_id("module_vpc").unwrap()];
+    /// assert!(vpc.contains_key(&parse_thing_id("aws_subnet_main").unwrap()));
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_module_vpc__aws_instance_web").unwrap()));
+    /// assert!(
+    ///     input_diagram.tag_things[&parse_tag_id("tag_create").unwrap()]
+    ///         .contains(&parse_thing_id("aws_instance_web").unwrap())
+    /// );
+    /// ```
+    pub fn import_json(
+        terraform_json: &str,
+    ) -> Result<InputDiagram<'static>, TerraformImportError> {
+        let terraform_json_parsed =
+            serde_json::from_str::<TerraformJson>(terraform_json).map_err(|error| {
+                TerraformImportError::JsonInvalid {
+                    detail: error.to_string(),
+                    span: ImportSourceSpan::from_line_column(
+                        terraform_json,
+                        error.line(),
+                        error.column(),
+                    ),
+                }
+            })?;
+
+        Ok(Self::graph_import(&terraform_json_parsed.graph()))
+    }
+
+    /// Returns the input diagram for `terraform graph` output.
+    ///
+    /// Nodes that are not resources, e.g. variables and providers, are not
+    /// imported, but dependencies through them are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use disposition_input_rt::{
+    /// #     id_parse::{parse_edge_group_id, parse_thing_id},
+    /// #     TerraformImporter,
+    /// # };
+    /// #
+    /// let input_diagram = TerraformImporter::import_graph_dot(
+    ///     r#"digraph G {
+    ///         "aws_instance.web" [label="aws_instance.web"];
+    ///         "aws_instance.web" -> "var.ami";
+    ///         "var.ami" -> "data.aws_ami.ubuntu";
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(input_diagram
+    ///     .thing_dependencies
+    ///     .contains_key(&parse_edge_group_id("edge_data_aws_ami_ubuntu__aws_instance_web").unwrap()));
+    /// ```
+    pub fn import_graph_dot(dot: &str) -> Result<InputDiagram<'static>, TerraformImportError> {
+        let tokens = DotLexer::tokenize(dot)?;
+        let dot_graph = DotParser::parse(&tokens, dot.len())?;
+
+        Ok(Self::graph_import(&TerraformDot::graph(&dot_graph)))
+    }
+
+    /// Returns the input diagram for the resources and dependencies.
+    fn graph_import(graph: &TerraformGraph) -> InputDiagram<'static> {
+        let mut ctx = TerraformImportCtx {
+            input_diagram: InputDiagram::new(),
+            id_generator: ImportIdGenerator::new(),
+            resource_thing_ids: Map::new(),
+            module_thing_ids: Map::new(),
+        };
+
+        graph
+            .resources
+            .iter()
+            .for_each(|(key, resource)| ctx.resource_import(key, resource));
+        ctx.dependencies_import(&graph.dependencies);
+        ctx.action_tags_import(graph);
+        ctx.theme_types_styles_import();
+
+        ctx.input_diagram
+    }
+}
+
+/// State while mapping a [`TerraformGraph`] to an `InputDiagram`.
+struct TerraformImportCtx {
+    /// The diagram being built.
+    input_diagram: InputDiagram<'static>,
+    /// Generates thing and edge group IDs.
+    id_generator: ImportIdGenerator,
+    /// Thing ID of each resource, keyed by its address.
+    resource_thing_ids: Map<String, ThingId<'static>>,
+    /// Thing ID of each module, keyed by its address, e.g. `module.vpc`.
+    module_thing_ids: Map<String, ThingId<'static>>,
+}
+
+impl TerraformImportCtx {
+    /// Adds the resource within the things for its modules.
+    fn resource_import(&mut self, key: &str, resource: &TerraformResource) {
+        let TerraformAddress::Resource {
+            module_path,
+            local_address,
+            resource_type,
+            is_data,
+        } = &resource.address
+        else {
+            return;
+        };
+
+        let mut module_key = String::new();
+        let mut module_thing_ids = Vec::with_capacity(module_path.len());
+        module_path.iter().for_each(|module_name| {
+            if !module_key.is_empty() {
+                module_key.push('.');
+            }
+            module_key.push_str("module.");
+            module_key.push_str(module_name);
+
+            let module_thing_id = match self.module_thing_ids.get(&module_key) {
+                Some(module_thing_id) => module_thing_id.clone(),
+                None => {
+                    let module_thing_id = ThingId::from(self.id_generator.generate(&module_key));
+                    self.input_diagram
+                        .thing_names
+                        .insert(module_thing_id.clone(), format!("module.{module_name}"));
+                    self.module_thing_ids
+                        .insert(module_key.clone(), module_thing_id.clone());
+                    module_thing_id
+                }
+            };
+            module_thing_ids.push(module_thing_id);
+        });
+        let thing_hierarchy = module_thing_ids.into_iter().fold(
+            &mut self.input_diagram.things,
+            |thing_hierarchy, module_thing_id| thing_hierarchy.entry(module_thing_id).or_default(),
+        );
+
+        let thing_id = ThingId::from(self.id_generator.generate(local_address));
+        thing_hierarchy.insert(thing_id.clone(), ThingHierarchy::new());
+        self.input_diagram
+            .thing_names
+            .insert(thing_id.clone(), local_address.clone());
+        if !module_path.is_empty() {
+            self.input_diagram
+                .entity_tooltips
+                .insert(thing_id.clone().into_inner(), key.to_string());
+        }
+
+        let mut entity_types = Set::new();
+        if let Some(provider) = resource.provider.as_deref() {
+            entity_types.insert(Self::entity_type("type_provider_", provider));
+        }
+        entity_types.insert(Self::entity_type("type_resource_", resource_type));
+        if *is_data {
+            entity_types.insert(EntityType::Custom(id!("type_data_source")));
+        }
+        self.input_diagram
+            .entity_types
+            .insert(thing_id.clone().into_inner(), entity_types);
+
+        self.resource_thing_ids.insert(key.to_string(), thing_id);
+    }
+
+    /// Returns the entity type with the prefix and name, e.g.
+    /// `type_provider_aws`.
+    fn entity_type(prefix: &str, name: &str) -> EntityType {
+        let type_id = ImportIdGenerator::sanitize(&format!("{prefix}{name}"));
+        EntityType::Custom(Id::try_from(type_id).expect("Expected sanitized ID to be valid."))
+    }
+
+    /// Returns the thing ID of the resource or module at the address.
+    fn thing_id(&self, address: &TerraformAddress) -> Option<ThingId<'static>> {
+        match address {
+            TerraformAddress::Resource { .. } => self.resource_thing_ids.get(&address.key()),
+            TerraformAddress::Module { .. } => self.module_thing_ids.get(&address.key()),
+        }
+        .cloned()
+    }
+
+    /// Adds an edge group for each dependency between things that were
+    /// imported.
+    fn dependencies_import(&mut self, dependencies: &[(TerraformAddress, TerraformAddress)]) {
+        let mut thing_id_pairs = Set::<(ThingId<'static>, ThingId<'static>)>::new();
+        dependencies.iter().for_each(|(dependency, dependent)| {
+            // Edges between a module and the things within it are not drawn.
+            let is_within = |module: &TerraformAddress, address: &TerraformAddress| {
+                matches!(module, TerraformAddress::Module { .. })
+                    && address.module_path().starts_with(module.module_path())
+            };
+            if is_within(dependency, dependent) || is_within(dependent, dependency) {
+                return;
+            }
+            if let (Some(thing_id_dependency), Some(thing_id_dependent)) =
+                (self.thing_id(dependency), self.thing_id(dependent))
+                && thing_id_dependency != thing_id_dependent
+            {
+                thing_id_pairs.insert((thing_id_dependency, thing_id_dependent));
+            }
+        });
+
+        thing_id_pairs
+            .into_iter()
+            .for_each(|(thing_id_dependency, thing_id_dependent)| {
+                let things = vec![thing_id_dependency, thing_id_dependent];
+                let edge_group_id = self.id_generator.edge_group_id_generate(&things);
+                self.input_diagram
+                    .thing_dependencies
+                    .insert(edge_group_id, EdgeGroup::new(EdgeKind::Sequence, things));
+            });
+    }
+
+    /// Adds a tag for each planned action, containing the resources it is
+    /// taken on.
+    fn action_tags_import(&mut self, graph: &TerraformGraph) {
+        TerraformAction::ALL.into_iter().for_each(|action| {
+            let thing_ids = graph
+                .resources
+                .iter()
+                .filter(|(_, resource)| resource.actions.contains(&action))
+                .filter_map(|(key, _)| self.resource_thing_ids.get(key).cloned())
+                .collect::<Set<ThingId<'static>>>();
+            if thing_ids.is_empty() {
+                return;
+            }
+
+            let tag_id = TagId::from(self.id_generator.generate(action.tag_id()));
+            self.input_diagram
+                .tags
+                .insert(tag_id.clone(), action.tag_name().to_string());
+            self.input_diagram.tag_things.insert(tag_id, thing_ids);
+        });
+    }
+
+    /// Adds a dashed outline for data sources, if there are any.
+    fn theme_types_styles_import(&mut self) {
+        let type_data_source = EntityType::Custom(id!("type_data_source"));
+        let data_source_exists = self
+            .input_diagram
+            .entity_types
+            .values()
+            .any(|entity_types| entity_types.contains(&type_data_source));
+        if !data_source_exists {
+            return;
+        }
+
+        ImportDiagramInsert::type_node_style(
+            &mut self.input_diagram,
+            id!("type_data_source"),
+            ThemeAttr::StrokeStyle,
+            "dashed",
+        );
+    }
+}
@@ -0,0 +1,138 @@
+/// Address of a resource or module in Terraform output, with instance keys
+/// removed.
+///
+/// `module.vpc[0].aws_subnet.main["a"]` is the resource `aws_subnet.main`
+/// within the module path `["vpc"]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TerraformAddress {
+    /// A managed resource or data source.
+    Resource {
+        /// Names of the modules the resource is within, outermost first.
+        module_path: Vec<String>,
+        /// Address of the resource within its module, e.g. `aws_subnet.main`
+        /// or `data.aws_ami.ubuntu`.
+        local_address: String,
+        /// Type of the resource, e.g. `aws_subnet`.
+        resource_type: String,
+        /// Whether the resource is a data source.
+        is_data: bool,
+    },
+    /// A module, or one of its outputs.
+    Module {
+        /// Names of the module and the modules it is within, outermost first.
+        module_path: Vec<String>,
+    },
+}
+
+impl TerraformAddress {
+    /// Returns the address that the reference refers to, or `None` if it does
+    /// not refer to a resource or module, e.g. `var.region`.
+    ///
+    /// `module_path` is the path of the module that the reference is written
+    /// in, which relative references are resolved from. Attributes after the
+    /// resource or module, e.g. `.id` in `aws_subnet.main.id`, are ignored.
+    pub(crate) fn parse(reference: &str, module_path: &[String]) -> Option<Self> {
+        let mut module_path = module_path.to_vec();
+        let module_path_len = module_path.len();
+        let mut segments = Self::segments(reference).into_iter();
+        loop {
+            match segments.next()? {
+                "module" => {
+                    module_path.push(segments.next()?.to_string());
+                    if segments.len() == 0 {
+                        return Some(Self::Module { module_path });
+                    }
+                }
+                "data" => {
+                    let resource_type = segments.next()?;
+                    let name = segments.next()?;
+                    return Some(Self::Resource {
+                        module_path,
+                        local_address: format!("data.{resource_type}.{name}"),
+                        resource_type: resource_type.to_string(),
+                        is_data: true,
+                    });
+                }
+                "var" | "local" | "each" | "count" | "path" | "terraform" | "self" | "output"
+                | "provider" => return None,
+                resource_type => {
+                    return match segments.next() {
+                        Some(name) => Some(Self::Resource {
+                            module_path,
+                            local_address: format!("{resource_type}.{name}"),
+                            resource_type: resource_type.to_string(),
+                            is_data: false,
+                        }),
+                        // `module.vpc.subnet_id` is an output of `module.vpc`.
+                        None if module_path.len() > module_path_len => {
+                            Some(Self::Module { module_path })
+                        }
+                        None => None,
+                    };
+                }
+            }
+        }
+    }
+
+    /// Returns the full address, e.g. `module.vpc.aws_subnet.main`.
+    pub(crate) fn key(&self) -> String {
+        let (module_path, local_address) = match self {
+            Self::Resource {
+                module_path,
+                local_address,
+                ..
+            } => (module_path, Some(local_address)),
+            Self::Module { module_path } => (module_path, None),
+        };
+        module_path
+            .iter()
+            .map(|module_name| format!("module.{module_name}"))
+            .chain(local_address.cloned())
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    /// Returns the names of the modules the resource is within, or of the
+    /// module and the modules it is within.
+    pub(crate) fn module_path(&self) -> &[String] {
+        match self {
+            Self::Resource { module_path, .. } | Self::Module { module_path } => module_path,
+        }
+    }
+
+    /// Returns the `.` separated segments of the address, with `[..]`
+    /// instance keys removed.
+    ///
+    /// `.`s within instance keys, e.g. `["a.b"]`, do not separate segments.
+    fn segments(address: &str) -> Vec<&str> {
+        let mut segments = Vec::new();
+        let mut segment_start = 0;
+        let mut bracket_depth = 0usize;
+        let mut in_quotes = false;
+        let mut escaped = false;
+        for (index, c) in address.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_quotes => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                '[' if !in_quotes => bracket_depth += 1,
+                ']' if !in_quotes => bracket_depth = bracket_depth.saturating_sub(1),
+                '.' if !in_quotes && bracket_depth == 0 => {
+                    segments.push(&address[segment_start..index]);
+                    segment_start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        segments.push(&address[segment_start..]);
+
+        segments
+            .into_iter()
+            .map(|segment| {
+                segment
+                    .split_once('[')
+                    .map_or(segment, |(segment, _instance_key)| segment)
+            })
+            .collect()
+    }
+}
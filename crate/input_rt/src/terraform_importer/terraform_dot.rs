@@ -0,0 +1,104 @@
+use disposition_model_common::{Map, Set};
+
+use crate::{
+    dot_importer::dot_graph::DotGraph,
+    terraform_importer::{terraform_address::TerraformAddress, terraform_graph::TerraformGraph},
+};
+
+/// Reads resources and dependencies from `terraform graph` output.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TerraformDot;
+
+impl TerraformDot {
+    /// Returns the resources and dependencies in the DOT graph.
+    ///
+    /// Each edge points from a node to what it depends on. Dependencies
+    /// through nodes that are not resources, e.g. variables, locals, and
+    /// module outputs, are imported as dependencies between the resources at
+    /// either end.
+    pub(crate) fn graph(dot_graph: &DotGraph) -> TerraformGraph {
+        let mut graph = TerraformGraph::default();
+
+        let mut node_dependencies = Map::<&str, Vec<&str>>::new();
+        dot_graph.edges.iter().for_each(|edge| {
+            edge.ends.windows(2).for_each(|ends| {
+                ends[0].iter().for_each(|dependent| {
+                    ends[1].iter().for_each(|dependency| {
+                        if let (Some(dependent), Some(dependency)) = (
+                            Self::node_address(dependent),
+                            Self::node_address(dependency),
+                        ) {
+                            node_dependencies
+                                .entry(dependent)
+                                .or_default()
+                                .push(dependency);
+                        }
+                    });
+                });
+            });
+        });
+
+        let resource_nodes = dot_graph
+            .nodes
+            .keys()
+            .filter_map(|dot_id| Self::node_address(dot_id))
+            .filter(|node| graph.resource_insert(node, None).is_some())
+            .collect::<Set<&str>>();
+
+        resource_nodes.iter().for_each(|resource_node| {
+            let Some(dependent) = TerraformAddress::parse(resource_node, &[]) else {
+                return;
+            };
+
+            let mut nodes_visited = Set::<&str>::new();
+            let mut nodes_to_visit = vec![*resource_node];
+            while let Some(node) = nodes_to_visit.pop() {
+                let Some(dependencies) = node_dependencies.get(node) else {
+                    continue;
+                };
+                dependencies.iter().for_each(|dependency| {
+                    if !nodes_visited.insert(*dependency) || dependency == resource_node {
+                        return;
+                    }
+                    if resource_nodes.contains(dependency) {
+                        if let Some(dependency) = TerraformAddress::parse(dependency, &[]) {
+                            graph.dependencies.push((dependency, dependent.clone()));
+                        }
+                    } else {
+                        nodes_to_visit.push(dependency);
+                    }
+                });
+            }
+        });
+
+        graph
+    }
+
+    /// Returns the address of the node, without the `[root] ` prefix and
+    /// ` (expand)` / ` (close)` suffixes of older Terraform versions.
+    ///
+    /// Returns `None` for:
+    ///
+    /// * Nodes that destroy resources, as their edges point from dependencies
+    ///   to dependents.
+    /// * Nodes that older Terraform versions add to order the graph, e.g.
+    ///   `[root] meta.count-boundary (EachMode fixup)`, `[root] root`, and
+    ///   `[root] provisioner.local-exec`.
+    /// * Nodes that close providers, as they depend on every resource that uses
+    ///   the provider, and would otherwise connect those resources to each
+    ///   other through the provider.
+    fn node_address(dot_id: &str) -> Option<&str> {
+        let node = dot_id.strip_prefix("[root] ").unwrap_or(dot_id);
+        let (node, suffix) = match node.rsplit_once(" (") {
+            Some((node, suffix)) if suffix.ends_with(')') => (node, Some(suffix)),
+            _ => (node, None),
+        };
+        let node_kind = node.split(['.', '[']).next().unwrap_or(node);
+        match (node_kind, suffix) {
+            (_, Some(suffix)) if suffix.starts_with("destroy") => None,
+            ("meta" | "root" | "provisioner", _) => None,
+            ("provider", Some("close)")) => None,
+            _ => Some(node),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use disposition_model_common::{Map, Set};
+
+use crate::terraform_importer::terraform_address::TerraformAddress;
+
+/// Resources and dependencies read from Terraform output.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TerraformGraph {
+    /// Resources keyed by their address without instance keys, in the order
+    /// they first appear.
+    pub(crate) resources: Map<String, TerraformResource>,
+    /// Dependencies as `(dependency, dependent)` pairs, in the order they
+    /// appear.
+    pub(crate) dependencies: Vec<(TerraformAddress, TerraformAddress)>,
+}
+
+/// A managed resource or data source.
+#[derive(Clone, Debug)]
+pub(crate) struct TerraformResource {
+    /// Address of the resource.
+    pub(crate) address: TerraformAddress,
+    /// Local name of the resource's provider, e.g. `aws`.
+    pub(crate) provider: Option<String>,
+    /// Actions that a plan takes on the resource's instances.
+    pub(crate) actions: Set<TerraformAction>,
+}
+
+/// An action that a plan takes on a resource instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum TerraformAction {
+    /// The instance is created.
+    Create,
+    /// The instance is updated in place.
+    Update,
+    /// The instance is deleted and created again.
+    Replace,
+    /// The instance is deleted.
+    Delete,
+    /// The data source is read during apply.
+    Read,
+}
+
+impl TerraformAction {
+    /// Every action, in the order their tags are listed.
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Create,
+        Self::Update,
+        Self::Replace,
+        Self::Delete,
+        Self::Read,
+    ];
+
+    /// Returns the action for a resource change's `actions`, or `None` for
+    /// `["no-op"]`.
+    pub(crate) fn from_actions(actions: &[String]) -> Option<Self> {
+        let actions = actions.iter().map(String::as_str).collect::<Vec<&str>>();
+        match actions.as_slice() {
+            ["create"] => Some(Self::Create),
+            ["update"] => Some(Self::Update),
+            ["delete", "create"] | ["create", "delete"] => Some(Self::Replace),
+            ["delete"] | ["forget"] => Some(Self::Delete),
+            ["read"] => Some(Self::Read),
+            _ => None,
+        }
+    }
+
+    /// Returns the tag ID for the action, e.g. `tag_create`.
+    pub(crate) fn tag_id(self) -> &'static str {
+        match self {
+            Self::Create => "tag_create",
+            Self::Update => "tag_update",
+            Self::Replace => "tag_replace",
+            Self::Delete => "tag_delete",
+            Self::Read => "tag_read",
+        }
+    }
+
+    /// Returns the tag name for the action, e.g. `Create`.
+    pub(crate) fn tag_name(self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Update => "Update",
+            Self::Replace => "Replace",
+            Self::Delete => "Delete",
+            Self::Read => "Read",
+        }
+    }
+}
+
+impl TerraformGraph {
+    /// Adds the resource at the address if it has not been added, and
+    /// returns it.
+    ///
+    /// Returns `None` if the address is not a resource.
+    pub(crate) fn resource_insert(
+        &mut self,
+        address: &str,
+        provider_name: Option<&str>,
+    ) -> Option<&mut TerraformResource> {
+        let address = TerraformAddress::parse(address, &[])?;
+        let TerraformAddress::Resource { resource_type, .. } = &address else {
+            return None;
+        };
+
+        // `provider_name` is e.g. `registry.terraform.io/hashicorp/aws`. By
+        // convention, resource types are prefixed with the provider's name.
+        let provider = provider_name
+            .and_then(|provider_name| provider_name.rsplit('/').next())
+            .or_else(|| resource_type.split('_').next())
+            .filter(|provider| !provider.is_empty())
+            .map(str::to_string);
+        let resource = self
+            .resources
+            .entry(address.key())
+            .or_insert_with(|| TerraformResource {
+                address,
+                provider: None,
+                actions: Set::new(),
+            });
+        if provider_name.is_some() || resource.provider.is_none() {
+            resource.provider = provider;
+        }
+        Some(resource)
+    }
+}
@@ -0,0 +1,270 @@
+use disposition_model_common::Map;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::terraform_importer::{
+    terraform_address::TerraformAddress,
+    terraform_graph::{TerraformAction, TerraformGraph},
+};
+
+/// The parts of `terraform show -json` output that are imported.
+///
+/// State output has `values`, and plan output has `planned_values`,
+/// `resource_changes`, and `configuration`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformJson {
+    /// Resources in the state.
+    pub(crate) values: Option<TerraformValues>,
+    /// Resources after the plan is applied.
+    pub(crate) planned_values: Option<TerraformValues>,
+    /// Actions the plan takes on each resource instance.
+    pub(crate) resource_changes: Vec<TerraformResourceChange>,
+    /// Resources and module calls as written in the configuration.
+    pub(crate) configuration: Option<TerraformConfiguration>,
+}
+
+/// Resource values of the root module.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformValues {
+    /// The root module.
+    pub(crate) root_module: TerraformModuleValues,
+}
+
+/// Resource values of a module.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformModuleValues {
+    /// Resource instances within the module.
+    pub(crate) resources: Vec<TerraformResourceValues>,
+    /// Module instances within the module.
+    pub(crate) child_modules: Vec<TerraformModuleValues>,
+}
+
+/// Values of a resource instance.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformResourceValues {
+    /// Absolute address of the instance, e.g. `module.vpc.aws_subnet.main[0]`.
+    pub(crate) address: String,
+    /// Source address of the provider, e.g.
+    /// `registry.terraform.io/hashicorp/aws`.
+    pub(crate) provider_name: Option<String>,
+    /// Absolute addresses of what the instance depended on when it was last
+    /// applied.
+    pub(crate) depends_on: Vec<String>,
+}
+
+/// Action the plan takes on a resource instance.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformResourceChange {
+    /// Absolute address of the instance.
+    pub(crate) address: String,
+    /// Source address of the provider.
+    pub(crate) provider_name: Option<String>,
+    /// The planned change.
+    pub(crate) change: TerraformChange,
+}
+
+/// A planned change to a resource instance.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformChange {
+    /// Actions taken, e.g. `["create"]` or `["delete", "create"]`.
+    pub(crate) actions: Vec<String>,
+}
+
+/// The configuration of the root module.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformConfiguration {
+    /// The root module.
+    pub(crate) root_module: TerraformModuleConfig,
+}
+
+/// The configuration of a module.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformModuleConfig {
+    /// Resources declared in the module.
+    pub(crate) resources: Vec<TerraformResourceConfig>,
+    /// Modules called by the module, keyed by module name.
+    pub(crate) module_calls: Map<String, TerraformModuleCall>,
+}
+
+/// A resource as declared in the configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformResourceConfig {
+    /// Address of the resource within its module, e.g. `aws_subnet.main`.
+    pub(crate) address: String,
+    /// Arguments of the resource, whose values list their `references`.
+    pub(crate) expressions: Value,
+    /// `count` argument of the resource.
+    pub(crate) count_expression: Value,
+    /// `for_each` argument of the resource.
+    pub(crate) for_each_expression: Value,
+    /// Addresses in the resource's `depends_on` argument.
+    pub(crate) depends_on: Vec<String>,
+}
+
+/// A module call as declared in the configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct TerraformModuleCall {
+    /// Input variables passed to the module, whose values list their
+    /// `references`.
+    pub(crate) expressions: Value,
+    /// `count` argument of the module call.
+    pub(crate) count_expression: Value,
+    /// `for_each` argument of the module call.
+    pub(crate) for_each_expression: Value,
+    /// Addresses in the module call's `depends_on` argument.
+    pub(crate) depends_on: Vec<String>,
+    /// Configuration of the called module.
+    pub(crate) module: TerraformModuleConfig,
+}
+
+impl TerraformJson {
+    /// Returns the resources and dependencies in the output.
+    pub(crate) fn graph(&self) -> TerraformGraph {
+        let mut graph = TerraformGraph::default();
+
+        [&self.values, &self.planned_values]
+            .into_iter()
+            .flatten()
+            .for_each(|values| Self::module_values_add(&mut graph, &values.root_module));
+        self.resource_changes.iter().for_each(|resource_change| {
+            let resource = graph.resource_insert(
+                &resource_change.address,
+                resource_change.provider_name.as_deref(),
+            );
+            if let (Some(resource), Some(action)) = (
+                resource,
+                TerraformAction::from_actions(&resource_change.change.actions),
+            ) {
+                resource.actions.insert(action);
+            }
+        });
+        if let Some(configuration) = &self.configuration {
+            Self::module_config_add(&mut graph, &configuration.root_module, &[]);
+        }
+
+        graph
+    }
+
+    /// Adds the resources in the module values and its child modules, and the
+    /// dependencies recorded in the state.
+    fn module_values_add(graph: &mut TerraformGraph, module_values: &TerraformModuleValues) {
+        module_values.resources.iter().for_each(|resource_values| {
+            let Some(resource) = graph.resource_insert(
+                &resource_values.address,
+                resource_values.provider_name.as_deref(),
+            ) else {
+                return;
+            };
+            let dependent = resource.address.clone();
+            resource_values
+                .depends_on
+                .iter()
+                .filter_map(|dependency| TerraformAddress::parse(dependency, &[]))
+                .for_each(|dependency| {
+                    graph.dependencies.push((dependency, dependent.clone()));
+                });
+        });
+        module_values
+            .child_modules
+            .iter()
+            .for_each(|child_module| Self::module_values_add(graph, child_module));
+    }
+
+    /// Adds the resources declared in the module configuration and the
+    /// modules it calls, and the dependencies from their references.
+    fn module_config_add(
+        graph: &mut TerraformGraph,
+        module_config: &TerraformModuleConfig,
+        module_path: &[String],
+    ) {
+        module_config.resources.iter().for_each(|resource_config| {
+            let Some(dependent) = TerraformAddress::parse(&resource_config.address, module_path)
+            else {
+                return;
+            };
+            let _ = graph.resource_insert(&dependent.key(), None);
+            Self::references_add(
+                graph,
+                &dependent,
+                module_path,
+                [
+                    &resource_config.expressions,
+                    &resource_config.count_expression,
+                    &resource_config.for_each_expression,
+                ],
+                &resource_config.depends_on,
+            );
+        });
+        module_config
+            .module_calls
+            .iter()
+            .for_each(|(module_name, module_call)| {
+                let mut module_path_call = module_path.to_vec();
+                module_path_call.push(module_name.clone());
+                let dependent = TerraformAddress::Module {
+                    module_path: module_path_call.clone(),
+                };
+                Self::references_add(
+                    graph,
+                    &dependent,
+                    module_path,
+                    [
+                        &module_call.expressions,
+                        &module_call.count_expression,
+                        &module_call.for_each_expression,
+                    ],
+                    &module_call.depends_on,
+                );
+                Self::module_config_add(graph, &module_call.module, &module_path_call);
+            });
+    }
+
+    /// Adds a dependency on each resource or module that the expressions and
+    /// `depends_on` refer to.
+    fn references_add(
+        graph: &mut TerraformGraph,
+        dependent: &TerraformAddress,
+        module_path: &[String],
+        expressions: [&Value; 3],
+        depends_on: &[String],
+    ) {
+        let mut references = Vec::new();
+        expressions
+            .into_iter()
+            .for_each(|expression| Self::references_collect(expression, &mut references));
+        references
+            .into_iter()
+            .chain(depends_on.iter().map(String::as_str))
+            .filter_map(|reference| TerraformAddress::parse(reference, module_path))
+            .for_each(|dependency| {
+                graph.dependencies.push((dependency, dependent.clone()));
+            });
+    }
+
+    /// Collects the `references` within the expression, including within
+    /// nested blocks.
+    fn references_collect<'v>(expression: &'v Value, references: &mut Vec<&'v str>) {
+        match expression {
+            Value::Object(object) => object.iter().for_each(|(key, value)| match value {
+                Value::Array(values) if key == "references" => {
+                    references.extend(values.iter().filter_map(Value::as_str))
+                }
+                value => Self::references_collect(value, references),
+            }),
+            Value::Array(values) => values
+                .iter()
+                .for_each(|value| Self::references_collect(value, references)),
+            _ => {}
+        }
+    }
+}
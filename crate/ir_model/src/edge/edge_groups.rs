@@ -1,6 +1,9 @@
 use std::ops::{Deref, DerefMut};
 
-use disposition_model_common::{edge::EdgeGroupId, Id, Map};
+use disposition_model_common::{
+    edge::{EdgeGroupId, EdgeId, EdgeIdGenerator},
+    Id, Map,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::edge::EdgeGroup;
@@ -72,6 +75,28 @@ impl<'id> EdgeGroups<'id> {
         self.0.contains_key(id.as_ref())
     }
 
+    /// Builds a lookup from every edge instance ID to its edge group ID.
+    ///
+    /// Used to resolve the group-ID fallback for `edge_descs` / `edge_labels`
+    /// at call sites that only have the edge instance ID at hand (e.g.
+    /// envelope label-slot construction).
+    pub fn edge_id_to_group_id_build(&self) -> Map<EdgeId<'id>, EdgeGroupId<'id>> {
+        self.0
+            .iter()
+            .flat_map(|(edge_group_id, edge_group)| {
+                edge_group
+                    .iter()
+                    .enumerate()
+                    .map(move |(edge_index, _edge)| {
+                        (
+                            EdgeIdGenerator::generate(edge_group_id, edge_index),
+                            edge_group_id.clone(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
     /// Converts this `EdgeGroups` into one with a `'static` lifetime.
     ///
     /// If any inner `Cow` is borrowed, this will clone the string to create
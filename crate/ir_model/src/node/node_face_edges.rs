@@ -1,4 +1,4 @@
-use disposition_model_common::{edge::EdgeGroupId, Id, Map};
+use disposition_model_common::{edge::EdgeIdGenerator, Map};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -94,7 +94,7 @@ impl<'id> NodeFaceEdges<'id> {
 
         for (edge_group_id, edge_group) in edge_groups.iter() {
             for (edge_index, edge) in edge_group.iter().enumerate() {
-                let edge_id = Self::edge_id_generate(edge_group_id, edge_index);
+                let edge_id = EdgeIdGenerator::generate(edge_group_id, edge_index);
 
                 let Some(assignment) = edge_face_assignments.get(&edge_id) else {
                     continue;
@@ -183,17 +183,6 @@ impl<'id> NodeFaceEdges<'id> {
         )
     }
 
-    /// Generates the edge ID for the edge at `edge_index` in `edge_group_id`.
-    ///
-    /// Mirrors the format used by `EdgeIdGenerator::generate`:
-    /// `"{edge_group_id}__{edge_index}"`.
-    fn edge_id_generate<'g>(edge_group_id: &EdgeGroupId<'g>, edge_index: usize) -> EdgeId<'g> {
-        let edge_id_str = format!("{edge_group_id}__{edge_index}");
-        Id::try_from(edge_id_str)
-            .expect("edge ID should be valid")
-            .into()
-    }
-
     /// Appends `edge_id` to `inner[node_id][face]` when `face` is `Some`.
     fn face_edge_append(
         inner: &mut Map<NodeId<'id>, Map<NodeFace, Vec<EdgeId<'id>>>>,
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{LayoutEdge, LayoutEdgeDescription, LayoutEdgeLabel, LayoutNode, LayoutProcess};
+
+/// The computed layout of a diagram, for renderers that draw diagrams without
+/// reading SVG.
+///
+/// All coordinates are absolute, in pixels, with the origin at the top left of
+/// the diagram and `y` increasing downwards.
+///
+/// Positions are laid out with every process expanded, i.e. each process
+/// step is placed below its process, and each node below a process is placed
+/// below all of that process' steps. Renderers that hide the steps of
+/// collapsed processes may use [`LayoutProcess`] to shift nodes up by the
+/// height of those steps.
+///
+/// # Compatibility
+///
+/// [`DiagramLayout::VERSION`] is incremented whenever a field is removed or
+/// renamed, or its meaning changes. Fields may be added without incrementing
+/// the version, so deserializers should ignore unknown fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use disposition_layout_model::DiagramLayout;
+///
+/// let diagram_layout = DiagramLayout::new(200.0, 100.0);
+///
+/// assert_eq!(diagram_layout.version, DiagramLayout::VERSION);
+/// assert!(diagram_layout.nodes.is_empty());
+/// ```
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DiagramLayout<'id> {
+    /// Version of the layout schema, [`DiagramLayout::VERSION`] when
+    /// generated by this crate.
+    pub version: u32,
+    /// Width of the diagram.
+    pub width: f32,
+    /// Height of the diagram.
+    pub height: f32,
+    /// Nodes in the diagram, with each node before the nodes nested within
+    /// it.
+    pub nodes: Vec<LayoutNode<'id>>,
+    /// Edges in the diagram, in the order they are drawn.
+    pub edges: Vec<LayoutEdge<'id>>,
+    /// Text placed at the ends of edges.
+    pub edge_labels: Vec<LayoutEdgeLabel<'id>>,
+    /// Text placed beside the middle of edges.
+    pub edge_descriptions: Vec<LayoutEdgeDescription<'id>>,
+    /// Processes in the diagram, in process order, with their steps.
+    pub processes: Vec<LayoutProcess<'id>>,
+}
+
+impl DiagramLayout<'_> {
+    /// Version of the layout schema that this crate generates.
+    pub const VERSION: u32 = 1;
+
+    /// Returns an empty `DiagramLayout` with the given size, at the current
+    /// [`DiagramLayout::VERSION`].
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            version: Self::VERSION,
+            width,
+            height,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            edge_labels: Vec::new(),
+            edge_descriptions: Vec::new(),
+            processes: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// An axis-aligned rectangle, with its top left corner at `x`, `y`.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutBox {
+    /// X coordinate of the left edge.
+    pub x: f32,
+    /// Y coordinate of the top edge.
+    pub y: f32,
+    /// Width of the rectangle.
+    pub width: f32,
+    /// Height of the rectangle.
+    pub height: f32,
+}
+
+impl LayoutBox {
+    /// Creates a new `LayoutBox`.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A circle drawn for a node, with its centre at `cx`, `cy`.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutCircle {
+    /// X coordinate of the circle's centre.
+    pub cx: f32,
+    /// Y coordinate of the circle's centre.
+    pub cy: f32,
+    /// Radius of the circle.
+    pub radius: f32,
+}
@@ -0,0 +1,31 @@
+use disposition_ir_model::{edge::EdgeId, node::NodeId};
+use disposition_model_common::edge::EdgeGroupId;
+use serde::{Deserialize, Serialize};
+
+use crate::{LayoutEdgeKind, LayoutPathSegment};
+
+/// The computed path of an edge between two nodes.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutEdge<'id> {
+    /// ID of the edge.
+    pub edge_id: EdgeId<'id>,
+    /// ID of the edge group this edge belongs to.
+    pub edge_group_id: EdgeGroupId<'id>,
+    /// ID of the node the edge starts from.
+    pub from_node_id: NodeId<'id>,
+    /// ID of the node the edge points to.
+    pub to_node_id: NodeId<'id>,
+    /// Whether the edge is a dependency or an interaction.
+    pub kind: LayoutEdgeKind,
+    /// Segments of the edge's path, from the `from` node to the `to` node.
+    pub path: Vec<LayoutPathSegment>,
+    /// Segments of the arrowhead at the `to` node's end of the edge.
+    ///
+    /// For interaction edges, the arrowhead is centred on the origin, as it
+    /// is meant to be moved along the edge's path.
+    pub arrow_head_path: Vec<LayoutPathSegment>,
+}
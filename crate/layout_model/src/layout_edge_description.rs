@@ -0,0 +1,17 @@
+use disposition_ir_model::edge::EdgeId;
+use serde::{Deserialize, Serialize};
+
+use crate::LayoutText;
+
+/// Text placed beside the middle of an edge.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutEdgeDescription<'id> {
+    /// ID of the edge the description is for.
+    pub edge_id: EdgeId<'id>,
+    /// The description's text.
+    pub text: LayoutText,
+}
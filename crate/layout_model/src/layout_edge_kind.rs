@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether a [`LayoutEdge`](crate::LayoutEdge) is a dependency or an
+/// interaction.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutEdgeKind {
+    /// A dependency between things, which is always shown.
+    Dependency,
+    /// An interaction between things, which is shown when a process step that
+    /// includes it is focused.
+    Interaction,
+}
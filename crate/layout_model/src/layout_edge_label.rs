@@ -0,0 +1,19 @@
+use disposition_ir_model::edge::EdgeId;
+use serde::{Deserialize, Serialize};
+
+use crate::LayoutText;
+
+/// Text placed at the ends of an edge.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutEdgeLabel<'id> {
+    /// ID of the edge the labels are for.
+    pub edge_id: EdgeId<'id>,
+    /// Label beside the `from` node's end of the edge.
+    pub from_label: Option<LayoutText>,
+    /// Label beside the `to` node's end of the edge.
+    pub to_label: Option<LayoutText>,
+}
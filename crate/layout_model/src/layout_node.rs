@@ -0,0 +1,34 @@
+use disposition_ir_model::node::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::{LayoutBox, LayoutCircle, LayoutNodeKind, LayoutText};
+
+/// The computed position of a node, and of the text within it.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutNode<'id> {
+    /// ID of the node.
+    pub node_id: NodeId<'id>,
+    /// ID of the node this node is nested within, if any.
+    pub parent_id: Option<NodeId<'id>>,
+    /// What the node represents in the diagram.
+    pub kind: LayoutNodeKind,
+    /// The node's box.
+    ///
+    /// For collapsed processes, this is the height of the process with its
+    /// steps hidden. See [`LayoutProcess::height_expanded`] for the expanded
+    /// height.
+    ///
+    /// [`LayoutProcess::height_expanded`]: crate::LayoutProcess::height_expanded
+    pub node_box: LayoutBox,
+    /// The box that encloses the node with the space reserved around it for
+    /// edges to be routed.
+    pub envelope_box: LayoutBox,
+    /// The circle drawn for the node, for nodes with a circle shape.
+    pub circle: Option<LayoutCircle>,
+    /// The node's name and description text, if it has any.
+    pub text: Option<LayoutText>,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// What a [`LayoutNode`](crate::LayoutNode) represents in the diagram.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutNodeKind {
+    /// A thing, which may have other things nested within it.
+    Thing,
+    /// A tag, which highlights the things associated with it.
+    Tag,
+    /// A process, whose steps are placed below it.
+    Process,
+    /// A step within a process.
+    ProcessStep,
+}
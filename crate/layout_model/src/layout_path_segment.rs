@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// A segment of a path, in absolute coordinates.
+///
+/// Each segment continues from the end point of the previous segment.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutPathSegment {
+    /// Starts a new subpath at the point.
+    MoveTo {
+        /// X coordinate of the point.
+        x: f64,
+        /// Y coordinate of the point.
+        y: f64,
+    },
+    /// Draws a straight line to the point.
+    LineTo {
+        /// X coordinate of the point.
+        x: f64,
+        /// Y coordinate of the point.
+        y: f64,
+    },
+    /// Draws a quadratic Bézier curve to the point.
+    QuadTo {
+        /// X coordinate of the control point.
+        x1: f64,
+        /// Y coordinate of the control point.
+        y1: f64,
+        /// X coordinate of the end point.
+        x: f64,
+        /// Y coordinate of the end point.
+        y: f64,
+    },
+    /// Draws a cubic Bézier curve to the point.
+    CubicTo {
+        /// X coordinate of the first control point.
+        x1: f64,
+        /// Y coordinate of the first control point.
+        y1: f64,
+        /// X coordinate of the second control point.
+        x2: f64,
+        /// Y coordinate of the second control point.
+        y2: f64,
+        /// X coordinate of the end point.
+        x: f64,
+        /// Y coordinate of the end point.
+        y: f64,
+    },
+    /// Draws a straight line back to the start of the subpath.
+    Close,
+}
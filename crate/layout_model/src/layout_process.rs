@@ -0,0 +1,28 @@
+use disposition_ir_model::node::NodeId;
+use serde::{Deserialize, Serialize};
+
+/// A process, and the steps within it.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutProcess<'id> {
+    /// Node ID of the process.
+    pub process_id: NodeId<'id>,
+    /// Node IDs of the process' steps, in step order.
+    pub process_step_ids: Vec<NodeId<'id>>,
+    /// Whether the process is drawn collapsed, and only expanded to show its
+    /// steps when the process or one of its steps is focused.
+    ///
+    /// When `false`, the process' node box already contains its steps.
+    pub is_collapsed: bool,
+    /// Height of the process node when it is expanded to contain its steps.
+    pub height_expanded: f32,
+    /// Total height of the process' steps, when the process is collapsed.
+    ///
+    /// Nodes below a collapsed process are placed as if its steps are shown,
+    /// so a renderer that hides the steps may shift those nodes up by this
+    /// height. `0.0` when the process is not collapsed.
+    pub steps_height: f32,
+}
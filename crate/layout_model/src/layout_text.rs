@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{LayoutBox, LayoutTextSpan};
+
+/// A block of text, with the box it is placed within and each positioned span.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutText {
+    /// The box the text is placed within.
+    pub text_box: LayoutBox,
+    /// Runs of text with the same style, in reading order.
+    ///
+    /// Spans with the same `y` are on the same line.
+    pub text_spans: Vec<LayoutTextSpan>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::LayoutTextStyle;
+
+/// A run of text with the same style, placed on one line.
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LayoutTextSpan {
+    /// X coordinate of the start of the text.
+    pub x: f32,
+    /// Y coordinate of the text's baseline.
+    pub y: f32,
+    /// Width of the span, or `0.0` if it was not measured.
+    pub width: f32,
+    /// Height of the span's line, or `0.0` if it was not measured.
+    pub height: f32,
+    /// The text, not escaped.
+    pub text: String,
+    /// Markdown style of the span. `None` for plain text.
+    pub style: Option<LayoutTextStyle>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Markdown formatting of a [`LayoutTextSpan`](crate::LayoutTextSpan).
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "test")),
+    derive(schemars::JsonSchema)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct LayoutTextStyle {
+    /// Whether the span is bold.
+    pub bold: bool,
+    /// Whether the span is italic.
+    pub italic: bool,
+    /// Whether the span is strikethrough.
+    pub strikethrough: bool,
+    /// Whether the span is inline code.
+    pub code: bool,
+    /// Whether the span is within a blockquote.
+    pub blockquote: bool,
+    /// Heading level: `1`--`6`, or `0` for non-heading text.
+    pub heading_level: u8,
+    /// Destination URL when the span is part of a link. `None` otherwise.
+    pub link_dest: Option<String>,
+}
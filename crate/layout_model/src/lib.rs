@@ -0,0 +1,29 @@
+//! Data types for disposition to represent a diagram's computed layout.
+//!
+//! The layout is a stable, versioned description of where each node, edge,
+//! and piece of text is placed, for renderers that draw diagrams without
+//! reading SVG. See [`DiagramLayout`] for the compatibility guarantees.
+
+pub use crate::{
+    diagram_layout::DiagramLayout, layout_box::LayoutBox, layout_circle::LayoutCircle,
+    layout_edge::LayoutEdge, layout_edge_description::LayoutEdgeDescription,
+    layout_edge_kind::LayoutEdgeKind, layout_edge_label::LayoutEdgeLabel, layout_node::LayoutNode,
+    layout_node_kind::LayoutNodeKind, layout_path_segment::LayoutPathSegment,
+    layout_process::LayoutProcess, layout_text::LayoutText, layout_text_span::LayoutTextSpan,
+    layout_text_style::LayoutTextStyle,
+};
+
+mod diagram_layout;
+mod layout_box;
+mod layout_circle;
+mod layout_edge;
+mod layout_edge_description;
+mod layout_edge_kind;
+mod layout_edge_label;
+mod layout_node;
+mod layout_node_kind;
+mod layout_path_segment;
+mod layout_process;
+mod layout_text;
+mod layout_text_span;
+mod layout_text_style;
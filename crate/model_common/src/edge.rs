@@ -1,12 +1,14 @@
 pub use self::{
     edge_curvature::EdgeCurvature, edge_descs::EdgeDescs, edge_group_id::EdgeGroupId,
-    edge_id::EdgeId, edge_label::EdgeLabel, edge_labels::EdgeLabels,
+    edge_id::EdgeId, edge_id_generator::EdgeIdGenerator, edge_label::EdgeLabel,
+    edge_labels::EdgeLabels,
 };
 
 mod edge_curvature;
 mod edge_descs;
 mod edge_group_id;
 mod edge_id;
+mod edge_id_generator;
 mod edge_label;
 mod edge_labels;
 
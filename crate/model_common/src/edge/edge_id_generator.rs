@@ -0,0 +1,37 @@
+use crate::{
+    edge::{EdgeGroupId, EdgeId},
+    Id,
+};
+
+/// Generates `EdgeId`s for edges in an edge group.
+///
+/// Edge IDs are not written in the input diagram, so this is shared by every
+/// crate that needs to refer to an individual edge, e.g. in `edge_labels`.
+///
+/// # Examples
+///
+/// ```rust
+/// use disposition_model_common::{
+///     edge::{EdgeGroupId, EdgeIdGenerator},
+///     id, Id,
+/// };
+///
+/// let edge_group_id: EdgeGroupId = id!("edge_a__b").into();
+/// let edge_id = EdgeIdGenerator::generate(&edge_group_id, 1);
+///
+/// assert_eq!("edge_a__b__1", edge_id.as_str());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EdgeIdGenerator;
+
+impl EdgeIdGenerator {
+    /// Generates an `EdgeId` from an edge group ID and edge index.
+    ///
+    /// Format: `"{edge_group_id}__{edge_index}"`
+    pub fn generate<'id>(edge_group_id: &EdgeGroupId<'id>, edge_index: usize) -> EdgeId<'id> {
+        let edge_id_str = format!("{edge_group_id}__{edge_index}");
+        Id::try_from(edge_id_str)
+            .expect("edge ID should be valid")
+            .into()
+    }
+}
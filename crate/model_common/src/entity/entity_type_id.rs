@@ -1,5 +1,6 @@
 use std::{
     borrow::Borrow,
+    fmt::{self, Display},
     ops::{Deref, DerefMut},
 };
 
@@ -64,6 +65,25 @@ impl<'s> EntityTypeId<'s> {
     pub fn into_inner(self) -> Id<'s> {
         self.0
     }
+
+    /// Converts this `EntityTypeId` into one with a `'static` lifetime.
+    ///
+    /// If the inner `Cow` is borrowed, this will clone the string to create
+    /// an owned version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use disposition_model_common::entity::EntityTypeId;
+    ///
+    /// let entity_type_id = EntityTypeId::new("type_organisation").unwrap();
+    /// let entity_type_id_static: EntityTypeId<'static> = entity_type_id.into_static();
+    ///
+    /// assert_eq!(entity_type_id_static.as_str(), "type_organisation");
+    /// ```
+    pub fn into_static(self) -> EntityTypeId<'static> {
+        EntityTypeId(self.0.into_static())
+    }
 }
 
 impl<'s> From<Id<'s>> for EntityTypeId<'s> {
@@ -97,3 +117,9 @@ impl<'s> DerefMut for EntityTypeId<'s> {
         &mut self.0
     }
 }
+
+impl<'s> Display for EntityTypeId<'s> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -8,6 +8,7 @@ pub use disposition_input_ir_rt as input_ir_rt;
 pub use disposition_input_model as input_model;
 pub use disposition_input_rt as input_rt;
 pub use disposition_ir_model as ir_model;
+pub use disposition_layout_model as layout_model;
 pub use disposition_model_common as model_common;
 pub use disposition_output_model as output_model;
 pub use disposition_svg_model as svg_model;
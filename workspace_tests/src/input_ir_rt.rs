@@ -152,10 +152,22 @@ pub(crate) const INPUT_DIAGRAM_0062_EDGES_FROM_HIGHER_RANK_TO_LOWER_RANK: &str =
     include_str!("input_diagram/0062_edges_from_higher_rank_to_lower_rank.yaml");
 
 mod diagram_generator;
+mod diagram_test_support;
+mod diagram_to_html_mapper;
+mod diagram_to_layout_mapper;
+mod diagrams_to_pdf_mapper;
 mod input_diagram_merger;
 mod input_to_ir_diagram_mapper;
+mod ir_to_dot_mapper;
+mod ir_to_mermaid_mapper;
 mod ir_to_taffy_builder;
 mod node_ranks_calculator;
+mod svg_elements_to_drawio_mapper;
+mod svg_elements_to_excalidraw_mapper;
+mod svg_elements_to_png_mapper;
 mod svg_elements_to_svg_mapper;
+mod svg_elements_to_tikz_mapper;
+mod svg_source_extractor;
 mod taffy_to_svg_elements_mapper;
 mod tailwind_consistency;
+mod theme_attr_validator;
@@ -1,5 +1,10 @@
-use disposition::input_model::{DiagramFocus, InputDiagram};
-use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive, InputDiagramMerger};
+use disposition::{
+    input_ir_model::issue::{IssueLevel, IssueLevels, ModelToIrIssue, ModelToIrIssueKind},
+    input_model::{DiagramFocus, InputDiagram},
+};
+use disposition_input_ir_rt::{
+    DiagramGenerateError, DiagramGenerator, EdgeAnimationActive, InputDiagramMerger,
+};
 
 use crate::input_ir_rt::{
     EXAMPLE_INPUT, INPUT_DIAGRAM_0012_EDGE_FROM_NESTED_NODE_TO_OUTER_NODE_CYCLIC,
@@ -64,6 +69,28 @@ fn generate_per_process_step_or_tag_produces_one_diagram_per_focus_in_order() {
     });
 }
 
+/// `focuses_collect` lists the focus states in the same order as the diagrams
+/// generated per process step or tag.
+#[test]
+fn focuses_collect_matches_generate_per_process_step_or_tag_order() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(EXAMPLE_INPUT).unwrap();
+    let merged = InputDiagramMerger::merge(InputDiagram::base(), &input_diagram);
+
+    let focuses = DiagramGenerator::focuses_collect(&merged);
+    let diagrams = DiagramGenerator::generate_per_process_step_or_tag(
+        &input_diagram,
+        EdgeAnimationActive::OnProcessStepFocus,
+    )
+    .expect("Expected diagrams to be generated.");
+
+    let focuses_generated = diagrams
+        .into_iter()
+        .map(|diagram_focus_generated| diagram_focus_generated.focus)
+        .collect::<Vec<_>>();
+    assert!(focuses.len() > 1);
+    assert_eq!(focuses_generated, focuses);
+}
+
 /// The interactive (single diagram) path keeps emitting `group-has-[...]` focus
 /// CSS, so the absence of those selectors in the baked path is meaningful.
 #[test]
@@ -143,3 +170,109 @@ fn generate_populates_consistent_edge_routing_diagnostics() {
         }
     }
 }
+
+const INPUT_WITH_ISSUES: &str = r#"
+things:
+  a: {}
+thing_names:
+  a: A
+  c: C
+tag_things:
+  tag_unknown: [a]
+"#;
+
+/// The default `IssueLevels` warn on every issue, so the diagram is still
+/// generated with the issues attached.
+#[test]
+fn generate_with_default_issue_levels_warns() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_ISSUES).unwrap();
+
+    let diagram_generated =
+        DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+            .expect("Expected diagram to be generated.");
+
+    assert_eq!(
+        vec![
+            ModelToIrIssueKind::ThingNamesThingUnknown,
+            ModelToIrIssueKind::TagThingsTagUnknown,
+        ],
+        diagram_generated
+            .ir_diagram_issues
+            .iter()
+            .map(ModelToIrIssue::kind)
+            .collect::<Vec<_>>()
+    );
+}
+
+/// Strict `IssueLevels` fail generation, carrying every issue that is not
+/// allowed.
+#[test]
+fn generate_with_strict_issue_levels_returns_issues_denied() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_ISSUES).unwrap();
+
+    let error = DiagramGenerator::generate_with_issue_levels(
+        &input_diagram,
+        EdgeAnimationActive::OnProcessStepFocus,
+        &IssueLevels::strict(),
+    )
+    .expect_err("Expected generation to fail on denied issues.");
+
+    let DiagramGenerateError::IssuesDenied { issues } = error else {
+        panic!("Expected `DiagramGenerateError::IssuesDenied`, got: {error:?}");
+    };
+    assert_eq!(2, issues.len());
+}
+
+/// Allowed issues are discarded, and warned issues don't fail generation even
+/// when the default level is `Deny`.
+#[test]
+fn generate_with_issue_levels_applies_per_kind_levels() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_ISSUES).unwrap();
+    let mut issue_levels = IssueLevels::strict();
+    issue_levels.kinds.insert(
+        ModelToIrIssueKind::ThingNamesThingUnknown,
+        IssueLevel::Allow,
+    );
+    issue_levels
+        .kinds
+        .insert(ModelToIrIssueKind::TagThingsTagUnknown, IssueLevel::Warn);
+
+    let diagrams = DiagramGenerator::generate_per_process_step_or_tag_with_issue_levels(
+        &input_diagram,
+        EdgeAnimationActive::OnProcessStepFocus,
+        &issue_levels,
+    )
+    .expect("Expected diagrams to be generated.");
+
+    diagrams.iter().for_each(|diagram_focus_generated| {
+        assert_eq!(
+            vec![ModelToIrIssueKind::TagThingsTagUnknown],
+            diagram_focus_generated
+                .diagram_generated
+                .ir_diagram_issues
+                .iter()
+                .map(ModelToIrIssue::kind)
+                .collect::<Vec<_>>()
+        );
+    });
+}
+
+/// `ir_diagram_map` reports the same issues as generating the diagram, with no
+/// issue levels applied.
+#[test]
+fn ir_diagram_map_returns_issues_without_generating() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_ISSUES).unwrap();
+
+    let (input_diagram_merged, ir_diagram_and_issues) =
+        DiagramGenerator::ir_diagram_map(&input_diagram);
+    let diagram_generated =
+        DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+            .expect("Expected diagram to be generated.");
+
+    assert_eq!(diagram_generated.input_diagram_merged, input_diagram_merged);
+    assert_eq!(diagram_generated.ir_diagram, ir_diagram_and_issues.diagram);
+    assert_eq!(
+        diagram_generated.ir_diagram_issues,
+        ir_diagram_and_issues.issues
+    );
+}
@@ -0,0 +1,27 @@
+//! Functions to generate diagrams from input diagram YAML.
+
+use disposition::{
+    input_model::InputDiagram,
+    output_model::{DiagramFocusGenerated, DiagramGenerated},
+};
+use disposition_input_ir_rt::{DiagramGenerator, EdgeAnimationActive};
+
+/// Returns the diagram generated from the input diagram YAML.
+pub(crate) fn diagram_generated(input_diagram_yaml: &str) -> DiagramGenerated {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(input_diagram_yaml)
+        .expect("Expected input diagram to be deserialized.");
+    DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+        .expect("Expected diagram to be generated.")
+}
+
+/// Returns the diagram generated for each focus state from the input diagram
+/// YAML.
+pub(crate) fn diagrams_focus_generated(input_diagram_yaml: &str) -> Vec<DiagramFocusGenerated> {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(input_diagram_yaml)
+        .expect("Expected input diagram to be deserialized.");
+    DiagramGenerator::generate_per_process_step_or_tag(
+        &input_diagram,
+        EdgeAnimationActive::OnProcessStepFocus,
+    )
+    .expect("Expected diagrams to be generated.")
+}
@@ -0,0 +1,124 @@
+use disposition_input_ir_rt::DiagramToHtmlMapper;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+thing_names:
+  t_a: "A"
+  t_b: "B"
+thing_interactions:
+  edge_ab:
+    kind: sequence
+    things: [t_a, t_b]
+processes:
+  proc_build:
+    name: "Build <app>"
+    desc: "Builds the app."
+    steps:
+      proc_build_step_compile: "Compile"
+      proc_build_step_link: "Link"
+    step_thing_interactions:
+      proc_build_step_compile: [edge_ab]
+tags:
+  tag_core: "Core"
+tag_things:
+  tag_core: [t_a]
+entity_tooltips:
+  proc_build_step_compile: "Compiles *every* crate."
+  proc_build_step_link: "![logo](https://example.com/logo.png) <script>alert(1)</script>"
+"#;
+
+#[test]
+fn map_lists_focuses_in_generation_order() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "example");
+
+    let hrefs = html
+        .match_indices(r#"<a class="focus" href=""#)
+        .map(|(index, prefix)| {
+            let href_start = index + prefix.len();
+            let href_end = href_start + html[href_start..].find('"').unwrap();
+            &html[href_start..href_end]
+        })
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        vec![
+            "#",
+            "#focus=proc_build",
+            "#focus=proc_build_step_compile",
+            "#focus=proc_build_step_link",
+            "#focus=tag_core",
+        ],
+        hrefs
+    );
+}
+
+#[test]
+fn map_escapes_names_and_title() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "a & b");
+
+    assert!(html.contains("<title>a &amp; b</title>"));
+    assert!(html.contains(r#"data-focus-id="proc_build">Build &lt;app&gt;</a>"#));
+}
+
+#[test]
+fn map_does_not_replace_placeholders_within_title() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "__DIAGRAM__ __FOCUSES__");
+
+    assert!(html.contains("<title>__DIAGRAM__ __FOCUSES__</title>"));
+    assert_eq!(1, html.matches("<svg").count());
+    assert_eq!(1, html.matches(r##"href="#focus=proc_build""##).count());
+}
+
+#[test]
+fn map_renders_descriptions_as_html() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "example");
+
+    assert!(html.contains(r#"<div class="desc"><p>Builds the app.</p>"#));
+    assert!(html.contains("<p>Compiles <em>every</em> crate.</p>"));
+}
+
+#[test]
+fn map_does_not_load_resources_or_run_description_html() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "example");
+
+    // The SVG embeds the input diagram, which contains the description's
+    // markdown, so only the page outside the SVG is checked.
+    let (page_head, _diagram) = html.split_once(r#"<main id="diagram">"#).unwrap();
+    assert!(!page_head.contains("https://example.com/logo.png"));
+    assert!(page_head.contains("<p>logo &lt;script&gt;alert(1)&lt;/script&gt;</p>"));
+    assert!(!page_head.contains(" src="));
+    assert!(!page_head.contains("<link"));
+}
+
+#[test]
+fn map_inlines_svg_without_xml_declaration() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+    assert!(diagram_generated.svg.starts_with("<?xml"));
+
+    let html = DiagramToHtmlMapper::map(&diagram_generated, "example");
+
+    assert!(!html.contains("<?xml"));
+    let svg_start = diagram_generated.svg.find("<svg").unwrap();
+    assert!(html.contains(&diagram_generated.svg[svg_start..]));
+}
+
+#[test]
+fn map_with_svg_uses_given_svg() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+    let svg = r#"<svg width="1" height="1"><g id="proc_build" tabindex="0"></g></svg>"#;
+
+    let html = DiagramToHtmlMapper::map_with_svg(&diagram_generated, svg, "example");
+
+    assert!(html.contains(svg));
+    assert!(!html.contains(&diagram_generated.svg));
+}
@@ -0,0 +1,282 @@
+use disposition::{
+    layout_model::{DiagramLayout, LayoutEdgeKind, LayoutNode, LayoutNodeKind, LayoutPathSegment},
+    output_model::DiagramGenerated,
+    svg_model::SvgNodeInfo,
+};
+use disposition_input_ir_rt::DiagramToLayoutMapper;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner"
+  t_b: "B & co"
+thing_dependencies:
+  edge_inner_b:
+    kind: sequence
+    things: [t_inner, t_b]
+thing_interactions:
+  edge_ix_b_inner:
+    kind: sequence
+    things: [t_b, t_inner]
+edge_descs:
+  edge_inner_b: "Sends"
+processes:
+  proc_deploy:
+    name: "Deploy"
+    steps:
+      proc_deploy_step_push: "Push"
+      proc_deploy_step_run: "Run"
+    step_thing_interactions:
+      proc_deploy_step_push: [edge_ix_b_inner]
+tags:
+  tag_infra: "Infrastructure"
+tag_things:
+  tag_infra: [t_outer]
+"#;
+
+#[test]
+fn map_sets_version_and_size() {
+    let (diagram_layout, diagram_generated) = diagram_layout_and_generated();
+
+    assert_eq!(DiagramLayout::VERSION, diagram_layout.version);
+    assert_eq!(
+        diagram_generated.svg_elements.svg_width,
+        diagram_layout.width
+    );
+    assert_eq!(
+        diagram_generated.svg_elements.svg_height,
+        diagram_layout.height
+    );
+}
+
+#[test]
+fn map_lists_nodes_with_kinds_and_parents() {
+    let (diagram_layout, _) = diagram_layout_and_generated();
+
+    let node_kind_and_parent = |node_id: &str| {
+        let layout_node = layout_node(&diagram_layout, node_id);
+        (
+            layout_node.kind,
+            layout_node
+                .parent_id
+                .as_ref()
+                .map(|parent_id| parent_id.as_str()),
+        )
+    };
+    assert_eq!(
+        (LayoutNodeKind::Thing, None),
+        node_kind_and_parent("t_outer")
+    );
+    assert_eq!(
+        (LayoutNodeKind::Thing, Some("t_outer")),
+        node_kind_and_parent("t_inner")
+    );
+    assert_eq!(
+        (LayoutNodeKind::Tag, None),
+        node_kind_and_parent("tag_infra")
+    );
+    assert_eq!(
+        (LayoutNodeKind::Process, None),
+        node_kind_and_parent("proc_deploy")
+    );
+    assert_eq!(
+        (LayoutNodeKind::ProcessStep, Some("proc_deploy")),
+        node_kind_and_parent("proc_deploy_step_push")
+    );
+
+    let node_index = |node_id: &str| {
+        diagram_layout
+            .nodes
+            .iter()
+            .position(|layout_node| layout_node.node_id.as_str() == node_id)
+            .unwrap()
+    };
+    assert!(node_index("t_outer") < node_index("t_inner"));
+}
+
+#[test]
+fn map_makes_node_boxes_and_text_spans_absolute() {
+    let (diagram_layout, diagram_generated) = diagram_layout_and_generated();
+
+    let svg_node_info = svg_node_info(&diagram_generated, "t_b");
+    let layout_node = layout_node(&diagram_layout, "t_b");
+    assert_eq!(svg_node_info.x, layout_node.node_box.x);
+    assert_eq!(svg_node_info.y, layout_node.node_box.y);
+    assert_eq!(svg_node_info.width, layout_node.node_box.width);
+    assert_eq!(svg_node_info.height_collapsed, layout_node.node_box.height);
+    assert_eq!(svg_node_info.envelope_x, layout_node.envelope_box.x);
+
+    let layout_text = layout_node
+        .text
+        .as_ref()
+        .expect("Expected `t_b` to have text.");
+    let svg_text_span = &svg_node_info.text_spans[0];
+    let layout_text_span = &layout_text.text_spans[0];
+    assert_eq!(svg_node_info.x + svg_text_span.x, layout_text_span.x);
+    assert_eq!(svg_node_info.y + svg_text_span.y, layout_text_span.y);
+    assert_eq!("B & co", layout_text_span.text);
+    assert!(layout_text.text_box.x <= layout_text_span.x);
+    assert!(layout_text.text_box.y <= layout_text_span.y);
+}
+
+#[test]
+fn map_makes_circle_centres_absolute() {
+    let (diagram_layout, diagram_generated) = diagram_layout_and_generated();
+
+    let svg_node_info = svg_node_info(&diagram_generated, "proc_deploy_step_push");
+    let svg_circle = svg_node_info
+        .circle
+        .as_ref()
+        .expect("Expected process step to have a circle.");
+    let layout_circle = layout_node(&diagram_layout, "proc_deploy_step_push")
+        .circle
+        .expect("Expected process step to have a circle.");
+    assert_eq!(svg_node_info.x + svg_circle.cx, layout_circle.cx);
+    assert_eq!(svg_node_info.y + svg_circle.cy, layout_circle.cy);
+    assert_eq!(svg_circle.radius, layout_circle.radius);
+}
+
+#[test]
+fn map_parses_edge_paths_into_segments() {
+    let (diagram_layout, _) = diagram_layout_and_generated();
+
+    let layout_edge = diagram_layout
+        .edges
+        .iter()
+        .find(|layout_edge| layout_edge.edge_id.as_str() == "edge_inner_b__0")
+        .expect("Expected `edge_inner_b__0` to be laid out.");
+    assert_eq!("edge_inner_b", layout_edge.edge_group_id.as_str());
+    assert_eq!("t_inner", layout_edge.from_node_id.as_str());
+    assert_eq!("t_b", layout_edge.to_node_id.as_str());
+    assert_eq!(LayoutEdgeKind::Dependency, layout_edge.kind);
+    assert!(matches!(
+        layout_edge.path.first(),
+        Some(LayoutPathSegment::MoveTo { .. })
+    ));
+    assert!(layout_edge.path.len() > 1);
+    assert!(!layout_edge.arrow_head_path.is_empty());
+
+    let layout_edge_interaction = diagram_layout
+        .edges
+        .iter()
+        .find(|layout_edge| layout_edge.edge_group_id.as_str() == "edge_ix_b_inner")
+        .expect("Expected `edge_ix_b_inner` to be laid out.");
+    assert_eq!(LayoutEdgeKind::Interaction, layout_edge_interaction.kind);
+}
+
+#[test]
+fn map_lists_edge_descriptions_and_processes() {
+    let (diagram_layout, _) = diagram_layout_and_generated();
+
+    let layout_edge_description = diagram_layout
+        .edge_descriptions
+        .iter()
+        .find(|layout_edge_description| {
+            layout_edge_description.edge_id.as_str() == "edge_inner_b__0"
+        })
+        .expect("Expected `edge_inner_b__0` to have a description.");
+    let text = layout_edge_description
+        .text
+        .text_spans
+        .iter()
+        .map(|text_span| text_span.text.as_str())
+        .collect::<String>();
+    assert_eq!("Sends", text);
+
+    let [layout_process] = diagram_layout.processes.as_slice() else {
+        panic!("Expected one process, got: {:?}", diagram_layout.processes);
+    };
+    assert_eq!("proc_deploy", layout_process.process_id.as_str());
+    let process_step_ids = layout_process
+        .process_step_ids
+        .iter()
+        .map(|process_step_id| process_step_id.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        vec!["proc_deploy_step_push", "proc_deploy_step_run"],
+        process_step_ids
+    );
+    assert!(!layout_process.is_collapsed);
+    assert_eq!(
+        layout_node(&diagram_layout, "proc_deploy").node_box.height,
+        layout_process.height_expanded
+    );
+    assert_eq!(0.0, layout_process.steps_height);
+}
+
+#[test]
+fn map_lists_collapsed_process_heights() {
+    let input_diagram =
+        format!("{INPUT_DIAGRAM}render_options:\n  process_render_collapse: collapse\n");
+    let (diagram_layout, diagram_generated) = diagram_layout_and_generated_for(&input_diagram);
+
+    let [layout_process] = diagram_layout.processes.as_slice() else {
+        panic!("Expected one process, got: {:?}", diagram_layout.processes);
+    };
+    let svg_process_info =
+        &diagram_generated.svg_elements.svg_process_infos[&layout_process.process_id];
+    assert!(layout_process.is_collapsed);
+    assert_eq!(
+        svg_process_info.height_to_expand_to,
+        layout_process.height_expanded
+    );
+    assert_eq!(svg_process_info.total_height, layout_process.steps_height);
+    assert!(
+        layout_process.height_expanded
+            > layout_node(&diagram_layout, "proc_deploy").node_box.height
+    );
+}
+
+#[test]
+fn layout_serializes_path_segments_with_type_tag() {
+    let (diagram_layout, _) = diagram_layout_and_generated();
+
+    let layout_json = serde_json::to_value(&diagram_layout).unwrap();
+
+    assert_eq!(1, layout_json["version"]);
+    assert_eq!("move_to", layout_json["edges"][0]["path"][0]["type"]);
+    let diagram_layout_deserialized =
+        serde_json::from_value::<DiagramLayout<'_>>(layout_json).unwrap();
+    assert_eq!(diagram_layout, diagram_layout_deserialized);
+}
+
+fn layout_node<'l>(
+    diagram_layout: &'l DiagramLayout<'static>,
+    node_id: &str,
+) -> &'l LayoutNode<'static> {
+    diagram_layout
+        .nodes
+        .iter()
+        .find(|layout_node| layout_node.node_id.as_str() == node_id)
+        .unwrap_or_else(|| panic!("Expected `{node_id}` to be laid out."))
+}
+
+fn svg_node_info<'d>(
+    diagram_generated: &'d DiagramGenerated,
+    node_id: &str,
+) -> &'d SvgNodeInfo<'static> {
+    diagram_generated
+        .svg_elements
+        .svg_node_infos
+        .iter()
+        .find(|svg_node_info| svg_node_info.node_id.as_str() == node_id)
+        .unwrap()
+}
+
+fn diagram_layout_and_generated() -> (DiagramLayout<'static>, DiagramGenerated) {
+    diagram_layout_and_generated_for(INPUT_DIAGRAM)
+}
+
+fn diagram_layout_and_generated_for(
+    input_diagram: &str,
+) -> (DiagramLayout<'static>, DiagramGenerated) {
+    let diagram_generated = diagram_generated(input_diagram);
+    let diagram_layout = DiagramToLayoutMapper::map(&diagram_generated);
+    (diagram_layout, diagram_generated)
+}
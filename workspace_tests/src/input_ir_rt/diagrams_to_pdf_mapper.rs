@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+
+use disposition_input_ir_rt::{DiagramsToPdfError, DiagramsToPdfMapper, PdfOptions, PdfPageSize};
+
+use crate::input_ir_rt::diagram_test_support::diagrams_focus_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_a: {}
+  t_b: {}
+thing_names:
+  t_a: "A"
+  t_b: "B"
+thing_interactions:
+  edge_ab:
+    kind: sequence
+    things: [t_a, t_b]
+processes:
+  proc_build:
+    name: "Build Ünïcode"
+    desc: "Builds the app."
+    steps:
+      proc_build_step_compile: "Compile"
+      proc_build_step_link: "Link"
+    step_thing_interactions:
+      proc_build_step_compile: [edge_ab]
+tags:
+  tag_core: "Core"
+tag_things:
+  tag_core: [t_a]
+entity_tooltips:
+  proc_build_step_compile: "Compiles *every* crate."
+"#;
+
+#[test]
+fn map_returns_pdf_with_page_per_focus() {
+    let diagrams_focus_generated = diagrams_focus_generated(INPUT_DIAGRAM);
+
+    let pdf = DiagramsToPdfMapper::map(&diagrams_focus_generated, &PdfOptions::default())
+        .expect("Expected PDF to be rendered.");
+
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.trim_ascii_end().ends_with(b"%%EOF"));
+    let pdf_text = String::from_utf8_lossy(&pdf);
+    assert_eq!(
+        diagrams_focus_generated.len(),
+        pdf_text.matches("/Type /Page\n").count()
+    );
+    assert!(pdf_text.contains(&format!("/Count {}", diagrams_focus_generated.len())));
+}
+
+#[test]
+fn map_bookmarks_pages_with_focus_titles() {
+    let pdf = DiagramsToPdfMapper::map(
+        &diagrams_focus_generated(INPUT_DIAGRAM),
+        &PdfOptions::default(),
+    )
+    .expect("Expected PDF to be rendered.");
+
+    let titles = pdf_titles(&pdf);
+    assert_eq!(
+        vec![
+            "Overview",
+            "Build Ünïcode",
+            "Build Ünïcode: Compile",
+            "Build Ünïcode: Link",
+            "Core",
+        ],
+        titles
+    );
+}
+
+#[test]
+fn map_draws_diagram_on_each_page() {
+    let diagrams_focus_generated = diagrams_focus_generated(INPUT_DIAGRAM);
+
+    let pdf = DiagramsToPdfMapper::map(&diagrams_focus_generated, &PdfOptions::default())
+        .expect("Expected PDF to be rendered.");
+
+    let pdf_text = String::from_utf8_lossy(&pdf);
+    assert_eq!(
+        diagrams_focus_generated.len(),
+        pdf_text.matches("/Page Do").count()
+    );
+}
+
+#[test]
+fn map_uses_paper_size_with_landscape_for_wide_diagrams() {
+    let diagrams_focus_generated = diagrams_focus_generated(INPUT_DIAGRAM);
+    let pdf_options = PdfOptions::builder()
+        .with_page_size(PdfPageSize::A4)
+        .build();
+
+    let pdf = DiagramsToPdfMapper::map(&diagrams_focus_generated[..1], &pdf_options)
+        .expect("Expected PDF to be rendered.");
+
+    let pdf_text = String::from_utf8_lossy(&pdf);
+    assert!(
+        pdf_text.contains("/MediaBox [0 0 841.89 595.276]")
+            || pdf_text.contains("/MediaBox [0 0 595.276 841.89]"),
+        "Expected A4 `MediaBox`, got: {pdf_text}"
+    );
+}
+
+#[test]
+fn map_returns_error_when_diagrams_empty() {
+    let error = DiagramsToPdfMapper::map(&[], &PdfOptions::default()).unwrap_err();
+
+    assert!(
+        matches!(error, DiagramsToPdfError::DiagramsEmpty),
+        "Expected `DiagramsEmpty`, got: {error:?}"
+    );
+}
+
+#[test]
+fn map_returns_error_when_font_invalid() {
+    let mut pdf_options = PdfOptions::default();
+    pdf_options.fonts.push(Cow::Borrowed(b"not a font"));
+
+    let error = DiagramsToPdfMapper::map(&diagrams_focus_generated(INPUT_DIAGRAM), &pdf_options)
+        .unwrap_err();
+
+    assert!(
+        matches!(error, DiagramsToPdfError::FontInvalid { font_index: 1 }),
+        "Expected `FontInvalid`, got: {error:?}"
+    );
+}
+
+/// Returns the bookmark titles, which are written as literal strings when
+/// they are ASCII, and as UTF-16BE hexadecimal strings otherwise.
+fn pdf_titles(pdf: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(pdf)
+        .split("/Title ")
+        .skip(1)
+        .map(|title| {
+            if let Some(title_hex) = title.strip_prefix("<FEFF") {
+                let title_hex = &title_hex[..title_hex.find('>').unwrap()];
+                let code_units = (0..title_hex.len())
+                    .step_by(4)
+                    .map(|index| u16::from_str_radix(&title_hex[index..index + 4], 16).unwrap())
+                    .collect::<Vec<u16>>();
+                String::from_utf16(&code_units).unwrap()
+            } else {
+                let title = title.strip_prefix('(').unwrap();
+                title[..title.find(')').unwrap()].to_string()
+            }
+        })
+        .collect()
+}
@@ -1,6 +1,14 @@
 use disposition::{
-    input_ir_model::IrDiagramAndIssues,
-    input_model::InputDiagram,
+    input_ir_model::{
+        issue::{DependencyCycleEdge, ModelToIrIssue},
+        IrDiagramAndIssues,
+    },
+    input_model::{
+        process::{ProcessId, ProcessStepId},
+        tag::TagId,
+        thing::ThingId,
+        InputDiagram, InputDiagramSpans,
+    },
     ir_model::{
         edge::{Edge, EdgeId},
         entity::EntityType,
@@ -1214,6 +1222,298 @@ thing_layout_edges:
     assert_eq!(1, diagram.thing_layout_edges.len());
 }
 
+/// Unknown IDs in edge groups, `thing_names`, `tag_things`, `entity_types`, and
+/// `step_thing_interactions` are each reported with the offending field path.
+#[test]
+fn test_unknown_references_reported_as_issues() {
+    let yaml = r#"
+things:
+  a: {}
+  b: {}
+thing_names:
+  a: A
+  c: C
+thing_dependencies:
+  edge_a_c:
+    kind: sequence
+    things: [a, c]
+thing_interactions:
+  edge_a_b:
+    kind: sequence
+    things: [a, b]
+processes:
+  proc_p:
+    steps:
+      proc_p_step_1: Step 1
+    step_thing_interactions:
+      proc_p_step_1: [edge_a_b, edge_unknown]
+      proc_p_step_2: [edge_a_b]
+tags:
+  tag_t: Tag
+tag_things:
+  tag_t: [a, d]
+  tag_unknown: [b]
+entity_types:
+  a: [type_x]
+  edge_a_b: [type_x]
+  edge_a_b__0: [type_x]
+  edge_a_b__1: [type_x]
+"#;
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(yaml).unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    let process_id = ProcessId::from(id!("proc_p"));
+    let tag_id_t = TagId::from(id!("tag_t"));
+    assert_eq!(
+        vec![
+            ModelToIrIssue::ThingNamesThingUnknown {
+                field_path: String::from("thing_names.c"),
+                thing_id: ThingId::from(id!("c")),
+                span: None,
+            },
+            ModelToIrIssue::EdgeGroupThingUnknown {
+                field_path: String::from("thing_dependencies.edge_a_c.things[1]"),
+                edge_group_id: EdgeGroupId::from(id!("edge_a_c")),
+                thing_id: ThingId::from(id!("c")),
+                span: None,
+            },
+            ModelToIrIssue::StepThingInteractionsEdgeGroupUnknown {
+                field_path: String::from(
+                    "processes.proc_p.step_thing_interactions.proc_p_step_1[1]"
+                ),
+                process_id: process_id.clone(),
+                process_step_id: ProcessStepId::from(id!("proc_p_step_1")),
+                edge_group_id: EdgeGroupId::from(id!("edge_unknown")),
+                span: None,
+            },
+            ModelToIrIssue::StepThingInteractionsStepUnknown {
+                field_path: String::from("processes.proc_p.step_thing_interactions.proc_p_step_2"),
+                process_id,
+                process_step_id: ProcessStepId::from(id!("proc_p_step_2")),
+                span: None,
+            },
+            ModelToIrIssue::TagThingsThingUnknown {
+                field_path: String::from("tag_things.tag_t[1]"),
+                tag_id: tag_id_t,
+                thing_id: ThingId::from(id!("d")),
+                span: None,
+            },
+            ModelToIrIssue::TagThingsTagUnknown {
+                field_path: String::from("tag_things.tag_unknown"),
+                tag_id: TagId::from(id!("tag_unknown")),
+                span: None,
+            },
+            ModelToIrIssue::EntityTypesEntityUnknown {
+                field_path: String::from("entity_types.edge_a_b__1"),
+                entity_id: id!("edge_a_b__1"),
+                span: None,
+            },
+        ],
+        issues
+    );
+    assert_eq!(
+        "thing_dependencies.edge_a_c.things[1]",
+        issues[1].field_path()
+    );
+}
+
+#[test]
+fn test_issue_span_attach_uses_input_diagram_spans() {
+    let yaml = r#"
+things:
+  a: {}
+tag_things:
+  tag_t: [a, d]
+"#;
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(yaml).unwrap();
+    let input_diagram_spans = InputDiagramSpans::from_yaml(yaml);
+    let IrDiagramAndIssues { mut issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+    issues
+        .iter_mut()
+        .for_each(|issue| issue.span_attach(&input_diagram_spans));
+
+    let tag_t_offset = yaml.find("tag_t:").unwrap();
+    let d_offset = yaml.find("d]").unwrap();
+    let tag_id_t = TagId::from(id!("tag_t"));
+    assert_eq!(
+        vec![
+            ModelToIrIssue::TagThingsTagUnknown {
+                field_path: String::from("tag_things.tag_t"),
+                tag_id: tag_id_t.clone(),
+                span: Some((tag_t_offset, "tag_t".len()).into()),
+            },
+            ModelToIrIssue::TagThingsThingUnknown {
+                field_path: String::from("tag_things.tag_t[1]"),
+                tag_id: tag_id_t,
+                thing_id: ThingId::from(id!("d")),
+                span: Some((d_offset, 1).into()),
+            },
+        ],
+        issues
+    );
+}
+
+/// Edges may connect tags and process steps as well as things, so those are
+/// not reported as unknown.
+#[test]
+fn test_edge_group_tag_and_process_step_references_are_not_issues() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        crate::input_ir_rt::INPUT_DIAGRAM_0005_TAG_NODES_CYCLIC_EDGE,
+    )
+    .unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    assert!(issues.is_empty(), "Expected no issues, got: {:?}", issues);
+}
+
+#[test]
+fn test_sequence_dependency_cycle_reported_as_issue() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        r#"
+things:
+  a: {}
+  b: {}
+  c: {}
+thing_dependencies:
+  edge_a__b:
+    kind: sequence
+    things: [a, b]
+  edge_b__c__a:
+    kind: sequence
+    things: [b, c, a]
+"#,
+    )
+    .unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    let dependency_cycle_edge =
+        |field_path: &str, from: &'static str, to: &'static str| DependencyCycleEdge {
+            field_path: String::from(field_path),
+            from: Id::new(from).unwrap(),
+            to: Id::new(to).unwrap(),
+        };
+    assert_eq!(
+        vec![ModelToIrIssue::ThingDependenciesCycle {
+            field_path: String::from("thing_dependencies.edge_a__b"),
+            node_ids: vec![id!("a"), id!("b"), id!("c")],
+            edges: vec![
+                dependency_cycle_edge("thing_dependencies.edge_a__b", "a", "b"),
+                dependency_cycle_edge("thing_dependencies.edge_b__c__a", "b", "c"),
+                dependency_cycle_edge("thing_dependencies.edge_b__c__a", "c", "a"),
+            ],
+            span: None,
+        }],
+        issues
+    );
+}
+
+#[test]
+fn test_nested_dependency_cycle_reported_at_sibling_ancestors() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        r#"
+things:
+  p:
+    p_child: {}
+  q:
+    q_child: {}
+thing_dependencies:
+  edge_p_child__q_child:
+    kind: sequence
+    things: [p_child, q_child]
+thing_layout_edges:
+  edge_layout_q__p:
+    from: q
+    to: p
+"#,
+    )
+    .unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    let [ModelToIrIssue::ThingDependenciesCycle {
+        field_path,
+        node_ids,
+        edges,
+        ..
+    }] = issues.as_slice()
+    else {
+        panic!("Expected one `ThingDependenciesCycle`, got: {issues:?}");
+    };
+    assert_eq!("thing_dependencies.edge_p_child__q_child", field_path);
+    assert_eq!(&vec![id!("p"), id!("q")], node_ids);
+    assert_eq!(
+        vec![
+            "thing_dependencies.edge_p_child__q_child",
+            "thing_layout_edges.edge_layout_q__p"
+        ],
+        edges
+            .iter()
+            .map(|edge| edge.field_path.as_str())
+            .collect::<Vec<_>>()
+    );
+}
+
+/// `cyclic` and `symmetric` edge groups are meant to form cycles, so neither
+/// they nor `sequence` edges within the same cycle are reported.
+#[test]
+fn test_intended_dependency_cycles_are_not_issues() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        r#"
+things:
+  a: {}
+  b: {}
+  c: {}
+thing_dependencies:
+  edge_a__b__c:
+    kind: cyclic
+    things: [a, b, c]
+  edge_c__a:
+    kind: symmetric
+    things: [c, a]
+  edge_a__c:
+    kind: sequence
+    things: [a, c]
+"#,
+    )
+    .unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    assert!(issues.is_empty(), "Expected no issues, got: {:?}", issues);
+}
+
+/// A `sequence` edge that joins two intended cycles into a larger cycle is
+/// reported.
+#[test]
+fn test_sequence_edges_joining_cyclic_groups_reported_as_issue() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        r#"
+things:
+  a: {}
+  b: {}
+  c: {}
+thing_dependencies:
+  edge_b__c:
+    kind: cyclic
+    things: [b, c]
+  edge_a__b:
+    kind: sequence
+    things: [a, b]
+  edge_c__a:
+    kind: sequence
+    things: [c, a]
+"#,
+    )
+    .unwrap();
+    let IrDiagramAndIssues { issues, .. } = InputToIrDiagramMapper::map(&input_diagram);
+
+    assert_eq!(
+        vec!["thing_dependencies.edge_a__b"],
+        issues
+            .iter()
+            .map(ModelToIrIssue::field_path)
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn test_example_input_maps_to_example_ir() {
     let input_diagram = serde_saphyr::from_str::<InputDiagram>(EXAMPLE_INPUT_MERGED).unwrap();
@@ -0,0 +1,129 @@
+use disposition::ir_model::IrDiagram;
+use disposition_input_ir_rt::IrToDotMapper;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+  t_c: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner \"quoted\""
+  t_b: "B"
+  t_c: "C"
+thing_descs:
+  t_b: "First line\nsecond line"
+thing_dependencies:
+  edge_cycle:
+    kind: cyclic
+    things: [t_inner, t_b, t_c]
+  edge_outer_b:
+    kind: sequence
+    things: [t_outer, t_b]
+  edge_outer_inner:
+    kind: sequence
+    things: [t_outer, t_inner]
+thing_interactions:
+  edge_b_c:
+    kind: symmetric
+    things: [t_b, t_c]
+edge_descs:
+  edge_b_c: "Request"
+edge_labels:
+  edge_b_c__0:
+    from: "sends"
+    to: ""
+render_options:
+  rank_dir: left_to_right
+"#;
+
+#[test]
+fn map_writes_node_hierarchy_as_nested_clusters() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    let cluster_start = dot
+        .find(r#"subgraph "cluster_t_outer" {"#)
+        .expect("Expected `t_outer` cluster.");
+    let cluster_end = cluster_start + dot[cluster_start..].find("\n    }\n").unwrap();
+    let cluster = &dot[cluster_start..cluster_end];
+    assert!(cluster.contains(r#"label="Outer";"#));
+    assert!(cluster.contains(r#""t_inner" [id="t_inner""#));
+    assert!(!dot.contains(r#""cluster_t_b""#));
+    assert!(dot.contains(r#""t_b" [id="t_b""#));
+}
+
+#[test]
+fn map_writes_rank_dir() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    assert!(dot.contains(r#"graph [rankdir="LR", compound="true"];"#));
+}
+
+#[test]
+fn map_escapes_names_and_joins_descs_to_labels() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    assert!(dot.contains(r#"label="Inner \"quoted\"""#));
+    assert!(dot.contains(r#"label="B\nFirst line\nsecond line""#));
+}
+
+#[test]
+fn map_does_not_rank_by_edges_that_close_cycles_or_reverse_symmetric_edges() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    let edge_cycle_2 = edge_line(&dot, "edge_cycle__2");
+    assert!(edge_cycle_2.starts_with(r#""t_c" -> "t_inner""#));
+    assert!(edge_cycle_2.contains(r#"constraint="false""#));
+    assert!(!edge_line(&dot, "edge_cycle__0").contains("constraint"));
+    assert!(!edge_line(&dot, "edge_b_c__0").contains("constraint"));
+
+    let edge_b_c_1 = edge_line(&dot, "edge_b_c__1");
+    assert!(edge_b_c_1.starts_with(r#""t_c" -> "t_b""#));
+    assert!(edge_b_c_1.contains(r#"constraint="false""#));
+    assert!(edge_b_c_1.contains("type_interaction_edge_symmetric_reverse_default"));
+}
+
+#[test]
+fn map_dashes_interaction_edges() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    assert!(edge_line(&dot, "edge_b_c__0").contains(r#"style="dashed""#));
+    assert!(!edge_line(&dot, "edge_cycle__0").contains("style"));
+}
+
+#[test]
+fn map_writes_edge_descs_and_labels() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    let edge_b_c_0 = edge_line(&dot, "edge_b_c__0");
+    assert!(edge_b_c_0.contains(r#"label="Request""#));
+    assert!(edge_b_c_0.contains(r#"taillabel="sends""#));
+    assert!(!edge_b_c_0.contains("headlabel"));
+    assert!(edge_line(&dot, "edge_b_c__1").contains(r#"label="Request""#));
+}
+
+#[test]
+fn map_clips_edges_to_clusters_outside_the_other_end() {
+    let dot = IrToDotMapper::map(&ir_diagram());
+
+    assert!(dot.contains(r#""t_outer" [shape="point", style="invis", label=""];"#));
+    assert!(edge_line(&dot, "edge_outer_b__0").contains(r#"ltail="cluster_t_outer""#));
+    // `t_inner` is within `t_outer`, so the edge cannot be clipped to it.
+    assert!(!edge_line(&dot, "edge_outer_inner__0").contains("ltail"));
+}
+
+fn ir_diagram() -> IrDiagram<'static> {
+    diagram_generated(INPUT_DIAGRAM).ir_diagram
+}
+
+/// Returns the DOT statement for the edge with the given ID.
+fn edge_line<'dot>(dot: &'dot str, edge_id: &str) -> &'dot str {
+    let id_attr = format!(r#"[id="{edge_id}""#);
+    dot.lines()
+        .map(str::trim_start)
+        .find(|line| line.contains(" -> ") && line.contains(&id_attr))
+        .unwrap_or_else(|| panic!("Expected edge `{edge_id}` in:\n{dot}"))
+}
@@ -0,0 +1,100 @@
+use disposition::ir_model::IrDiagram;
+use disposition_input_ir_rt::IrToMermaidMapper;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+  end: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner \"quoted\" <tag>"
+  t_b: "B"
+  end: "End"
+thing_descs:
+  t_b: "First line\nsecond line"
+thing_dependencies:
+  edge_inner_b:
+    kind: sequence
+    things: [t_inner, t_b, end]
+thing_interactions:
+  edge_b_end:
+    kind: symmetric
+    things: [t_b, end]
+edge_descs:
+  edge_b_end: "Request"
+edge_labels:
+  edge_b_end__0:
+    from: "sends"
+    to: ""
+processes:
+  proc_deploy:
+    name: "Deploy"
+    steps:
+      proc_deploy_step_push: "Push\nimage"
+    step_thing_interactions:
+      proc_deploy_step_push: [edge_b_end]
+tags:
+  tag_infra: "Infrastructure"
+tag_things:
+  tag_infra: [t_outer]
+render_options:
+  rank_dir: left_to_right
+"#;
+
+#[test]
+fn map_writes_rank_dir_as_direction() {
+    let mermaid = IrToMermaidMapper::map(&ir_diagram());
+
+    assert!(mermaid.starts_with("flowchart LR\n"));
+}
+
+#[test]
+fn map_writes_node_hierarchy_as_nested_subgraphs() {
+    let mermaid = IrToMermaidMapper::map(&ir_diagram());
+
+    let subgraph_start = mermaid
+        .find(r#"subgraph t_outer ["Outer"]"#)
+        .expect("Expected `t_outer` subgraph.");
+    let subgraph_end = subgraph_start + mermaid[subgraph_start..].find("\n    end\n").unwrap();
+    let subgraph = &mermaid[subgraph_start..subgraph_end];
+    assert!(subgraph.contains(r#"t_inner("Inner #quot;quoted#quot; #lt;tag#gt;")"#));
+    assert!(mermaid.contains(r#"    t_b("B<br/>First line<br/>second line")"#));
+}
+
+#[test]
+fn map_suffixes_ids_that_are_keywords() {
+    let mermaid = IrToMermaidMapper::map(&ir_diagram());
+
+    assert!(mermaid.contains(r#"    end_("End")"#));
+    assert!(mermaid.contains("    t_b --> end_\n"));
+}
+
+#[test]
+fn map_writes_edges_as_links() {
+    let mermaid = IrToMermaidMapper::map(&ir_diagram());
+
+    assert!(mermaid.contains("    %% edge_inner_b\n    t_inner --> t_b\n    t_b --> end_\n"));
+    assert!(mermaid.contains(
+        "    %% edge_b_end\n    t_b -.->|\"sends<br/>Request\"| end_\n    end_ -.->|\"Request\"| t_b\n"
+    ));
+}
+
+#[test]
+fn map_lists_processes_and_tags_in_comments() {
+    let mermaid = IrToMermaidMapper::map(&ir_diagram());
+
+    assert!(mermaid.contains(
+        "    %% Processes:\n    %% * proc_deploy: Deploy\n    %%     * proc_deploy_step_push: Push image (edge_b_end)\n"
+    ));
+    assert!(mermaid.contains("    %% Tags:\n    %% * tag_infra: Infrastructure\n"));
+    assert!(!mermaid.contains("proc_deploy["));
+    assert!(!mermaid.contains("tag_infra("));
+}
+
+fn ir_diagram() -> IrDiagram<'static> {
+    diagram_generated(INPUT_DIAGRAM).ir_diagram
+}
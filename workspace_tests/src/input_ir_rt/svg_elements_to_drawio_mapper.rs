@@ -0,0 +1,125 @@
+use disposition::output_model::DiagramGenerated;
+use disposition_input_ir_rt::SvgElementsToDrawioMapper;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner"
+  t_b: "B & co"
+thing_dependencies:
+  edge_inner_b:
+    kind: sequence
+    things: [t_inner, t_b]
+thing_interactions:
+  edge_ix_b_inner:
+    kind: sequence
+    things: [t_b, t_inner]
+"#;
+
+#[test]
+fn map_writes_mx_graph_model() {
+    let drawio = drawio();
+
+    assert!(drawio.starts_with("<mxfile"));
+    assert!(drawio.contains(r#"<mxCell id="0" />"#));
+    assert!(drawio.contains(r#"<mxCell id="1" parent="0" />"#));
+    assert!(drawio.trim_end().ends_with("</mxfile>"));
+}
+
+#[test]
+fn map_writes_nested_nodes_as_children_of_containers() {
+    let drawio = drawio();
+
+    let t_outer = cell_line(&drawio, "t_outer");
+    assert!(t_outer.contains("container=1;"));
+    assert!(t_outer.contains(r#"value="Outer""#));
+    assert!(t_outer.contains(r#"parent="1""#));
+    let t_inner = cell_line(&drawio, "t_inner");
+    assert!(t_inner.contains(r#"parent="t_outer""#));
+    assert!(!t_inner.contains("container=1;"));
+    assert!(cell_line(&drawio, "t_b").contains(r#"value="B &amp; co""#));
+}
+
+#[test]
+fn map_positions_child_cells_relative_to_their_container() {
+    let (drawio, diagram_generated) = drawio_and_diagram();
+
+    let svg_node_info = |node_id: &str| {
+        diagram_generated
+            .svg_elements
+            .svg_node_infos
+            .iter()
+            .find(|svg_node_info| svg_node_info.node_id.as_str() == node_id)
+            .unwrap()
+    };
+    let outer = svg_node_info("t_outer");
+    let inner = svg_node_info("t_inner");
+    assert_eq!(
+        (f64::from(outer.x), f64::from(outer.y)),
+        cell_position(&drawio, "t_outer")
+    );
+    assert_eq!(
+        (f64::from(inner.x - outer.x), f64::from(inner.y - outer.y)),
+        cell_position(&drawio, "t_inner")
+    );
+}
+
+#[test]
+fn map_connects_edges_to_endpoint_cells() {
+    let drawio = drawio();
+
+    let edge = cell_line(&drawio, "edge_inner_b__0");
+    assert!(edge.contains(r#"edge="1""#));
+    assert!(edge.contains(r#"source="t_inner" target="t_b""#));
+    assert!(!edge.contains("dashed=1;"));
+    assert!(cell_line(&drawio, "edge_ix_b_inner__0").contains("dashed=1;"));
+}
+
+fn drawio() -> String {
+    drawio_and_diagram().0
+}
+
+fn drawio_and_diagram() -> (String, DiagramGenerated) {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+    let drawio = SvgElementsToDrawioMapper::map(
+        &diagram_generated.ir_diagram,
+        &diagram_generated.svg_elements,
+    );
+    (drawio, diagram_generated)
+}
+
+/// Returns the `<mxCell>` line for the cell with the given ID.
+fn cell_line<'d>(drawio: &'d str, id: &str) -> &'d str {
+    let id_attr = format!(r#"<mxCell id="{id}" "#);
+    drawio
+        .lines()
+        .map(str::trim_start)
+        .find(|line| line.starts_with(&id_attr))
+        .unwrap_or_else(|| panic!("Expected cell `{id}` in:\n{drawio}"))
+}
+
+/// Returns the `x` and `y` of the cell's geometry.
+fn cell_position(drawio: &str, id: &str) -> (f64, f64) {
+    let id_attr = format!(r#"<mxCell id="{id}" "#);
+    let geometry = drawio
+        .lines()
+        .map(str::trim_start)
+        .skip_while(|line| !line.starts_with(&id_attr))
+        .nth(1)
+        .unwrap_or_else(|| panic!("Expected cell `{id}` in:\n{drawio}"));
+    let attr = |name: &str| -> f64 {
+        let prefix = format!(r#" {name}=""#);
+        let value_start = geometry.find(&prefix).unwrap() + prefix.len();
+        let value_len = geometry[value_start..].find('"').unwrap();
+        geometry[value_start..value_start + value_len]
+            .parse()
+            .unwrap()
+    };
+    (attr("x"), attr("y"))
+}
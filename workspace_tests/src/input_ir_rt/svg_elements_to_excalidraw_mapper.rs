@@ -0,0 +1,121 @@
+use disposition::output_model::DiagramGenerated;
+use disposition_input_ir_rt::SvgElementsToExcalidrawMapper;
+use serde_json::Value;
+
+use crate::input_ir_rt::diagram_test_support::diagram_generated;
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner"
+  t_b: "B & co"
+thing_dependencies:
+  edge_inner_b:
+    kind: sequence
+    things: [t_inner, t_b]
+thing_interactions:
+  edge_ix_b_inner:
+    kind: sequence
+    things: [t_b, t_inner]
+"#;
+
+#[test]
+fn map_writes_nodes_at_layout_positions() {
+    let (elements, diagram_generated) = elements();
+
+    let svg_node_info = diagram_generated
+        .svg_elements
+        .svg_node_infos
+        .iter()
+        .find(|svg_node_info| svg_node_info.node_id.as_str() == "t_b")
+        .unwrap();
+    let t_b = element(&elements, "t_b");
+    assert_eq!("rectangle", t_b["type"]);
+    assert_eq!(f64::from(svg_node_info.x), t_b["x"]);
+    assert_eq!(f64::from(svg_node_info.y), t_b["y"]);
+    assert_eq!(f64::from(svg_node_info.width), t_b["width"]);
+}
+
+#[test]
+fn map_binds_node_text_to_its_shape() {
+    let (elements, _) = elements();
+
+    let t_b_text = element(&elements, "t_b_text");
+    assert_eq!("B & co", t_b_text["text"]);
+    assert_eq!("t_b", t_b_text["containerId"]);
+    assert!(element(&elements, "t_b")["boundElements"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!({ "id": "t_b_text", "type": "text" })));
+}
+
+#[test]
+fn map_groups_nested_nodes_with_their_container() {
+    let (elements, _) = elements();
+
+    assert_eq!(
+        serde_json::json!(["group_t_outer"]),
+        element(&elements, "t_outer")["groupIds"]
+    );
+    assert_eq!(
+        serde_json::json!(["group_t_outer"]),
+        element(&elements, "t_inner")["groupIds"]
+    );
+    assert_eq!(serde_json::json!([]), element(&elements, "t_b")["groupIds"]);
+    // Container text is positioned separately, and moves with its group.
+    let t_outer_text = element(&elements, "t_outer_text");
+    assert_eq!(Value::Null, t_outer_text["containerId"]);
+    assert_eq!(
+        serde_json::json!(["group_t_outer"]),
+        t_outer_text["groupIds"]
+    );
+}
+
+#[test]
+fn map_binds_arrows_to_endpoint_shapes() {
+    let (elements, _) = elements();
+
+    let arrow = element(&elements, "edge_inner_b__0");
+    assert_eq!("arrow", arrow["type"]);
+    assert_eq!("t_inner", arrow["startBinding"]["elementId"]);
+    assert_eq!("t_b", arrow["endBinding"]["elementId"]);
+    assert_eq!("solid", arrow["strokeStyle"]);
+    let points = arrow["points"].as_array().unwrap();
+    assert!(points.len() >= 2);
+    assert_eq!(serde_json::json!([0.0, 0.0]), points[0]);
+    assert!(element(&elements, "t_inner")["boundElements"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!({ "id": "edge_inner_b__0", "type": "arrow" })));
+
+    assert_eq!(
+        "dashed",
+        element(&elements, "edge_ix_b_inner__0")["strokeStyle"]
+    );
+}
+
+fn elements() -> (Vec<Value>, DiagramGenerated) {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+    let excalidraw = SvgElementsToExcalidrawMapper::map(
+        &diagram_generated.ir_diagram,
+        &diagram_generated.svg_elements,
+    );
+    let mut scene = serde_json::from_str::<Value>(&excalidraw).unwrap();
+    assert_eq!("excalidraw", scene["type"]);
+    let Value::Array(elements) = scene["elements"].take() else {
+        panic!("Expected `elements` array in:\n{excalidraw}");
+    };
+    (elements, diagram_generated)
+}
+
+/// Returns the element with the given ID.
+fn element<'e>(elements: &'e [Value], id: &str) -> &'e Value {
+    elements
+        .iter()
+        .find(|element| element["id"] == id)
+        .unwrap_or_else(|| panic!("Expected element `{id}`."))
+}
@@ -0,0 +1,177 @@
+use std::{borrow::Cow, io::Cursor};
+
+use disposition_input_ir_rt::{
+    ColorScheme, PngOptions, SvgElementsToPngError, SvgElementsToPngMapper,
+};
+
+use crate::input_ir_rt::{
+    diagram_test_support::{diagram_generated, diagrams_focus_generated},
+    INPUT_DIAGRAM_0052_PROCESS_STEP_TWO_PROCESSES_COLLAPSE,
+};
+
+const INPUT_DIAGRAM_THING: &str = "{ things: { t_a: {} }, thing_names: { t_a: A } }";
+
+#[test]
+fn map_returns_png_with_dimensions_multiplied_by_scale() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+
+    let png_1x = SvgElementsToPngMapper::map(&svg_elements, &PngOptions::default())
+        .expect("Expected PNG to be rendered.");
+    let png_2x = SvgElementsToPngMapper::map(
+        &svg_elements,
+        &PngOptions::builder().with_scale(2.0).build(),
+    )
+    .expect("Expected PNG to be rendered.");
+
+    let png_1x = PngDecoded::decode(&png_1x);
+    let png_2x = PngDecoded::decode(&png_2x);
+    assert!(png_1x.width > 0 && png_1x.height > 0);
+    assert_eq!(
+        (png_1x.width * 2, png_1x.height * 2),
+        (png_2x.width, png_2x.height)
+    );
+}
+
+#[test]
+fn map_records_dpi_in_phys_chunk() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+    let png_options = PngOptions::builder().with_dpi(254.0).build();
+
+    let png = SvgElementsToPngMapper::map(&svg_elements, &png_options)
+        .expect("Expected PNG to be rendered.");
+
+    // 254 pixels per inch is 10000 pixels per meter.
+    let pixel_dims = PngDecoded::decode(&png)
+        .pixel_dims
+        .expect("Expected `pHYs` chunk.");
+    assert_eq!(
+        (10000, 10000, png::Unit::Meter),
+        (pixel_dims.xppu, pixel_dims.yppu, pixel_dims.unit)
+    );
+}
+
+#[test]
+fn map_renders_light_and_dark_color_schemes_differently() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+
+    let png_light = SvgElementsToPngMapper::map(
+        &svg_elements,
+        &PngOptions::builder()
+            .with_color_scheme(ColorScheme::Light)
+            .build(),
+    )
+    .expect("Expected PNG to be rendered.");
+    let png_dark = SvgElementsToPngMapper::map(
+        &svg_elements,
+        &PngOptions::builder()
+            .with_color_scheme(ColorScheme::Dark)
+            .build(),
+    )
+    .expect("Expected PNG to be rendered.");
+
+    assert_ne!(
+        PngDecoded::decode(&png_light).rgba8,
+        PngDecoded::decode(&png_dark).rgba8
+    );
+}
+
+#[test]
+fn map_renders_generated_diagram_for_each_focus() {
+    let diagrams_focus_generated =
+        diagrams_focus_generated(INPUT_DIAGRAM_0052_PROCESS_STEP_TWO_PROCESSES_COLLAPSE);
+    assert!(diagrams_focus_generated.len() > 1);
+
+    let pngs_decoded = diagrams_focus_generated
+        .iter()
+        .map(|diagram_focus_generated| {
+            let png = SvgElementsToPngMapper::map(
+                &diagram_focus_generated.diagram_generated.svg_elements,
+                &PngOptions::default(),
+            )
+            .expect("Expected PNG to be rendered.");
+            PngDecoded::decode(&png)
+        })
+        .collect::<Vec<PngDecoded>>();
+
+    let png_idle = &pngs_decoded[0];
+    assert!(png_idle.rgba8.chunks_exact(4).any(|pixel| pixel[3] == 255));
+    assert!(
+        pngs_decoded[1..]
+            .iter()
+            .any(|png_focus| png_focus.rgba8 != png_idle.rgba8),
+        "Expected focused diagrams to render differently to the idle diagram."
+    );
+}
+
+#[test]
+fn map_returns_error_when_scale_invalid() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+    let png_options = PngOptions::builder().with_scale(0.0).build();
+
+    let error = SvgElementsToPngMapper::map(&svg_elements, &png_options).unwrap_err();
+
+    assert!(
+        matches!(error, SvgElementsToPngError::ScaleInvalid { scale } if scale == 0.0),
+        "Expected `ScaleInvalid`, got: {error:?}"
+    );
+}
+
+#[test]
+fn map_returns_error_when_image_too_large() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+    let png_options = PngOptions::builder().with_scale(1000.0).build();
+
+    let error = SvgElementsToPngMapper::map(&svg_elements, &png_options).unwrap_err();
+
+    assert!(
+        matches!(error, SvgElementsToPngError::ImageTooLarge { .. }),
+        "Expected `ImageTooLarge`, got: {error:?}"
+    );
+}
+
+#[test]
+fn map_returns_error_when_font_invalid() {
+    let svg_elements = diagram_generated(INPUT_DIAGRAM_THING).svg_elements;
+    let mut png_options = PngOptions::default();
+    png_options.fonts.push(Cow::Borrowed(b"not a font"));
+
+    let error = SvgElementsToPngMapper::map(&svg_elements, &png_options).unwrap_err();
+
+    assert!(
+        matches!(error, SvgElementsToPngError::FontInvalid { font_index: 1 }),
+        "Expected `FontInvalid`, got: {error:?}"
+    );
+}
+
+/// Dimensions, physical pixel dimensions, and pixels of an 8-bit RGBA PNG
+/// rendered by `SvgElementsToPngMapper`.
+struct PngDecoded {
+    width: u32,
+    height: u32,
+    pixel_dims: Option<png::PixelDimensions>,
+    rgba8: Vec<u8>,
+}
+
+impl PngDecoded {
+    fn decode(png: &[u8]) -> Self {
+        let mut png_reader = png::Decoder::new(Cursor::new(png))
+            .read_info()
+            .expect("Expected PNG header to be valid.");
+        let mut rgba8 = vec![0u8; png_reader.output_buffer_size()];
+        let output_info = png_reader
+            .next_frame(&mut rgba8)
+            .expect("Expected PNG image data to be valid.");
+        assert_eq!(
+            (png::ColorType::Rgba, png::BitDepth::Eight),
+            (output_info.color_type, output_info.bit_depth),
+            "Expected 8-bit RGBA."
+        );
+
+        Self {
+            width: output_info.width,
+            height: output_info.height,
+            pixel_dims: png_reader.info().pixel_dims,
+            rgba8,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use disposition::output_model::DiagramGenerated;
+use disposition_input_ir_rt::{ColorScheme, SvgElementsToTikzMapper};
+
+use crate::input_ir_rt::{
+    diagram_test_support::diagram_generated, INPUT_DIAGRAM_0018_PROCESS_STEP_BRANCH_MERGE,
+};
+
+const INPUT_DIAGRAM: &str = r#"---
+things:
+  t_outer:
+    t_inner: {}
+  t_b: {}
+thing_names:
+  t_outer: "Outer"
+  t_inner: "Inner"
+  t_b: "B & co_{1}"
+thing_dependencies:
+  edge_inner_b:
+    kind: sequence
+    things: [t_inner, t_b]
+processes:
+  proc_deploy:
+    name: "Deploy"
+    steps:
+      proc_deploy_step_push: "Push"
+"#;
+
+#[test]
+fn map_writes_tikz_picture_sized_to_the_diagram() {
+    let (tikz, diagram_generated) = tikz_and_diagram(ColorScheme::Light);
+
+    assert!(tikz.contains("\\begin{tikzpicture}[x=0.75pt, y=-0.75pt,"));
+    assert!(tikz.trim_end().ends_with("\\end{tikzpicture}"));
+    let bounding_box = format!(
+        "\\useasboundingbox (0,0) rectangle ({},{});",
+        diagram_generated.svg_elements.svg_width, diagram_generated.svg_elements.svg_height
+    );
+    assert!(tikz.contains(&bounding_box), "{tikz}");
+}
+
+#[test]
+fn map_writes_node_shapes_and_escaped_text() {
+    let (tikz, _) = tikz_and_diagram(ColorScheme::Light);
+
+    let t_b = element_lines(&tikz, "t_b");
+    assert!(t_b[0].starts_with("  \\path[fill=dsp"), "{t_b:?}");
+    assert!(t_b[0].contains(" .. controls "), "{t_b:?}");
+    assert!(t_b[0].trim_end().ends_with("-- cycle;"), "{t_b:?}");
+    assert!(
+        t_b.iter()
+            .any(|line| line.contains("\\node[anchor=base west")
+                && line.ends_with("{B \\& co\\_\\{1\\}};")),
+        "{t_b:?}"
+    );
+}
+
+#[test]
+fn map_writes_edge_body_and_arrow_head() {
+    let (tikz, _) = tikz_and_diagram(ColorScheme::Light);
+
+    let edge = element_lines(&tikz, "edge_inner_b__0");
+    let edge_body = edge
+        .iter()
+        .find(|line| line.starts_with("  \\path[draw=dsp"))
+        .unwrap_or_else(|| panic!("Expected edge body to be drawn: {edge:?}"));
+    assert!(!edge_body.contains("fill="), "{edge_body}");
+    assert!(
+        edge.iter()
+            .any(|line| line.starts_with("  \\path[fill=dsp") && line.ends_with("-- cycle;")),
+        "{edge:?}"
+    );
+}
+
+#[test]
+fn map_resolves_colors_from_color_scheme() {
+    let (tikz_light, _) = tikz_and_diagram(ColorScheme::Light);
+    let (tikz_dark, _) = tikz_and_diagram(ColorScheme::Dark);
+
+    // Text is `neutral-900` in light mode and `neutral-100` in dark mode.
+    assert!(tikz_light.contains("\\definecolor{dsp171717}{HTML}{171717}"));
+    assert!(tikz_dark.contains("\\definecolor{dspF5F5F5}{HTML}{F5F5F5}"));
+    assert!(tikz_light.contains("text=dsp171717] at"));
+    assert!(tikz_dark.contains("text=dspF5F5F5] at"));
+}
+
+#[test]
+fn map_writes_process_step_circles_as_curves() {
+    let (tikz, _) = tikz_and_diagram(ColorScheme::Light);
+
+    let proc_deploy_step_push = element_lines(&tikz, "proc_deploy_step_push");
+    let circle = proc_deploy_step_push
+        .iter()
+        .find(|line| line.starts_with("  \\path["))
+        .unwrap_or_else(|| panic!("Expected circle to be drawn: {proc_deploy_step_push:?}"));
+    assert_eq!(4, circle.matches(" .. controls ").count(), "{circle}");
+    assert!(
+        proc_deploy_step_push
+            .iter()
+            .any(|line| line.ends_with("{Push};")),
+        "{proc_deploy_step_push:?}"
+    );
+}
+
+#[test]
+fn map_writes_quadratic_process_step_edge_corners_as_cubic_curves() {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM_0018_PROCESS_STEP_BRANCH_MERGE);
+    let tikz = SvgElementsToTikzMapper::map(&diagram_generated.svg_elements, ColorScheme::Light);
+
+    let svg_edge_info = diagram_generated
+        .svg_elements
+        .svg_edge_infos
+        .iter()
+        .find(|svg_edge_info| svg_edge_info.path_d.contains('Q'))
+        .expect("Expected a process step edge with rounded corners.");
+    let edge_lines = element_lines(&tikz, svg_edge_info.edge_id.as_str());
+    let edge_path = edge_lines
+        .iter()
+        .find(|line| line.contains(" .. controls "))
+        .unwrap_or_else(|| panic!("Expected edge to be drawn with curves: {edge_lines:?}"));
+    let curves = edge_path
+        .split(" .. controls ")
+        .skip(1)
+        .collect::<Vec<&str>>();
+    assert_eq!(svg_edge_info.path_d.matches('Q').count(), curves.len());
+    // A single control point would be read by TikZ as a cubic curve with both
+    // control points at that point.
+    assert!(
+        curves
+            .iter()
+            .all(|curve| curve.split(" .. ").next().unwrap().contains(" and ")),
+        "{edge_path}"
+    );
+}
+
+/// Returns the lines written for the element with the given ID, up to the
+/// next element's comment.
+fn element_lines<'t>(tikz: &'t str, id: &str) -> Vec<&'t str> {
+    let comment = format!("  % {id}");
+    tikz.lines()
+        .skip_while(|line| *line != comment)
+        .skip(1)
+        .take_while(|line| !line.starts_with("  % ") && !line.starts_with("\\end"))
+        .collect()
+}
+
+fn tikz_and_diagram(color_scheme: ColorScheme) -> (String, DiagramGenerated) {
+    let diagram_generated = diagram_generated(INPUT_DIAGRAM);
+    let tikz = SvgElementsToTikzMapper::map(&diagram_generated.svg_elements, color_scheme);
+    (tikz, diagram_generated)
+}
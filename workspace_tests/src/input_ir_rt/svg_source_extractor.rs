@@ -0,0 +1,68 @@
+use disposition::input_model::InputDiagram;
+use disposition_input_ir_rt::{
+    DiagramGenerator, EdgeAnimationActive, SvgSourceExtractError, SvgSourceExtractor,
+};
+
+use crate::input_ir_rt::EXAMPLE_INPUT;
+
+#[test]
+fn extract_returns_input_diagram_from_generated_svg() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(EXAMPLE_INPUT).unwrap();
+    let diagram_generated =
+        DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+            .expect("Expected diagram to be generated.");
+
+    let input_diagram_extracted = SvgSourceExtractor::extract(&diagram_generated.svg)
+        .expect("Expected input diagram to be extracted.");
+
+    assert_eq!(input_diagram, input_diagram_extracted);
+}
+
+#[test]
+fn extract_rejoins_cdata_sections_split_at_cdata_end() {
+    let input_diagram =
+        serde_saphyr::from_str::<InputDiagram>("thing_names:\n  t_a: \"[[a]]>b\"\n").unwrap();
+    let diagram_generated =
+        DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+            .expect("Expected diagram to be generated.");
+    assert!(diagram_generated.svg.contains("]]]]><![CDATA[>"));
+
+    let input_diagram_extracted = SvgSourceExtractor::extract(&diagram_generated.svg)
+        .expect("Expected input diagram to be extracted.");
+
+    assert_eq!(input_diagram, input_diagram_extracted);
+}
+
+#[test]
+fn source_yaml_extract_unescapes_text_outside_cdata() {
+    let svg = "<svg><source>things:\n  t_a: &quot;a &amp; b&quot;\n</source></svg>";
+
+    let source_yaml = SvgSourceExtractor::source_yaml_extract(svg)
+        .expect("Expected source YAML to be extracted.");
+
+    assert_eq!("things:\n  t_a: \"a & b\"\n", source_yaml);
+}
+
+#[test]
+fn source_yaml_extract_returns_error_when_source_missing() {
+    let svg = "<?xml version=\"1.0\"?>\n<!-- <source> -->\n<svg><sources/></svg>";
+
+    let error = SvgSourceExtractor::source_yaml_extract(svg).unwrap_err();
+
+    assert!(
+        matches!(error, SvgSourceExtractError::SourceNotFound),
+        "Expected `SourceNotFound`, got: {error:?}"
+    );
+}
+
+#[test]
+fn source_yaml_extract_returns_error_when_source_unclosed() {
+    let svg = "<svg><source><![CDATA[things: {}\n";
+
+    let error = SvgSourceExtractor::source_yaml_extract(svg).unwrap_err();
+
+    assert!(
+        matches!(error, SvgSourceExtractError::SourceMalformed { .. }),
+        "Expected `SourceMalformed`, got: {error:?}"
+    );
+}
@@ -0,0 +1,176 @@
+use disposition::{
+    input_ir_model::{issue::ModelToIrIssue, ThemeValueSource},
+    input_model::{theme::ThemeAttr, InputDiagram, InputDiagramSpans},
+};
+use disposition_input_ir_rt::{
+    DiagramGenerator, EdgeAnimationActive, InputDiagramMerger, InputDiagramThemeSources,
+    ThemeAttrValidator,
+};
+
+use crate::input_ir_rt::EXAMPLE_INPUT;
+
+const INPUT_WITH_INVALID_THEME_VALUES: &str = r#"
+theme_default:
+  base_styles:
+    node_defaults:
+      fill_shade_normal: "1000"
+      padding: "4px"
+      stroke_style: "dasharray:4 2"
+theme_types_styles:
+  type_thing_default:
+    node_defaults:
+      fill_color: "bluish"
+theme_tag_things_focus:
+  tag_defaults:
+    node_defaults:
+      animate: "[wiggle_1s_infinite]"
+      opacity: "half"
+"#;
+
+fn validate(input_diagram: &InputDiagram<'static>) -> Vec<ModelToIrIssue> {
+    let input_diagram_base = InputDiagram::base();
+    let input_diagram_merged = InputDiagramMerger::merge(input_diagram_base.clone(), input_diagram);
+    let theme_sources = InputDiagramThemeSources::new(&input_diagram_base, input_diagram);
+
+    ThemeAttrValidator::validate(&input_diagram_merged, &theme_sources)
+}
+
+#[test]
+fn validate_base_diagram_returns_no_issues() {
+    let issues = validate(&InputDiagram::new());
+
+    assert_eq!(Vec::<ModelToIrIssue>::new(), issues);
+}
+
+#[test]
+fn validate_example_input_returns_no_issues() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(EXAMPLE_INPUT).unwrap();
+
+    let issues = validate(&input_diagram);
+
+    assert_eq!(Vec::<ModelToIrIssue>::new(), issues);
+}
+
+#[test]
+fn validate_returns_issue_for_each_invalid_value() {
+    let input_diagram =
+        serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_INVALID_THEME_VALUES).unwrap();
+
+    let issues = validate(&input_diagram);
+
+    assert_eq!(
+        vec![
+            ModelToIrIssue::ThemeAttrValueInvalid {
+                field_path: String::from(
+                    "theme_default.base_styles.node_defaults.fill_shade_normal"
+                ),
+                entity: String::from("node_defaults"),
+                theme_attr: ThemeAttr::FillShadeNormal,
+                value: String::from("1000"),
+                expected: String::from(
+                    "a tailwind colour shade: `50`, `100`, `200`, .., `900`, or `950`"
+                ),
+                value_source: ThemeValueSource::UserInput,
+                span: None,
+            },
+            ModelToIrIssue::ThemeAttrValueInvalid {
+                field_path: String::from("theme_default.base_styles.node_defaults.padding"),
+                entity: String::from("node_defaults"),
+                theme_attr: ThemeAttr::Padding,
+                value: String::from("4px"),
+                expected: String::from("a number, e.g. `4.0`"),
+                value_source: ThemeValueSource::UserInput,
+                span: None,
+            },
+            ModelToIrIssue::ThemeAttrValueInvalid {
+                field_path: String::from(
+                    "theme_types_styles.type_thing_default.node_defaults.fill_color"
+                ),
+                entity: String::from("node_defaults"),
+                theme_attr: ThemeAttr::FillColor,
+                value: String::from("bluish"),
+                expected: String::from("a tailwind colour name, e.g. `slate`"),
+                value_source: ThemeValueSource::UserInput,
+                span: None,
+            },
+            ModelToIrIssue::ThemeAttrValueInvalid {
+                field_path: String::from(
+                    "theme_tag_things_focus.tag_defaults.node_defaults.opacity"
+                ),
+                entity: String::from("node_defaults"),
+                theme_attr: ThemeAttr::Opacity,
+                value: String::from("half"),
+                expected: String::from("a number, e.g. `2`, or an arbitrary value such as `[3px]`"),
+                value_source: ThemeValueSource::UserInput,
+                span: None,
+            },
+        ],
+        issues
+    );
+}
+
+#[test]
+fn validate_style_alias_invalid_value_returns_issue() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(
+        r#"
+theme_default:
+  style_aliases:
+    shade_light:
+      visibility: "none"
+"#,
+    )
+    .unwrap();
+
+    let issues = validate(&input_diagram);
+
+    let [ModelToIrIssue::ThemeAttrValueInvalid {
+        field_path,
+        entity,
+        theme_attr,
+        value_source,
+        ..
+    }] = issues.as_slice()
+    else {
+        panic!("Expected one `ThemeAttrValueInvalid`, got: {issues:?}");
+    };
+    assert_eq!(
+        "theme_default.style_aliases.shade_light.visibility",
+        field_path
+    );
+    assert_eq!("shade_light", entity);
+    assert_eq!(ThemeAttr::Visibility, *theme_attr);
+    assert_eq!(ThemeValueSource::UserInput, *value_source);
+}
+
+#[test]
+fn span_attach_sets_span_to_field_value() {
+    let yaml = INPUT_WITH_INVALID_THEME_VALUES;
+    let input_diagram = serde_saphyr::from_str::<InputDiagram>(yaml).unwrap();
+    let input_diagram_spans = InputDiagramSpans::from_yaml(yaml);
+
+    let mut issues = validate(&input_diagram);
+    issues
+        .iter_mut()
+        .for_each(|issue| issue.span_attach(&input_diagram_spans));
+
+    let ModelToIrIssue::ThemeAttrValueInvalid { span, .. } = &issues[0] else {
+        panic!("Expected `ThemeAttrValueInvalid`, got: {:?}", issues[0]);
+    };
+    let value_offset = yaml.find("\"1000\"").unwrap();
+    assert_eq!(Some((value_offset, "\"1000\"".len()).into()), *span);
+}
+
+#[test]
+fn diagram_generator_includes_theme_attr_value_issues() {
+    let input_diagram =
+        serde_saphyr::from_str::<InputDiagram>(INPUT_WITH_INVALID_THEME_VALUES).unwrap();
+
+    let diagram_generated =
+        DiagramGenerator::generate(&input_diagram, EdgeAnimationActive::OnProcessStepFocus)
+            .expect("Expected diagram to be generated.");
+
+    assert_eq!(
+        validate(&input_diagram),
+        diagram_generated.ir_diagram_issues
+    );
+}
@@ -2,6 +2,8 @@ const EXAMPLE_INPUT: &str = include_str!("example_input.yaml");
 
 use disposition::input_model::InputDiagram;
 
+mod input_diagram_spans;
+
 #[test]
 fn test_parse_example_input() {
     let diagram = serde_saphyr::from_str::<InputDiagram>(EXAMPLE_INPUT).unwrap();
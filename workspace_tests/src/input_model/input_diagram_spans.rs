@@ -0,0 +1,106 @@
+use disposition::input_model::InputDiagramSpans;
+use pretty_assertions::assert_eq;
+
+const YAML: &str = "\
+things:
+  t_a:
+    t_a_nested: {}
+thing_names:
+  t_a: \"A\"
+thing_dependencies:
+  edge_a_b:
+    kind: sequence
+    things: [t_a, t_b]
+tag_things:
+  tag_a:
+    - t_a
+    - t_b
+";
+
+#[test]
+fn test_from_yaml_captures_map_key_and_value_spans() {
+    let input_diagram_spans = InputDiagramSpans::from_yaml(YAML);
+
+    let field_span = input_diagram_spans.get("thing_names.t_a").unwrap();
+    assert_eq!(Some("t_a"), field_span.key.clone().map(|key| &YAML[key]));
+    assert_eq!("\"A\"", &YAML[field_span.value.clone()]);
+
+    let field_span = input_diagram_spans.get("things.t_a.t_a_nested").unwrap();
+    assert_eq!("t_a_nested", &YAML[field_span.key_or_value()]);
+    assert_eq!("{}", &YAML[field_span.value.clone()]);
+}
+
+#[test]
+fn test_from_yaml_captures_sequence_item_spans() {
+    let input_diagram_spans = InputDiagramSpans::from_yaml(YAML);
+
+    let field_span = input_diagram_spans
+        .get("thing_dependencies.edge_a_b.things[1]")
+        .unwrap();
+    assert_eq!(None, field_span.key);
+    assert_eq!("t_b", &YAML[field_span.value.clone()]);
+
+    let field_span = input_diagram_spans.get("tag_things.tag_a[0]").unwrap();
+    assert_eq!("t_a", &YAML[field_span.key_or_value()]);
+    let field_span = input_diagram_spans.get("tag_things.tag_a[1]").unwrap();
+    assert_eq!("t_b", &YAML[field_span.key_or_value()]);
+}
+
+#[test]
+fn test_from_yaml_collection_value_span_excludes_trailing_whitespace() {
+    let input_diagram_spans = InputDiagramSpans::from_yaml(YAML);
+
+    let field_span = input_diagram_spans.get("tag_things.tag_a").unwrap();
+    assert_eq!("- t_a\n    - t_b", &YAML[field_span.value.clone()]);
+
+    let field_span = input_diagram_spans
+        .get("thing_dependencies.edge_a_b")
+        .unwrap();
+    assert_eq!(
+        "kind: sequence\n    things: [t_a, t_b]",
+        &YAML[field_span.value.clone()]
+    );
+}
+
+#[test]
+fn test_from_yaml_returns_spans_before_syntax_error() {
+    let yaml = "\
+thing_names:
+  t_a: A
+tag_things:
+  tag_a: [t_a
+";
+    let input_diagram_spans = InputDiagramSpans::from_yaml(yaml);
+
+    assert!(input_diagram_spans.contains_key("thing_names.t_a"));
+    assert!(!input_diagram_spans.contains_key("tag_things.tag_a"));
+}
+
+#[test]
+fn test_from_yaml_skips_entries_merged_with_merge_keys() {
+    let yaml = "\
+entity_descs: &entity_descs
+  t_a: A
+thing_names:
+  <<: [*entity_descs, { t_b: B }]
+  t_c: C
+";
+    let input_diagram_spans = InputDiagramSpans::from_yaml(yaml);
+
+    let field_span = input_diagram_spans.get("entity_descs.t_a").unwrap();
+    assert_eq!("A", &yaml[field_span.value.clone()]);
+    let field_span = input_diagram_spans.get("thing_names.t_c").unwrap();
+    assert_eq!("C", &yaml[field_span.value.clone()]);
+    assert_eq!(
+        vec![
+            "entity_descs.t_a",
+            "entity_descs",
+            "thing_names.t_c",
+            "thing_names"
+        ],
+        input_diagram_spans
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<&str>>()
+    );
+}
@@ -1,13 +1,22 @@
 //! Tests for `disposition_input_rt` mutation operations.
 
+mod cargo_metadata_importer;
+mod docker_compose_importer;
+mod dot_importer;
 mod edge_group_card_ops;
 mod entity_page_ops;
 mod flat_entry;
+mod import_test_support;
+mod input_diagram_linter;
+mod kubernetes_importer;
+mod mermaid_importer;
+mod otlp_trace_importer;
 mod process_card_ops;
 mod processes_page_ops;
 mod step_dependency_card_ops;
 mod step_interaction_card_ops;
 mod style_aliases_section_ops;
 mod tags_page_ops;
+mod terraform_importer;
 mod thing_layout_ops;
 mod things_page_ops;
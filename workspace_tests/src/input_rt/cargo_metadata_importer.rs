@@ -0,0 +1,233 @@
+//! Tests for `disposition_input_rt::CargoMetadataImporter`.
+
+use disposition::{input_model::edge::EdgeKind, model_common::entity::EntityTypeId};
+use disposition_input_rt::{
+    id_parse::parse_thing_id, CargoMetadataImportError, CargoMetadataImportOptions,
+    CargoMetadataImporter,
+};
+use serde_json::{json, Value};
+
+use crate::input_rt::import_test_support::{edge_groups, entity_types, hierarchy_ids, shape_color};
+
+fn package(name: &str, dir: &str, kinds: &[&str], dependencies: Value) -> Value {
+    json!({
+        "name": name,
+        "version": "0.1.0",
+        "id": format!("path+file:///ws/{dir}#{name}@0.1.0"),
+        "manifest_path": format!("/ws/{dir}/Cargo.toml"),
+        "dependencies": dependencies,
+        "targets": [{ "kind": kinds, "name": name }],
+    })
+}
+
+/// Returns `cargo metadata` output for a workspace with crates in `crate/`
+/// and `tests/`, which depend on each other and on `serde`.
+fn cargo_metadata_json() -> String {
+    let mut model = package(
+        "ws-model",
+        "crate/model",
+        &["lib"],
+        json!([
+            { "name": "serde", "source": "registry+https://github.com/rust-lang/crates.io-index", "kind": null },
+            { "name": "ws_derive", "kind": "build", "path": "/ws/crate/derive" },
+            { "name": "ws_test_util", "kind": "dev", "path": "/ws/tests/test_util" },
+        ]),
+    );
+    model["description"] = json!("Data types.");
+    let packages = vec![
+        package(
+            "ws_app",
+            "crate/app",
+            &["bin"],
+            json!([
+                { "name": "ws-model", "kind": null, "path": "/ws/crate/model" },
+                { "name": "ws-model", "kind": null, "path": "/ws/crate/model", "target": "cfg(unix)" },
+                { "name": "serde", "kind": null },
+            ]),
+        ),
+        model,
+        package("ws_derive", "crate/derive", &["proc-macro"], json!([])),
+        package(
+            "ws_test_util",
+            "tests/test_util",
+            &["lib"],
+            json!([{ "name": "ws-model", "kind": null, "path": "/ws/crate/model" }]),
+        ),
+        json!({
+            "name": "serde",
+            "id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.0",
+            "description": "A serialization framework.",
+            "manifest_path": "/registry/serde-1.0.0/Cargo.toml",
+            "dependencies": [],
+            "targets": [{ "kind": ["lib"], "name": "serde" }],
+        }),
+    ];
+    let workspace_members = packages[..4]
+        .iter()
+        .map(|package| package["id"].clone())
+        .collect::<Vec<Value>>();
+
+    json!({
+        "packages": packages,
+        "workspace_members": workspace_members,
+        "workspace_root": "/ws",
+        "version": 1,
+    })
+    .to_string()
+}
+
+#[test]
+fn members_are_imported_within_directory_things() {
+    let input_diagram = CargoMetadataImporter::import(
+        &cargo_metadata_json(),
+        &CargoMetadataImportOptions::default(),
+    )
+    .unwrap();
+    let things = &input_diagram.things;
+
+    assert_eq!(vec!["crate", "tests"], hierarchy_ids(things));
+    let crate_dir = things.get(&parse_thing_id("crate").unwrap()).unwrap();
+    assert_eq!(
+        vec!["ws_app", "ws_model", "ws_derive"],
+        hierarchy_ids(crate_dir)
+    );
+    let tests_dir = things.get(&parse_thing_id("tests").unwrap()).unwrap();
+    assert_eq!(vec!["ws_test_util"], hierarchy_ids(tests_dir));
+    assert_eq!(
+        Some("ws-model"),
+        input_diagram
+            .thing_names
+            .get(&parse_thing_id("ws_model").unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("Data types."),
+        input_diagram
+            .entity_tooltips
+            .get(parse_thing_id("ws_model").unwrap().as_ref())
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn path_dependencies_are_imported_as_sequence_edge_groups() {
+    let input_diagram = CargoMetadataImporter::import(
+        &cargo_metadata_json(),
+        &CargoMetadataImportOptions::default(),
+    )
+    .unwrap();
+
+    // Dev dependencies and crates from outside the workspace are not imported,
+    // and a dependency listed for multiple platforms is imported once.
+    assert_eq!(
+        vec![
+            (
+                String::from("edge_ws_model__ws_app"),
+                EdgeKind::Sequence,
+                vec![String::from("ws_model"), String::from("ws_app")]
+            ),
+            (
+                String::from("edge_ws_derive__ws_model"),
+                EdgeKind::Sequence,
+                vec![String::from("ws_derive"), String::from("ws_model")]
+            ),
+            (
+                String::from("edge_ws_model__ws_test_util"),
+                EdgeKind::Sequence,
+                vec![String::from("ws_model"), String::from("ws_test_util")]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+}
+
+#[test]
+fn target_kinds_are_imported_as_entity_types_with_styles() {
+    let input_diagram = CargoMetadataImporter::import(
+        &cargo_metadata_json(),
+        &CargoMetadataImportOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec!["type_crate_bin"],
+        entity_types(&input_diagram, "ws_app")
+    );
+    assert_eq!(
+        vec!["type_crate_lib"],
+        entity_types(&input_diagram, "ws_model")
+    );
+    assert_eq!(
+        vec!["type_crate_proc_macro"],
+        entity_types(&input_diagram, "ws_derive")
+    );
+
+    assert_eq!(
+        Some("emerald"),
+        shape_color(&input_diagram, "type_crate_bin")
+    );
+    assert_eq!(
+        Some("violet"),
+        shape_color(&input_diagram, "type_crate_proc_macro")
+    );
+    assert!(!input_diagram
+        .theme_types_styles
+        .contains_key(&EntityTypeId::new("type_crate_external").unwrap()));
+}
+
+#[test]
+fn external_crates_are_imported_within_external_thing_when_included() {
+    let cargo_metadata_import_options = CargoMetadataImportOptions::builder()
+        .with_external_crates_include(true)
+        .build();
+    let input_diagram =
+        CargoMetadataImporter::import(&cargo_metadata_json(), &cargo_metadata_import_options)
+            .unwrap();
+    let things = &input_diagram.things;
+
+    assert_eq!(vec!["crate", "tests", "external"], hierarchy_ids(things));
+    let external = things.get(&parse_thing_id("external").unwrap()).unwrap();
+    assert_eq!(vec!["serde"], hierarchy_ids(external));
+    assert_eq!(
+        vec!["type_crate_lib", "type_crate_external"],
+        entity_types(&input_diagram, "serde")
+    );
+    let edge_groups = edge_groups(&input_diagram.thing_dependencies);
+    assert!(edge_groups.contains(&(
+        String::from("edge_serde__ws_app"),
+        EdgeKind::Sequence,
+        vec![String::from("serde"), String::from("ws_app")]
+    )));
+    assert!(edge_groups.contains(&(
+        String::from("edge_serde__ws_model"),
+        EdgeKind::Sequence,
+        vec![String::from("serde"), String::from("ws_model")]
+    )));
+}
+
+#[test]
+fn invalid_metadata_is_returned_as_error() {
+    let error = CargoMetadataImporter::import(
+        "{\n  \"packages\": [}",
+        &CargoMetadataImportOptions::default(),
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        CargoMetadataImportError::JsonInvalid { span, .. } if span.offset() == 17
+    ));
+
+    let error = CargoMetadataImporter::import(
+        r#"{ "packages": [], "workspace_members": ["a"], "workspace_root": "/ws" }"#,
+        &CargoMetadataImportOptions::default(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        CargoMetadataImportError::WorkspaceMemberNotFound {
+            package_id: String::from("a")
+        },
+        error
+    );
+}
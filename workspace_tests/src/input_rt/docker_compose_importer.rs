@@ -0,0 +1,155 @@
+//! Tests for `disposition_input_rt::DockerComposeImporter`.
+
+use disposition::input_model::edge::EdgeKind;
+use disposition_input_rt::{
+    id_parse::parse_thing_id, DockerComposeImportError, DockerComposeImporter,
+};
+
+use crate::input_rt::import_test_support::{
+    edge_group, edge_groups, entity_types, hierarchy_ids, shape_color,
+};
+
+const DOCKER_COMPOSE_YAML: &str = "\
+name: shop
+services:
+  proxy:
+    image: docker.io/library/nginx:1.27-alpine
+    ports:
+      - 80
+      - '443:443'
+      - target: 8080
+        published: 18080
+        host_ip: 127.0.0.1
+        protocol: tcp
+    depends_on: [shop-api]
+  shop-api:
+    build: ./api
+    networks:
+      backend:
+        aliases: [api]
+      frontend: {}
+    depends_on:
+      db:
+        condition: service_healthy
+      cache:
+        condition: service_started
+  db:
+    image: postgres:16@sha256:0123456789abcdef
+    networks: [backend]
+    volumes: [db_data:/var/lib/postgresql/data]
+  cache:
+    image: ghcr.io/example/redis-stack:7
+    networks: [backend]
+networks:
+  backend: {}
+  frontend: {}
+volumes:
+  db_data: {}
+";
+
+#[test]
+fn services_are_imported_within_their_first_network() {
+    let input_diagram = DockerComposeImporter::import(DOCKER_COMPOSE_YAML).unwrap();
+    let things = &input_diagram.things;
+
+    assert_eq!(vec!["proxy", "backend"], hierarchy_ids(things));
+    let backend = things.get(&parse_thing_id("backend").unwrap()).unwrap();
+    assert_eq!(vec!["shop_api", "db", "cache"], hierarchy_ids(backend));
+    assert_eq!(
+        Some("shop-api"),
+        input_diagram
+            .thing_names
+            .get(&parse_thing_id("shop_api").unwrap())
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn depends_on_is_imported_as_sequence_edge_groups() {
+    let input_diagram = DockerComposeImporter::import(DOCKER_COMPOSE_YAML).unwrap();
+
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_shop_api__proxy",
+                EdgeKind::Sequence,
+                &["shop_api", "proxy"]
+            ),
+            edge_group("edge_db__shop_api", EdgeKind::Sequence, &["db", "shop_api"]),
+            edge_group(
+                "edge_cache__shop_api",
+                EdgeKind::Sequence,
+                &["cache", "shop_api"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+}
+
+#[test]
+fn ports_are_imported_as_descriptions() {
+    let input_diagram = DockerComposeImporter::import(DOCKER_COMPOSE_YAML).unwrap();
+
+    assert_eq!(
+        Some("Ports: `80`, `443:443`, `127.0.0.1:18080:8080/tcp`"),
+        input_diagram
+            .thing_descs
+            .get(parse_thing_id("proxy").unwrap().as_ref())
+            .map(String::as_str)
+    );
+    assert!(!input_diagram
+        .thing_descs
+        .contains_key(parse_thing_id("db").unwrap().as_ref()));
+}
+
+#[test]
+fn image_families_are_imported_as_entity_types_with_styles() {
+    let input_diagram = DockerComposeImporter::import(DOCKER_COMPOSE_YAML).unwrap();
+
+    assert_eq!(
+        vec!["type_image_nginx"],
+        entity_types(&input_diagram, "proxy")
+    );
+    assert_eq!(
+        vec!["type_image_postgres"],
+        entity_types(&input_diagram, "db")
+    );
+    assert_eq!(
+        vec!["type_image_redis_stack"],
+        entity_types(&input_diagram, "cache")
+    );
+    // Services that are built have no image.
+    assert!(entity_types(&input_diagram, "shop_api").is_empty());
+
+    assert_eq!(
+        Some("emerald"),
+        shape_color(&input_diagram, "type_image_nginx")
+    );
+    assert_eq!(
+        Some("blue"),
+        shape_color(&input_diagram, "type_image_postgres")
+    );
+    assert_eq!(None, shape_color(&input_diagram, "type_image_redis_stack"));
+}
+
+#[test]
+fn invalid_compose_file_is_returned_as_error() {
+    let error = DockerComposeImporter::import("services:\n  web:\n    ports: 80\n").unwrap_err();
+
+    // Points to the `80`, which should be a list.
+    assert!(matches!(
+        error,
+        DockerComposeImportError::YamlInvalid { span, .. } if span.offset() == 28
+    ));
+
+    let error =
+        DockerComposeImporter::import("services:\n  web:\n    depends_on: [api]\n").unwrap_err();
+
+    assert_eq!(
+        DockerComposeImportError::DependsOnServiceNotFound {
+            service: String::from("web"),
+            dependency: String::from("api"),
+        },
+        error
+    );
+}
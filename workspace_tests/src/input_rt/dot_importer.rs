@@ -0,0 +1,272 @@
+//! Tests for `disposition_input_rt::DotImporter`.
+
+use disposition::{input_model::edge::EdgeKind, model_common::RankDir};
+use disposition_input_rt::{
+    id_parse::{parse_edge_id, parse_id, parse_thing_id},
+    DotImportError, DotImportWarning, DotImporter,
+};
+
+use crate::input_rt::import_test_support::{edge_group, edge_groups, hierarchy_ids};
+
+#[test]
+fn clusters_are_imported_as_thing_hierarchy() {
+    let dot_import_outcome = DotImporter::import(
+        r#"digraph {
+            subgraph cluster_aws {
+                subgraph cluster_vpc { app; db; }
+                bucket;
+            }
+            user;
+        }"#,
+    )
+    .unwrap();
+    let things = &dot_import_outcome.input_diagram.things;
+
+    assert_eq!(vec!["aws", "user"], hierarchy_ids(things));
+    let aws = things.get(&parse_thing_id("aws").unwrap()).unwrap();
+    assert_eq!(vec!["vpc", "bucket"], hierarchy_ids(aws));
+    let vpc = aws.get(&parse_thing_id("vpc").unwrap()).unwrap();
+    assert_eq!(vec!["app", "db"], hierarchy_ids(vpc));
+    assert!(dot_import_outcome.warnings.is_empty());
+}
+
+#[test]
+fn subgraphs_other_than_clusters_are_flattened() {
+    let dot_import_outcome =
+        DotImporter::import("graph { subgraph group { a; b; } { rank=same; c; } }").unwrap();
+
+    assert_eq!(
+        vec!["a", "b", "c"],
+        hierarchy_ids(&dot_import_outcome.input_diagram.things)
+    );
+    assert!(matches!(
+        dot_import_outcome.warnings.as_slice(),
+        [DotImportWarning::AttrUnsupported { attr_name, .. }] if attr_name == "rank"
+    ));
+}
+
+#[test]
+fn labels_are_imported_as_thing_names() {
+    let dot_import_outcome = DotImporter::import(
+        r#"digraph {
+            subgraph cluster_aws { label="AWS"; }
+            a [label="Line 1\nLine 2\l"];
+            b [label="\N node"];
+            c [label=c];
+            d [label=<<b>Bold</b><br/>text &amp; more>];
+        }"#,
+    )
+    .unwrap();
+    let thing_names = &dot_import_outcome.input_diagram.thing_names;
+
+    assert_eq!(
+        Some("AWS"),
+        thing_names
+            .get(&parse_thing_id("aws").unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("Line 1\nLine 2"),
+        thing_names
+            .get(&parse_thing_id("a").unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some("b node"),
+        thing_names
+            .get(&parse_thing_id("b").unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(None, thing_names.get(&parse_thing_id("c").unwrap()));
+    assert_eq!(
+        Some("Bold\ntext & more"),
+        thing_names
+            .get(&parse_thing_id("d").unwrap())
+            .map(String::as_str)
+    );
+    assert!(matches!(
+        dot_import_outcome.warnings.as_slice(),
+        [DotImportWarning::LabelHtml { .. }]
+    ));
+}
+
+#[test]
+fn edges_are_imported_as_edge_groups() {
+    let dot_import_outcome = DotImporter::import(
+        r#"digraph {
+            a -> b -> c [label="request", taillabel="1", headlabel="n"];
+            a -> {d e};
+            c -> d [dir=back];
+            d -> e [dir=both];
+            e -> e;
+        }"#,
+    )
+    .unwrap();
+    let input_diagram = &dot_import_outcome.input_diagram;
+    let thing_dependencies = &input_diagram.thing_dependencies;
+
+    assert_eq!(
+        vec![
+            edge_group("edge_a__b__c", EdgeKind::Sequence, &["a", "b", "c"]),
+            edge_group("edge_a__d", EdgeKind::Sequence, &["a", "d"]),
+            edge_group("edge_a__e", EdgeKind::Sequence, &["a", "e"]),
+            edge_group("edge_d__c", EdgeKind::Sequence, &["d", "c"]),
+            edge_group("edge_d__e", EdgeKind::Symmetric, &["d", "e"]),
+            edge_group("edge_e", EdgeKind::Cyclic, &["e"]),
+        ],
+        edge_groups(thing_dependencies)
+    );
+    assert_eq!(
+        Some("request"),
+        input_diagram
+            .edge_descs
+            .get(&parse_id("edge_a__b__c").unwrap())
+            .map(String::as_str)
+    );
+    let edge_label = input_diagram
+        .edge_labels
+        .get(&parse_edge_id("edge_a__b__c").unwrap())
+        .unwrap();
+    assert_eq!("1", edge_label.from);
+    assert_eq!("n", edge_label.to);
+    assert!(dot_import_outcome.warnings.is_empty());
+}
+
+#[test]
+fn undirected_edges_are_imported_as_symmetric_edge_groups() {
+    let dot_import_outcome = DotImporter::import(
+        r#"graph {
+            a -- b -- c;
+            c -- d [dir=forward];
+            e -- d [dir=back];
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            edge_group("edge_a__b__c", EdgeKind::Symmetric, &["a", "b", "c"]),
+            edge_group("edge_c__d", EdgeKind::Sequence, &["c", "d"]),
+            edge_group("edge_d__e", EdgeKind::Sequence, &["d", "e"]),
+        ],
+        edge_groups(&dot_import_outcome.input_diagram.thing_dependencies)
+    );
+    assert!(dot_import_outcome.warnings.is_empty());
+}
+
+#[test]
+fn lhead_and_ltail_connect_edges_to_clusters() {
+    let dot_import_outcome = DotImporter::import(
+        r#"digraph {
+            compound=true;
+            subgraph cluster_a { a1; a2 [style=invis]; }
+            subgraph cluster_b { b1; }
+            a2 -> b1 [ltail=cluster_a, lhead=cluster_b];
+        }"#,
+    )
+    .unwrap();
+    let thing_dependencies = &dot_import_outcome.input_diagram.thing_dependencies;
+
+    assert_eq!(
+        vec![edge_group("edge_a__b", EdgeKind::Sequence, &["a", "b"])],
+        edge_groups(thing_dependencies)
+    );
+    assert!(matches!(
+        dot_import_outcome.warnings.as_slice(),
+        [DotImportWarning::NodeInvisible { node_id, .. }] if node_id == "a2"
+    ));
+}
+
+#[test]
+fn rankdir_is_imported_as_rank_dir() {
+    let dot_import_outcome = DotImporter::import("digraph { rankdir=BT; a; }").unwrap();
+
+    assert_eq!(
+        RankDir::BottomToTop,
+        dot_import_outcome.input_diagram.render_options.rank_dir
+    );
+
+    let dot_import_outcome = DotImporter::import("digraph { rankdir=XY; a; }").unwrap();
+
+    assert!(matches!(
+        dot_import_outcome.warnings.as_slice(),
+        [DotImportWarning::AttrValueUnsupported { attr_name, value, .. }]
+            if attr_name == "rankdir" && value == "XY"
+    ));
+}
+
+#[test]
+fn unsupported_attributes_are_warned_once() {
+    let dot_import_outcome = DotImporter::import(
+        r#"digraph {
+            node [shape=box];
+            a; b; c;
+            a -> b [color=red];
+        }"#,
+    )
+    .unwrap();
+
+    let attr_names = dot_import_outcome
+        .warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            DotImportWarning::AttrUnsupported { attr_name, .. } => Some(attr_name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<&str>>();
+    assert_eq!(vec!["shape", "color"], attr_names);
+}
+
+#[test]
+fn node_ids_that_are_not_valid_thing_ids_are_renamed() {
+    let dot_import_outcome = DotImporter::import(r#"digraph { "my-app" -> 1; my_app; }"#).unwrap();
+    let input_diagram = &dot_import_outcome.input_diagram;
+
+    assert_eq!(
+        vec!["my_app", "t_1", "my_app_2"],
+        hierarchy_ids(&input_diagram.things)
+    );
+    assert_eq!(
+        Some("my-app"),
+        input_diagram
+            .thing_names
+            .get(&parse_thing_id("my_app").unwrap())
+            .map(String::as_str)
+    );
+    assert_eq!(
+        vec![edge_group(
+            "edge_my_app__t_1",
+            EdgeKind::Sequence,
+            &["my_app", "t_1"]
+        )],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+    let ids_renamed = dot_import_outcome
+        .warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            DotImportWarning::IdRenamed {
+                dot_id, thing_id, ..
+            } => Some((dot_id.as_str(), thing_id.as_str())),
+            _ => None,
+        })
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        vec![("my-app", "my_app"), ("1", "t_1"), ("my_app", "my_app_2")],
+        ids_renamed
+    );
+}
+
+#[test]
+fn syntax_error_is_returned_with_span() {
+    let error = DotImporter::import("digraph { a -> ; }").unwrap_err();
+
+    assert!(matches!(
+        error,
+        DotImportError::TokenUnexpected { span, .. } if span.offset() == 15
+    ));
+
+    let error = DotImporter::import("digraph { a [label=\"b] }").unwrap_err();
+
+    assert!(matches!(error, DotImportError::Unterminated { .. }));
+}
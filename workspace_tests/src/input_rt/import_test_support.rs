@@ -0,0 +1,79 @@
+//! Functions to inspect the `InputDiagram`s returned by the importers.
+
+use disposition::{
+    input_model::{
+        edge::{EdgeGroup, EdgeKind},
+        theme::{IdOrDefaults, ThemeAttr},
+        thing::ThingHierarchy,
+        InputDiagram,
+    },
+    model_common::{edge::EdgeGroupId, entity::EntityTypeId, Map},
+};
+use disposition_input_rt::id_parse::parse_thing_id;
+
+/// Returns the IDs of the top level things in the hierarchy.
+pub(crate) fn hierarchy_ids(things: &ThingHierarchy<'static>) -> Vec<String> {
+    things
+        .iter()
+        .map(|(thing_id, _)| thing_id.to_string())
+        .collect()
+}
+
+/// Returns the ID, kind, and thing IDs of each edge group.
+pub(crate) fn edge_groups(
+    edge_groups: &Map<EdgeGroupId<'static>, EdgeGroup<'static>>,
+) -> Vec<(String, EdgeKind, Vec<String>)> {
+    edge_groups
+        .iter()
+        .map(|(edge_group_id, edge_group)| {
+            (
+                edge_group_id.to_string(),
+                edge_group.kind,
+                edge_group.things.iter().map(ToString::to_string).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Returns an expected edge group, in the form returned by [`edge_groups`].
+pub(crate) fn edge_group(
+    edge_group_id: &str,
+    kind: EdgeKind,
+    things: &[&str],
+) -> (String, EdgeKind, Vec<String>) {
+    (
+        String::from(edge_group_id),
+        kind,
+        things
+            .iter()
+            .map(|thing_id| String::from(*thing_id))
+            .collect(),
+    )
+}
+
+/// Returns the entity types of the thing.
+pub(crate) fn entity_types(input_diagram: &InputDiagram<'static>, thing_id: &str) -> Vec<String> {
+    input_diagram
+        .entity_types
+        .get(parse_thing_id(thing_id).unwrap().as_ref())
+        .map(|entity_types| {
+            entity_types
+                .iter()
+                .map(|entity_type| entity_type.as_str().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the node shape color of the entity type.
+pub(crate) fn shape_color<'d>(
+    input_diagram: &'d InputDiagram<'static>,
+    type_id: &'static str,
+) -> Option<&'d str> {
+    input_diagram
+        .theme_types_styles
+        .get(&EntityTypeId::new(type_id).unwrap())
+        .and_then(|theme_styles| theme_styles.get(&IdOrDefaults::NodeDefaults))
+        .and_then(|css_class_partials| css_class_partials.get(&ThemeAttr::ShapeColor))
+        .map(String::as_str)
+}
@@ -0,0 +1,228 @@
+//! Tests for `disposition_input_rt::InputDiagramLinter`.
+
+use disposition::input_model::{InputDiagram, InputDiagramSpans};
+use disposition_input_rt::{
+    id_parse::{parse_entity_type_id, parse_style_alias, parse_tag_id, parse_thing_id},
+    InputDiagramLint, InputDiagramLintFix, InputDiagramLinter,
+};
+
+const INPUT_WITH_LINTS: &str = "\
+things:
+  t_parent:
+    t_a: {}
+    t_b: {}
+  t_orphan: {}
+thing_dependencies:
+  edge_a__b:
+    kind: sequence
+    things: [t_a, t_b]
+tags:
+  tag_used: Used
+  tag_empty: Empty
+  tag_undeclared: Undeclared
+tag_things:
+  tag_used: [t_a]
+  tag_empty: []
+processes:
+  proc_a:
+    steps:
+      proc_a_step_1: Step 1
+      proc_a_step_2: Step 2
+    step_thing_interactions:
+      proc_a_step_1: [edge_a__b]
+entity_types:
+  t_a: [type_server]
+theme_default:
+  style_aliases:
+    alias_used:
+      fill_color: red
+    alias_unused:
+      fill_color: blue
+  base_styles:
+    t_a:
+      style_aliases_applied: [alias_used, padding_normal]
+theme_types_styles:
+  type_server:
+    node_defaults:
+      fill_color: green
+  type_unused:
+    node_defaults:
+      fill_color: green
+";
+
+fn input_diagram_with_lints() -> InputDiagram<'static> {
+    serde_saphyr::from_str(INPUT_WITH_LINTS).unwrap()
+}
+
+#[test]
+fn lint_returns_empty_for_clean_diagram() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram<'static>>(
+        "\
+things:
+  t_a: {}
+  t_b: {}
+thing_dependencies:
+  edge_a__b:
+    kind: sequence
+    things: [t_a, t_b]
+",
+    )
+    .unwrap();
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+
+    assert_eq!(Vec::<InputDiagramLint>::new(), lints);
+}
+
+#[test]
+fn lint_returns_lints_in_order() {
+    let input_diagram = input_diagram_with_lints();
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+
+    let field_paths = lints
+        .iter()
+        .map(InputDiagramLint::field_path)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "theme_default.style_aliases.alias_unused",
+            "theme_types_styles.type_unused",
+            "tag_things.tag_empty",
+            "tags.tag_undeclared",
+            "things.t_orphan",
+            "processes.proc_a.steps.proc_a_step_2",
+        ],
+        field_paths
+    );
+}
+
+#[test]
+fn lint_does_not_report_built_in_entity_types() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram<'static>>(
+        "\
+theme_types_styles:
+  type_thing_default:
+    node_defaults:
+      fill_color: green
+",
+    )
+    .unwrap();
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+
+    assert_eq!(Vec::<InputDiagramLint>::new(), lints);
+}
+
+#[test]
+fn lint_counts_layout_edges_as_edges() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram<'static>>(
+        "\
+things:
+  t_a: {}
+  t_b: {}
+thing_layout_edges:
+  edge_layout_a__b:
+    from: t_a
+    to: t_b
+",
+    )
+    .unwrap();
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+
+    assert_eq!(Vec::<InputDiagramLint>::new(), lints);
+}
+
+#[test]
+fn lint_reports_nested_thing_field_path() {
+    let input_diagram = serde_saphyr::from_str::<InputDiagram<'static>>(
+        "\
+things:
+  t_parent:
+    t_child: {}
+",
+    )
+    .unwrap();
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+
+    assert_eq!(1, lints.len());
+    assert_eq!("things.t_parent.t_child", lints[0].field_path());
+}
+
+#[test]
+fn span_attach_sets_span_to_field_key() {
+    let input_diagram = input_diagram_with_lints();
+    let input_diagram_spans = InputDiagramSpans::from_yaml(INPUT_WITH_LINTS);
+
+    let mut lints = InputDiagramLinter::lint(&input_diagram);
+    lints
+        .iter_mut()
+        .for_each(|lint| lint.span_attach(&input_diagram_spans));
+
+    let InputDiagramLint::StyleAliasUnused { span, .. } = &lints[0] else {
+        panic!("Expected `StyleAliasUnused`, got: {:?}", lints[0]);
+    };
+    let span = span.expect("Expected span to be attached.");
+    assert_eq!(
+        "alias_unused",
+        &INPUT_WITH_LINTS[span.offset()..span.offset() + span.len()]
+    );
+}
+
+#[test]
+fn fix_apply_removes_dead_configuration() {
+    let mut input_diagram = input_diagram_with_lints();
+
+    InputDiagramLinter::lint(&input_diagram)
+        .iter()
+        .filter_map(InputDiagramLint::fix)
+        .for_each(|fix| fix.apply(&mut input_diagram));
+
+    let lints = InputDiagramLinter::lint(&input_diagram);
+    assert_eq!(1, lints.len());
+    assert!(matches!(
+        lints[0],
+        InputDiagramLint::ProcessStepInteractionsMissing { .. }
+    ));
+    let tag_empty = parse_tag_id("tag_empty").unwrap();
+    assert!(!input_diagram
+        .things
+        .contains_key(&parse_thing_id("t_orphan").unwrap()));
+    assert!(!input_diagram.tags.contains_key(&tag_empty));
+    assert!(!input_diagram.tag_things.contains_key(&tag_empty));
+    assert!(!input_diagram
+        .theme_types_styles
+        .contains_key(&parse_entity_type_id("type_unused").unwrap()));
+    assert!(!input_diagram
+        .theme_default
+        .style_aliases
+        .contains_key(&parse_style_alias("alias_unused").unwrap()));
+}
+
+#[test]
+fn fix_display_describes_fix() {
+    let input_diagram = input_diagram_with_lints();
+
+    let fix_titles = InputDiagramLinter::lint(&input_diagram)
+        .iter()
+        .filter_map(InputDiagramLint::fix)
+        .map(|fix| fix.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            "Remove style alias `alias_unused`",
+            "Remove styles for type `type_unused`",
+            "Remove tag `tag_empty`",
+            "Remove tag `tag_undeclared`",
+            "Remove thing `t_orphan`",
+        ],
+        fix_titles
+    );
+    assert!(matches!(
+        InputDiagramLinter::lint(&input_diagram)[0].fix(),
+        Some(InputDiagramLintFix::StyleAliasRemove(_))
+    ));
+}
@@ -0,0 +1,328 @@
+//! Tests for `disposition_input_rt::KubernetesImporter`.
+
+use disposition::input_model::{edge::EdgeKind, InputDiagram};
+use disposition_input_rt::{
+    id_parse::{parse_edge_group_id, parse_thing_id},
+    KubernetesImportError, KubernetesImporter, KubernetesManifests,
+};
+
+use crate::input_rt::import_test_support::{edge_group, edge_groups, hierarchy_ids};
+
+const WEB_YAML: &str = "\
+apiVersion: v1
+kind: Namespace
+metadata:
+  name: shop
+  labels:
+    team: storefront
+---
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+  namespace: shop
+  labels:
+    app.kubernetes.io/name: web
+    app.kubernetes.io/part-of: shop
+spec:
+  selector:
+    matchLabels: { app: web }
+  template:
+    metadata:
+      labels: { app: web, tier: frontend }
+    spec:
+      containers:
+        - name: web
+          image: nginx:1.27
+          envFrom:
+            - configMapRef: { name: web-config }
+        - name: log-shipper
+          image: fluent-bit:3
+      volumes:
+        - name: tls
+          secret: { secretName: web-tls }
+        - name: config
+          configMap: { name: web-config }
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+  namespace: shop
+spec:
+  selector: { app: web }
+  ports: [{ port: 80, targetPort: 8080 }]
+";
+
+const API_YAML: &str = "\
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: shop
+  namespace: shop
+spec:
+  defaultBackend:
+    service: { name: web, port: { number: 80 } }
+  rules:
+    - host: shop.example.com
+      http:
+        paths:
+          - path: /api
+            pathType: Prefix
+            backend:
+              service: { name: api, port: { number: 80 } }
+          - path: /
+            pathType: Prefix
+            backend:
+              service: { name: web, port: { number: 80 } }
+---
+apiVersion: apps/v1
+kind: StatefulSet
+metadata:
+  name: api
+  namespace: shop
+  labels: { app: api, team: checkout }
+spec:
+  template:
+    metadata:
+      labels: { app: api }
+    spec:
+      containers:
+        - name: api
+          image: ghcr.io/example/api:2
+          env:
+            - name: DB_PASSWORD
+              valueFrom:
+                secretKeyRef: { name: db-credentials, key: password }
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: api
+  namespace: shop
+spec:
+  selector: { app: api }
+---
+apiVersion: batch/v1
+kind: CronJob
+metadata:
+  name: cleanup
+spec:
+  schedule: '0 * * * *'
+  jobTemplate:
+    spec:
+      template:
+        spec:
+          containers: [{ name: cleanup, image: busybox }]
+---
+apiVersion: v1
+kind: ServiceAccount
+metadata:
+  name: api
+  namespace: shop
+";
+
+fn input_diagram_import() -> InputDiagram<'static> {
+    let mut kubernetes_manifests = KubernetesManifests::new();
+    kubernetes_manifests.extend_from_yaml(WEB_YAML).unwrap();
+    kubernetes_manifests.extend_from_yaml(API_YAML).unwrap();
+    KubernetesImporter::import(&kubernetes_manifests)
+}
+
+#[test]
+fn objects_are_imported_within_namespace_and_workload_things() {
+    let input_diagram = input_diagram_import();
+    let things = &input_diagram.things;
+
+    assert_eq!(
+        vec!["namespace_shop", "namespace_default"],
+        hierarchy_ids(things)
+    );
+    let shop = &things[&parse_thing_id("namespace_shop").unwrap()];
+    assert_eq!(
+        vec![
+            "deployment_web",
+            "service_web",
+            "ingress_shop",
+            "statefulset_api",
+            "service_api",
+            "secret_web_tls",
+            "configmap_web_config",
+            "secret_db_credentials",
+        ],
+        hierarchy_ids(shop)
+    );
+    assert_eq!(
+        vec!["deployment_web_pod"],
+        hierarchy_ids(&shop[&parse_thing_id("deployment_web").unwrap()])
+    );
+    let default = &things[&parse_thing_id("namespace_default").unwrap()];
+    assert_eq!(
+        vec!["cronjob_cleanup_pod"],
+        hierarchy_ids(&default[&parse_thing_id("cronjob_cleanup").unwrap()])
+    );
+
+    let thing_name = |thing_id: &str| {
+        input_diagram
+            .thing_names
+            .get(&parse_thing_id(thing_id).unwrap())
+            .cloned()
+    };
+    assert_eq!(
+        Some(String::from("namespace/shop")),
+        thing_name("namespace_shop")
+    );
+    assert_eq!(
+        Some(String::from("configmap/web-config")),
+        thing_name("configmap_web_config")
+    );
+    assert_eq!(
+        Some(String::from("pod template")),
+        thing_name("deployment_web_pod")
+    );
+    assert_eq!(
+        Some("* `web`: `nginx:1.27`\n* `log-shipper`: `fluent-bit:3`"),
+        input_diagram
+            .thing_descs
+            .get(parse_thing_id("deployment_web_pod").unwrap().as_ref())
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn services_and_ingresses_are_imported_as_interactions() {
+    let input_diagram = input_diagram_import();
+
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_service_web__deployment_web",
+                EdgeKind::Sequence,
+                &["service_web", "deployment_web"]
+            ),
+            edge_group(
+                "edge_ingress_shop__service_web",
+                EdgeKind::Sequence,
+                &["ingress_shop", "service_web"]
+            ),
+            edge_group(
+                "edge_ingress_shop__service_api",
+                EdgeKind::Sequence,
+                &["ingress_shop", "service_api"]
+            ),
+            edge_group(
+                "edge_service_api__statefulset_api",
+                EdgeKind::Sequence,
+                &["service_api", "statefulset_api"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_interactions)
+    );
+
+    let edge_desc = |edge_group_id: &str| {
+        input_diagram
+            .edge_descs
+            .get(parse_edge_group_id(edge_group_id).unwrap().as_ref())
+            .cloned()
+    };
+    assert_eq!(
+        Some(String::from("*, shop.example.com/")),
+        edge_desc("edge_ingress_shop__service_web")
+    );
+    assert_eq!(
+        Some(String::from("shop.example.com/api")),
+        edge_desc("edge_ingress_shop__service_api")
+    );
+}
+
+#[test]
+fn config_map_and_secret_references_are_imported_as_dependencies() {
+    let input_diagram = input_diagram_import();
+
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_secret_web_tls__deployment_web_pod",
+                EdgeKind::Sequence,
+                &["secret_web_tls", "deployment_web_pod"]
+            ),
+            edge_group(
+                "edge_configmap_web_config__deployment_web_pod",
+                EdgeKind::Sequence,
+                &["configmap_web_config", "deployment_web_pod"]
+            ),
+            edge_group(
+                "edge_secret_db_credentials__statefulset_api_pod",
+                EdgeKind::Sequence,
+                &["secret_db_credentials", "statefulset_api_pod"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+}
+
+#[test]
+fn common_labels_are_imported_as_tags() {
+    let input_diagram = input_diagram_import();
+
+    let tags = input_diagram
+        .tags
+        .iter()
+        .map(|(tag_id, tag_name)| (tag_id.to_string(), tag_name.clone()))
+        .collect::<Vec<(String, String)>>();
+    assert_eq!(
+        vec![
+            (
+                String::from("tag_team_storefront"),
+                String::from("team: storefront")
+            ),
+            (
+                String::from("tag_part_of_shop"),
+                String::from("part of: shop")
+            ),
+            (String::from("tag_app_web"), String::from("app: web")),
+            (String::from("tag_app_api"), String::from("app: api")),
+            (
+                String::from("tag_team_checkout"),
+                String::from("team: checkout")
+            ),
+        ],
+        tags
+    );
+
+    let tag_things = |tag_id: &str| {
+        input_diagram
+            .tag_things
+            .iter()
+            .find(|(tag_id_existing, _)| tag_id_existing.as_str() == tag_id)
+            .map(|(_, thing_ids)| {
+                thing_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default()
+    };
+    assert_eq!(
+        vec!["deployment_web", "deployment_web_pod"],
+        tag_things("tag_app_web")
+    );
+    assert_eq!(
+        vec!["statefulset_api", "statefulset_api_pod"],
+        tag_things("tag_app_api")
+    );
+}
+
+#[test]
+fn invalid_manifest_is_returned_as_error() {
+    let mut kubernetes_manifests = KubernetesManifests::new();
+    let error = kubernetes_manifests
+        .extend_from_yaml("kind: Service\n---\nmetadata: { name: web }\n")
+        .unwrap_err();
+
+    assert!(
+        matches!(error, KubernetesImportError::YamlInvalid { .. }),
+        "Expected `YamlInvalid`, got: {error:?}"
+    );
+    assert!(kubernetes_manifests.is_empty());
+}
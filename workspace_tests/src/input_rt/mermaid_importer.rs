@@ -0,0 +1,250 @@
+//! Tests for `disposition_input_rt::MermaidImporter`.
+
+use disposition::{
+    input_model::{edge::EdgeKind, InputDiagram},
+    model_common::RankDir,
+};
+use disposition_input_rt::{
+    id_parse::{parse_edge_id, parse_thing_id},
+    MermaidImportError, MermaidImportWarning, MermaidImporter,
+};
+
+use crate::input_rt::import_test_support::{edge_group, edge_groups, hierarchy_ids};
+
+fn thing_name<'d>(input_diagram: &'d InputDiagram<'static>, thing_id: &str) -> Option<&'d str> {
+    input_diagram
+        .thing_names
+        .get(&parse_thing_id(thing_id).unwrap())
+        .map(String::as_str)
+}
+
+#[test]
+fn subgraphs_are_imported_as_thing_hierarchy() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "flowchart TD
+            subgraph aws [AWS]
+                subgraph vpc
+                    app
+                    db
+                end
+                bucket
+            end
+            user --> app",
+    )
+    .unwrap();
+    let input_diagram = &mermaid_import_outcome.input_diagram;
+    let things = &input_diagram.things;
+
+    assert_eq!(vec!["aws", "user"], hierarchy_ids(things));
+    let aws = things.get(&parse_thing_id("aws").unwrap()).unwrap();
+    assert_eq!(vec!["vpc", "bucket"], hierarchy_ids(aws));
+    let vpc = aws.get(&parse_thing_id("vpc").unwrap()).unwrap();
+    assert_eq!(vec!["app", "db"], hierarchy_ids(vpc));
+    assert_eq!(Some("AWS"), thing_name(input_diagram, "aws"));
+    assert_eq!(None, thing_name(input_diagram, "vpc"));
+    assert!(mermaid_import_outcome.warnings.is_empty());
+}
+
+#[test]
+fn nodes_first_linked_outside_subgraph_are_moved_into_subgraph() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "graph LR
+            a --> b
+            subgraph group
+                b
+            end",
+    )
+    .unwrap();
+    let things = &mermaid_import_outcome.input_diagram.things;
+
+    assert_eq!(vec!["a", "group"], hierarchy_ids(things));
+    let group = things.get(&parse_thing_id("group").unwrap()).unwrap();
+    assert_eq!(vec!["b"], hierarchy_ids(group));
+}
+
+#[test]
+fn node_text_is_imported_as_thing_names() {
+    let mermaid = r##"flowchart LR
+            a[Server]
+            b("Line 1<br/>Line 2")
+            c["#quot;Quoted#quot; & more"]
+            d[ "`**Markdown**`" ]
+            e[e]"##;
+    let mermaid_import_outcome = MermaidImporter::import(mermaid).unwrap();
+    let input_diagram = &mermaid_import_outcome.input_diagram;
+
+    assert_eq!(Some("Server"), thing_name(input_diagram, "a"));
+    assert_eq!(Some("Line 1\nLine 2"), thing_name(input_diagram, "b"));
+    assert_eq!(Some("\"Quoted\" & more"), thing_name(input_diagram, "c"));
+    assert_eq!(Some("**Markdown**"), thing_name(input_diagram, "d"));
+    assert_eq!(None, thing_name(input_diagram, "e"));
+    let [MermaidImportWarning::LabelMarkdown { span }] = mermaid_import_outcome.warnings.as_slice()
+    else {
+        panic!(
+            "Expected `LabelMarkdown` warning, got: {:?}",
+            mermaid_import_outcome.warnings
+        );
+    };
+    assert_eq!(
+        "`**Markdown**`",
+        &mermaid[span.offset()..span.offset() + span.len()]
+    );
+}
+
+#[test]
+fn links_are_imported_as_edge_groups() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "flowchart LR
+            a --> b --> c
+            a & b --> d
+            d <--> e
+            e --- f
+            f --> f",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            edge_group("edge_a__b__c", EdgeKind::Sequence, &["a", "b", "c"]),
+            edge_group("edge_a__d", EdgeKind::Sequence, &["a", "d"]),
+            edge_group("edge_b__d", EdgeKind::Sequence, &["b", "d"]),
+            edge_group("edge_d__e", EdgeKind::Symmetric, &["d", "e"]),
+            edge_group("edge_e__f", EdgeKind::Sequence, &["e", "f"]),
+            edge_group("edge_f", EdgeKind::Cyclic, &["f"]),
+        ],
+        edge_groups(&mermaid_import_outcome.input_diagram.thing_dependencies)
+    );
+    assert!(mermaid_import_outcome.warnings.is_empty());
+}
+
+#[test]
+fn link_text_is_imported_as_edge_labels() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "flowchart LR
+            a -- request --> b
+            c -->|one| d -->|two| e",
+    )
+    .unwrap();
+    let edge_labels = &mermaid_import_outcome.input_diagram.edge_labels;
+
+    let edge_label = edge_labels
+        .get(&parse_edge_id("edge_a__b").unwrap())
+        .unwrap();
+    assert_eq!("request", edge_label.from);
+    assert_eq!("", edge_label.to);
+    let edge_label = edge_labels
+        .get(&parse_edge_id("edge_c__d__e__0").unwrap())
+        .unwrap();
+    assert_eq!("one", edge_label.from);
+    let edge_label = edge_labels
+        .get(&parse_edge_id("edge_c__d__e__1").unwrap())
+        .unwrap();
+    assert_eq!("two", edge_label.from);
+    assert_eq!(3, edge_labels.len());
+}
+
+#[test]
+fn link_styles_are_imported_as_solid_links() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "flowchart LR
+            a -.-> b
+            b ==> c
+            c --o d
+            d ~~~ e",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            edge_group("edge_a__b", EdgeKind::Sequence, &["a", "b"]),
+            edge_group("edge_b__c", EdgeKind::Sequence, &["b", "c"]),
+            edge_group("edge_c__d", EdgeKind::Sequence, &["c", "d"]),
+        ],
+        edge_groups(&mermaid_import_outcome.input_diagram.thing_dependencies)
+    );
+    let warnings = mermaid_import_outcome
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            MermaidImportWarning::LinkStyleUnsupported { style, .. } => *style,
+            MermaidImportWarning::LinkInvisible { .. } => "invisible",
+            _ => "other",
+        })
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        vec!["dotted", "thick", "circle arrow head", "invisible"],
+        warnings
+    );
+}
+
+#[test]
+fn direction_is_imported_as_rank_dir() {
+    let mermaid_import_outcome = MermaidImporter::import("flowchart RL\n a").unwrap();
+
+    assert_eq!(
+        RankDir::RightToLeft,
+        mermaid_import_outcome.input_diagram.render_options.rank_dir
+    );
+
+    let mermaid_import_outcome = MermaidImporter::import("graph\n a").unwrap();
+
+    assert_eq!(
+        RankDir::TopToBottom,
+        mermaid_import_outcome.input_diagram.render_options.rank_dir
+    );
+}
+
+#[test]
+fn unsupported_statements_and_shapes_are_warned() {
+    let mermaid_import_outcome = MermaidImporter::import(
+        "flowchart LR
+            classDef hot fill:#f00
+            a{Decision}:::hot
+            subgraph group
+                direction TB
+                b
+            end
+            style a fill:#fff",
+    )
+    .unwrap();
+
+    let warnings = mermaid_import_outcome
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            MermaidImportWarning::StatementUnsupported { statement, .. } => statement.clone(),
+            MermaidImportWarning::NodeShapeUnsupported { shape, .. } => String::from(*shape),
+            MermaidImportWarning::ClassUnsupported { .. } => String::from(":::"),
+            MermaidImportWarning::DirectionSubgraphUnsupported { .. } => String::from("direction"),
+            _ => String::from("other"),
+        })
+        .collect::<Vec<String>>();
+    assert_eq!(
+        vec!["classDef", "rhombus", ":::", "direction", "style"],
+        warnings
+    );
+}
+
+#[test]
+fn syntax_errors_are_returned_with_span() {
+    let error = MermaidImporter::import("sequenceDiagram\n a->>b: hi").unwrap_err();
+
+    assert!(matches!(
+        error,
+        MermaidImportError::HeaderInvalid { span, .. } if span.offset() == 0 && span.len() == 15
+    ));
+
+    let error = MermaidImporter::import("flowchart\n subgraph a\n b").unwrap_err();
+
+    assert!(matches!(
+        error,
+        MermaidImportError::SubgraphNotClosed { subgraph_id, .. } if subgraph_id == "a"
+    ));
+
+    let error = MermaidImporter::import("flowchart\n end").unwrap_err();
+
+    assert!(matches!(
+        error,
+        MermaidImportError::SubgraphEndUnexpected { .. }
+    ));
+}
@@ -0,0 +1,262 @@
+//! Tests for `disposition_input_rt::OtlpTraceImporter`.
+
+use disposition::input_model::{edge::EdgeKind, process::ProcessDiagram, InputDiagram};
+use disposition_input_rt::{
+    id_parse::{parse_process_id, parse_process_step_id, parse_thing_id},
+    OtlpTraceImportError, OtlpTraceImporter,
+};
+
+use crate::input_rt::import_test_support::{edge_group, edge_groups, hierarchy_ids};
+
+/// Two exports, one per line, as written by the collector's `file` exporter.
+///
+/// Trace `aaaa`:
+///
+/// ```text
+/// frontend: GET /checkout   100 ms .. 400 ms
+/// frontend:   render        110 ms .. 390 ms
+/// checkout:     PlaceOrder  120 ms .. 320 ms
+/// payment:        Charge    150 ms .. 250 ms
+/// checkout:       db query  260 ms .. 300 ms
+/// checkout:   GetOrder      330 ms .. 380 ms
+/// ```
+///
+/// Trace `bbbb` only has a `Reconcile` span in `payment`.
+const TRACES_JSON: &str = concat!(
+    r#"{"resourceSpans":[{"resource":{"attributes":["#,
+    r#"{"key":"service.name","value":{"stringValue":"frontend"}},"#,
+    r#"{"key":"service.namespace","value":{"stringValue":"shop"}}]},"#,
+    r#""scopeSpans":[{"spans":["#,
+    r#"{"traceId":"aaaa","spanId":"f1","name":"GET /checkout","#,
+    r#""startTimeUnixNano":"100000000","endTimeUnixNano":"400000000"},"#,
+    r#"{"traceId":"aaaa","spanId":"f2","parentSpanId":"f1","name":"render","#,
+    r#""startTimeUnixNano":"110000000","endTimeUnixNano":"390000000"}"#,
+    r#"]}]}]}"#,
+    "\n",
+    r#"{"resourceSpans":[{"resource":{"attributes":["#,
+    r#"{"key":"service.name","value":{"stringValue":"checkout"}},"#,
+    r#"{"key":"service.namespace","value":{"stringValue":"shop"}}]},"#,
+    r#""scopeSpans":[{"spans":["#,
+    r#"{"traceId":"aaaa","spanId":"c3","parentSpanId":"f1","name":"GetOrder","#,
+    r#""startTimeUnixNano":"330000000","endTimeUnixNano":"380000000"},"#,
+    r#"{"traceId":"aaaa","spanId":"c1","parentSpanId":"f2","name":"PlaceOrder","#,
+    r#""startTimeUnixNano":"120000000","endTimeUnixNano":"320000000"},"#,
+    r#"{"traceId":"aaaa","spanId":"c2","parentSpanId":"c1","name":"db query","#,
+    r#""startTimeUnixNano":"260000000","endTimeUnixNano":"300000000"}"#,
+    r#"]}]},"#,
+    r#"{"resource":{"attributes":["#,
+    r#"{"key":"service.name","value":{"stringValue":"payment"}}]},"#,
+    r#""instrumentationLibrarySpans":[{"spans":["#,
+    r#"{"traceId":"aaaa","spanId":"p1","parentSpanId":"c1","name":"Charge","#,
+    r#""startTimeUnixNano":150000000,"endTimeUnixNano":250000000},"#,
+    r#"{"traceId":"bbbb","spanId":"p2","name":"Reconcile","#,
+    r#""startTimeUnixNano":500000000,"endTimeUnixNano":600000000}"#,
+    r#"]}]}]}"#,
+    "\n",
+);
+
+fn process_checkout<'d>(input_diagram: &'d InputDiagram<'static>) -> &'d ProcessDiagram<'static> {
+    &input_diagram.processes[&parse_process_id("proc_get__checkout").unwrap()]
+}
+
+#[test]
+fn services_are_imported_within_namespace_things() {
+    let input_diagram = OtlpTraceImporter::import(TRACES_JSON).unwrap();
+    let things = &input_diagram.things;
+
+    assert_eq!(vec!["shop", "payment"], hierarchy_ids(things));
+    assert_eq!(
+        vec!["frontend", "checkout"],
+        hierarchy_ids(&things[&parse_thing_id("shop").unwrap()])
+    );
+    assert!(input_diagram.thing_names.is_empty());
+}
+
+#[test]
+fn spans_across_services_are_imported_as_interactions() {
+    let input_diagram = OtlpTraceImporter::import(TRACES_JSON).unwrap();
+
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_frontend__checkout",
+                EdgeKind::Sequence,
+                &["frontend", "checkout"]
+            ),
+            edge_group(
+                "edge_checkout__payment",
+                EdgeKind::Sequence,
+                &["checkout", "payment"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_interactions)
+    );
+}
+
+#[test]
+fn traces_are_imported_as_processes_with_steps_in_start_order() {
+    let input_diagram = OtlpTraceImporter::import(TRACES_JSON).unwrap();
+
+    assert_eq!(
+        vec!["proc_get__checkout"],
+        input_diagram
+            .processes
+            .keys()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+    );
+    let process = process_checkout(&input_diagram);
+    assert_eq!(Some("GET /checkout"), process.name.as_deref());
+    assert_eq!(Some("Trace `aaaa`."), process.desc.as_deref());
+    assert_eq!(
+        vec![
+            (
+                String::from("proc_get__checkout_step_placeorder"),
+                String::from("PlaceOrder")
+            ),
+            (
+                String::from("proc_get__checkout_step_charge"),
+                String::from("Charge")
+            ),
+            (
+                String::from("proc_get__checkout_step_getorder"),
+                String::from("GetOrder")
+            ),
+        ],
+        process
+            .steps
+            .iter()
+            .map(|(step_id, step_name)| (step_id.to_string(), step_name.clone()))
+            .collect::<Vec<(String, String)>>()
+    );
+    assert_eq!(
+        vec![
+            (
+                String::from("proc_get__checkout_step_placeorder"),
+                vec![String::from("edge_frontend__checkout")]
+            ),
+            (
+                String::from("proc_get__checkout_step_charge"),
+                vec![String::from("edge_checkout__payment")]
+            ),
+            (
+                String::from("proc_get__checkout_step_getorder"),
+                vec![String::from("edge_frontend__checkout")]
+            ),
+        ],
+        process
+            .step_thing_interactions
+            .iter()
+            .map(|(step_id, edge_group_ids)| (
+                step_id.to_string(),
+                edge_group_ids.iter().map(ToString::to_string).collect()
+            ))
+            .collect::<Vec<(String, Vec<String>)>>()
+    );
+}
+
+#[test]
+fn steps_depend_on_nearest_ancestor_step_and_have_tooltips() {
+    let input_diagram = OtlpTraceImporter::import(TRACES_JSON).unwrap();
+    let process = process_checkout(&input_diagram);
+
+    assert_eq!(
+        vec![(
+            String::from("proc_get__checkout_step_charge"),
+            vec![String::from("proc_get__checkout_step_placeorder")]
+        )],
+        process
+            .process_step_dependencies
+            .iter()
+            .map(|(step_id, step_ids_dependency)| (
+                step_id.to_string(),
+                step_ids_dependency
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            ))
+            .collect::<Vec<(String, Vec<String>)>>()
+    );
+
+    let step_tooltip = |step_id: &str| {
+        input_diagram
+            .entity_tooltips
+            .get(parse_process_step_id(step_id).unwrap().as_ref())
+            .cloned()
+    };
+    assert_eq!(
+        Some(String::from("`frontend` → `checkout`, 200.0 ms")),
+        step_tooltip("proc_get__checkout_step_placeorder")
+    );
+    assert_eq!(
+        Some(String::from("`checkout` → `payment`, 100.0 ms")),
+        step_tooltip("proc_get__checkout_step_charge")
+    );
+}
+
+#[test]
+fn invalid_json_is_returned_as_error() {
+    let error =
+        OtlpTraceImporter::import("{\"resourceSpans\":[]}\n{\"resourceSpans\": 1}\n").unwrap_err();
+
+    let OtlpTraceImportError::JsonInvalid { span, .. } = error;
+    // After the `1` on the second line.
+    assert_eq!(39, span.offset());
+}
+
+#[test]
+fn steps_are_ordered_after_parent_step_when_start_times_are_skewed() {
+    // `payment`'s clock is behind, so `Charge` appears to start before the
+    // `PlaceOrder` span that called it.
+    let traces_json = concat!(
+        r#"{"resourceSpans":[{"resource":{"attributes":["#,
+        r#"{"key":"service.name","value":{"stringValue":"frontend"}}]},"#,
+        r#""scopeSpans":[{"spans":["#,
+        r#"{"traceId":"cccc","spanId":"f1","name":"POST /order","#,
+        r#""startTimeUnixNano":"100000000","endTimeUnixNano":"400000000"}"#,
+        r#"]}]},"#,
+        r#"{"resource":{"attributes":["#,
+        r#"{"key":"service.name","value":{"stringValue":"checkout"}}]},"#,
+        r#""scopeSpans":[{"spans":["#,
+        r#"{"traceId":"cccc","spanId":"c1","parentSpanId":"f1","name":"PlaceOrder","#,
+        r#""startTimeUnixNano":"200000000","endTimeUnixNano":"300000000"}"#,
+        r#"]}]},"#,
+        r#"{"resource":{"attributes":["#,
+        r#"{"key":"service.name","value":{"stringValue":"payment"}}]},"#,
+        r#""scopeSpans":[{"spans":["#,
+        r#"{"traceId":"cccc","spanId":"p1","parentSpanId":"c1","name":"Charge","#,
+        r#""startTimeUnixNano":"150000000","endTimeUnixNano":"250000000"}"#,
+        r#"]}]}]}"#,
+    );
+    let input_diagram = OtlpTraceImporter::import(traces_json).unwrap();
+    let process = &input_diagram.processes[&parse_process_id("proc_post__order").unwrap()];
+
+    assert_eq!(
+        vec![
+            "proc_post__order_step_placeorder",
+            "proc_post__order_step_charge"
+        ],
+        process
+            .steps
+            .keys()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+    );
+    assert_eq!(
+        vec![(
+            String::from("proc_post__order_step_charge"),
+            vec![String::from("proc_post__order_step_placeorder")]
+        )],
+        process
+            .process_step_dependencies
+            .iter()
+            .map(|(step_id, step_ids_dependency)| (
+                step_id.to_string(),
+                step_ids_dependency
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            ))
+            .collect::<Vec<(String, Vec<String>)>>()
+    );
+}
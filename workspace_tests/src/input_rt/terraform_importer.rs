@@ -0,0 +1,394 @@
+//! Tests for `disposition_input_rt::TerraformImporter`.
+
+use disposition::{
+    input_model::{
+        edge::EdgeKind,
+        theme::{IdOrDefaults, ThemeAttr},
+    },
+    model_common::entity::EntityTypeId,
+};
+use disposition_input_rt::{id_parse::parse_thing_id, TerraformImportError, TerraformImporter};
+
+use crate::input_rt::import_test_support::{edge_group, edge_groups, entity_types, hierarchy_ids};
+
+/// `terraform show -json` output for a plan with a `vpc` module that calls a
+/// `subnets` module.
+const PLAN_JSON: &str = r#"{
+  "format_version": "1.2",
+  "planned_values": {
+    "root_module": {
+      "resources": [
+        { "address": "aws_instance.web[0]", "mode": "managed", "type": "aws_instance", "name": "web", "index": 0, "provider_name": "registry.terraform.io/hashicorp/aws" },
+        { "address": "aws_instance.web[1]", "mode": "managed", "type": "aws_instance", "name": "web", "index": 1, "provider_name": "registry.terraform.io/hashicorp/aws" },
+        { "address": "data.aws_ami.ubuntu", "mode": "data", "type": "aws_ami", "name": "ubuntu", "provider_name": "registry.terraform.io/hashicorp/aws" }
+      ],
+      "child_modules": [
+        {
+          "address": "module.vpc",
+          "resources": [
+            { "address": "module.vpc.aws_vpc.main", "mode": "managed", "type": "aws_vpc", "name": "main", "provider_name": "registry.terraform.io/hashicorp/aws" }
+          ],
+          "child_modules": [
+            {
+              "address": "module.vpc.module.subnets",
+              "resources": [
+                { "address": "module.vpc.module.subnets.aws_subnet.this[\"a.1\"]", "mode": "managed", "type": "aws_subnet", "name": "this", "index": "a.1", "provider_name": "registry.terraform.io/hashicorp/aws" }
+              ]
+            }
+          ]
+        }
+      ]
+    }
+  },
+  "resource_changes": [
+    { "address": "aws_instance.web[0]", "provider_name": "registry.terraform.io/hashicorp/aws", "change": { "actions": ["create"] } },
+    { "address": "aws_instance.web[1]", "provider_name": "registry.terraform.io/hashicorp/aws", "change": { "actions": ["delete", "create"] } },
+    { "address": "module.vpc.aws_vpc.main", "provider_name": "registry.terraform.io/hashicorp/aws", "change": { "actions": ["no-op"] } },
+    { "address": "module.vpc.module.subnets.aws_subnet.this[\"a.1\"]", "provider_name": "registry.terraform.io/hashicorp/aws", "change": { "actions": ["update"] } },
+    { "address": "random_pet.old", "provider_name": "registry.terraform.io/hashicorp/random", "change": { "actions": ["delete"] } }
+  ],
+  "configuration": {
+    "root_module": {
+      "resources": [
+        {
+          "address": "aws_instance.web",
+          "mode": "managed",
+          "type": "aws_instance",
+          "name": "web",
+          "expressions": {
+            "ami": { "references": ["data.aws_ami.ubuntu.id", "data.aws_ami.ubuntu"] },
+            "subnet_id": { "references": ["module.vpc.subnet_id", "module.vpc"] },
+            "tags": { "references": ["var.env", "local.name"] },
+            "ebs_block_device": [{ "kms_key_id": { "references": ["aws_kms_key.main.arn", "aws_kms_key.main"] } }]
+          },
+          "count_expression": { "constant_value": 2 }
+        },
+        { "address": "data.aws_ami.ubuntu", "mode": "data", "type": "aws_ami", "name": "ubuntu" }
+      ],
+      "module_calls": {
+        "vpc": {
+          "source": "./vpc",
+          "expressions": { "name": { "references": ["var.env"] } },
+          "module": {
+            "resources": [
+              { "address": "aws_vpc.main", "mode": "managed", "type": "aws_vpc", "name": "main" }
+            ],
+            "module_calls": {
+              "subnets": {
+                "source": "./subnets",
+                "expressions": { "vpc_id": { "references": ["aws_vpc.main.id", "aws_vpc.main"] } },
+                "module": {
+                  "resources": [
+                    { "address": "aws_subnet.this", "mode": "managed", "type": "aws_subnet", "name": "this", "depends_on": ["aws_route_table.main"] }
+                  ]
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+#[test]
+fn modules_are_imported_as_things_containing_their_resources() {
+    let input_diagram = TerraformImporter::import_json(PLAN_JSON).unwrap();
+    let things = &input_diagram.things;
+
+    // Instances of `aws_instance.web` are imported as one thing.
+    assert_eq!(
+        vec![
+            "aws_instance_web",
+            "data_aws_ami_ubuntu",
+            "module_vpc",
+            "random_pet_old"
+        ],
+        hierarchy_ids(things)
+    );
+    let vpc = &things[&parse_thing_id("module_vpc").unwrap()];
+    assert_eq!(
+        vec!["aws_vpc_main", "module_vpc_module_subnets"],
+        hierarchy_ids(vpc)
+    );
+    assert_eq!(
+        vec!["aws_subnet_this"],
+        hierarchy_ids(&vpc[&parse_thing_id("module_vpc_module_subnets").unwrap()])
+    );
+
+    let thing_name = |thing_id: &str| {
+        input_diagram
+            .thing_names
+            .get(&parse_thing_id(thing_id).unwrap())
+            .cloned()
+    };
+    assert_eq!(
+        Some(String::from("module.subnets")),
+        thing_name("module_vpc_module_subnets")
+    );
+    assert_eq!(
+        Some(String::from("aws_subnet.this")),
+        thing_name("aws_subnet_this")
+    );
+    assert_eq!(
+        Some("module.vpc.module.subnets.aws_subnet.this"),
+        input_diagram
+            .entity_tooltips
+            .get(parse_thing_id("aws_subnet_this").unwrap().as_ref())
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn resources_are_typed_by_provider_and_resource_type() {
+    let input_diagram = TerraformImporter::import_json(PLAN_JSON).unwrap();
+
+    assert_eq!(
+        vec!["type_provider_aws", "type_resource_aws_instance"],
+        entity_types(&input_diagram, "aws_instance_web")
+    );
+    assert_eq!(
+        vec![
+            "type_provider_aws",
+            "type_resource_aws_ami",
+            "type_data_source"
+        ],
+        entity_types(&input_diagram, "data_aws_ami_ubuntu")
+    );
+    assert_eq!(
+        vec!["type_provider_random", "type_resource_random_pet"],
+        entity_types(&input_diagram, "random_pet_old")
+    );
+    assert_eq!(
+        Some("dashed"),
+        input_diagram
+            .theme_types_styles
+            .get(&EntityTypeId::new("type_data_source").unwrap())
+            .and_then(|theme_styles| theme_styles.get(&IdOrDefaults::NodeDefaults))
+            .and_then(|css_class_partials| css_class_partials.get(&ThemeAttr::StrokeStyle))
+            .map(String::as_str)
+    );
+}
+
+#[test]
+fn configuration_references_are_imported_as_dependencies() {
+    let input_diagram = TerraformImporter::import_json(PLAN_JSON).unwrap();
+
+    // References to variables, locals, and resources that are not in the
+    // plan, e.g. `aws_kms_key.main`, are not imported.
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_data_aws_ami_ubuntu__aws_instance_web",
+                EdgeKind::Sequence,
+                &["data_aws_ami_ubuntu", "aws_instance_web"]
+            ),
+            edge_group(
+                "edge_module_vpc__aws_instance_web",
+                EdgeKind::Sequence,
+                &["module_vpc", "aws_instance_web"]
+            ),
+            edge_group(
+                "edge_aws_vpc_main__module_vpc_module_subnets",
+                EdgeKind::Sequence,
+                &["aws_vpc_main", "module_vpc_module_subnets"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+}
+
+#[test]
+fn planned_actions_are_imported_as_tags() {
+    let input_diagram = TerraformImporter::import_json(PLAN_JSON).unwrap();
+
+    let tags = input_diagram
+        .tags
+        .iter()
+        .map(|(tag_id, tag_name)| (tag_id.as_str(), tag_name.as_str()))
+        .collect::<Vec<(&str, &str)>>();
+    assert_eq!(
+        vec![
+            ("tag_create", "Create"),
+            ("tag_update", "Update"),
+            ("tag_replace", "Replace"),
+            ("tag_delete", "Delete"),
+        ],
+        tags
+    );
+
+    let tag_things = input_diagram
+        .tag_things
+        .iter()
+        .map(|(tag_id, thing_ids)| {
+            (
+                tag_id.as_str(),
+                thing_ids
+                    .iter()
+                    .map(|thing_id| thing_id.as_str())
+                    .collect::<Vec<&str>>(),
+            )
+        })
+        .collect::<Vec<(&str, Vec<&str>)>>();
+    assert_eq!(
+        vec![
+            ("tag_create", vec!["aws_instance_web"]),
+            ("tag_update", vec!["aws_subnet_this"]),
+            ("tag_replace", vec!["aws_instance_web"]),
+            ("tag_delete", vec!["random_pet_old"]),
+        ],
+        tag_things
+    );
+}
+
+#[test]
+fn state_dependencies_are_imported_as_dependencies() {
+    let input_diagram = TerraformImporter::import_json(
+        r#"{
+          "values": {
+            "root_module": {
+              "resources": [
+                {
+                  "address": "aws_instance.web",
+                  "provider_name": "registry.terraform.io/hashicorp/aws",
+                  "depends_on": ["module.vpc.aws_subnet.main", "data.aws_ami.ubuntu"]
+                }
+              ],
+              "child_modules": [
+                {
+                  "address": "module.vpc",
+                  "resources": [
+                    { "address": "module.vpc.aws_subnet.main", "provider_name": "registry.terraform.io/hashicorp/aws" }
+                  ]
+                }
+              ]
+            }
+          }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![edge_group(
+            "edge_aws_subnet_main__aws_instance_web",
+            EdgeKind::Sequence,
+            &["aws_subnet_main", "aws_instance_web"]
+        )],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+    assert!(input_diagram.tags.is_empty());
+}
+
+#[test]
+fn graph_dot_is_imported_with_dependencies_through_other_nodes() {
+    let input_diagram = TerraformImporter::import_graph_dot(
+        r#"digraph {
+          compound = "true"
+          newrank = "true"
+          subgraph "root" {
+            "[root] aws_instance.web (expand)" [label = "aws_instance.web", shape = "box"]
+            "[root] aws_security_group.web (expand)" [label = "aws_security_group.web", shape = "box"]
+            "[root] module.vpc.aws_subnet.main (expand)" [label = "module.vpc.aws_subnet.main", shape = "box"]
+            "[root] provider[\"registry.terraform.io/hashicorp/aws\"]" [label = "provider[\"registry.terraform.io/hashicorp/aws\"]", shape = "diamond"]
+            "[root] aws_instance.web (expand)" -> "[root] aws_security_group.web (expand)"
+            "[root] aws_instance.web (expand)" -> "[root] module.vpc.output.subnet_id (expand)"
+            "[root] aws_instance.web (expand)" -> "[root] provider[\"registry.terraform.io/hashicorp/aws\"]"
+            "[root] aws_security_group.web (expand)" -> "[root] provider[\"registry.terraform.io/hashicorp/aws\"]"
+            "[root] aws_security_group.web (destroy)" -> "[root] aws_instance.web (expand)"
+            "[root] module.vpc.output.subnet_id (expand)" -> "[root] module.vpc.aws_subnet.main (expand)"
+            "[root] module.vpc.aws_subnet.main (expand)" -> "[root] provider[\"registry.terraform.io/hashicorp/aws\"]"
+            "[root] root" -> "[root] aws_instance.web (expand)"
+          }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec!["aws_instance_web", "aws_security_group_web", "module_vpc"],
+        hierarchy_ids(&input_diagram.things)
+    );
+    assert_eq!(
+        vec![
+            edge_group(
+                "edge_aws_security_group_web__aws_instance_web",
+                EdgeKind::Sequence,
+                &["aws_security_group_web", "aws_instance_web"]
+            ),
+            edge_group(
+                "edge_aws_subnet_main__aws_instance_web",
+                EdgeKind::Sequence,
+                &["aws_subnet_main", "aws_instance_web"]
+            ),
+        ],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+    assert_eq!(
+        vec!["type_provider_aws", "type_resource_aws_instance"],
+        entity_types(&input_diagram, "aws_instance_web")
+    );
+}
+
+#[test]
+fn graph_dot_meta_root_and_provider_close_nodes_are_not_imported() {
+    // `terraform graph` output from Terraform 0.12.
+    let input_diagram = TerraformImporter::import_graph_dot(
+        r#"digraph {
+	compound = "true"
+	newrank = "true"
+	subgraph "root" {
+		"[root] aws_instance.web" [label = "aws_instance.web", shape = "box"]
+		"[root] aws_s3_bucket.logs" [label = "aws_s3_bucket.logs", shape = "box"]
+		"[root] aws_security_group.web" [label = "aws_security_group.web", shape = "box"]
+		"[root] provider.aws" [label = "provider.aws", shape = "diamond"]
+		"[root] provisioner.local-exec" [label = "provisioner.local-exec", shape = "diamond"]
+		"[root] var.region" [label = "var.region", shape = "note"]
+		"[root] aws_instance.web" -> "[root] aws_security_group.web"
+		"[root] aws_instance.web" -> "[root] provisioner.local-exec"
+		"[root] aws_s3_bucket.logs" -> "[root] provider.aws"
+		"[root] aws_security_group.web" -> "[root] provider.aws"
+		"[root] meta.count-boundary (EachMode fixup)" -> "[root] aws_instance.web"
+		"[root] meta.count-boundary (EachMode fixup)" -> "[root] aws_s3_bucket.logs"
+		"[root] provider.aws (close)" -> "[root] aws_instance.web"
+		"[root] provider.aws (close)" -> "[root] aws_s3_bucket.logs"
+		"[root] provider.aws" -> "[root] var.region"
+		"[root] provisioner.local-exec (close)" -> "[root] aws_instance.web"
+		"[root] root" -> "[root] meta.count-boundary (EachMode fixup)"
+		"[root] root" -> "[root] provider.aws (close)"
+		"[root] root" -> "[root] provisioner.local-exec (close)"
+	}
+}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            "aws_instance_web",
+            "aws_s3_bucket_logs",
+            "aws_security_group_web"
+        ],
+        hierarchy_ids(&input_diagram.things)
+    );
+    assert_eq!(
+        vec![edge_group(
+            "edge_aws_security_group_web__aws_instance_web",
+            EdgeKind::Sequence,
+            &["aws_security_group_web", "aws_instance_web"]
+        )],
+        edge_groups(&input_diagram.thing_dependencies)
+    );
+}
+
+#[test]
+fn invalid_output_is_returned_as_error() {
+    let error = TerraformImporter::import_json("{\n  \"values\": 1\n}").unwrap_err();
+    assert!(
+        matches!(error, TerraformImportError::JsonInvalid { span, .. } if span.offset() == 14),
+        "Expected `JsonInvalid`, got: {error:?}"
+    );
+
+    let error = TerraformImporter::import_graph_dot("digraph {").unwrap_err();
+    assert!(
+        matches!(error, TerraformImportError::GraphDotInvalid(_)),
+        "Expected `GraphDotInvalid`, got: {error:?}"
+    );
+}